@@ -0,0 +1,436 @@
+//! `#[derive(Accounts)]`: declarative account validation for pinocchio
+//! programs.
+//!
+//! The derive writes the same `TryFrom<&[AccountInfo]>` impl the programs
+//! used to write by hand: a slice pattern that requires an exact number of
+//! accounts, followed by one `if` per check, in field order and then in the
+//! order the checks are written. The generated code only uses `core` and
+//! `pinocchio`, so it builds for `no_std` programs.
+//!
+//! ```ignore
+//! #[derive(Accounts)]
+//! #[accounts(system_program)]
+//! pub struct MutateCounterIxsAccounts<'info> {
+//!     #[account(signer @ CounterError::NotSigner)]
+//!     pub maker: &'info AccountInfo,
+//!     #[account(
+//!         mut @ CounterError::CounterNotWritable,
+//!         owner = crate::ID @ CounterError::InvalidCounterOwner,
+//!     )]
+//!     pub counter: &'info AccountInfo,
+//! }
+//! ```
+//!
+//! Checks, with the error returned when no `@ <error>` is given:
+//!
+//! | Check                      | Passes when                        | Default error               |
+//! |----------------------------|------------------------------------|-----------------------------|
+//! | `signer`                   | `is_signer()`                      | `MissingRequiredSignature`  |
+//! | `mut`                      | `is_writable()`                    | `Immutable`                 |
+//! | `owner = <pubkey>`         | `is_owned_by(&owner)`              | `InvalidAccountOwner`       |
+//! | `address = <pubkey>`       | `key() == &address`                | `InvalidArgument`           |
+//! | `empty`                    | `data_len() == 0`                  | `AccountAlreadyInitialized` |
+//! | `initialized`              | `data_len() != 0`                  | `UninitializedAccount`      |
+//! | `len = <usize>`            | `data_len() == len`                | `InvalidAccountData`        |
+//! | `seeds = [..]`             | key is the PDA of the seeds        | `InvalidSeeds`              |
+//! | `constraint = <bool expr>` | the expression holds               | `InvalidArgument`           |
+//! | `check = <Result expr>`    | the expression is `Ok`             | the expression's error      |
+//!
+//! `seeds` derives the PDA with `find_program_address` under the program's
+//! `crate::ID`. Add `bump = <u8>` to the same attribute to check a known bump
+//! with the cheaper `create_program_address` instead; any `if` or `@` goes on
+//! `seeds`, not `bump`. Expressions can refer
+//! to the accounts by field name, so `check` runs a program's own helpers,
+//! as in `check = MintInterface::check(mint)`. Any check followed by
+//! `if <bool expr>` only runs when the expression holds.
+//!
+//! The last field may instead take the accounts left after the others:
+//! `&'info [AccountInfo]` takes all of them, `Option<&'info AccountInfo>` at
+//! most one. Only `constraint` and `check` apply to such a field.
+//!
+//! `#[accounts(system_program)]` on the struct requires one more account
+//! after the fields, for instructions that need the system program in the
+//! transaction but never read it, and fails with `IncorrectProgramId` when
+//! it is not the system program.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Data, DeriveInput, Error, Expr, Fields, GenericArgument, GenericParam, Ident, PathArguments,
+    Result, Token, Type,
+};
+
+#[proc_macro_derive(Accounts, attributes(account, accounts))]
+pub fn derive_accounts(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+const EXPECTED_CHECK: &str = "expected `signer`, `mut`, `owner`, `address`, `empty`, \
+                              `initialized`, `len`, `seeds`, `bump`, `constraint` or `check`";
+
+enum Kind {
+    Signer,
+    Mut,
+    Owner(Expr),
+    Address(Expr),
+    Empty,
+    Initialized,
+    Len(Expr),
+    Seeds(Vec<Expr>),
+    Bump(Expr),
+    Constraint(Expr),
+    Check(Expr),
+}
+
+struct Check {
+    kind: Kind,
+    guard: Option<Expr>,
+    error: Option<Expr>,
+}
+
+impl Parse for Check {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kind = if input.peek(Token![mut]) {
+            input.parse::<Token![mut]>()?;
+            Kind::Mut
+        } else {
+            let name: Ident = input.parse()?;
+            match name.to_string().as_str() {
+                "signer" => Kind::Signer,
+                "empty" => Kind::Empty,
+                "initialized" => Kind::Initialized,
+                "owner" | "address" | "len" | "bump" | "constraint" | "check" => {
+                    input.parse::<Token![=]>()?;
+                    let expr: Expr = input.parse()?;
+                    match name.to_string().as_str() {
+                        "owner" => Kind::Owner(expr),
+                        "address" => Kind::Address(expr),
+                        "len" => Kind::Len(expr),
+                        "bump" => Kind::Bump(expr),
+                        "constraint" => Kind::Constraint(expr),
+                        _ => Kind::Check(expr),
+                    }
+                }
+                "seeds" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    bracketed!(content in input);
+                    let seeds = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                    Kind::Seeds(seeds.into_iter().collect())
+                }
+                _ => return Err(Error::new(name.span(), EXPECTED_CHECK)),
+            }
+        };
+
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let error = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { kind, guard, error })
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let ident = &input.ident;
+
+    let lifetime = match input.generics.params.first() {
+        Some(GenericParam::Lifetime(param)) if input.generics.params.len() == 1 => &param.lifetime,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.generics,
+                "expected a single lifetime parameter, as in `Accounts<'info>`",
+            ))
+        }
+    };
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(ident, "expected named fields")),
+        },
+        _ => return Err(Error::new_spanned(ident, "expected a struct")),
+    };
+
+    let mut system_program = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("accounts"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("system_program") {
+                system_program = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `system_program`"))
+            }
+        })?;
+    }
+
+    let names: Vec<&Ident> = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("named field"))
+        .collect();
+
+    let mut patterns = Vec::new();
+    let mut rest = None;
+    let mut checks = Vec::new();
+    for (index, (field, name)) in fields.iter().zip(&names).enumerate() {
+        let shape = Shape::of(&field.ty);
+        if shape != Shape::Account && (index + 1 != fields.len() || system_program) {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "only the last account, with no `system_program` after it, can take the \
+                 remaining accounts",
+            ));
+        }
+
+        match shape {
+            Shape::Account => patterns.push(quote!(#name)),
+            Shape::Rest => patterns.push(quote!(#name @ ..)),
+            Shape::Optional => {
+                patterns.push(quote!(__remaining @ ..));
+                rest = Some(quote! {
+                    let #name = match __remaining {
+                        [] => ::core::option::Option::None,
+                        [#name] => ::core::option::Option::Some(#name),
+                        _ => {
+                            return ::core::result::Result::Err(
+                                ::pinocchio::program_error::ProgramError::NotEnoughAccountKeys,
+                            )
+                        }
+                    };
+                });
+            }
+        }
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("account"))
+        {
+            let parsed: Vec<Check> = attr
+                .parse_args_with(Punctuated::<Check, Token![,]>::parse_terminated)?
+                .into_iter()
+                .collect();
+            let only_expressions = parsed
+                .iter()
+                .all(|check| matches!(check.kind, Kind::Constraint(_) | Kind::Check(_)));
+            if shape != Shape::Account && !only_expressions {
+                return Err(Error::new_spanned(
+                    attr,
+                    "only `constraint` and `check` apply to the remaining accounts",
+                ));
+            }
+            checks.extend(expand_checks(name, parsed)?);
+        }
+    }
+
+    if system_program {
+        patterns.push(quote!(__system_program));
+        // The system program's address is all zeroes
+        checks.push(quote! {
+            if __system_program.key() != &[0u8; 32] {
+                return ::core::result::Result::Err(
+                    ::pinocchio::program_error::ProgramError::IncorrectProgramId,
+                );
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl<#lifetime> ::core::convert::TryFrom<&#lifetime [::pinocchio::account_info::AccountInfo]>
+            for #ident<#lifetime>
+        {
+            type Error = ::pinocchio::program_error::ProgramError;
+
+            fn try_from(
+                accounts: &#lifetime [::pinocchio::account_info::AccountInfo],
+            ) -> ::core::result::Result<Self, Self::Error> {
+                let [#(#patterns),*] = accounts else {
+                    return ::core::result::Result::Err(
+                        ::pinocchio::program_error::ProgramError::NotEnoughAccountKeys,
+                    );
+                };
+                #rest
+
+                #(#checks)*
+
+                ::core::result::Result::Ok(Self { #(#names),* })
+            }
+        }
+    })
+}
+
+/// How many accounts a field takes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    /// `&'info AccountInfo`: exactly one.
+    Account,
+    /// `&'info [AccountInfo]`: all the remaining ones.
+    Rest,
+    /// `Option<&'info AccountInfo>`: at most one more.
+    Optional,
+}
+
+impl Shape {
+    fn of(ty: &Type) -> Self {
+        match ty {
+            Type::Reference(reference) if matches!(*reference.elem, Type::Slice(_)) => Self::Rest,
+            Type::Path(path) => match path.path.segments.last() {
+                Some(segment) if segment.ident == "Option" => match &segment.arguments {
+                    PathArguments::AngleBracketed(args)
+                        if matches!(args.args.first(), Some(GenericArgument::Type(_))) =>
+                    {
+                        Self::Optional
+                    }
+                    _ => Self::Account,
+                },
+                _ => Self::Account,
+            },
+            _ => Self::Account,
+        }
+    }
+}
+
+fn expand_checks(account: &Ident, checks: Vec<Check>) -> Result<Vec<TokenStream2>> {
+    let bump = checks.iter().find_map(|check| match &check.kind {
+        Kind::Bump(bump) => Some(bump.clone()),
+        _ => None,
+    });
+    let has_seeds = checks
+        .iter()
+        .any(|check| matches!(check.kind, Kind::Seeds(_)));
+    if bump.is_some() && !has_seeds {
+        return Err(Error::new_spanned(account, "`bump` needs `seeds`"));
+    }
+
+    let mut expanded = Vec::new();
+    for Check { kind, guard, error } in checks {
+        let (failed, default) = match kind {
+            Kind::Signer => (quote!(!#account.is_signer()), "MissingRequiredSignature"),
+            Kind::Mut => (quote!(!#account.is_writable()), "Immutable"),
+            Kind::Owner(owner) => (
+                quote!(!#account.is_owned_by(&#owner)),
+                "InvalidAccountOwner",
+            ),
+            Kind::Address(address) => (quote!(#account.key() != &#address), "InvalidArgument"),
+            Kind::Empty => (
+                quote!(#account.data_len() != 0),
+                "AccountAlreadyInitialized",
+            ),
+            Kind::Initialized => (quote!(#account.data_len() == 0), "UninitializedAccount"),
+            Kind::Len(len) => (quote!(#account.data_len() != #len), "InvalidAccountData"),
+            Kind::Constraint(constraint) => (quote!(!(#constraint)), "InvalidArgument"),
+            Kind::Check(check) => {
+                let check = match error {
+                    Some(error) => {
+                        let error = into_program_error(&error);
+                        quote! {
+                            if (#check).is_err() {
+                                return ::core::result::Result::Err(#error);
+                            }
+                        }
+                    }
+                    None => quote!(#check?;),
+                };
+                expanded.push(guarded(guard, check));
+                continue;
+            }
+            // `bump` only feeds the `seeds` check, so it has nothing of its
+            // own to guard or to fail with
+            Kind::Bump(_) => {
+                if let Some(modifier) = guard.or(error) {
+                    return Err(Error::new_spanned(
+                        modifier,
+                        "`bump` takes no `if` guard or `@` error, put them on `seeds`",
+                    ));
+                }
+                continue;
+            }
+            Kind::Seeds(seeds) => {
+                let error = error_expr(error, "InvalidSeeds");
+                let pda = format_ident!("__{}_pda", account);
+                let derive = match &bump {
+                    Some(bump) => quote! {
+                        let #pda = ::pinocchio::pubkey::create_program_address(
+                            &[#(#seeds,)* &[#bump]],
+                            &crate::ID,
+                        )
+                        .map_err(|_| #error)?;
+                    },
+                    None => quote! {
+                        let (#pda, _) =
+                            ::pinocchio::pubkey::find_program_address(&[#(#seeds),*], &crate::ID);
+                    },
+                };
+                expanded.push(guarded(
+                    guard,
+                    quote! {
+                        #derive
+                        if #account.key() != &#pda {
+                            return ::core::result::Result::Err(#error);
+                        }
+                    },
+                ));
+                continue;
+            }
+        };
+
+        let error = error_expr(error, default);
+        expanded.push(guarded(
+            guard,
+            quote! {
+                if #failed {
+                    return ::core::result::Result::Err(#error);
+                }
+            },
+        ));
+    }
+
+    Ok(expanded)
+}
+
+fn error_expr(error: Option<Expr>, default: &str) -> TokenStream2 {
+    match error {
+        Some(error) => into_program_error(&error),
+        None => {
+            let default = format_ident!("{}", default);
+            quote!(::pinocchio::program_error::ProgramError::#default)
+        }
+    }
+}
+
+fn into_program_error(error: &Expr) -> TokenStream2 {
+    quote!(::core::convert::Into::<::pinocchio::program_error::ProgramError>::into(#error))
+}
+
+/// Runs `check` only when `guard` holds.
+fn guarded(guard: Option<Expr>, check: TokenStream2) -> TokenStream2 {
+    match guard {
+        Some(guard) => quote! {
+            if #guard {
+                #check
+            }
+        },
+        None => check,
+    }
+}
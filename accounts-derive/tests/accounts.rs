@@ -0,0 +1,338 @@
+use pinocchio::pubkey::Pubkey;
+
+/// Program id the generated PDA checks derive under.
+pub const ID: Pubkey = [7; 32];
+
+#[cfg(test)]
+mod tests {
+    use accounts_derive::Accounts;
+    use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+    use super::ID;
+
+    const SYSTEM_PROGRAM: Pubkey = [0; 32];
+
+    #[derive(Clone, Copy, Debug)]
+    enum TestError {
+        NotSigner = 6000,
+        AlreadyInitialized = 6001,
+        SameAccount = 6002,
+        NotProgramState = 6003,
+    }
+
+    impl From<TestError> for ProgramError {
+        fn from(error: TestError) -> Self {
+            ProgramError::Custom(error as u32)
+        }
+    }
+
+    #[derive(Accounts)]
+    #[accounts(system_program)]
+    struct CreateAccounts<'info> {
+        #[account(signer @ TestError::NotSigner)]
+        payer: &'info AccountInfo,
+        #[account(
+            mut,
+            empty @ TestError::AlreadyInitialized,
+            constraint = target.key() != payer.key() @ TestError::SameAccount,
+        )]
+        target: &'info AccountInfo,
+    }
+
+    #[derive(Accounts)]
+    struct UpdateAccounts<'info> {
+        #[account(signer, address = [1; 32])]
+        authority: &'info AccountInfo,
+        #[account(mut, owner = ID, initialized, len = 8)]
+        state: &'info AccountInfo,
+    }
+
+    // PDA checks need the `sol_*_program_address` syscalls, so this only
+    // checks that the seeds type-check.
+    #[allow(dead_code)]
+    #[derive(Accounts)]
+    struct PdaAccounts<'info> {
+        owner: &'info AccountInfo,
+        #[account(seeds = [b"vault", owner.key()])]
+        vault: &'info AccountInfo,
+        #[account(seeds = [b"state", owner.key()], bump = 254)]
+        state: &'info AccountInfo,
+    }
+
+    fn check_state(account: &AccountInfo) -> Result<(), TestError> {
+        if !account.is_owned_by(&ID) {
+            return Err(TestError::NotProgramState);
+        }
+        Ok(())
+    }
+
+    #[derive(Accounts)]
+    struct CheckedAccounts<'info> {
+        #[account(
+            check = check_state(authority) if authority.is_writable() @ ProgramError::IllegalOwner,
+        )]
+        authority: &'info AccountInfo,
+        #[account(check = check_state(state), len = 8 if authority.is_signer())]
+        state: &'info AccountInfo,
+        #[account(constraint = extra.is_none_or(|extra| extra.key() != state.key()))]
+        extra: Option<&'info AccountInfo>,
+    }
+
+    #[derive(Accounts)]
+    struct LegsAccounts<'info> {
+        #[account(signer)]
+        maker: &'info AccountInfo,
+        #[account(constraint = legs.len() % 2 == 0 @ TestError::SameAccount)]
+        legs: &'info [AccountInfo],
+    }
+
+    /// Mirror of the account header the runtime serializes and pinocchio's
+    /// `AccountInfo` points to.
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct RawAccount {
+        borrow_state: u8,
+        is_signer: u8,
+        is_writable: u8,
+        executable: u8,
+        original_data_len: u32,
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data_len: u64,
+    }
+
+    fn raw(
+        key: u8,
+        is_signer: bool,
+        is_writable: bool,
+        owner: Pubkey,
+        data_len: u64,
+    ) -> RawAccount {
+        RawAccount {
+            borrow_state: u8::MAX,
+            is_signer: is_signer as u8,
+            is_writable: is_writable as u8,
+            executable: 0,
+            original_data_len: data_len as u32,
+            key: [key; 32],
+            owner,
+            lamports: 1_000_000,
+            data_len,
+        }
+    }
+
+    fn infos(raw: &mut [RawAccount]) -> Vec<AccountInfo> {
+        raw.iter_mut()
+            .map(|account| {
+                // SAFETY: `AccountInfo` is a `repr(C)` wrapper around a pointer
+                // to this header, and `raw` outlives the returned infos.
+                unsafe { core::mem::transmute::<*mut RawAccount, AccountInfo>(account) }
+            })
+            .collect()
+    }
+
+    fn create_accounts() -> Vec<RawAccount> {
+        vec![
+            raw(1, true, true, SYSTEM_PROGRAM, 0),
+            raw(2, true, true, SYSTEM_PROGRAM, 0),
+            raw(0, false, false, SYSTEM_PROGRAM, 0),
+        ]
+    }
+
+    fn update_accounts() -> Vec<RawAccount> {
+        vec![
+            raw(1, true, false, SYSTEM_PROGRAM, 0),
+            raw(2, false, true, ID, 8),
+        ]
+    }
+
+    fn create_error(raw: &mut [RawAccount]) -> ProgramError {
+        CreateAccounts::try_from(infos(raw).as_slice())
+            .err()
+            .unwrap()
+    }
+
+    fn checked_accounts() -> Vec<RawAccount> {
+        vec![
+            raw(1, false, false, SYSTEM_PROGRAM, 0),
+            raw(2, false, true, ID, 9),
+        ]
+    }
+
+    fn checked_error(raw: &mut [RawAccount]) -> ProgramError {
+        CheckedAccounts::try_from(infos(raw).as_slice())
+            .err()
+            .unwrap()
+    }
+
+    fn update_error(raw: &mut [RawAccount]) -> ProgramError {
+        UpdateAccounts::try_from(infos(raw).as_slice())
+            .err()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_accepts_valid_accounts() {
+        let mut raw = create_accounts();
+        let accounts = infos(&mut raw);
+        let accounts = CreateAccounts::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(accounts.payer.key(), &[1; 32]);
+        assert_eq!(accounts.target.key(), &[2; 32]);
+
+        let mut raw = update_accounts();
+        let accounts = infos(&mut raw);
+        let accounts = UpdateAccounts::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(accounts.authority.key(), &[1; 32]);
+        assert_eq!(accounts.state.data_len(), 8);
+    }
+
+    #[test]
+    fn test_requires_exact_account_count() {
+        let mut raw = create_accounts();
+        assert_eq!(
+            create_error(&mut raw[..2]),
+            ProgramError::NotEnoughAccountKeys
+        );
+
+        raw.push(raw[2]);
+        assert_eq!(create_error(&mut raw), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[test]
+    fn test_returns_the_given_errors() {
+        let mut raw = create_accounts();
+        raw[0].is_signer = 0;
+        assert_eq!(create_error(&mut raw), TestError::NotSigner.into());
+
+        let mut raw = create_accounts();
+        raw[1].data_len = 1;
+        assert_eq!(create_error(&mut raw), TestError::AlreadyInitialized.into());
+
+        let mut raw = create_accounts();
+        raw[1].key = raw[0].key;
+        assert_eq!(create_error(&mut raw), TestError::SameAccount.into());
+    }
+
+    #[test]
+    fn test_returns_default_errors() {
+        let mut raw = create_accounts();
+        raw[1].is_writable = 0;
+        assert_eq!(create_error(&mut raw), ProgramError::Immutable);
+
+        let mut raw = update_accounts();
+        raw[0].is_signer = 0;
+        assert_eq!(
+            update_error(&mut raw),
+            ProgramError::MissingRequiredSignature
+        );
+
+        let mut raw = update_accounts();
+        raw[0].key = [9; 32];
+        assert_eq!(update_error(&mut raw), ProgramError::InvalidArgument);
+
+        let mut raw = update_accounts();
+        raw[1].owner = SYSTEM_PROGRAM;
+        assert_eq!(update_error(&mut raw), ProgramError::InvalidAccountOwner);
+
+        let mut raw = update_accounts();
+        raw[1].data_len = 0;
+        assert_eq!(update_error(&mut raw), ProgramError::UninitializedAccount);
+
+        let mut raw = update_accounts();
+        raw[1].data_len = 9;
+        assert_eq!(update_error(&mut raw), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_checks_run_in_declaration_order() {
+        // Both the signer and the writable check fail, the signer is first
+        let mut raw = create_accounts();
+        raw[0].is_signer = 0;
+        raw[1].is_writable = 0;
+        assert_eq!(create_error(&mut raw), TestError::NotSigner.into());
+
+        // `initialized` is written before `len`
+        let mut raw = update_accounts();
+        raw[1].data_len = 0;
+        assert_eq!(update_error(&mut raw), ProgramError::UninitializedAccount);
+    }
+
+    #[test]
+    fn test_requires_the_system_program() {
+        let mut raw = create_accounts();
+        raw[2].key = [9; 32];
+        assert_eq!(create_error(&mut raw), ProgramError::IncorrectProgramId);
+    }
+
+    #[test]
+    fn test_check_returns_its_own_error() {
+        let mut raw = checked_accounts();
+        raw[1].owner = SYSTEM_PROGRAM;
+        assert_eq!(checked_error(&mut raw), TestError::NotProgramState.into());
+
+        let mut raw = checked_accounts();
+        raw[0].is_writable = 1;
+        assert_eq!(checked_error(&mut raw), ProgramError::IllegalOwner);
+    }
+
+    #[test]
+    fn test_guarded_checks_only_run_when_the_guard_holds() {
+        // `state` is 9 bytes long, which only matters for a signing authority
+        let mut raw = checked_accounts();
+        let accounts = infos(&mut raw);
+        assert!(CheckedAccounts::try_from(accounts.as_slice()).is_ok());
+
+        let mut raw = checked_accounts();
+        raw[0].is_signer = 1;
+        assert_eq!(checked_error(&mut raw), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_optional_account_takes_at_most_one() {
+        let extra = raw(3, false, false, SYSTEM_PROGRAM, 0);
+
+        let mut raw = checked_accounts();
+        let accounts = infos(&mut raw);
+        let accounts = CheckedAccounts::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(accounts.authority.key(), &[1; 32]);
+        assert_eq!(accounts.state.key(), &[2; 32]);
+        assert!(accounts.extra.is_none());
+
+        let mut raw = checked_accounts();
+        raw.push(extra);
+        let accounts = infos(&mut raw);
+        let accounts = CheckedAccounts::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(accounts.extra.unwrap().key(), &[3; 32]);
+
+        let mut raw = checked_accounts();
+        raw.push(raw[1]);
+        assert_eq!(checked_error(&mut raw), ProgramError::InvalidArgument);
+
+        raw.push(raw[0]);
+        assert_eq!(checked_error(&mut raw), ProgramError::NotEnoughAccountKeys);
+    }
+
+    #[test]
+    fn test_rest_takes_the_remaining_accounts() {
+        let mut legs: Vec<RawAccount> = (1..6)
+            .map(|key| raw(key, key == 1, key != 1, SYSTEM_PROGRAM, 0))
+            .collect();
+        let accounts = infos(&mut legs);
+        let accounts = LegsAccounts::try_from(accounts.as_slice()).unwrap();
+        assert_eq!(accounts.maker.key(), &[1; 32]);
+        assert_eq!(accounts.legs.len(), 4);
+        assert_eq!(accounts.legs[3].key(), &[5; 32]);
+
+        let accounts = infos(&mut legs[..4]);
+        assert_eq!(
+            LegsAccounts::try_from(accounts.as_slice()).err().unwrap(),
+            TestError::SameAccount.into()
+        );
+
+        assert_eq!(
+            LegsAccounts::try_from(&[][..]).err().unwrap(),
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+
+/// Errors returned by the address info program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressInfoError {
+    NotSigner = 6000,
+    AddressInfoNotSigner = 6001,
+    AddressInfoNotWritable = 6002,
+    AddressInfoAlreadyInitialized = 6003,
+    InvalidAddressInfoData = 6004,
+    InvalidAddressInfoOwner = 6005,
+    InvalidAccountDiscriminator = 6006,
+    UnsupportedAccountVersion = 6007,
+    AlreadyMigrated = 6008,
+}
+
+impl AddressInfoError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::AddressInfoNotSigner),
+            6002 => Some(Self::AddressInfoNotWritable),
+            6003 => Some(Self::AddressInfoAlreadyInitialized),
+            6004 => Some(Self::InvalidAddressInfoData),
+            6005 => Some(Self::InvalidAddressInfoOwner),
+            6006 => Some(Self::InvalidAccountDiscriminator),
+            6007 => Some(Self::UnsupportedAccountVersion),
+            6008 => Some(Self::AlreadyMigrated),
+            _ => None,
+        }
+    }
+}
+
+impl From<AddressInfoError> for ProgramError {
+    fn from(error: AddressInfoError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl fmt::Display for AddressInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Payer must sign the transaction",
+            Self::AddressInfoNotSigner => "Address info account must sign its own creation",
+            Self::AddressInfoNotWritable => "Address info account must be writable",
+            Self::AddressInfoAlreadyInitialized => "Address info account is already initialized",
+            Self::InvalidAddressInfoData => "Address info account data has the wrong size",
+            Self::InvalidAddressInfoOwner => "Address info account is not owned by the program",
+            Self::InvalidAccountDiscriminator => "Address info account has the wrong discriminator",
+            Self::AlreadyMigrated => "Address info account is already on the current layout",
+            Self::UnsupportedAccountVersion => {
+                "Address info account layout version is not supported"
+            }
+        };
+
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,83 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`AddressInfoEvent::decode`].
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+pub trait Event: Pod {
+    const DISCRIMINATOR: u8;
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[bytemuck::bytes_of(self)]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct AddressInfoCreated {
+    pub header: EventHeader,
+    pub address_info: Pubkey,
+    pub payer: Pubkey,
+}
+
+impl Event for AddressInfoCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl AddressInfoCreated {
+    pub fn new(address_info: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            header: Self::header(),
+            address_info,
+            payer,
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressInfoEvent {
+    Created(AddressInfoCreated),
+}
+
+impl AddressInfoEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let header = data
+            .get(..core::mem::size_of::<EventHeader>())
+            .and_then(|bytes| bytemuck::try_pod_read_unaligned::<EventHeader>(bytes).ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if header.version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match header.discriminator {
+            AddressInfoCreated::DISCRIMINATOR => read(data).map(Self::Created),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+fn read<T: Pod>(data: &[u8]) -> Result<T, ProgramError> {
+    bytemuck::try_pod_read_unaligned(data).map_err(|_| ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,31 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    error::AddressInfoError,
+    instructions::{
+        CreateAddressInfoAccounts, CreateAddressInfoInstructionData, Instruction,
+        MigrateAddressInfoAccounts,
+    },
+    state::AddressInfo,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create", Instruction::Create as u8)
+            .accounts::<CreateAddressInfoAccounts>()
+            .args::<CreateAddressInfoInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("migrate", Instruction::Migrate as u8)
+            .accounts::<MigrateAddressInfoAccounts>(),
+    )
+    .account::<AddressInfo>(&[AddressInfo::DISCRIMINATOR, AddressInfo::VERSION])
+    .errors(AddressInfoError::from_code)
+}
@@ -6,57 +6,39 @@ use pinocchio::{
 };
 
 use bytemuck::{Pod, Zeroable};
-use crate::state::AddressInfo;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+use crate::{
+    error::AddressInfoError,
+    events::{AddressInfoCreated, Event},
+    state::AddressInfo,
+};
 
 // A struct to hold the accounts required by the `CreateAddressInfo` instruction.
 // This provides a layer of validation and abstraction over the raw `accounts` slice.
+// The derive turns the raw `accounts` slice into this struct, checking each
+// account in the order the checks are written.
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct CreateAddressInfoAccounts<'info> {
+    /// Signs to authorize account creation and pay the rent.
+    #[account(signer @ AddressInfoError::NotSigner)]
+    #[idl(writable)]
     pub payer: &'info AccountInfo,
+    /// Signs because it is being created, must be writable to store data in
+    /// it, and must not be initialized yet.
+    #[account(
+        signer @ AddressInfoError::AddressInfoNotSigner,
+        mut @ AddressInfoError::AddressInfoNotWritable,
+        empty @ AddressInfoError::AddressInfoAlreadyInitialized,
+    )]
     pub address_info: &'info AccountInfo,
 }
 
-// This implementation allows us to safely convert the raw `accounts` slice
-// into our structured `CreateAddressInfoAccounts` type.
-impl<'info> TryFrom<&'info [AccountInfo]> for CreateAddressInfoAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        // This is a destructuring assignment. It checks if `accounts` has at least 3 elements.
-        // If not, it returns `NotEnoughAccountKeys`, ensuring we have the accounts we expect.
-        let [payer, address_info, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        // --- Account Validation ---
-        // 1. The `payer` must sign the transaction to authorize account creation and rent payment.
-        if !payer.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-        // 2. The `address_info` account must also be a signer, as we are creating it.
-        if !address_info.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-        // 3. The `address_info` account must be writable so we can store data in it.
-        if !address_info.is_writable() {
-            return Err(ProgramError::InvalidAccountData)
-        }
-        // 4. We check that the account is not already initialized by ensuring its data length is 0.
-        if address_info.data_len() != 0 {
-            return Err(ProgramError::AccountAlreadyInitialized);
-        }
-
-        // If all checks pass, return the structured accounts.
-        Ok(Self {
-            payer,
-            address_info,
-        })
-    }
-}
-
 // This struct defines the expected layout of the instruction's data buffer.
 // `#[repr(C)]` and `#[derive(Pod, Zeroable)]` are crucial for safe, zero-copy deserialization.
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
 pub struct CreateAddressInfoInstructionData {
     pub name: [u8; 50],
     pub house_number: u8,
@@ -73,7 +55,7 @@ impl<'info> TryFrom<&'info [u8]> for CreateAddressInfoInstructionData {
         // `bytemuck::try_from_bytes` attempts to safely interpret the byte slice as our struct.
         // It succeeds only if the slice's length exactly matches the struct's size.
         // This is a zero-cost abstraction; no data is actually copied here.
-        let result = bytemuck::try_from_bytes::<Self>(&data)
+        let result = bytemuck::try_from_bytes::<Self>(data)
             // If `try_from_bytes` fails, we map its error to our standard instruction data error.
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
@@ -135,17 +117,23 @@ impl<'info> Create<'info> {
             bytemuck::try_from_bytes_mut::<AddressInfo>(
                 self.accounts.address_info.borrow_mut_data_unchecked(),
             )
-            .map_err(|_| ProgramError::InvalidAccountData)?
+            .map_err(|_| AddressInfoError::InvalidAddressInfoData)?
         };
 
         // With a mutable reference to the on-chain state, we can now update its fields.
         address_info_state.set_inner(AddressInfo {
+            discriminator: AddressInfo::DISCRIMINATOR,
+            version: AddressInfo::VERSION,
             name: self.instruction_datas.name,
             house_number: self.instruction_datas.house_number,
             street: self.instruction_datas.street,
             city: self.instruction_datas.city,
         });
 
+        // Emit a binary event so indexers don't have to parse log strings.
+        AddressInfoCreated::new(*self.accounts.address_info.key(), *self.accounts.payer.key())
+            .emit();
+
         Ok(())
     }
 }
@@ -0,0 +1,75 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{error::AddressInfoError, state::AddressInfo};
+
+// The accounts required by the `Migrate` instruction.
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
+pub struct MigrateAddressInfoAccounts<'info> {
+    /// Covers any extra rent the larger layout needs.
+    #[account(signer @ AddressInfoError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'info AccountInfo,
+    /// Resized and rewritten. Only accounts this program created can be
+    /// migrated.
+    #[account(
+        mut @ AddressInfoError::AddressInfoNotWritable,
+        owner = crate::ID @ AddressInfoError::InvalidAddressInfoOwner,
+    )]
+    pub address_info: &'info AccountInfo,
+}
+
+pub struct Migrate<'info> {
+    pub accounts: MigrateAddressInfoAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for Migrate<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateAddressInfoAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> Migrate<'info> {
+    // Upgrades an untagged v1 account to the current layout in place.
+    pub fn handler(&mut self) -> ProgramResult {
+        // The data length tells the layouts apart: v1 has no header bytes.
+        match self.accounts.address_info.data_len() {
+            AddressInfo::V1_LEN => {}
+            AddressInfo::LEN => return Err(AddressInfoError::AlreadyMigrated.into()),
+            _ => return Err(AddressInfoError::InvalidAddressInfoData.into()),
+        }
+
+        // Top up the account so it stays rent exempt at its new size.
+        let minimum_balance = Rent::get()?.minimum_balance(AddressInfo::LEN);
+        let lamports = self.accounts.address_info.lamports();
+        if lamports < minimum_balance {
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.address_info,
+                lamports: minimum_balance - lamports,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.address_info.realloc(AddressInfo::LEN, false)?;
+
+        // Shift the v1 fields behind the new header, then write the header itself.
+        let mut data = self.accounts.address_info.try_borrow_mut_data()?;
+        data.copy_within(0..AddressInfo::V1_LEN, AddressInfo::LEN - AddressInfo::V1_LEN);
+        data[0] = AddressInfo::DISCRIMINATOR;
+        data[1] = AddressInfo::VERSION;
+
+        Ok(())
+    }
+}
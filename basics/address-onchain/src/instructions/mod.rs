@@ -2,11 +2,15 @@ pub mod create;
 
 pub use create::*;
 
+pub mod migrate;
+pub use migrate::*;
+
 use pinocchio::program_error::ProgramError;
 
 #[repr(u8)]
 pub enum Instruction {
     Create,
+    Migrate,
 }
 
 impl TryFrom<&u8> for Instruction {
@@ -15,6 +19,7 @@ impl TryFrom<&u8> for Instruction {
     fn try_from(value: &u8) -> Result<Self, Self::Error> {
         match *value {
             0 => Ok(Instruction::Create),
+            1 => Ok(Instruction::Migrate),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -4,6 +4,10 @@ use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
 use processor::process_instruction;
 
 pub mod constants;
+pub mod error;
+pub mod events;
+#[cfg(feature = "idl-build")]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
 pub mod state;
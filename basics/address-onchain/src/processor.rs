@@ -2,7 +2,7 @@ use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
 };
 
-use crate::instructions::{Create, Instruction};
+use crate::instructions::{Create, Instruction, Migrate};
 use pinocchio_log::log;
 
 #[inline(always)]
@@ -35,5 +35,9 @@ pub fn process_instruction(
             //    3. Executes the instruction's business logic.
             Create::try_from((accounts, data))?.handler()
         }
+        Instruction::Migrate => {
+            log!("Instruction: Migrate");
+            Migrate::try_from(accounts)?.handler()
+        }
     }
 }
@@ -1,4 +1,8 @@
 use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
+use idl_derive::IdlType;
+
+use crate::error::AddressInfoError;
 
 // #[repr(C)] ensures that the struct has a C-like memory representation,
 // guaranteeing the order of fields for safe serialization.
@@ -8,8 +12,14 @@ use bytemuck::{Pod, Zeroable};
 // - `Pod` (Plain Old Data): A bytemuck trait indicating this struct is a simple block of data,
 //   allowing for safe, zero-copy conversions between the struct and a byte slice.
 // - `Zeroable`: A bytemuck trait indicating that an all-zero byte pattern is a valid state for this struct.
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
 pub struct AddressInfo {
+    // Tags the account type so another account of the same size can't pass as `AddressInfo`.
+    #[idl(skip)]
+    pub discriminator: u8,
+    // Layout version, bumped whenever the fields below change shape.
+    #[idl(skip)]
+    pub version: u8,
     // Fixed-size byte arrays are used for string-like data to ensure a predictable on-chain size.
     pub name: [u8; 50],
     pub house_number: u8,
@@ -21,6 +31,26 @@ impl AddressInfo {
     // `LEN` provides a single source of truth for the on-chain account data size.
     // `core::mem::size_of` calculates the struct's size at compile time.
     pub const LEN: usize = core::mem::size_of::<AddressInfo>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 2;
+    // v1 accounts held the same fields without the two header bytes.
+    pub const V1_LEN: usize = Self::LEN - 2;
+
+    // Reinterprets account data as `AddressInfo`, rejecting anything that isn't a
+    // current-version `AddressInfo` account.
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        let address_info = bytemuck::try_from_bytes::<Self>(data)
+            .map_err(|_| AddressInfoError::InvalidAddressInfoData)?;
+
+        if address_info.discriminator != Self::DISCRIMINATOR {
+            return Err(AddressInfoError::InvalidAccountDiscriminator.into());
+        }
+        if address_info.version != Self::VERSION {
+            return Err(AddressInfoError::UnsupportedAccountVersion.into());
+        }
+
+        Ok(address_info)
+    }
 
     // This method updates the struct's fields from another instance.
     pub fn set_inner(&mut self, data: Self) -> Self {
@@ -5,34 +5,26 @@ mod tests {
         ID,
         instructions::CreateAddressInfoInstructionData
     };
-    use mollusk_svm::{
-        result::{Check, ProgramResult},
-        Mollusk,
-    };
+    use mollusk_svm::result::{Check, ProgramResult};
     use solana_sdk::{
         account::AccountSharedData,
         instruction::{AccountMeta, Instruction},
         native_token::LAMPORTS_PER_SOL,
         pubkey::Pubkey,
     };
+    use test_support::create_padded_array;
 
     pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
 
     #[test]
     fn test_create_account_data() {
-        pub fn create_padded_array<const N: usize>(data: &[u8], size: usize) -> [u8; N] {
-            let mut result = [0u8; N];
-            let copy_size = data.len().min(size).min(N);
-            result[..copy_size].copy_from_slice(&data[..copy_size]);
-            result
-        }
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/account_data");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "account_data");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let owner = Pubkey::new_from_array([0x02; 32]);
-        let owner_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
         let address_info_pubkey = Pubkey::new_unique();
         let address_info_account = AccountSharedData::new(0, 0, &system_program);
@@ -69,13 +61,19 @@ mod tests {
                 &[
                     Check::success(),
                     Check::account(&address_info_pubkey)
-                        .data(ix_data_bytes)
+                        .data(
+                            &[
+                                &[AddressInfo::DISCRIMINATOR, AddressInfo::VERSION],
+                                ix_data_bytes,
+                            ]
+                            .concat(),
+                        )
                         .build(),
                 ],
             );
 
         let updated_data = result.get_account(&address_info_pubkey).unwrap();
-        let parsed_data = bytemuck::from_bytes::<AddressInfo>(&updated_data.data);
+        let parsed_data = AddressInfo::load(&updated_data.data).unwrap();
 
         assert_eq!(parsed_data.name, create_padded_array(b"Raunit", 50));
         assert_eq!(parsed_data.house_number, 69);
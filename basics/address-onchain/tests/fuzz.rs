@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use account_data::{
+        instructions::{CreateAddressInfoInstructionData, Instruction},
+        state::AddressInfo,
+        ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, check_rejected, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 2;
+
+    const CREATE_LEN: usize = core::mem::size_of::<CreateAddressInfoInstructionData>();
+
+    fn fuzzer() -> Fuzzer {
+        Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .data_lens(&[AddressInfo::LEN, AddressInfo::V1_LEN])
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_data(data in vec(any::<u8>(), 0..=CREATE_LEN + 8)) {
+            let parsed = CreateAddressInfoInstructionData::try_from(data.as_slice());
+            prop_assert_eq!(parsed.is_ok(), data.len() == CREATE_LEN);
+        }
+
+        #[test]
+        fn test_parse_discriminator(discriminator in any::<u8>()) {
+            let parsed = Instruction::try_from(&discriminator);
+            prop_assert_eq!(parsed.is_ok(), discriminator < INSTRUCTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "account_data");
+        let fuzzer = fuzzer();
+        let data = vec(any::<u8>(), 0..=CREATE_LEN + 8);
+        fuzz(fuzzer.cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "account_data");
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "account_data");
+        fuzz(fuzzer().wrong_length_cases(0, CREATE_LEN), |case| {
+            check_rejected(&mollusk, &case)
+        });
+    }
+}
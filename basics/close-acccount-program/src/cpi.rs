@@ -0,0 +1,154 @@
+//! Instructions for invoking the close-account program from another program.
+//!
+//! Depend on this crate with the `cpi` feature, which leaves out the
+//! entrypoint, the allocator and the panic handler.
+
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+};
+
+use crate::instructions::{Instruction as UserInstruction, create_user::CreateUserInstructionData};
+
+/// Create the payer's user PDA.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Payer, pays for the account
+///   1. `[WRITE]` User PDA
+///   2. `[]` System program
+pub struct CreateUser<'a> {
+    /// Payer, pays for the account.
+    pub payer: &'a AccountInfo,
+
+    /// User PDA.
+    pub user: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+
+    /// Name, zero-padded.
+    pub name: &'a [u8; 64],
+}
+
+impl CreateUser<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.payer.key()),
+            AccountMeta::writable(self.user.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // - [0     ]: instruction discriminator
+        // - [1..65 ]: name
+        let mut instruction_data = [0; 1 + CreateUserInstructionData::LEN];
+        instruction_data[0] = UserInstruction::CreateUser as u8;
+        instruction_data[1..65].copy_from_slice(self.name);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.payer, self.user, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Close the payer's user PDA and refund its rent to the payer.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Payer, receives the rent
+///   1. `[WRITE]` User PDA
+///   2. `[]` System program
+pub struct CloseUser<'a> {
+    /// Payer, receives the rent.
+    pub payer: &'a AccountInfo,
+
+    /// User PDA.
+    pub user: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl CloseUser<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_with_payer(
+            UserInstruction::CloseUser,
+            [self.payer, self.user, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Migrate the user PDA to the current layout.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Payer, funds the extra rent
+///   1. `[WRITE]` User PDA
+///   2. `[]` System program
+pub struct MigrateUser<'a> {
+    /// Payer, funds the extra rent.
+    pub payer: &'a AccountInfo,
+
+    /// User PDA.
+    pub user: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl MigrateUser<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_with_payer(
+            UserInstruction::MigrateUser,
+            [self.payer, self.user, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// `CloseUser` and `MigrateUser` only differ in their discriminator.
+fn invoke_with_payer(
+    discriminator: UserInstruction,
+    [payer, user, system_program]: [&AccountInfo; 3],
+    signers: &[Signer],
+) -> ProgramResult {
+    // account metadata
+    let account_metas: [AccountMeta; 3] = [
+        AccountMeta::writable_signer(payer.key()),
+        AccountMeta::writable(user.key()),
+        AccountMeta::readonly(system_program.key()),
+    ];
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: &account_metas,
+        data: &[discriminator as u8],
+    };
+
+    invoke_signed(&instruction, &[payer, user, system_program], signers)
+}
@@ -0,0 +1,69 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+
+/// Errors returned by the close account program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UserError {
+    NotSigner = 6000,
+    UserNotWritable = 6001,
+    UserAlreadyInitialized = 6002,
+    UserNotInitialized = 6003,
+    InvalidUserOwner = 6004,
+    InvalidUserAddress = 6005,
+    InvalidUserData = 6006,
+    InvalidAccountDiscriminator = 6007,
+    UnsupportedAccountVersion = 6008,
+    AlreadyMigrated = 6009,
+    NonCanonicalBump = 6010,
+}
+
+impl UserError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::UserNotWritable),
+            6002 => Some(Self::UserAlreadyInitialized),
+            6003 => Some(Self::UserNotInitialized),
+            6004 => Some(Self::InvalidUserOwner),
+            6005 => Some(Self::InvalidUserAddress),
+            6006 => Some(Self::InvalidUserData),
+            6007 => Some(Self::InvalidAccountDiscriminator),
+            6008 => Some(Self::UnsupportedAccountVersion),
+            6009 => Some(Self::AlreadyMigrated),
+            6010 => Some(Self::NonCanonicalBump),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserError> for ProgramError {
+    fn from(error: UserError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Payer must sign the transaction",
+            Self::UserNotWritable => "User account must be writable",
+            Self::UserAlreadyInitialized => "User account is already initialized",
+            Self::UserNotInitialized => "User account is not initialized",
+            Self::InvalidUserOwner => "User account is not owned by the program",
+            Self::InvalidUserAddress => "User account does not match the user PDA",
+            Self::InvalidUserData => "User account data has the wrong size",
+            Self::InvalidAccountDiscriminator => "User account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "User account layout version is not supported",
+            Self::AlreadyMigrated => "User account is already on the current layout",
+            Self::NonCanonicalBump => "Bump is not the canonical bump of the user PDA",
+        };
+
+        f.write_str(message)
+    }
+}
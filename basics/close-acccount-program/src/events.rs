@@ -0,0 +1,135 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`UserEvent::decode`].
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+pub trait Event: Pod {
+    const DISCRIMINATOR: u8;
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[bytemuck::bytes_of(self)]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct UserCreated {
+    pub header: EventHeader,
+    pub user: Pubkey,
+    pub payer: Pubkey,
+}
+
+impl Event for UserCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl UserCreated {
+    pub fn new(user: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            header: Self::header(),
+            user,
+            payer,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct UserClosed {
+    pub header: EventHeader,
+    pub user: Pubkey,
+    pub payer: Pubkey,
+    pub lamports: [u8; 8],
+}
+
+impl Event for UserClosed {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl UserClosed {
+    pub fn new(user: Pubkey, payer: Pubkey, lamports: u64) -> Self {
+        Self {
+            header: Self::header(),
+            user,
+            payer,
+            lamports: lamports.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct UserMigrated {
+    pub header: EventHeader,
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+impl Event for UserMigrated {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl UserMigrated {
+    pub fn new(account: Pubkey, from_version: u8, to_version: u8) -> Self {
+        Self {
+            header: Self::header(),
+            account,
+            from_version,
+            to_version,
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserEvent {
+    Created(UserCreated),
+    Closed(UserClosed),
+    Migrated(UserMigrated),
+}
+
+impl UserEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let header = data
+            .get(..core::mem::size_of::<EventHeader>())
+            .and_then(|bytes| bytemuck::try_pod_read_unaligned::<EventHeader>(bytes).ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if header.version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match header.discriminator {
+            UserCreated::DISCRIMINATOR => read(data).map(Self::Created),
+            UserClosed::DISCRIMINATOR => read(data).map(Self::Closed),
+            UserMigrated::DISCRIMINATOR => read(data).map(Self::Migrated),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+fn read<T: Pod>(data: &[u8]) -> Result<T, ProgramError> {
+    bytemuck::try_pod_read_unaligned(data).map_err(|_| ProgramError::InvalidAccountData)
+}
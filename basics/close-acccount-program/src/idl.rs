@@ -0,0 +1,37 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    error::UserError,
+    instructions::{
+        close_user::CloseUserAccounts,
+        create_user::{CreateUserAccounts, CreateUserInstructionData},
+        migrate_user::MigrateUserAccounts,
+        Instruction,
+    },
+    state::User,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create_user", Instruction::CreateUser as u8)
+            .accounts::<CreateUserAccounts>()
+            .args::<CreateUserInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("close_user", Instruction::CloseUser as u8)
+            .accounts::<CloseUserAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("migrate_user", Instruction::MigrateUser as u8)
+            .accounts::<MigrateUserAccounts>(),
+    )
+    .account::<User>(&[User::DISCRIMINATOR, User::VERSION])
+    .errors(UserError::from_code)
+}
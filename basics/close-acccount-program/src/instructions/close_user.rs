@@ -1,46 +1,27 @@
-use pinocchio::{
-    ProgramResult,account_info::AccountInfo, program_error::ProgramError,
-    pubkey::find_program_address,
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    error::UserError,
+    events::{Event, UserClosed},
+    state::User,
 };
 
-use crate::state::User;
-
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct CloseUserAccounts<'info> {
+    #[account(signer @ UserError::NotSigner)]
+    #[idl(writable)]
     pub payer: &'info AccountInfo,
+    #[account(
+        mut @ UserError::UserNotWritable,
+        initialized @ UserError::UserNotInitialized,
+        owner = crate::ID @ UserError::InvalidUserOwner,
+    )]
     pub target_account: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for CloseUserAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [payer, target_account, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        if !payer.is_signer() {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        }
-
-        if !target_account.is_writable() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if target_account.data_len() == 0 {
-            return Err(ProgramError::UninitializedAccount);
-        }
-
-        if !target_account.is_owned_by(&crate::ID) {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        Ok(Self {
-            payer,
-            target_account,
-        })
-    }
-}
-
 pub struct CloseUser<'info> {
     pub accounts: CloseUserAccounts<'info>,
 }
@@ -57,17 +38,20 @@ impl<'info> TryFrom<&'info [AccountInfo]> for CloseUser<'info> {
 
 impl<'info> CloseUser<'info> {
     pub fn handler(&mut self) -> ProgramResult {
-        let (t_account, _) = find_program_address(
-            &[User::SEED_PREFIX, self.accounts.payer.key().as_ref()],
-            &crate::ID,
-        );
-
-        if t_account.ne(self.accounts.target_account.key()) {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        // only close accounts that really hold a `User` of this payer
+        User::load(&self.accounts.target_account.try_borrow_data()?)?
+            .check_address(self.accounts.payer.key(), self.accounts.target_account.key())?;
 
+        let lamports = self.accounts.target_account.lamports();
         self.close_program_account(self.accounts.target_account, self.accounts.payer)?;
 
+        UserClosed::new(
+            *self.accounts.target_account.key(),
+            *self.accounts.payer.key(),
+            lamports,
+        )
+        .emit();
+
         Ok(())
     }
 
@@ -8,43 +8,30 @@ use pinocchio::{
     pubkey::find_program_address,
     sysvars::{Sysvar, rent::Rent},
 };
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
 
-use crate::state::User;
+use crate::{
+    error::UserError,
+    events::{Event, UserCreated},
+    state::User,
+};
 
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct CreateUserAccounts<'info> {
+    #[account(signer @ UserError::NotSigner)]
+    #[idl(writable)]
     pub payer: &'info AccountInfo,
+    #[account(
+        mut @ UserError::UserNotWritable,
+        empty @ UserError::UserAlreadyInitialized,
+    )]
     pub target_account: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for CreateUserAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [payer, target_account, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        if !payer.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        if !target_account.is_writable() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if target_account.data_len() != 0 {
-            return Err(ProgramError::AccountAlreadyInitialized);
-        }
-
-        Ok(Self {
-            payer,
-            target_account,
-        })
-    }
-}
-
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, IdlType)]
 pub struct CreateUserInstructionData {
     pub name: [u8; 64],
 }
@@ -58,7 +45,7 @@ impl<'info> TryFrom<&'info [u8]> for CreateUserInstructionData {
 
     fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
         Ok(unsafe {
-            transmute(
+            transmute::<[u8; size_of::<CreateUserInstructionData>()], CreateUserInstructionData>(
                 TryInto::<[u8; size_of::<CreateUserInstructionData>()]>::try_into(data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?,
             )
@@ -95,7 +82,7 @@ impl<'info> CreateUser<'info> {
         );
 
         if t_account.ne(self.accounts.target_account.key()) {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(UserError::InvalidUserAddress.into());
         }
 
         let bump_binding = [bump];
@@ -115,8 +102,14 @@ impl<'info> CreateUser<'info> {
         }
         .invoke_signed(&[signer_seeds])?;
 
-        let mut data = self.accounts.target_account.try_borrow_mut_data()?;
-        let user = User::load_mut(data.as_mut())?;
+        {
+            let mut data = self.accounts.target_account.try_borrow_mut_data()?;
+            let user = User::init(data.as_mut())?;
+            user.name = self.instruction_datas.name;
+            user.bump = bump;
+        }
+
+        UserCreated::new(*self.accounts.target_account.key(), *self.accounts.payer.key()).emit();
         Ok(())
     }
 }
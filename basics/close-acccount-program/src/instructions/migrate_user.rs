@@ -0,0 +1,100 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, rent::Rent},
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    error::UserError,
+    events::{Event, UserMigrated},
+    state::User,
+};
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
+pub struct MigrateUserAccounts<'info> {
+    #[account(signer @ UserError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'info AccountInfo,
+    #[account(
+        mut @ UserError::UserNotWritable,
+        owner = crate::ID @ UserError::InvalidUserOwner,
+    )]
+    pub target_account: &'info AccountInfo,
+}
+
+pub struct MigrateUser<'info> {
+    pub accounts: MigrateUserAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for MigrateUser<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateUserAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> MigrateUser<'info> {
+    /// Upgrades a v1 or v2 user account to the current layout in place.
+    ///
+    /// Older layouts never stored the bump, so it is derived from the payer
+    /// here. Only the payer that created the account can migrate it.
+    pub fn handler(&mut self) -> ProgramResult {
+        let old_len = self.accounts.target_account.data_len();
+        let from_version = match old_len {
+            User::V1_LEN => 1,
+            // v2 is already tagged, so it must carry the user discriminator
+            User::V2_LEN => {
+                if self.accounts.target_account.try_borrow_data()?[0] != User::DISCRIMINATOR {
+                    return Err(UserError::InvalidAccountDiscriminator.into());
+                }
+                2
+            }
+            User::LEN => return Err(UserError::AlreadyMigrated.into()),
+            _ => return Err(UserError::InvalidUserData.into()),
+        };
+
+        let (t_account, bump) = find_program_address(
+            &[User::SEED_PREFIX, self.accounts.payer.key().as_ref()],
+            &crate::ID,
+        );
+
+        if t_account.ne(self.accounts.target_account.key()) {
+            return Err(UserError::InvalidUserAddress.into());
+        }
+
+        // top up rent for the extra header and bump bytes
+        let minimum_balance = Rent::get()?.minimum_balance(User::LEN);
+        let lamports = self.accounts.target_account.lamports();
+        if lamports < minimum_balance {
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.target_account,
+                lamports: minimum_balance - lamports,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.target_account.realloc(User::LEN, false)?;
+
+        let mut data = self.accounts.target_account.try_borrow_mut_data()?;
+        if old_len == User::V1_LEN {
+            // shift the v1 name behind the header
+            data.copy_within(0..User::V1_LEN, User::V2_LEN - User::V1_LEN);
+        }
+        data[0] = User::DISCRIMINATOR;
+        data[1] = User::VERSION;
+        data[User::V2_LEN] = bump;
+
+        UserMigrated::new(*self.accounts.target_account.key(), from_version, User::VERSION).emit();
+
+        Ok(())
+    }
+}
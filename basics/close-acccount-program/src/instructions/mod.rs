@@ -1,11 +1,13 @@
 pub mod close_user;
 pub mod create_user;
+pub mod migrate_user;
 use pinocchio::program_error::ProgramError;
 
 #[repr(u8)]
 pub enum Instruction {
     CreateUser,
     CloseUser,
+    MigrateUser,
 }
 
 impl TryFrom<&u8> for Instruction {
@@ -15,6 +17,7 @@ impl TryFrom<&u8> for Instruction {
         match *value {
             0 => Ok(Instruction::CreateUser),
             1 => Ok(Instruction::CloseUser),
+            2 => Ok(Instruction::MigrateUser),
             _ => Err(ProgramError::InvalidInstructionData)
         }
     }
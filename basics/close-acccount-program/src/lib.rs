@@ -1,14 +1,25 @@
 #![no_std]
 #![allow(unexpected_cfgs)]
+#[cfg(not(feature = "no-entrypoint"))]
 use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
+#[cfg(not(feature = "no-entrypoint"))]
 use processor::process_instruction;
 
+#[cfg(feature = "cpi")]
+pub mod cpi;
+pub mod error;
+pub mod events;
+#[cfg(feature = "idl-build")]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
 pub mod state;
 
 pinocchio_pubkey::declare_id!("H9ZpziEUkrhakmLKaFXeokJFhTFm69jJ8aVSso43PopB");
 
+#[cfg(not(feature = "no-entrypoint"))]
 program_entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
 no_allocator!();
+#[cfg(not(feature = "no-entrypoint"))]
 nostd_panic_handler!();
\ No newline at end of file
@@ -4,7 +4,9 @@ use pinocchio::{
 
 use pinocchio_log::log;
 
-use crate::instructions::{close_user::CloseUser, create_user::CreateUser, Instruction};
+use crate::instructions::{
+    close_user::CloseUser, create_user::CreateUser, migrate_user::MigrateUser, Instruction,
+};
 
 #[inline(always)]
 pub fn process_instruction(
@@ -30,5 +32,9 @@ pub fn process_instruction(
             log!("Instruction: CloseUser");
             CloseUser::try_from(accounts)?.handler()
         }
+        Instruction::MigrateUser => {
+            log!("Instruction: MigrateUser");
+            MigrateUser::try_from(accounts)?.handler()
+        }
     }
 }
\ No newline at end of file
@@ -1,29 +1,81 @@
-use pinocchio::program_error::ProgramError;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+};
+use idl_derive::IdlType;
+
+use crate::error::UserError;
 
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, IdlType)]
 pub struct User {
+    #[idl(skip)]
+    pub discriminator: u8,
+    #[idl(skip)]
+    pub version: u8,
     pub name: [u8; 64],
+    pub bump: u8,
 }
 
 impl User {
     pub const SEED_PREFIX: &[u8] = b"USER";
     pub const LEN: usize = core::mem::size_of::<User>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 3;
+    /// Size of the untagged v1 layout, which held only `name`.
+    pub const V1_LEN: usize = 64;
+    /// Size of the v2 layout, which had the header but no `bump`.
+    pub const V2_LEN: usize = Self::LEN - 1;
 
+    /// Writes the header into freshly allocated account data.
     #[inline(always)]
-    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
         if bytes.len() != User::LEN {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(UserError::InvalidUserData.into());
         }
+        bytes[0] = Self::DISCRIMINATOR;
+        bytes[1] = Self::VERSION;
+        Self::load_mut(bytes)
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check(bytes)?;
         Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr() )})
     }
 
     #[inline(always)]
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Check that `key` is the user PDA of `payer`, derived from the stored bump.
+    #[inline(always)]
+    pub fn check_address(&self, payer: &Pubkey, key: &Pubkey) -> Result<(), ProgramError> {
+        let user_pubkey =
+            pubkey::create_program_address(&[Self::SEED_PREFIX, payer, &[self.bump]], &crate::ID)
+                .map_err(|_| UserError::InvalidUserAddress)?;
+
+        if key.ne(&user_pubkey) {
+            return Err(UserError::InvalidUserAddress.into());
+        }
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn check(bytes: &[u8]) -> Result<(), ProgramError> {
         if bytes.len() != User::LEN {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(UserError::InvalidUserData.into());
         }
-        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+        if bytes[0] != Self::DISCRIMINATOR {
+            return Err(UserError::InvalidAccountDiscriminator.into());
+        }
+        if bytes[1] != Self::VERSION {
+            return Err(UserError::UnsupportedAccountVersion.into());
+        }
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,17 +1,21 @@
 #[cfg(test)]
 mod tests {
 
-    use close_account::{instructions::create_user::CreateUserInstructionData, state::User, ID};
-    use mollusk_svm::{
-        result::{Check, ProgramResult},
-        Mollusk,
+    use close_acccount::{
+        error::UserError,
+        events::{UserEvent, UserMigrated},
+        instructions::create_user::CreateUserInstructionData,
+        state::User,
+        ID,
     };
+    use mollusk_svm::result::{Check, ProgramResult};
 
-    use pinocchio_helper::create_padded_array;
+    use test_support::create_padded_array;
     use solana_sdk::{
         account::AccountSharedData,
         instruction::{AccountMeta, Instruction},
         native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
         pubkey::Pubkey,
     };
 
@@ -19,15 +23,15 @@ mod tests {
 
     #[test]
     fn test_create_user_account() {
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/close_acccount");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let payer = Pubkey::new_from_array([0x02; 32]);
-        let payer_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
-        let (user_account_pubkey, _) = solana_sdk::pubkey::Pubkey::find_program_address(
+        let (user_account_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
             &[User::SEED_PREFIX, payer.as_ref()],
             &PROGRAM_ID,
         );
@@ -77,6 +81,7 @@ mod tests {
             let parsed_data = User::load(&updated_data.data).unwrap();
 
             assert_eq!(parsed_data.name, user_state.name);
+            assert_eq!(parsed_data.bump, bump);
             assert!(updated_data.owner.eq(&PROGRAM_ID));
 
             assert!(result.program_result == ProgramResult::Success);
@@ -85,15 +90,15 @@ mod tests {
 
     #[test]
     fn test_close_user_account() {
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/close_acccount");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let payer = Pubkey::new_from_array([0x02; 32]);
-        let payer_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
-        let (user_account_pubkey, _) = solana_sdk::pubkey::Pubkey::find_program_address(
+        let (user_account_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
             &[User::SEED_PREFIX, payer.as_ref()],
             &PROGRAM_ID,
         );
@@ -110,12 +115,10 @@ mod tests {
                 &PROGRAM_ID,
             );
 
-            user_account.set_data_from_slice(unsafe {
-                core::slice::from_raw_parts(
-                    &user_state as *const CreateUserInstructionData as *const u8,
-                    core::mem::size_of::<CreateUserInstructionData>(),
-                )
-            });
+            let mut user_data = vec![User::DISCRIMINATOR, User::VERSION];
+            user_data.extend_from_slice(&user_state.name);
+            user_data.push(bump);
+            user_account.set_data_from_slice(&user_data);
 
             let data = vec![1];
 
@@ -148,4 +151,120 @@ mod tests {
             assert!(result.program_result == ProgramResult::Success);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_migrate_v1_user_account() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
+        let logs = test_support::record_logs(&mut mollusk);
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (user_account_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
+            &[User::SEED_PREFIX, payer.as_ref()],
+            &PROGRAM_ID,
+        );
+
+        // v1 user accounts hold only the name, with no header or bump
+        let name: [u8; 64] = create_padded_array(b"raunit", 64);
+        let mut user_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(User::V1_LEN),
+            User::V1_LEN,
+            &PROGRAM_ID,
+        );
+        user_account.set_data_from_slice(&name);
+
+        let mut expected = vec![User::DISCRIMINATOR, User::VERSION];
+        expected.extend_from_slice(&name);
+        expected.push(bump);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[2],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(user_account_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (user_account_pubkey, user_account.into()),
+                (system_program, system_account),
+            ],
+            &[
+                Check::success(),
+                Check::account(&user_account_pubkey)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(User::LEN))
+                    .data(&expected)
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| UserEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [UserEvent::Migrated(UserMigrated::new(
+                user_account_pubkey.to_bytes(),
+                1,
+                User::VERSION,
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_migrate_v2_rejects_wrong_discriminator() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (user_account_pubkey, _) = solana_sdk::pubkey::Pubkey::find_program_address(
+            &[User::SEED_PREFIX, payer.as_ref()],
+            &PROGRAM_ID,
+        );
+
+        // v2 length, but not tagged as a user account
+        let mut v2_data = vec![User::DISCRIMINATOR + 1, 2];
+        v2_data.extend_from_slice(&create_padded_array::<64>(b"raunit", 64));
+        let mut user_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(User::V2_LEN),
+            User::V2_LEN,
+            &PROGRAM_ID,
+        );
+        user_account.set_data_from_slice(&v2_data);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[2],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(user_account_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (user_account_pubkey, user_account.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                UserError::InvalidAccountDiscriminator as u32,
+            ))],
+        );
+    }
+}
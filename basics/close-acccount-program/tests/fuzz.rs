@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use close_acccount::{
+        instructions::{create_user::CreateUserInstructionData, Instruction},
+        state::User,
+        ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, check_rejected, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 3;
+
+    fn fuzzer() -> Fuzzer {
+        let payer = Pubkey::new_unique();
+        let user =
+            Pubkey::find_program_address(&[User::SEED_PREFIX, payer.as_ref()], &PROGRAM_ID).0;
+        Fuzzer::new(PROGRAM_ID, vec![payer, user, Pubkey::new_unique()])
+            .data_lens(&[User::LEN, User::V1_LEN])
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_user_data(data in vec(any::<u8>(), 0..=CreateUserInstructionData::LEN + 8)) {
+            let parsed = CreateUserInstructionData::try_from(data.as_slice());
+            prop_assert_eq!(parsed.is_ok(), data.len() == CreateUserInstructionData::LEN);
+        }
+
+        #[test]
+        fn test_parse_discriminator(discriminator in any::<u8>()) {
+            let parsed = Instruction::try_from(&discriminator);
+            prop_assert_eq!(parsed.is_ok(), discriminator < INSTRUCTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
+        fuzz(fuzzer().cases(), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_user_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "close_acccount");
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreateUserInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
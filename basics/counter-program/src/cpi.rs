@@ -0,0 +1,213 @@
+//! Instructions for invoking the counter program from another program.
+//!
+//! Depend on this crate with the `cpi` feature, which leaves out the
+//! entrypoint, the allocator and the panic handler:
+//!
+//! ```ignore
+//! counter::cpi::Increase {
+//!     counter,
+//!     authority,
+//!     system_program,
+//! }
+//! .invoke_signed(&[signer])?;
+//! ```
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    ProgramResult,
+};
+
+use crate::instructions::Instruction as CounterInstruction;
+
+/// Create the counter PDA.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Maker, pays for the account
+///   1. `[WRITE]` Counter PDA
+///   2. `[]` System program
+pub struct Create<'a> {
+    /// Maker, pays for the account.
+    pub maker: &'a AccountInfo,
+
+    /// Counter PDA.
+    pub counter: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+
+    /// Value the counter starts at.
+    pub initial_value: u64,
+
+    /// Canonical bump of the counter PDA.
+    pub bump: u8,
+}
+
+impl Create<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.maker.key()),
+            AccountMeta::writable(self.counter.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // - [0    ]: instruction discriminator
+        // - [1..9 ]: initial value
+        // - [9    ]: bump
+        let mut instruction_data = [0; 10];
+        instruction_data[0] = CounterInstruction::Create as u8;
+        instruction_data[1..9].copy_from_slice(&self.initial_value.to_le_bytes());
+        instruction_data[9] = self.bump;
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.maker, self.counter, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Increase the counter by one.
+///
+/// ### Accounts:
+///   0. `[SIGNER]` Authority
+///   1. `[WRITE]` Counter PDA
+///   2. `[]` System program
+pub struct Increase<'a> {
+    /// Counter PDA.
+    pub counter: &'a AccountInfo,
+
+    /// Authority, signs the update.
+    pub authority: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl Increase<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_mutate(
+            CounterInstruction::Increase,
+            [self.authority, self.counter, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Decrease the counter by one.
+///
+/// ### Accounts:
+///   0. `[SIGNER]` Authority
+///   1. `[WRITE]` Counter PDA
+///   2. `[]` System program
+pub struct Decrease<'a> {
+    /// Counter PDA.
+    pub counter: &'a AccountInfo,
+
+    /// Authority, signs the update.
+    pub authority: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl Decrease<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        invoke_mutate(
+            CounterInstruction::Decrease,
+            [self.authority, self.counter, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Migrate the counter to the current layout.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Payer, funds the extra rent
+///   1. `[WRITE]` Counter PDA
+///   2. `[]` System program
+pub struct Migrate<'a> {
+    /// Payer, funds the extra rent.
+    pub payer: &'a AccountInfo,
+
+    /// Counter PDA.
+    pub counter: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl Migrate<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.payer.key()),
+            AccountMeta::writable(self.counter.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &[CounterInstruction::Migrate as u8],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.payer, self.counter, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// `Increase` and `Decrease` only differ in their discriminator.
+fn invoke_mutate(
+    discriminator: CounterInstruction,
+    [authority, counter, system_program]: [&AccountInfo; 3],
+    signers: &[Signer],
+) -> ProgramResult {
+    // account metadata
+    let account_metas: [AccountMeta; 3] = [
+        AccountMeta::readonly_signer(authority.key()),
+        AccountMeta::writable(counter.key()),
+        AccountMeta::readonly(system_program.key()),
+    ];
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: &account_metas,
+        data: &[discriminator as u8],
+    };
+
+    invoke_signed(&instruction, &[authority, counter, system_program], signers)
+}
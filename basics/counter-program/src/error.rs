@@ -1,12 +1,72 @@
+use core::fmt;
+
 use pinocchio::program_error::ProgramError;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Errors returned by the counter program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum CounterError {
-    Overflow,
+    NotSigner = 6000,
+    Overflow = 6001,
+    Underflow = 6002,
+    CounterNotWritable = 6003,
+    CounterAlreadyInitialized = 6004,
+    InvalidCounterOwner = 6005,
+    InvalidCounterAddress = 6006,
+    InvalidCounterData = 6007,
+    InvalidAccountDiscriminator = 6008,
+    UnsupportedAccountVersion = 6009,
+    AlreadyMigrated = 6010,
+    NonCanonicalBump = 6011,
+}
+
+impl CounterError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::Overflow),
+            6002 => Some(Self::Underflow),
+            6003 => Some(Self::CounterNotWritable),
+            6004 => Some(Self::CounterAlreadyInitialized),
+            6005 => Some(Self::InvalidCounterOwner),
+            6006 => Some(Self::InvalidCounterAddress),
+            6007 => Some(Self::InvalidCounterData),
+            6008 => Some(Self::InvalidAccountDiscriminator),
+            6009 => Some(Self::UnsupportedAccountVersion),
+            6010 => Some(Self::AlreadyMigrated),
+            6011 => Some(Self::NonCanonicalBump),
+            _ => None,
+        }
+    }
 }
 
 impl From<CounterError> for ProgramError {
-    fn from(_error: CounterError) -> Self {
-        ProgramError::Custom(6001)
+    fn from(error: CounterError) -> Self {
+        ProgramError::Custom(error as u32)
     }
-}
\ No newline at end of file
+}
+
+impl fmt::Display for CounterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Maker must sign the transaction",
+            Self::Overflow => "Counter would overflow",
+            Self::Underflow => "Counter would underflow",
+            Self::CounterNotWritable => "Counter account must be writable",
+            Self::CounterAlreadyInitialized => "Counter account is already initialized",
+            Self::InvalidCounterOwner => "Counter account is not owned by the program",
+            Self::InvalidCounterAddress => "Counter account does not match the counter PDA",
+            Self::InvalidCounterData => "Counter account data has the wrong size",
+            Self::InvalidAccountDiscriminator => "Counter account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Counter account layout version is not supported",
+            Self::AlreadyMigrated => "Counter account is already on the current layout",
+            Self::NonCanonicalBump => "Bump is not the canonical bump of the counter PDA",
+        };
+
+        f.write_str(message)
+    }
+}
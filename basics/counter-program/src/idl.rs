@@ -0,0 +1,39 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    error::CounterError,
+    instructions::{
+        CreateCounterInstructionData, CreateCounterIxsAccounts, Instruction,
+        MigrateCounterIxsAccounts, MutateCounterIxsAccounts,
+    },
+    state::Counter,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create", Instruction::Create as u8)
+            .accounts::<CreateCounterIxsAccounts>()
+            .args::<CreateCounterInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("increase", Instruction::Increase as u8)
+            .accounts::<MutateCounterIxsAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("decrease", Instruction::Decrease as u8)
+            .accounts::<MutateCounterIxsAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("migrate", Instruction::Migrate as u8)
+            .accounts::<MigrateCounterIxsAccounts>(),
+    )
+    .account::<Counter>(&[Counter::DISCRIMINATOR, Counter::VERSION])
+    .errors(CounterError::from_code)
+}
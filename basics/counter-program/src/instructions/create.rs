@@ -7,42 +7,33 @@ use pinocchio::{
     sysvars::{rent::Rent, Sysvar},
     ProgramResult,
 };
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    constants::COUNTER_SEED,
+    error::CounterError,
+    events::{CounterCreated, Event},
+    state::Counter,
+};
 
-use crate::{constants::COUNTER_SEED, state::Counter};
-
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct CreateCounterIxsAccounts<'info> {
+    #[account(signer @ CounterError::NotSigner)]
+    #[idl(writable)]
     pub maker: &'info AccountInfo,
+    #[account(
+        mut @ CounterError::CounterNotWritable,
+        empty @ CounterError::CounterAlreadyInitialized,
+    )]
     pub counter: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for CreateCounterIxsAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [maker, counter, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        // check payer is signer
-        if !maker.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-        // check counter is writable
-        if !counter.is_writable() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        // check counter is not already initialized
-        if counter.data_len() != 0 {
-            return Err(ProgramError::AccountAlreadyInitialized);
-        }
-
-        Ok(Self { maker, counter })
-    }
-}
-
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
 pub struct CreateCounterInstructionData {
+    #[idl(ty = "u64")]
     pub initial_value: [u8; 8],
     pub bump: u8,
 }
@@ -55,7 +46,7 @@ impl<'info> TryFrom<&'info [u8]> for CreateCounterInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
-        let result = bytemuck::try_from_bytes::<Self>(&data)
+        let result = bytemuck::try_from_bytes::<Self>(data)
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
         Ok(*result)
@@ -86,16 +77,18 @@ impl<'info> TryFrom<(&'info [AccountInfo], &'info [u8])> for Create<'info> {
 impl<'info> Create<'info> {
     pub fn handler(&mut self) -> ProgramResult { // goal -> Perform a CPI to the Solana System Program; it must ask the System Program to do it on its behalf
 
-        let counter_pubkey = pubkey::create_program_address(
-            &[COUNTER_SEED, &[self.instruction_datas.bump as u8]],
-            &crate::ID,
-        )
-        .map_err(|_| ProgramError::InvalidSeeds)?;
+        // Derive once here so every later instruction can trust the stored bump
+        let (counter_pubkey, canonical_bump) =
+            pubkey::find_program_address(&[COUNTER_SEED], &crate::ID);
+
+        if self.instruction_datas.bump != canonical_bump {
+            return Err(CounterError::NonCanonicalBump.into());
+        }
 
         if self.accounts.counter.key() != &counter_pubkey {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(CounterError::InvalidCounterAddress.into());
         }
-        let bump = [self.instruction_datas.bump as u8];
+        let bump = [self.instruction_datas.bump];
         let seed = [Seed::from(COUNTER_SEED), Seed::from(&bump)];
         let signer_seeds = Signer::from(&seed);
 
@@ -114,12 +107,21 @@ impl<'info> Create<'info> {
             bytemuck::try_from_bytes_mut::<Counter>(
                 self.accounts.counter.borrow_mut_data_unchecked(),
             )
-            .map_err(|_| ProgramError::InvalidAccountData)?
+            .map_err(|_| CounterError::InvalidCounterData)?
         };
 
-        counter.set_inner(Counter {
-            count: self.instruction_datas.initial_value,
-        });
+        counter.set_inner(Counter::new(
+            u64::from_le_bytes(self.instruction_datas.initial_value),
+            self.instruction_datas.bump,
+        ));
+
+        CounterCreated::new(
+            *self.accounts.counter.key(),
+            *self.accounts.maker.key(),
+            u64::from_le_bytes(self.instruction_datas.initial_value),
+        )
+        .emit();
+
         Ok(())
     }
 }
\ No newline at end of file
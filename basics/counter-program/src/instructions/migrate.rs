@@ -0,0 +1,96 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    constants::COUNTER_SEED,
+    error::CounterError,
+    events::{CounterMigrated, Event},
+    state::Counter,
+};
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
+pub struct MigrateCounterIxsAccounts<'info> {
+    /// Funds the extra rent for the larger layout.
+    #[account(signer @ CounterError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'info AccountInfo,
+    #[account(
+        mut @ CounterError::CounterNotWritable,
+        owner = crate::ID @ CounterError::InvalidCounterOwner,
+    )]
+    pub counter: &'info AccountInfo,
+}
+
+pub struct Migrate<'info> {
+    pub accounts: MigrateCounterIxsAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for Migrate<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateCounterIxsAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> Migrate<'info> {
+    /// Upgrades a v1 or v2 counter to the current layout in place.
+    pub fn handler(&mut self) -> ProgramResult {
+        let old_len = self.accounts.counter.data_len();
+        let from_version = match old_len {
+            Counter::V1_LEN => 1,
+            // v2 is already tagged, so it must carry the counter discriminator
+            Counter::V2_LEN => {
+                if self.accounts.counter.try_borrow_data()?[0] != Counter::DISCRIMINATOR {
+                    return Err(CounterError::InvalidAccountDiscriminator.into());
+                }
+                2
+            }
+            Counter::LEN => return Err(CounterError::AlreadyMigrated.into()),
+            _ => return Err(CounterError::InvalidCounterData.into()),
+        };
+
+        // older layouts never stored the bump, so derive it once here
+        let (counter_pubkey, bump) = pubkey::find_program_address(&[COUNTER_SEED], &crate::ID);
+        if self.accounts.counter.key().ne(&counter_pubkey) {
+            return Err(CounterError::InvalidCounterAddress.into());
+        }
+
+        // top up rent for the extra header and bump bytes
+        let minimum_balance = Rent::get()?.minimum_balance(Counter::LEN);
+        let lamports = self.accounts.counter.lamports();
+        if lamports < minimum_balance {
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.counter,
+                lamports: minimum_balance - lamports,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.counter.realloc(Counter::LEN, false)?;
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        if old_len == Counter::V1_LEN {
+            // shift the v1 count behind the header
+            data.copy_within(0..Counter::V1_LEN, Counter::V2_LEN - Counter::V1_LEN);
+        }
+        data[0] = Counter::DISCRIMINATOR;
+        data[1] = Counter::VERSION;
+        data[Counter::V2_LEN] = bump;
+
+        CounterMigrated::new(*self.accounts.counter.key(), from_version, Counter::VERSION).emit();
+
+        Ok(())
+    }
+}
@@ -5,6 +5,9 @@ pub use create::*;
 pub mod mutate;
 pub use mutate::*;
 
+pub mod migrate;
+pub use migrate::*;
+
 use pinocchio::program_error::ProgramError;
 
 #[repr(u8)]
@@ -12,6 +15,7 @@ pub enum Instruction {
     Create,
     Increase,
     Decrease,
+    Migrate,
 }
 
 impl TryFrom<&u8> for Instruction {
@@ -22,6 +26,7 @@ impl TryFrom<&u8> for Instruction {
             0 => Ok(Instruction::Create),
             1 => Ok(Instruction::Increase),
             2 => Ok(Instruction::Decrease),
+            3 => Ok(Instruction::Migrate),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -1,8 +1,10 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey, ProgramResult};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
 
 use crate::{
-    constants::COUNTER_SEED,
     error::CounterError,
+    events::{CounterMutated, Event},
     state::{Counter, MutationType},
 };
 
@@ -25,35 +27,18 @@ impl<'info> TryFrom<&'info [u8]> for MutateCounterInstructionData {
     }
 }
 
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct MutateCounterIxsAccounts<'info> {
+    #[account(signer @ CounterError::NotSigner)]
     pub maker: &'info AccountInfo,
+    #[account(
+        mut @ CounterError::CounterNotWritable,
+        owner = crate::ID @ CounterError::InvalidCounterOwner,
+    )]
     pub counter: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for MutateCounterIxsAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [maker, counter, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        if !maker.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        if !counter.is_writable() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if !counter.is_owned_by(&crate::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
-
-        Ok(Self { maker, counter })
-    }
-}
-
 pub struct Mutate<'info> {
     pub accounts: MutateCounterIxsAccounts<'info>,
 }
@@ -70,19 +55,10 @@ impl<'info> TryFrom<&'info [AccountInfo]> for Mutate<'info> {
 
 impl<'info> Mutate<'info> {
     pub fn handler(&mut self, action: MutationType) -> ProgramResult {
-        let counter = unsafe {
-            bytemuck::try_from_bytes_mut::<Counter>(
-                self.accounts.counter.borrow_mut_data_unchecked(),
-            )
-            .map_err(|_| ProgramError::InvalidAccountData)?
-        };
-
-        let seeds = &[COUNTER_SEED];
-        let (counter_pubkey, _) = pubkey::find_program_address(seeds, &crate::ID);
-
-        if self.accounts.counter.key().ne(&counter_pubkey) {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        let counter =
+            unsafe { Counter::load_mut(self.accounts.counter.borrow_mut_data_unchecked())? };
+
+        counter.check_address(self.accounts.counter.key())?;
 
         match action {
             MutationType::INCREASE => self.increment(counter)?,
@@ -93,20 +69,24 @@ impl<'info> Mutate<'info> {
     }
 
     fn increment(&mut self, counter: &mut Counter) -> ProgramResult {
-        let mutated_state = u64::from_le_bytes(counter.count)
+        let current_state = u64::from_le_bytes(counter.count);
+        let mutated_state = current_state
             .checked_add(1)
             .ok_or(CounterError::Overflow)?;
 
         counter.count = mutated_state.to_le_bytes();
+        CounterMutated::new(current_state, mutated_state).emit();
         Ok(())
     }
 
     fn decrement(&mut self, counter: &mut Counter) -> ProgramResult {
-        let mutated_state = u64::from_le_bytes(counter.count)
+        let current_state = u64::from_le_bytes(counter.count);
+        let mutated_state = current_state
             .checked_sub(1)
-            .ok_or(CounterError::Overflow)?;
+            .ok_or(CounterError::Underflow)?;
 
         counter.count = mutated_state.to_le_bytes();
+        CounterMutated::new(current_state, mutated_state).emit();
         Ok(())
     }
 }
@@ -1,16 +1,19 @@
 #![no_std]
 #![allow(unexpected_cfgs)]
 
-use pinocchio::no_allocator;
-use pinocchio::nostd_panic_handler;
 #[cfg(not(feature = "no-entrypoint"))]
-use pinocchio::program_entrypoint;
+use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
 
 #[cfg(not(feature = "no-entrypoint"))]
 use crate::processor::process_instruction;
 
 pub mod constants;
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod error;
+pub mod events;
+#[cfg(feature = "idl-build")]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
 pub mod state;
@@ -19,5 +22,7 @@ pinocchio_pubkey::declare_id!("Ag8tR8rXHLwUGPCfgGUJYjcYnFnqFdJ8XfjGP5LeRpg6");
 
 #[cfg(not(feature = "no-entrypoint"))]
 program_entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
 no_allocator!();
+#[cfg(not(feature = "no-entrypoint"))]
 nostd_panic_handler!();
\ No newline at end of file
@@ -3,7 +3,7 @@ use pinocchio::{
 };
 
 use crate::{
-    instructions::{Create, Instruction, Mutate},
+    instructions::{Create, Instruction, Migrate, Mutate},
     state::MutationType,
 };
 use pinocchio_log::log;
@@ -36,5 +36,9 @@ pub fn process_instruction(
             log!("Instruction: Decrease");
             Mutate::try_from(accounts)?.handler(MutationType::DECREASE)
         }
+        Instruction::Migrate => {
+            log!("Instruction: Migrate");
+            Migrate::try_from(accounts)?.handler()
+        }
     }
 }
\ No newline at end of file
@@ -1,18 +1,80 @@
 use bytemuck::{Pod, Zeroable};
-use pinocchio::program_error::ProgramError;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+};
+use idl_derive::IdlType;
+
+use crate::{constants::COUNTER_SEED, error::CounterError};
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
 pub struct Counter {
+    #[idl(skip)]
+    pub discriminator: u8,
+    #[idl(skip)]
+    pub version: u8,
+    #[idl(ty = "u64")]
     pub count: [u8; 8],
+    pub bump: u8,
 }
 
 impl Counter {
     pub const LEN: usize = core::mem::size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 3;
+    /// Size of the untagged v1 layout, which held only `count`.
+    pub const V1_LEN: usize = 8;
+    /// Size of the v2 layout, which had the header but no `bump`.
+    pub const V2_LEN: usize = Self::LEN - 1;
+
+    pub fn new(count: u64, bump: u8) -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            count: count.to_le_bytes(),
+            bump,
+        }
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        let counter = bytemuck::try_from_bytes_mut::<Self>(data)
+            .map_err(|_| CounterError::InvalidCounterData)?;
+        counter.validate()?;
+
+        Ok(counter)
+    }
+
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.discriminator != Self::DISCRIMINATOR {
+            return Err(CounterError::InvalidAccountDiscriminator.into());
+        }
+        if self.version != Self::VERSION {
+            return Err(CounterError::UnsupportedAccountVersion.into());
+        }
+
+        Ok(())
+    }
+
+    /// Check that `key` is the counter PDA derived from the stored bump.
+    pub fn check_address(&self, key: &Pubkey) -> Result<(), ProgramError> {
+        let counter_pubkey =
+            pubkey::create_program_address(&[COUNTER_SEED, &[self.bump]], &crate::ID)
+                .map_err(|_| CounterError::InvalidCounterAddress)?;
+
+        if key.ne(&counter_pubkey) {
+            return Err(CounterError::InvalidCounterAddress.into());
+        }
+
+        Ok(())
+    }
 
     pub fn set_inner(&mut self, data: Self) -> Self {
+        self.discriminator = data.discriminator;
+        self.version = data.version;
         self.count = data.count;
-        self.clone()
+        self.bump = data.bump;
+        *self
     }
 }
 
@@ -1,17 +1,20 @@
 #[cfg(test)]
 mod tests {
     use counter::{
-        constants::COUNTER_SEED, instructions::CreateCounterInstructionData, state::Counter, ID,
-    };
-    use mollusk_svm::{
-        result::{Check, ProgramResult},
-        Mollusk,
+        constants::COUNTER_SEED,
+        error::CounterError,
+        events::{CounterCreated, CounterEvent, CounterMigrated, CounterMutated, EVENT_VERSION},
+        instructions::CreateCounterInstructionData,
+        state::Counter,
+        ID,
     };
+    use mollusk_svm::result::{Check, ProgramResult};
 
     use solana_sdk::{
         account::AccountSharedData,
         instruction::{AccountMeta, Instruction},
         native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
         pubkey::Pubkey,
     };
 
@@ -20,11 +23,11 @@ mod tests {
     #[test]
     fn test_create_counter_data() {
         // ARRANGE: Part 1 - Setup the environment
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/counter"); // Load the compiled program
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter"); // Load the compiled program
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program(); // Get the system program
         let owner = Pubkey::new_from_array([0x02; 32]); // Create a fake user/payer public key
-        let owner_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program); // Give the user 1 SOL to pay for things
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program); // Give the user 1 SOL to pay for things
 
         // ARRANGE: Part 2 - Setup the counter account
         // Calculate the expected Program Derived Address (PDA) for the counter.
@@ -35,9 +38,7 @@ mod tests {
         let counter_account = AccountSharedData::new(0, 0, &system_program);
 
         // ARRANGE: Part 3 - Define the instruction and expected outcome
-        let counter_init_state = Counter {
-            count: 100u64.to_le_bytes(), // We expect the final count to be 100
-        };
+        let counter_init_state = Counter::new(100u64, bump); // We expect the final count to be 100
 
         // Build the data for our `Create` instruction
         let ix_data = CreateCounterInstructionData {
@@ -88,20 +89,18 @@ mod tests {
 
     #[test]
     fn test_increase_counter_data() {
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/counter");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let owner = Pubkey::new_from_array([0x02; 32]);
-        let owner_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
-        let (counter_pubkey, _) =
+        let (counter_pubkey, bump) =
             solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
 
-        let counter_init_state = Counter {
-            count: 100u64.to_le_bytes(),
-        };
+        let counter_init_state = Counter::new(100u64, bump);
 
         {
             let mut counter_account = AccountSharedData::new(
@@ -136,9 +135,7 @@ mod tests {
                         Check::success(),
                         Check::account(&counter_pubkey).owner(&PROGRAM_ID).build(),
                         Check::account(&counter_pubkey)
-                            .data(bytemuck::bytes_of(&Counter {
-                                count: 101u64.to_le_bytes(),
-                            }))
+                            .data(bytemuck::bytes_of(&Counter::new(101u64, bump)))
                             .build(),
                     ],
                 );
@@ -154,20 +151,18 @@ mod tests {
 
     #[test]
     fn test_decrease_counter_data() {
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/counter");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let owner = Pubkey::new_from_array([0x02; 32]);
-        let owner_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
-        let (counter_pubkey, _) =
+        let (counter_pubkey, bump) =
             solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
 
-        let counter_init_state = Counter {
-            count: 100u64.to_le_bytes(),
-        };
+        let counter_init_state = Counter::new(100u64, bump);
 
         // increase counter instruction
         {
@@ -203,9 +198,7 @@ mod tests {
                         Check::success(),
                         Check::account(&counter_pubkey).owner(&PROGRAM_ID).build(),
                         Check::account(&counter_pubkey)
-                            .data(bytemuck::bytes_of(&Counter {
-                                count: 99u64.to_le_bytes(),
-                            }))
+                            .data(bytemuck::bytes_of(&Counter::new(99u64, bump)))
                             .build(),
                     ],
                 );
@@ -218,4 +211,330 @@ mod tests {
             assert!(result.program_result == ProgramResult::Success);
         }
     }
+
+    #[test]
+    fn test_mutate_rejects_wrong_discriminator() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (counter_pubkey, bump) =
+            solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
+
+        let mut counter_state = Counter::new(100u64, bump);
+        counter_state.discriminator = 0;
+
+        let mut counter_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Counter::LEN),
+            Counter::LEN,
+            &PROGRAM_ID,
+        );
+        counter_account.set_data_from_slice(bytemuck::bytes_of(&counter_state));
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[1],
+            vec![
+                AccountMeta::new(owner, true),
+                AccountMeta::new(counter_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account.into()),
+                (counter_pubkey, counter_account.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                CounterError::InvalidAccountDiscriminator as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_migrate_v1_counter() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+        let logs = test_support::record_logs(&mut mollusk);
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (counter_pubkey, bump) =
+            solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
+
+        // v1 counters hold only the count, with no header
+        let mut counter_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Counter::V1_LEN),
+            Counter::V1_LEN,
+            &PROGRAM_ID,
+        );
+        counter_account.set_data_from_slice(&100u64.to_le_bytes());
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[3],
+            vec![
+                AccountMeta::new(owner, true),
+                AccountMeta::new(counter_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account.into()),
+                (counter_pubkey, counter_account.into()),
+                (system_program, system_account.clone()),
+            ],
+            &[
+                Check::success(),
+                Check::account(&counter_pubkey)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(Counter::LEN))
+                    .data(bytemuck::bytes_of(&Counter::new(100u64, bump)))
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| CounterEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [CounterEvent::Migrated(CounterMigrated::new(
+                counter_pubkey.to_bytes(),
+                1,
+                Counter::VERSION,
+            ))]
+        );
+
+        // A second migration is rejected
+        let migrated = result.get_account(&counter_pubkey).unwrap().clone();
+        let payer = result.get_account(&owner).unwrap().clone();
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, payer),
+                (counter_pubkey, migrated),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                CounterError::AlreadyMigrated as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_create_rejects_non_canonical_bump() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (_, canonical_bump) =
+            solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
+
+        // The next valid bump below the canonical one derives another PDA
+        let (counter_pubkey, bump) = (0..canonical_bump)
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(&[COUNTER_SEED, &[bump]], &PROGRAM_ID)
+                    .ok()
+                    .map(|key| (key, bump))
+            })
+            .unwrap();
+
+        let ix_data = CreateCounterInstructionData {
+            initial_value: 100u64.to_le_bytes(),
+            bump,
+        };
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[&[0], bytemuck::bytes_of(&ix_data)].concat(),
+            vec![
+                AccountMeta::new(owner, true),
+                AccountMeta::new(counter_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account.into()),
+                (counter_pubkey, AccountSharedData::new(0, 0, &system_program).into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                CounterError::NonCanonicalBump as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_migrate_v2_counter() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+        let logs = test_support::record_logs(&mut mollusk);
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (counter_pubkey, bump) =
+            solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
+
+        // v2 counters have the header but no bump
+        let mut v2_data = bytemuck::bytes_of(&Counter::new(100u64, bump))[..Counter::V2_LEN].to_vec();
+        v2_data[1] = 2;
+        let mut counter_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Counter::V2_LEN),
+            Counter::V2_LEN,
+            &PROGRAM_ID,
+        );
+        counter_account.set_data_from_slice(&v2_data);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[3],
+            vec![
+                AccountMeta::new(owner, true),
+                AccountMeta::new(counter_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account.into()),
+                (counter_pubkey, counter_account.into()),
+                (system_program, system_account),
+            ],
+            &[
+                Check::success(),
+                Check::account(&counter_pubkey)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(Counter::LEN))
+                    .data(bytemuck::bytes_of(&Counter::new(100u64, bump)))
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| CounterEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [CounterEvent::Migrated(CounterMigrated::new(
+                counter_pubkey.to_bytes(),
+                2,
+                Counter::VERSION,
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_migrate_v2_rejects_wrong_discriminator() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let owner_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (counter_pubkey, bump) =
+            solana_sdk::pubkey::Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID);
+
+        // v2 length, but not tagged as a counter
+        let mut v2_data = bytemuck::bytes_of(&Counter::new(100u64, bump))[..Counter::V2_LEN].to_vec();
+        v2_data[0] = Counter::DISCRIMINATOR + 1;
+        v2_data[1] = 2;
+        let mut counter_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Counter::V2_LEN),
+            Counter::V2_LEN,
+            &PROGRAM_ID,
+        );
+        counter_account.set_data_from_slice(&v2_data);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[3],
+            vec![
+                AccountMeta::new(owner, true),
+                AccountMeta::new(counter_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account.into()),
+                (counter_pubkey, counter_account.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                CounterError::InvalidAccountDiscriminator as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_decode_counter_events() {
+        let mutated = CounterMutated::new(100, 101);
+        let bytes = bytemuck::bytes_of(&mutated);
+
+        assert_eq!(bytes[0], EVENT_VERSION);
+        assert_eq!(
+            CounterEvent::decode(bytes).unwrap(),
+            CounterEvent::Mutated(mutated)
+        );
+
+        let created = CounterCreated::new([0x01; 32], [0x02; 32], 100);
+        assert_eq!(
+            CounterEvent::decode(bytemuck::bytes_of(&created)).unwrap(),
+            CounterEvent::Created(created)
+        );
+
+        // Unknown versions and truncated payloads are rejected
+        let mut future = bytes.to_vec();
+        future[0] = EVENT_VERSION + 1;
+        assert!(CounterEvent::decode(&future).is_err());
+        assert!(CounterEvent::decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_counter_error_codes() {
+        // Overflow keeps the code it had before the enum got explicit discriminants
+        assert_eq!(
+            pinocchio::program_error::ProgramError::from(CounterError::Overflow),
+            pinocchio::program_error::ProgramError::Custom(6001)
+        );
+
+        for code in 6000..6012 {
+            let error = CounterError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(CounterError::from_code(6012), None);
+
+        assert_eq!(
+            CounterError::InvalidCounterAddress.to_string(),
+            "Counter account does not match the counter PDA"
+        );
+    }
 }
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use counter::{
+        constants::COUNTER_SEED,
+        instructions::{CreateCounterInstructionData, Instruction, MutateCounterInstructionData},
+        state::{Counter, MutationType},
+        ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, check_rejected, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 4;
+
+    fn fuzzer() -> Fuzzer {
+        let counter = Pubkey::find_program_address(&[COUNTER_SEED], &PROGRAM_ID).0;
+        Fuzzer::new(
+            PROGRAM_ID,
+            vec![Pubkey::new_unique(), counter, Pubkey::new_unique()],
+        )
+        .data_lens(&[Counter::LEN, Counter::V1_LEN])
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_data(data in vec(any::<u8>(), 0..32)) {
+            let parsed = CreateCounterInstructionData::try_from(data.as_slice());
+            prop_assert_eq!(parsed.is_ok(), data.len() == CreateCounterInstructionData::LEN);
+        }
+
+        #[test]
+        fn test_parse_mutation(data in vec(any::<u8>(), 0..4)) {
+            let parsed = MutateCounterInstructionData::try_from(data.as_slice());
+            prop_assert_eq!(parsed.is_ok(), matches!(data.first(), Some(0 | 1)));
+            prop_assert_eq!(MutationType::try_from(data.as_slice()).is_ok(), parsed.is_ok());
+        }
+
+        #[test]
+        fn test_parse_discriminator(discriminator in any::<u8>()) {
+            let parsed = Instruction::try_from(&discriminator);
+            prop_assert_eq!(parsed.is_ok(), discriminator < INSTRUCTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+        fuzz(fuzzer().cases(), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "counter");
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreateCounterInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use hello_solana::ID;
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    // The program only logs, so every input must succeed and move nothing
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "hello_solana");
+        let fuzzer = Fuzzer::new(PROGRAM_ID, vec![Pubkey::new_unique(), Pubkey::new_unique()]);
+        fuzz(fuzzer.cases(), |case| {
+            let result = check_invariants(&mollusk, &case)?;
+            proptest::prop_assert!(!result.program_result.is_err());
+            proptest::prop_assert_eq!(&result.resulting_accounts, &case.accounts);
+            Ok(())
+        });
+    }
+}
@@ -1,16 +1,13 @@
 #[cfg(test)]
 mod tests {
     use hello_solana::ID;
-    use mollusk_svm::{
-        result::{Check, ProgramResult},
-        Mollusk,
-    };
+    use mollusk_svm::result::{Check, ProgramResult};
     use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
     pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
     
     #[test]
     fn test_hello_solana() {
-        let mollusk = Mollusk::new(&PROGRAM_ID,"./target/deploy/hello_solana");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "hello_solana");
 
         let instruction = Instruction::new_with_bytes(PROGRAM_ID, &[], vec![]);
 
@@ -0,0 +1,2 @@
+/// Seed of the payment stream account PDA.
+pub const STREAM_SEED: &[u8] = b"stream";
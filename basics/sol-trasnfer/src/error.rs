@@ -0,0 +1,105 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+
+/// Errors returned by the sol transfer program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferError {
+    NotSigner = 6000,
+    RecipientNotWritable = 6001,
+    InvalidRecipientOwner = 6002,
+    InvalidPayerOwner = 6003,
+    PayerNotWritable = 6004,
+    InsufficientFunds = 6005,
+    BelowRentExemption = 6006,
+    Overflow = 6007,
+    SelfStream = 6008,
+    StreamNotWritable = 6009,
+    StreamAlreadyInitialized = 6010,
+    InvalidStreamOwner = 6011,
+    InvalidStreamData = 6012,
+    InvalidStreamAddress = 6013,
+    PayerMismatch = 6014,
+    RecipientMismatch = 6015,
+    ZeroStreamTotal = 6016,
+    InvalidStreamSchedule = 6017,
+    InvalidAccountDiscriminator = 6018,
+    UnsupportedAccountVersion = 6019,
+    AlreadyMigrated = 6020,
+    NonCanonicalBump = 6021,
+    SelfTransfer = 6022,
+}
+
+impl TransferError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::RecipientNotWritable),
+            6002 => Some(Self::InvalidRecipientOwner),
+            6003 => Some(Self::InvalidPayerOwner),
+            6004 => Some(Self::PayerNotWritable),
+            6005 => Some(Self::InsufficientFunds),
+            6006 => Some(Self::BelowRentExemption),
+            6007 => Some(Self::Overflow),
+            6008 => Some(Self::SelfStream),
+            6009 => Some(Self::StreamNotWritable),
+            6010 => Some(Self::StreamAlreadyInitialized),
+            6011 => Some(Self::InvalidStreamOwner),
+            6012 => Some(Self::InvalidStreamData),
+            6013 => Some(Self::InvalidStreamAddress),
+            6014 => Some(Self::PayerMismatch),
+            6015 => Some(Self::RecipientMismatch),
+            6016 => Some(Self::ZeroStreamTotal),
+            6017 => Some(Self::InvalidStreamSchedule),
+            6018 => Some(Self::InvalidAccountDiscriminator),
+            6019 => Some(Self::UnsupportedAccountVersion),
+            6020 => Some(Self::AlreadyMigrated),
+            6021 => Some(Self::NonCanonicalBump),
+            6022 => Some(Self::SelfTransfer),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransferError> for ProgramError {
+    fn from(error: TransferError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Payer must sign the transaction",
+            Self::RecipientNotWritable => "Recipient account must be writable",
+            Self::InvalidRecipientOwner => "Recipient account must be owned by the System Program",
+            Self::InvalidPayerOwner => "Payer account must be owned by the program to be debited directly",
+            Self::PayerNotWritable => "Payer account must be writable",
+            Self::InsufficientFunds => "Source account does not hold enough lamports",
+            Self::BelowRentExemption => "Transfer would leave the source below the rent-exempt minimum",
+            Self::Overflow => "Lamport arithmetic overflowed",
+            Self::SelfStream => "Payer and recipient of a stream must differ",
+            Self::StreamNotWritable => "Stream account must be writable",
+            Self::StreamAlreadyInitialized => "Stream account is already initialized",
+            Self::InvalidStreamOwner => "Stream account is not owned by the program",
+            Self::InvalidStreamData => "Stream account data is malformed",
+            Self::InvalidStreamAddress => "Stream account does not match the stream PDA",
+            Self::PayerMismatch => "Payer does not match the stream payer",
+            Self::RecipientMismatch => "Recipient does not match the stream recipient",
+            Self::ZeroStreamTotal => "Stream total must be greater than zero",
+            Self::InvalidStreamSchedule => "Stream must end after it starts",
+            Self::InvalidAccountDiscriminator => "Stream account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Stream account layout version is not supported",
+            Self::AlreadyMigrated => "Stream account is already on the current layout",
+            Self::NonCanonicalBump => "Bump is not the canonical bump of the stream PDA",
+            Self::SelfTransfer => "Payer and recipient of a transfer must differ",
+        };
+
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,202 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`SolTransferEvent::decode`].
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+pub trait Event: Pod {
+    const DISCRIMINATOR: u8;
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[bytemuck::bytes_of(self)]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct SolTransferred {
+    pub header: EventHeader,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: [u8; 8],
+}
+
+impl Event for SolTransferred {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl SolTransferred {
+    pub fn new(from: Pubkey, to: Pubkey, amount: u64) -> Self {
+        Self {
+            header: Self::header(),
+            from,
+            to,
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct StreamCreated {
+    pub header: EventHeader,
+    pub stream: Pubkey,
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub total: [u8; 8],
+    pub start_ts: [u8; 8],
+    pub end_ts: [u8; 8],
+}
+
+impl Event for StreamCreated {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl StreamCreated {
+    pub fn new(
+        stream: Pubkey,
+        payer: Pubkey,
+        recipient: Pubkey,
+        total: u64,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            stream,
+            payer,
+            recipient,
+            total: total.to_le_bytes(),
+            start_ts: start_ts.to_le_bytes(),
+            end_ts: end_ts.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct StreamWithdrawn {
+    pub header: EventHeader,
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: [u8; 8],
+}
+
+impl Event for StreamWithdrawn {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl StreamWithdrawn {
+    pub fn new(stream: Pubkey, recipient: Pubkey, amount: u64) -> Self {
+        Self {
+            header: Self::header(),
+            stream,
+            recipient,
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct StreamCancelled {
+    pub header: EventHeader,
+    pub stream: Pubkey,
+    pub paid: [u8; 8],
+    pub refunded: [u8; 8],
+}
+
+impl Event for StreamCancelled {
+    const DISCRIMINATOR: u8 = 3;
+}
+
+impl StreamCancelled {
+    pub fn new(stream: Pubkey, paid: u64, refunded: u64) -> Self {
+        Self {
+            header: Self::header(),
+            stream,
+            paid: paid.to_le_bytes(),
+            refunded: refunded.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct StreamMigrated {
+    pub header: EventHeader,
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+impl Event for StreamMigrated {
+    const DISCRIMINATOR: u8 = 4;
+}
+
+impl StreamMigrated {
+    pub fn new(account: Pubkey, from_version: u8, to_version: u8) -> Self {
+        Self {
+            header: Self::header(),
+            account,
+            from_version,
+            to_version,
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolTransferEvent {
+    Transferred(SolTransferred),
+    StreamCreated(StreamCreated),
+    StreamWithdrawn(StreamWithdrawn),
+    StreamCancelled(StreamCancelled),
+    StreamMigrated(StreamMigrated),
+}
+
+impl SolTransferEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let header = data
+            .get(..core::mem::size_of::<EventHeader>())
+            .and_then(|bytes| bytemuck::try_pod_read_unaligned::<EventHeader>(bytes).ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if header.version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match header.discriminator {
+            SolTransferred::DISCRIMINATOR => read(data).map(Self::Transferred),
+            StreamCreated::DISCRIMINATOR => read(data).map(Self::StreamCreated),
+            StreamWithdrawn::DISCRIMINATOR => read(data).map(Self::StreamWithdrawn),
+            StreamCancelled::DISCRIMINATOR => read(data).map(Self::StreamCancelled),
+            StreamMigrated::DISCRIMINATOR => read(data).map(Self::StreamMigrated),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+fn read<T: Pod>(data: &[u8]) -> Result<T, ProgramError> {
+    bytemuck::try_pod_read_unaligned(data).map_err(|_| ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,59 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    error::TransferError,
+    instructions::{
+        cancel_stream::CancelStreamAccounts,
+        create_stream::{CreateStreamAccounts, CreateStreamInstructionData},
+        migrate_stream::MigrateStreamAccounts,
+        shared::{TransferSolAccounts, TransferSolInstructionData},
+        withdraw_stream::WithdrawStreamAccounts,
+        Instruction,
+    },
+    state::Stream,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new(
+            "transfer_sol_with_program",
+            Instruction::TransferSolWithProgram as u8,
+        )
+        .accounts::<TransferSolAccounts>()
+        .args::<TransferSolInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new(
+            "transfer_sol_with_cpi",
+            Instruction::TransferSolWithCpi as u8,
+        )
+        .accounts::<TransferSolAccounts>()
+        .args::<TransferSolInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("create_stream", Instruction::CreateStream as u8)
+            .accounts::<CreateStreamAccounts>()
+            .args::<CreateStreamInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("withdraw_stream", Instruction::WithdrawStream as u8)
+            .accounts::<WithdrawStreamAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("cancel_stream", Instruction::CancelStream as u8)
+            .accounts::<CancelStreamAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("migrate_stream", Instruction::MigrateStream as u8)
+            .accounts::<MigrateStreamAccounts>(),
+    )
+    .account::<Stream>(&[Stream::DISCRIMINATOR, Stream::VERSION])
+    .errors(TransferError::from_code)
+}
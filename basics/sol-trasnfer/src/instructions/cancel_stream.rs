@@ -0,0 +1,94 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    error::TransferError,
+    events::{Event, StreamCancelled},
+    state::Stream,
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct CancelStreamAccounts<'info> {
+    #[account(
+        signer @ TransferError::NotSigner,
+        mut @ TransferError::PayerNotWritable,
+    )]
+    pub payer: &'info AccountInfo,
+    #[account(mut @ TransferError::RecipientNotWritable)]
+    pub recipient: &'info AccountInfo,
+    #[account(
+        mut @ TransferError::StreamNotWritable,
+        owner = crate::ID @ TransferError::InvalidStreamOwner,
+        len = Stream::LEN @ TransferError::InvalidStreamData,
+    )]
+    pub stream: &'info AccountInfo,
+}
+
+pub struct CancelStream<'info> {
+    pub accounts: CancelStreamAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for CancelStream<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CancelStreamAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> CancelStream<'info> {
+    pub fn handler(&mut self) -> ProgramResult {
+        let vested = {
+            let data = self.accounts.stream.try_borrow_data()?;
+            let stream = Stream::load(&data)?;
+
+            stream.check_address(self.accounts.stream.key())?;
+
+            if self.accounts.payer.key().ne(&stream.payer) {
+                return Err(TransferError::PayerMismatch.into());
+            }
+
+            if self.accounts.recipient.key().ne(&stream.recipient) {
+                return Err(TransferError::RecipientMismatch.into());
+            }
+
+            stream.withdrawable(Clock::get()?.unix_timestamp)?
+        };
+
+        // Vested lamports go to the recipient, everything else (including rent) back to the payer
+        let refund = self
+            .accounts
+            .stream
+            .lamports()
+            .checked_sub(vested)
+            .ok_or(TransferError::InsufficientFunds)?;
+        let recipient_lamports = self
+            .accounts
+            .recipient
+            .lamports()
+            .checked_add(vested)
+            .ok_or(TransferError::Overflow)?;
+        let payer_lamports = self
+            .accounts
+            .payer
+            .lamports()
+            .checked_add(refund)
+            .ok_or(TransferError::Overflow)?;
+
+        *self.accounts.stream.try_borrow_mut_lamports()? = 0;
+        *self.accounts.recipient.try_borrow_mut_lamports()? = recipient_lamports;
+        *self.accounts.payer.try_borrow_mut_lamports()? = payer_lamports;
+
+        StreamCancelled::new(*self.accounts.stream.key(), vested, refund).emit();
+
+        self.accounts.stream.close()
+    }
+}
@@ -0,0 +1,173 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::{CreateAccount, Transfer};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    error::TransferError,
+    constants::STREAM_SEED,
+    events::{Event, StreamCreated},
+    state::Stream,
+};
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
+pub struct CreateStreamAccounts<'info> {
+    #[account(signer @ TransferError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'info AccountInfo,
+    /// A stream to oneself would alias the lamport accounts on cancel.
+    #[account(constraint = payer.key() != recipient.key() @ TransferError::SelfStream)]
+    pub recipient: &'info AccountInfo,
+    #[account(
+        mut @ TransferError::StreamNotWritable,
+        empty @ TransferError::StreamAlreadyInitialized,
+    )]
+    pub stream: &'info AccountInfo,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
+pub struct CreateStreamInstructionData {
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],
+    #[idl(ty = "u64")]
+    pub total: [u8; 8],
+    #[idl(ty = "i64")]
+    pub start_ts: [u8; 8],
+    #[idl(ty = "i64")]
+    pub end_ts: [u8; 8],
+    pub bump: u8,
+}
+
+impl CreateStreamInstructionData {
+    pub const LEN: usize = core::mem::size_of::<CreateStreamInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for CreateStreamInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        let result = bytemuck::try_from_bytes::<Self>(data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        if u64::from_le_bytes(result.total) == 0 {
+            return Err(TransferError::ZeroStreamTotal.into());
+        }
+
+        if i64::from_le_bytes(result.end_ts) <= i64::from_le_bytes(result.start_ts) {
+            return Err(TransferError::InvalidStreamSchedule.into());
+        }
+
+        Ok(*result)
+    }
+}
+
+pub struct CreateStream<'info> {
+    pub accounts: CreateStreamAccounts<'info>,
+    pub instruction_datas: CreateStreamInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [AccountInfo], &'info [u8])> for CreateStream<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, data): (&'info [AccountInfo], &'info [u8]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = CreateStreamAccounts::try_from(accounts)?;
+        let instruction_datas = CreateStreamInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'info> CreateStream<'info> {
+    pub fn handler(&mut self) -> ProgramResult {
+        let bump = [self.instruction_datas.bump];
+
+        // Derive once here so every later instruction can trust the stored bump
+        let (stream_pubkey, canonical_bump) = pubkey::find_program_address(
+            &[
+                STREAM_SEED,
+                self.accounts.payer.key(),
+                self.accounts.recipient.key(),
+                &self.instruction_datas.seed,
+            ],
+            &crate::ID,
+        );
+
+        if self.instruction_datas.bump != canonical_bump {
+            return Err(TransferError::NonCanonicalBump.into());
+        }
+
+        if self.accounts.stream.key() != &stream_pubkey {
+            return Err(TransferError::InvalidStreamAddress.into());
+        }
+
+        let seed = [
+            Seed::from(STREAM_SEED),
+            Seed::from(self.accounts.payer.key()),
+            Seed::from(self.accounts.recipient.key()),
+            Seed::from(&self.instruction_datas.seed),
+            Seed::from(&bump),
+        ];
+        let signer_seeds = Signer::from(&seed);
+
+        CreateAccount {
+            from: self.accounts.payer,
+            to: self.accounts.stream,
+            space: Stream::LEN as u64,
+            lamports: Rent::get()?.minimum_balance(Stream::LEN),
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[signer_seeds])?;
+
+        // Fund the stream on top of its rent-exempt reserve
+        Transfer {
+            from: self.accounts.payer,
+            to: self.accounts.stream,
+            lamports: u64::from_le_bytes(self.instruction_datas.total),
+        }
+        .invoke()?;
+
+        let mut data = self.accounts.stream.try_borrow_mut_data()?;
+        let stream = bytemuck::try_from_bytes_mut::<Stream>(&mut data)
+            .map_err(|_| TransferError::InvalidStreamData)?;
+
+        *stream = Stream {
+            discriminator: Stream::DISCRIMINATOR,
+            version: Stream::VERSION,
+            payer: *self.accounts.payer.key(),
+            recipient: *self.accounts.recipient.key(),
+            seed: self.instruction_datas.seed,
+            total: self.instruction_datas.total,
+            withdrawn: [0; 8],
+            start_ts: self.instruction_datas.start_ts,
+            end_ts: self.instruction_datas.end_ts,
+            bump: self.instruction_datas.bump,
+        };
+
+        StreamCreated::new(
+            *self.accounts.stream.key(),
+            *self.accounts.payer.key(),
+            *self.accounts.recipient.key(),
+            u64::from_le_bytes(self.instruction_datas.total),
+            i64::from_le_bytes(self.instruction_datas.start_ts),
+            i64::from_le_bytes(self.instruction_datas.end_ts),
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    error::TransferError,
+    events::{Event, StreamMigrated},
+    state::Stream,
+};
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
+pub struct MigrateStreamAccounts<'info> {
+    #[account(signer @ TransferError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'info AccountInfo,
+    #[account(
+        mut @ TransferError::StreamNotWritable,
+        owner = crate::ID @ TransferError::InvalidStreamOwner,
+    )]
+    pub stream: &'info AccountInfo,
+}
+
+pub struct MigrateStream<'info> {
+    pub accounts: MigrateStreamAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for MigrateStream<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateStreamAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> MigrateStream<'info> {
+    /// Upgrades an untagged v1 stream to the tagged v2 layout in place.
+    pub fn handler(&mut self) -> ProgramResult {
+        match self.accounts.stream.data_len() {
+            Stream::V1_LEN => {}
+            Stream::LEN => return Err(TransferError::AlreadyMigrated.into()),
+            _ => return Err(TransferError::InvalidStreamData.into()),
+        }
+
+        // The stream's balance also holds unvested lamports, so top up by the
+        // difference in rent rather than comparing against the new minimum
+        let rent = Rent::get()?;
+        let top_up = rent
+            .minimum_balance(Stream::LEN)
+            .saturating_sub(rent.minimum_balance(Stream::V1_LEN));
+        if top_up > 0 {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.stream,
+                lamports: top_up,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.stream.realloc(Stream::LEN, false)?;
+
+        // Shift the v1 fields behind the header, then tag the account
+        let mut data = self.accounts.stream.try_borrow_mut_data()?;
+        data.copy_within(0..Stream::V1_LEN, Stream::LEN - Stream::V1_LEN);
+        data[0] = Stream::DISCRIMINATOR;
+        data[1] = Stream::VERSION;
+
+        StreamMigrated::new(*self.accounts.stream.key(), 1, Stream::VERSION).emit();
+
+        Ok(())
+    }
+}
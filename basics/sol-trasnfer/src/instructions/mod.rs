@@ -1,12 +1,20 @@
+pub mod cancel_stream;
+pub mod create_stream;
+pub mod migrate_stream;
 pub mod shared;
 pub mod transfer_sol_with_cpi;
 pub mod transfer_sol_with_program;
+pub mod withdraw_stream;
 use pinocchio::program_error::ProgramError;
 
 #[repr(u8)]
 pub enum Instruction {
     TransferSolWithProgram,
     TransferSolWithCpi,
+    CreateStream,
+    WithdrawStream,
+    CancelStream,
+    MigrateStream,
 }
 
 impl TryFrom<&u8> for Instruction {
@@ -16,6 +24,10 @@ impl TryFrom<&u8> for Instruction {
         match *value {
            0 => Ok(Instruction::TransferSolWithProgram),
            1 => Ok(Instruction::TransferSolWithCpi),
+           2 => Ok(Instruction::CreateStream),
+           3 => Ok(Instruction::WithdrawStream),
+           4 => Ok(Instruction::CancelStream),
+           5 => Ok(Instruction::MigrateStream),
            _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -28,5 +40,10 @@ impl TryFrom<&u8> for Instruction {
 //      incoming instruction data.
 //        * 0: Maps to TransferSolWithProgram.
 //        * 1: Maps to TransferSolWithCpi.
+//        * 2: Maps to CreateStream.
+//        * 3: Maps to WithdrawStream.
+//        * 4: Maps to CancelStream.
+//        * 5: Maps to MigrateStream.
 //        * Any other value results in ProgramError::InvalidInstructionData.
-//    * It also declares the modules within the instructions directory: shared, transfer_sol_with_cpi, and transfer_sol_with_program.
\ No newline at end of file
+//    * It also declares the modules within the instructions directory: shared, transfer_sol_with_cpi, transfer_sol_with_program and the
+//      payment stream instructions create_stream, withdraw_stream and cancel_stream, plus migrate_stream for upgrading v1 stream accounts.
\ No newline at end of file
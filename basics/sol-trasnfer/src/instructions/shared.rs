@@ -1,43 +1,33 @@
 use core::mem::transmute;
 // use bytemuck::{Pod, Zeroable};
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::error::TransferError;
 
 
 // What is Zero-Copy?
 // Zero-copy deserialization means no new memory allocation or data copying happens when you turn raw bytes into a typed struct.
 // Instead, the program interprets the existing bytes in-place as the struct.
 
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct TransferSolAccounts<'info> {
+    #[account(signer @ TransferError::NotSigner)]
+    #[idl(writable)]
     pub payer: &'info AccountInfo,
+    /// Paying oneself would alias the lamport accounts in a direct transfer.
+    #[account(
+        constraint = payer.key() != recipient.key() @ TransferError::SelfTransfer,
+        mut @ TransferError::RecipientNotWritable,
+        owner = pinocchio_system::ID @ TransferError::InvalidRecipientOwner,
+    )]
     pub recipient: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for TransferSolAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [payer, recipient, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        if !payer.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        if !recipient.is_writable() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if !recipient.is_owned_by(&pinocchio_system::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
-
-        Ok(Self { payer, recipient })
-    }
-}
-
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, IdlType)]
 pub struct TransferSolInstructionData {
     pub amount: u64,
 }
@@ -51,7 +41,7 @@ impl<'info> TryFrom<&'info [u8]> for TransferSolInstructionData {
 
     fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
         Ok(unsafe {
-            transmute(
+            transmute::<[u8; size_of::<TransferSolInstructionData>()], TransferSolInstructionData>(
                 TryInto::<[u8; size_of::<TransferSolInstructionData>()]>::try_into(data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?,
             )
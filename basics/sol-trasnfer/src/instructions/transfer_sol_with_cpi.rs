@@ -1,7 +1,10 @@
 use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
 use pinocchio_system::instructions::Transfer;
 
-use crate::instructions::shared::{TransferSolAccounts, TransferSolInstructionData};
+use crate::{
+    events::{Event, SolTransferred},
+    instructions::shared::{TransferSolAccounts, TransferSolInstructionData},
+};
 
 pub struct TransferSolWithCpi<'info> {
     pub accounts: TransferSolAccounts<'info>,
@@ -32,6 +35,14 @@ impl<'info> TransferSolWithCpi<'info> {
             lamports: self.instruction_datas.amount,
         }
         .invoke()?;
+
+        SolTransferred::new(
+            *self.accounts.payer.key(),
+            *self.accounts.recipient.key(),
+            self.instruction_datas.amount,
+        )
+        .emit();
+
         Ok(())
     }
 }
@@ -1,7 +1,15 @@
-use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{Sysvar, rent::Rent},
+};
 
-
-use crate::instructions::shared::{TransferSolAccounts, TransferSolInstructionData};
+use crate::{
+    error::TransferError,
+    events::{Event, SolTransferred},
+    instructions::shared::{TransferSolAccounts, TransferSolInstructionData},
+};
 
 pub struct TransferSolWithProgram<'info> {
     pub accounts: TransferSolAccounts<'info>,
@@ -17,6 +25,15 @@ impl<'info> TryFrom<(&'info [AccountInfo], &'info [u8])> for TransferSolWithProg
         let accounts = TransferSolAccounts::try_from(accounts)?;
         let instruction_datas = TransferSolInstructionData::try_from(data)?;
 
+        // Only accounts owned by this program can have their lamports debited directly
+        if !accounts.payer.is_owned_by(&crate::ID) {
+            return Err(TransferError::InvalidPayerOwner.into());
+        }
+
+        if !accounts.payer.is_writable() {
+            return Err(TransferError::PayerNotWritable.into());
+        }
+
         Ok(Self {
             accounts,
             instruction_datas,
@@ -26,8 +43,44 @@ impl<'info> TryFrom<(&'info [AccountInfo], &'info [u8])> for TransferSolWithProg
 
 impl<'info> TransferSolWithProgram<'info> {
     pub fn handler(&mut self) -> ProgramResult {
-        *self.accounts.payer.try_borrow_mut_lamports()? -= self.instruction_datas.amount;
-        *self.accounts.recipient.try_borrow_mut_lamports()? += self.instruction_datas.amount;
+        let amount = self.instruction_datas.amount;
+
+        let remaining = self
+            .accounts
+            .payer
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(TransferError::InsufficientFunds)?;
+
+        // The source either stays rent-exempt or gets fully drained and closed
+        if remaining != 0 {
+            let minimum = Rent::get()?.minimum_balance(self.accounts.payer.data_len());
+            if remaining < minimum {
+                return Err(TransferError::BelowRentExemption.into());
+            }
+        }
+
+        let credited = self
+            .accounts
+            .recipient
+            .lamports()
+            .checked_add(amount)
+            .ok_or(TransferError::Overflow)?;
+
+        *self.accounts.payer.try_borrow_mut_lamports()? = remaining;
+        *self.accounts.recipient.try_borrow_mut_lamports()? = credited;
+
+        SolTransferred::new(
+            *self.accounts.payer.key(),
+            *self.accounts.recipient.key(),
+            amount,
+        )
+        .emit();
+
+        if remaining == 0 {
+            self.accounts.payer.close()?;
+        }
+
         Ok(())
     }
 }
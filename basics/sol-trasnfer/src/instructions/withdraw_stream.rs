@@ -0,0 +1,92 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    error::TransferError,
+    events::{Event, StreamWithdrawn},
+    state::Stream,
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct WithdrawStreamAccounts<'info> {
+    #[account(
+        signer @ TransferError::NotSigner,
+        mut @ TransferError::RecipientNotWritable,
+    )]
+    pub recipient: &'info AccountInfo,
+    #[account(
+        mut @ TransferError::StreamNotWritable,
+        owner = crate::ID @ TransferError::InvalidStreamOwner,
+        len = Stream::LEN @ TransferError::InvalidStreamData,
+    )]
+    pub stream: &'info AccountInfo,
+}
+
+pub struct WithdrawStream<'info> {
+    pub accounts: WithdrawStreamAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for WithdrawStream<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = WithdrawStreamAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> WithdrawStream<'info> {
+    pub fn handler(&mut self) -> ProgramResult {
+        let mut data = self.accounts.stream.try_borrow_mut_data()?;
+        let stream = Stream::load_mut(&mut data)?;
+
+        stream.check_address(self.accounts.stream.key())?;
+
+        if self.accounts.recipient.key().ne(&stream.recipient) {
+            return Err(TransferError::RecipientMismatch.into());
+        }
+
+        let amount = stream.withdrawable(Clock::get()?.unix_timestamp)?;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        stream.withdrawn = u64::from_le_bytes(stream.withdrawn)
+            .checked_add(amount)
+            .ok_or(TransferError::Overflow)?
+            .to_le_bytes();
+
+        // The stream is owned by this program, so its lamports are debited directly
+        let remaining = self
+            .accounts
+            .stream
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(TransferError::InsufficientFunds)?;
+        let credited = self
+            .accounts
+            .recipient
+            .lamports()
+            .checked_add(amount)
+            .ok_or(TransferError::Overflow)?;
+
+        *self.accounts.stream.try_borrow_mut_lamports()? = remaining;
+        *self.accounts.recipient.try_borrow_mut_lamports()? = credited;
+
+        StreamWithdrawn::new(
+            *self.accounts.stream.key(),
+            *self.accounts.recipient.key(),
+            amount,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -3,8 +3,14 @@
 use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
 use processor::process_instruction;
 
+pub mod constants;
+pub mod error;
+pub mod events;
+#[cfg(feature = "idl-build")]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
+pub mod state;
 
 pinocchio_pubkey::declare_id!("QBDA4wAjJpX1rmpW7g6eSdize5Dq4mHbnRxkfNQCWya");
 
@@ -5,8 +5,9 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::instructions::{
+    cancel_stream::CancelStream, create_stream::CreateStream, migrate_stream::MigrateStream,
     transfer_sol_with_cpi::TransferSolWithCpi, transfer_sol_with_program::TransferSolWithProgram,
-    Instruction,
+    withdraw_stream::WithdrawStream, Instruction,
 };
 
 #[inline(always)]
@@ -33,5 +34,21 @@ pub fn process_instruction(
             log!("Instruction: TransferSolWithCpi");
             TransferSolWithCpi::try_from((accounts, data))?.handler()
         }
+        Instruction::CreateStream => {
+            log!("Instruction: CreateStream");
+            CreateStream::try_from((accounts, data))?.handler()
+        }
+        Instruction::WithdrawStream => {
+            log!("Instruction: WithdrawStream");
+            WithdrawStream::try_from(accounts)?.handler()
+        }
+        Instruction::CancelStream => {
+            log!("Instruction: CancelStream");
+            CancelStream::try_from(accounts)?.handler()
+        }
+        Instruction::MigrateStream => {
+            log!("Instruction: MigrateStream");
+            MigrateStream::try_from(accounts)?.handler()
+        }
     }
 }
\ No newline at end of file
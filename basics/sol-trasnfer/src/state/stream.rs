@@ -0,0 +1,124 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+};
+use idl_derive::IdlType;
+
+use crate::{constants::STREAM_SEED, error::TransferError};
+
+/// A lamport drip from `payer` to `recipient`, vesting linearly between
+/// `start_ts` and `end_ts`. The lamports backing the stream are held
+/// directly by the stream PDA on top of its rent-exempt reserve.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
+pub struct Stream {
+    #[idl(skip)]
+    pub discriminator: u8,
+    #[idl(skip)]
+    pub version: u8,
+    #[idl(ty = "pubkey")]
+    pub payer: Pubkey,
+    #[idl(ty = "pubkey")]
+    pub recipient: Pubkey,
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],
+    #[idl(ty = "u64")]
+    pub total: [u8; 8],
+    #[idl(ty = "u64")]
+    pub withdrawn: [u8; 8],
+    #[idl(ty = "i64")]
+    pub start_ts: [u8; 8],
+    #[idl(ty = "i64")]
+    pub end_ts: [u8; 8],
+    pub bump: u8,
+}
+
+impl Stream {
+    pub const LEN: usize = core::mem::size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 2;
+    /// Size of the untagged v1 layout, which started directly at `payer`.
+    pub const V1_LEN: usize = Self::LEN - 2;
+
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        let stream =
+            bytemuck::try_from_bytes::<Self>(data).map_err(|_| TransferError::InvalidStreamData)?;
+        stream.validate()?;
+
+        Ok(stream)
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        let stream = bytemuck::try_from_bytes_mut::<Self>(data)
+            .map_err(|_| TransferError::InvalidStreamData)?;
+        stream.validate()?;
+
+        Ok(stream)
+    }
+
+    fn validate(&self) -> Result<(), ProgramError> {
+        if self.discriminator != Self::DISCRIMINATOR {
+            return Err(TransferError::InvalidAccountDiscriminator.into());
+        }
+        if self.version != Self::VERSION {
+            return Err(TransferError::UnsupportedAccountVersion.into());
+        }
+
+        Ok(())
+    }
+
+    /// Check that `key` is the stream PDA derived from the stored seeds and bump.
+    pub fn check_address(&self, key: &Pubkey) -> Result<(), ProgramError> {
+        let stream_pubkey = pubkey::create_program_address(
+            &[
+                STREAM_SEED,
+                &self.payer,
+                &self.recipient,
+                &self.seed,
+                &[self.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| TransferError::InvalidStreamAddress)?;
+
+        if key.ne(&stream_pubkey) {
+            return Err(TransferError::InvalidStreamAddress.into());
+        }
+
+        Ok(())
+    }
+
+    /// Amount of lamports vested at `now`, in `[0, total]`.
+    pub fn vested(&self, now: i64) -> Result<u64, ProgramError> {
+        let total = u64::from_le_bytes(self.total);
+        let start = i64::from_le_bytes(self.start_ts);
+        let end = i64::from_le_bytes(self.end_ts);
+
+        if now <= start {
+            return Ok(0);
+        }
+        if now >= end {
+            return Ok(total);
+        }
+
+        // end > now > start, so both spans are positive. `abs_diff` keeps
+        // them from overflowing `i64` when the stream spans most of its range
+        let elapsed = now.abs_diff(start) as u128;
+        let duration = end.abs_diff(start) as u128;
+
+        let vested = (total as u128)
+            .checked_mul(elapsed)
+            .ok_or(TransferError::Overflow)?
+            / duration;
+
+        Ok(vested as u64)
+    }
+
+    /// Amount of vested lamports that has not been withdrawn yet.
+    pub fn withdrawable(&self, now: i64) -> Result<u64, ProgramError> {
+        self.vested(now)?
+            .checked_sub(u64::from_le_bytes(self.withdrawn))
+            .ok_or(TransferError::InvalidStreamData.into())
+    }
+}
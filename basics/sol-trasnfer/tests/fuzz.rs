@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use proptest::{collection::vec, prelude::*};
+    use sol_trasnfer::{
+        constants::STREAM_SEED,
+        error::TransferError,
+        instructions::{
+            create_stream::CreateStreamInstructionData, shared::TransferSolInstructionData,
+            Instruction,
+        },
+        state::Stream,
+        ID,
+    };
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, check_rejected, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 6;
+
+    fn fuzzer() -> Fuzzer {
+        let payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let stream = Pubkey::find_program_address(
+            &[
+                STREAM_SEED,
+                payer.as_ref(),
+                recipient.as_ref(),
+                &0u64.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0;
+        Fuzzer::new(
+            PROGRAM_ID,
+            vec![payer, recipient, stream, Pubkey::new_unique()],
+        )
+        .data_lens(&[Stream::LEN, Stream::V1_LEN])
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_transfer_data(data in vec(any::<u8>(), 0..16)) {
+            let parsed = TransferSolInstructionData::try_from(data.as_slice());
+            prop_assert_eq!(parsed.is_ok(), data.len() == TransferSolInstructionData::LEN);
+        }
+
+        #[test]
+        fn test_parse_create_stream_data(data in vec(any::<u8>(), 0..=CreateStreamInstructionData::LEN + 8)) {
+            let parsed = CreateStreamInstructionData::try_from(data.as_slice());
+
+            if data.len() != CreateStreamInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let total = u64::from_le_bytes(data[8..16].try_into().unwrap());
+            let start = i64::from_le_bytes(data[16..24].try_into().unwrap());
+            let end = i64::from_le_bytes(data[24..32].try_into().unwrap());
+            let expected = if total == 0 {
+                Err(TransferError::ZeroStreamTotal)
+            } else if end <= start {
+                Err(TransferError::InvalidStreamSchedule)
+            } else {
+                Ok(())
+            };
+            prop_assert_eq!(parsed.map(|_| ()), expected.map_err(ProgramError::from));
+        }
+
+        #[test]
+        fn test_vested_stays_within_total(
+            total in any::<u64>(),
+            start in any::<i64>(),
+            end in any::<i64>(),
+            now in any::<i64>(),
+        ) {
+            prop_assume!(start < end);
+            let stream = Stream {
+                discriminator: Stream::DISCRIMINATOR,
+                version: Stream::VERSION,
+                payer: [1; 32],
+                recipient: [2; 32],
+                seed: [0; 8],
+                total: total.to_le_bytes(),
+                withdrawn: [0; 8],
+                start_ts: start.to_le_bytes(),
+                end_ts: end.to_le_bytes(),
+                bump: 0,
+            };
+
+            let vested = stream.vested(now).unwrap();
+            prop_assert!(vested <= total);
+            if now <= start {
+                prop_assert_eq!(vested, 0);
+            }
+            if now >= end {
+                prop_assert_eq!(vested, total);
+            }
+        }
+
+        #[test]
+        fn test_parse_discriminator(discriminator in any::<u8>()) {
+            let parsed = Instruction::try_from(&discriminator);
+            prop_assert_eq!(parsed.is_ok(), discriminator < INSTRUCTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+        let fuzzer = fuzzer();
+        let data = (
+            0..INSTRUCTION_COUNT,
+            vec(any::<u8>(), 0..=CreateStreamInstructionData::LEN + 8),
+        )
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer.cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_transfer_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+        for discriminator in [0, 1] {
+            fuzz(
+                fuzzer().wrong_length_cases(discriminator, TransferSolInstructionData::LEN),
+                |case| check_rejected(&mollusk, &case),
+            );
+        }
+    }
+
+    #[test]
+    fn test_fuzz_create_stream_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+        fuzz(
+            fuzzer().wrong_length_cases(2, CreateStreamInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
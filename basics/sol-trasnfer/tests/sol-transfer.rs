@@ -1,23 +1,21 @@
 #[cfg(test)]
 mod tests {
 
-    use mollusk_svm::{
-        Mollusk,
-        result::{Check, ProgramResult},
-    };
-    use sol_trasnfer::{ID, instructions::shared::TransferSolInstructionData};
+    use mollusk_svm::result::{Check, ProgramResult};
+    use sol_trasnfer::{ID, error::TransferError, instructions::shared::TransferSolInstructionData};
 
     use solana_sdk::{
         account::AccountSharedData,
         instruction::{AccountMeta, Instruction},
         native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
         pubkey::Pubkey,
     };
 
     pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
 
     //        * Integration Tests: This file contains integration tests for the Solana program, using the mollusk-svm crate to simulate the Solana runtime environment.
-    //    * Mollusk::new(): Initializes a simulated Solana Virtual Machine (SVM) instance, loading your program.
+    //    * test_support::mollusk(): Initializes a simulated Solana Virtual Machine (SVM) instance, loading your program from the workspace target/deploy.
     //    * keyed_account_for_system_program(): Helper to get the System Program's public key and account data.
     //    * Test Setup: Both test_transfer_sol_with_program and test_transfer_sol_with_cpi follow a similar pattern:
     //        * Define payer and recipient public keys and their initial account states (AccountSharedData).
@@ -32,13 +30,13 @@ mod tests {
 
     #[test]
     fn test_transfer_sol_with_program() {
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/sol_trasnfer");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let payer: Pubkey = Pubkey::new_from_array([0x02; 32]);
-        let payer_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &PROGRAM_ID);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &PROGRAM_ID);
 
         let receipent = Pubkey::new_from_array([0x03; 32]);
         let receipent_account = AccountSharedData::new(0, 0, &system_program);
@@ -78,6 +76,7 @@ mod tests {
                     &[
                         Check::success(),
                         Check::account(&payer).lamports(0).build(),
+                        Check::account(&payer).owner(&system_program).build(),
                         Check::account(&receipent).lamports(1000000000).build(),
                     ],
                 );
@@ -85,15 +84,167 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_transfer_sol_with_program_rejects_foreign_payer() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        // The payer is owned by the System Program, so the program may not debit it
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let receipent = Pubkey::new_from_array([0x03; 32]);
+        let receipent_account = AccountSharedData::new(0, 0, &system_program);
+
+        let mut data = vec![0];
+        data.extend_from_slice(&1000u64.to_le_bytes());
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(receipent, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (receipent, receipent_account.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                TransferError::InvalidPayerOwner as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_transfer_sol_with_program_rejects_self_transfer() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &PROGRAM_ID);
+
+        let mut data = vec![0];
+        data.extend_from_slice(&1000u64.to_le_bytes());
+
+        // The payer is passed again as the recipient
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                TransferError::SelfTransfer as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_transfer_sol_with_program_keeps_payer_rent_exempt() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let space = 8;
+        let minimum = mollusk.sysvars.rent.minimum_balance(space);
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(minimum + 1000, space, &PROGRAM_ID);
+
+        let receipent = Pubkey::new_from_array([0x03; 32]);
+        let receipent_account = AccountSharedData::new(0, 0, &system_program);
+
+        // Moving the excess above rent is fine
+        {
+            let mut data = vec![0];
+            data.extend_from_slice(&1000u64.to_le_bytes());
+
+            let instruction = Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(receipent, false),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+            );
+
+            mollusk.process_and_validate_instruction(
+                &instruction,
+                &[
+                    (payer, payer_account.clone().into()),
+                    (receipent, receipent_account.clone().into()),
+                    (system_program, system_account.clone()),
+                ],
+                &[
+                    Check::success(),
+                    Check::account(&payer).lamports(minimum).build(),
+                    Check::account(&payer).owner(&PROGRAM_ID).build(),
+                    Check::account(&receipent).lamports(1000).build(),
+                ],
+            );
+        }
+
+        // Dipping into the rent-exempt reserve without draining the account is not
+        {
+            let mut data = vec![0];
+            data.extend_from_slice(&1001u64.to_le_bytes());
+
+            let instruction = Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(receipent, false),
+                    AccountMeta::new_readonly(system_program, false),
+                ],
+            );
+
+            mollusk.process_and_validate_instruction(
+                &instruction,
+                &[
+                    (payer, payer_account.into()),
+                    (receipent, receipent_account.into()),
+                    (system_program, system_account),
+                ],
+                &[Check::err(ProgramError::Custom(
+                    TransferError::BelowRentExemption as u32,
+                ))],
+            );
+        }
+    }
+
     #[test]
     fn test_transfer_sol_with_cpi() {
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/sol_trasnfer");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let payer = Pubkey::new_from_array([0x02; 32]);
-        let payer_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
         let receipent = Pubkey::new_from_array([0x03; 32]);
         let receipent_account = AccountSharedData::new(0, 0, &system_program);
@@ -0,0 +1,442 @@
+#[cfg(test)]
+mod tests {
+
+    use mollusk_svm::{result::Check, Mollusk};
+    use sol_trasnfer::{
+        constants::STREAM_SEED,
+        error::TransferError,
+        events::{SolTransferEvent, StreamMigrated},
+        instructions::create_stream::CreateStreamInstructionData,
+        state::Stream,
+        ID,
+    };
+
+    use solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const TOTAL: u64 = 1_000_000;
+    const START: i64 = 1_000;
+    const END: i64 = 2_000;
+
+    fn stream_address(payer: &Pubkey, recipient: &Pubkey, seed: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                STREAM_SEED,
+                payer.as_ref(),
+                recipient.as_ref(),
+                &seed.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+    }
+
+    // Build an already funded stream account, as left behind by CreateStream
+    fn stream_account(
+        mollusk: &Mollusk,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        withdrawn: u64,
+    ) -> AccountSharedData {
+        let (_, bump) = stream_address(payer, recipient, 7);
+
+        let state = Stream {
+            discriminator: Stream::DISCRIMINATOR,
+            version: Stream::VERSION,
+            payer: payer.to_bytes(),
+            recipient: recipient.to_bytes(),
+            seed: 7u64.to_le_bytes(),
+            total: TOTAL.to_le_bytes(),
+            withdrawn: withdrawn.to_le_bytes(),
+            start_ts: START.to_le_bytes(),
+            end_ts: END.to_le_bytes(),
+            bump,
+        };
+
+        let mut account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Stream::LEN) + TOTAL - withdrawn,
+            Stream::LEN,
+            &PROGRAM_ID,
+        );
+        account.set_data_from_slice(bytemuck::bytes_of(&state));
+        account
+    }
+
+    #[test]
+    fn test_create_stream() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let recipient = Pubkey::new_from_array([0x03; 32]);
+        let recipient_account = AccountSharedData::new(0, 0, &system_program);
+
+        let (stream, bump) = stream_address(&payer, &recipient, 7);
+        let stream_account = AccountSharedData::new(0, 0, &system_program);
+
+        let ix_data = CreateStreamInstructionData {
+            seed: 7u64.to_le_bytes(),
+            total: TOTAL.to_le_bytes(),
+            start_ts: START.to_le_bytes(),
+            end_ts: END.to_le_bytes(),
+            bump,
+        };
+        let data = [vec![2], bytemuck::bytes_of(&ix_data).to_vec()].concat();
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(recipient, false),
+                AccountMeta::new(stream, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        let rent = mollusk.sysvars.rent.minimum_balance(Stream::LEN);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (recipient, recipient_account.into()),
+                (stream, stream_account.into()),
+                (system_program, system_account),
+            ],
+            &[
+                Check::success(),
+                Check::account(&stream).owner(&PROGRAM_ID).build(),
+                Check::account(&stream).lamports(rent + TOTAL).build(),
+                Check::account(&payer)
+                    .lamports(LAMPORTS_PER_SOL - rent - TOTAL)
+                    .build(),
+            ],
+        );
+
+        let account = result.get_account(&stream).unwrap();
+        let state = bytemuck::from_bytes::<Stream>(account.data());
+        assert_eq!(state.payer, payer.to_bytes());
+        assert_eq!(state.recipient, recipient.to_bytes());
+        assert_eq!(u64::from_le_bytes(state.withdrawn), 0);
+    }
+
+    #[test]
+    fn test_create_stream_rejects_non_canonical_bump() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let recipient = Pubkey::new_from_array([0x03; 32]);
+        let recipient_account = AccountSharedData::new(0, 0, &system_program);
+
+        // The next valid bump below the canonical one derives another PDA
+        let (_, canonical_bump) = stream_address(&payer, &recipient, 7);
+        let (stream, bump) = (0..canonical_bump)
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(
+                    &[
+                        STREAM_SEED,
+                        payer.as_ref(),
+                        recipient.as_ref(),
+                        &7u64.to_le_bytes(),
+                        &[bump],
+                    ],
+                    &PROGRAM_ID,
+                )
+                .ok()
+                .map(|key| (key, bump))
+            })
+            .unwrap();
+
+        let ix_data = CreateStreamInstructionData {
+            seed: 7u64.to_le_bytes(),
+            total: TOTAL.to_le_bytes(),
+            start_ts: START.to_le_bytes(),
+            end_ts: END.to_le_bytes(),
+            bump,
+        };
+        let data = [vec![2], bytemuck::bytes_of(&ix_data).to_vec()].concat();
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(recipient, false),
+                AccountMeta::new(stream, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (recipient, recipient_account.into()),
+                (stream, AccountSharedData::new(0, 0, &system_program).into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                TransferError::NonCanonicalBump as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_withdraw_stream_follows_clock() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let recipient = Pubkey::new_from_array([0x03; 32]);
+        let recipient_account = AccountSharedData::new(0, 0, &system_program);
+
+        let (stream, _) = stream_address(&payer, &recipient, 7);
+        let rent = mollusk.sysvars.rent.minimum_balance(Stream::LEN);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[3],
+            vec![
+                AccountMeta::new(recipient, true),
+                AccountMeta::new(stream, false),
+            ],
+        );
+
+        // Before the start nothing has vested
+        mollusk.sysvars.clock.unix_timestamp = START - 10;
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (recipient, recipient_account.clone().into()),
+                (
+                    stream,
+                    stream_account(&mollusk, &payer, &recipient, 0).into(),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&recipient).lamports(0).build(),
+                Check::account(&stream).lamports(rent + TOTAL).build(),
+            ],
+        );
+
+        // A quarter of the way through, a quarter has vested
+        mollusk.sysvars.clock.unix_timestamp = START + 250;
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (recipient, recipient_account.clone().into()),
+                (
+                    stream,
+                    stream_account(&mollusk, &payer, &recipient, 0).into(),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&recipient).lamports(TOTAL / 4).build(),
+                Check::account(&stream)
+                    .lamports(rent + TOTAL * 3 / 4)
+                    .build(),
+            ],
+        );
+
+        // Past the end only the part not yet withdrawn is paid out
+        mollusk.sysvars.clock.unix_timestamp = END + 10;
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (recipient, recipient_account.into()),
+                (
+                    stream,
+                    stream_account(&mollusk, &payer, &recipient, TOTAL / 4).into(),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&recipient).lamports(TOTAL * 3 / 4).build(),
+                Check::account(&stream).lamports(rent).build(),
+            ],
+        );
+
+        let account = result.get_account(&stream).unwrap();
+        let state = bytemuck::from_bytes::<Stream>(account.data());
+        assert_eq!(u64::from_le_bytes(state.withdrawn), TOTAL);
+    }
+
+    #[test]
+    fn test_withdraw_stream_rejects_other_recipient() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let recipient = Pubkey::new_from_array([0x03; 32]);
+        let thief = Pubkey::new_from_array([0x04; 32]);
+        let thief_account = AccountSharedData::new(0, 0, &system_program);
+
+        let (stream, _) = stream_address(&payer, &recipient, 7);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[3],
+            vec![
+                AccountMeta::new(thief, true),
+                AccountMeta::new(stream, false),
+            ],
+        );
+
+        mollusk.sysvars.clock.unix_timestamp = END;
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (thief, thief_account.into()),
+                (
+                    stream,
+                    stream_account(&mollusk, &payer, &recipient, 0).into(),
+                ),
+            ],
+            &[Check::err(ProgramError::Custom(
+                TransferError::RecipientMismatch as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_cancel_stream_splits_vested_and_refund() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(0, 0, &system_program);
+
+        let recipient = Pubkey::new_from_array([0x03; 32]);
+        let recipient_account = AccountSharedData::new(0, 0, &system_program);
+
+        let (stream, _) = stream_address(&payer, &recipient, 7);
+        let rent = mollusk.sysvars.rent.minimum_balance(Stream::LEN);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[4],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(stream, false),
+            ],
+        );
+
+        // Halfway through, with a tenth already withdrawn by the recipient
+        mollusk.sysvars.clock.unix_timestamp = START + 500;
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (recipient, recipient_account.into()),
+                (
+                    stream,
+                    stream_account(&mollusk, &payer, &recipient, TOTAL / 10).into(),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&recipient)
+                    .lamports(TOTAL / 2 - TOTAL / 10)
+                    .build(),
+                Check::account(&payer).lamports(rent + TOTAL / 2).build(),
+                Check::account(&stream).lamports(0).build(),
+                Check::account(&stream).owner(&system_program).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_migrate_v1_stream() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "sol_trasnfer");
+        let logs = test_support::record_logs(&mut mollusk);
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let payer = Pubkey::new_from_array([0x02; 32]);
+        let payer_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let recipient = Pubkey::new_from_array([0x03; 32]);
+        let (stream, _) = stream_address(&payer, &recipient, 7);
+
+        // A v1 stream is the current layout minus the discriminator and version
+        let current = stream_account(&mollusk, &payer, &recipient, 0);
+        let v1_rent = mollusk.sysvars.rent.minimum_balance(Stream::V1_LEN);
+        let mut v1_account = AccountSharedData::new(v1_rent + TOTAL, Stream::V1_LEN, &PROGRAM_ID);
+        v1_account.set_data_from_slice(&current.data()[2..]);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[5],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(stream, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        // Unvested lamports stay in the stream, only the extra rent is added
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.clone().into()),
+                (stream, v1_account.into()),
+                (system_program, system_account.clone()),
+            ],
+            &[
+                Check::success(),
+                Check::account(&stream)
+                    .lamports(current.lamports())
+                    .data(current.data())
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| SolTransferEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [SolTransferEvent::StreamMigrated(StreamMigrated::new(
+                stream.to_bytes(),
+                1,
+                Stream::VERSION,
+            ))]
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (payer, payer_account.into()),
+                (stream, current.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                TransferError::AlreadyMigrated as u32,
+            ))],
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Instructions for invoking the favorites program from another program.
+//!
+//! Depend on this crate with the `cpi` feature, which leaves out the
+//! entrypoint, the allocator and the panic handler.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    ProgramResult,
+};
+
+use crate::instructions::{CreatePdaInstructionData, Instruction as FavoritesInstruction};
+
+/// Create the user's favorites PDA.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` User, pays for the account
+///   1. `[WRITE]` Favorites PDA
+///   2. `[]` System program
+pub struct CreatePda<'a> {
+    /// User, pays for the account.
+    pub user: &'a AccountInfo,
+
+    /// Favorites PDA.
+    pub favorites: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+
+    /// Favorite number.
+    pub number: u64,
+
+    /// Favorite color, zero-padded.
+    pub color: &'a [u8; 50],
+
+    /// Hobbies, each zero-padded.
+    pub hobbies: &'a [[u8; 50]; 5],
+
+    /// Canonical bump of the favorites PDA.
+    pub bump: u8,
+}
+
+impl CreatePda<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.user.key()),
+            AccountMeta::writable(self.favorites.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // - [0       ]: instruction discriminator
+        // - [1..9    ]: number
+        // - [9..59   ]: color
+        // - [59..309 ]: hobbies
+        // - [309     ]: bump
+        let mut instruction_data = [0; 1 + CreatePdaInstructionData::LEN];
+        instruction_data[0] = FavoritesInstruction::CreatePda as u8;
+        instruction_data[1..9].copy_from_slice(&self.number.to_le_bytes());
+        instruction_data[9..59].copy_from_slice(self.color);
+        for (chunk, hobby) in instruction_data[59..309]
+            .chunks_exact_mut(50)
+            .zip(self.hobbies)
+        {
+            chunk.copy_from_slice(hobby);
+        }
+        instruction_data[309] = self.bump;
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.user, self.favorites, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Log the user's favorites.
+///
+/// ### Accounts:
+///   0. `[SIGNER]` User
+///   1. `[]` Favorites PDA
+pub struct GetPda<'a> {
+    /// User.
+    pub user: &'a AccountInfo,
+
+    /// Favorites PDA.
+    pub favorites: &'a AccountInfo,
+}
+
+impl GetPda<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 2] = [
+            AccountMeta::readonly_signer(self.user.key()),
+            AccountMeta::readonly(self.favorites.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &[FavoritesInstruction::GetPda as u8],
+        };
+
+        invoke_signed(&instruction, &[self.user, self.favorites], signers)
+    }
+}
+
+/// Migrate the favorites account to the current layout.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Payer, funds the extra rent
+///   1. `[WRITE]` Favorites PDA
+///   2. `[]` System program
+pub struct Migrate<'a> {
+    /// Payer, funds the extra rent.
+    pub payer: &'a AccountInfo,
+
+    /// Favorites PDA.
+    pub favorites: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl Migrate<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.payer.key()),
+            AccountMeta::writable(self.favorites.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &[FavoritesInstruction::Migrate as u8],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.payer, self.favorites, self.system_program],
+            signers,
+        )
+    }
+}
@@ -1,12 +1,69 @@
+use core::fmt;
+
 use pinocchio::program_error::ProgramError;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum CounterError {
-    Overflow,
+/// Errors returned by the favorites program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FavoritesError {
+    NotSigner = 6000,
+    FavoritesNotWritable = 6001,
+    FavoritesAlreadyInitialized = 6002,
+    FavoritesNotInitialized = 6003,
+    InvalidFavoritesOwner = 6004,
+    InvalidFavoritesAddress = 6005,
+    InvalidFavoritesData = 6006,
+    InvalidAccountDiscriminator = 6007,
+    UnsupportedAccountVersion = 6008,
+    AlreadyMigrated = 6009,
+    NonCanonicalBump = 6010,
 }
 
-impl From<CounterError> for ProgramError {
-    fn from(_error: CounterError) -> Self {
-        ProgramError::Custom(6001) // You can use different error codes for different errors
+impl FavoritesError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::FavoritesNotWritable),
+            6002 => Some(Self::FavoritesAlreadyInitialized),
+            6003 => Some(Self::FavoritesNotInitialized),
+            6004 => Some(Self::InvalidFavoritesOwner),
+            6005 => Some(Self::InvalidFavoritesAddress),
+            6006 => Some(Self::InvalidFavoritesData),
+            6007 => Some(Self::InvalidAccountDiscriminator),
+            6008 => Some(Self::UnsupportedAccountVersion),
+            6009 => Some(Self::AlreadyMigrated),
+            6010 => Some(Self::NonCanonicalBump),
+            _ => None,
+        }
     }
-}
\ No newline at end of file
+}
+
+impl From<FavoritesError> for ProgramError {
+    fn from(error: FavoritesError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl fmt::Display for FavoritesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "User must sign the transaction",
+            Self::FavoritesNotWritable => "Favorites account must be writable",
+            Self::FavoritesAlreadyInitialized => "Favorites account is already initialized",
+            Self::FavoritesNotInitialized => "Favorites account is not initialized",
+            Self::InvalidFavoritesOwner => "Favorites account is not owned by the program",
+            Self::InvalidFavoritesAddress => "Favorites account does not match the favorites PDA",
+            Self::InvalidFavoritesData => "Favorites account data has the wrong size",
+            Self::InvalidAccountDiscriminator => "Favorites account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Favorites account layout version is not supported",
+            Self::AlreadyMigrated => "Favorites account is already on the current layout",
+            Self::NonCanonicalBump => "Bump is not the canonical bump of the favorites PDA",
+        };
+
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,111 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`FavoritesEvent::decode`].
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+pub trait Event: Pod {
+    const DISCRIMINATOR: u8;
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[bytemuck::bytes_of(self)]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct FavoritesCreated {
+    pub header: EventHeader,
+    pub favorites: Pubkey,
+    pub user: Pubkey,
+    pub number: [u8; 8],
+}
+
+impl Event for FavoritesCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl FavoritesCreated {
+    pub fn new(favorites: Pubkey, user: Pubkey, number: u64) -> Self {
+        Self {
+            header: Self::header(),
+            favorites,
+            user,
+            number: number.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct FavoritesMigrated {
+    pub header: EventHeader,
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+impl Event for FavoritesMigrated {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl FavoritesMigrated {
+    pub fn new(account: Pubkey, from_version: u8, to_version: u8) -> Self {
+        Self {
+            header: Self::header(),
+            account,
+            from_version,
+            to_version,
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FavoritesEvent {
+    Created(FavoritesCreated),
+    Migrated(FavoritesMigrated),
+}
+
+impl FavoritesEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let header = data
+            .get(..core::mem::size_of::<EventHeader>())
+            .and_then(|bytes| bytemuck::try_pod_read_unaligned::<EventHeader>(bytes).ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if header.version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match header.discriminator {
+            FavoritesCreated::DISCRIMINATOR => read(data).map(Self::Created),
+            FavoritesMigrated::DISCRIMINATOR => read(data).map(Self::Migrated),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+fn read<T: Pod>(data: &[u8]) -> Result<T, ProgramError> {
+    bytemuck::try_pod_read_unaligned(data).map_err(|_| ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,33 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    error::FavoritesError,
+    instructions::{
+        CreatePdaInstructionData, CreatePdaIxsAccounts, GetPdaIxsAccounts, Instruction,
+        MigrateIxsAccounts,
+    },
+    state::Favorites,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create_pda", Instruction::CreatePda as u8)
+            .accounts::<CreatePdaIxsAccounts>()
+            .args::<CreatePdaInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("get_pda", Instruction::GetPda as u8).accounts::<GetPdaIxsAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("migrate", Instruction::Migrate as u8).accounts::<MigrateIxsAccounts>(),
+    )
+    .account::<Favorites>(&[Favorites::DISCRIMINATOR, Favorites::VERSION])
+    .errors(FavoritesError::from_code)
+}
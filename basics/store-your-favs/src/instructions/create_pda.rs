@@ -7,42 +7,33 @@ use pinocchio::{
     sysvars::{rent::Rent, Sysvar},
     ProgramResult,
 };
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    constants::FAVORITES_SEED,
+    error::FavoritesError,
+    events::{Event, FavoritesCreated},
+    state::Favorites,
+};
 
-use crate::{constants::FAVORITES_SEED, state::Favorites};
-
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct CreatePdaIxsAccounts<'info> {
+    #[account(signer @ FavoritesError::NotSigner)]
+    #[idl(writable)]
     pub user: &'info AccountInfo,
+    #[account(
+        mut @ FavoritesError::FavoritesNotWritable,
+        empty @ FavoritesError::FavoritesAlreadyInitialized,
+    )]
     pub favorites: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for CreatePdaIxsAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, favorites, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        // check payer is signer
-        if !user.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-        // check counter is writable
-        if !favorites.is_writable() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        // check counter is not already initialized
-        if favorites.data_len() != 0 {
-            return Err(ProgramError::AccountAlreadyInitialized);
-        }
-
-        Ok(Self { user, favorites })
-    }
-}
-
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
 pub struct CreatePdaInstructionData {
+    #[idl(ty = "u64")]
     pub number: [u8; 8],
     pub color: [u8; 50],
     pub hobbies: [[u8; 50]; 5],
@@ -57,7 +48,7 @@ impl<'info> TryFrom<&'info [u8]> for CreatePdaInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
-        let result = bytemuck::try_from_bytes::<Self>(&data)
+        let result = bytemuck::try_from_bytes::<Self>(data)
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
         Ok(*result)
@@ -87,21 +78,21 @@ impl<'info> TryFrom<(&'info [AccountInfo], &'info [u8])> for CreatePda<'info> {
 
 impl<'info> CreatePda<'info> {
     pub fn handler(&mut self) -> ProgramResult {
-        let favorites_pubkey = pubkey::create_program_address(
-            &[
-                FAVORITES_SEED,
-                self.accounts.user.key().as_ref(),
-                &[self.instruction_datas.bump as u8],
-            ],
+        // Derive once here so every later instruction can trust the stored bump
+        let (favorites_pubkey, canonical_bump) = pubkey::find_program_address(
+            &[FAVORITES_SEED, self.accounts.user.key().as_ref()],
             &crate::ID,
-        )
-        .map_err(|_| ProgramError::InvalidSeeds)?;
+        );
+
+        if self.instruction_datas.bump != canonical_bump {
+            return Err(FavoritesError::NonCanonicalBump.into());
+        }
 
         if self.accounts.favorites.key() != &favorites_pubkey {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(FavoritesError::InvalidFavoritesAddress.into());
         }
 
-        let bump = [self.instruction_datas.bump as u8];
+        let bump = [self.instruction_datas.bump];
         let seed = [
             Seed::from(FAVORITES_SEED),
             Seed::from(self.accounts.user.key().as_ref()),
@@ -124,16 +115,25 @@ impl<'info> CreatePda<'info> {
             bytemuck::try_from_bytes_mut::<Favorites>(
                 self.accounts.favorites.borrow_mut_data_unchecked(),
             )
-            .map_err(|_| ProgramError::InvalidAccountData)?
+            .map_err(|_| FavoritesError::InvalidFavoritesData)?
         };
 
         favorites.set_inner(Favorites {
+            discriminator: Favorites::DISCRIMINATOR,
+            version: Favorites::VERSION,
             number: self.instruction_datas.number,
             color: self.instruction_datas.color,
             hobbies: self.instruction_datas.hobbies,
             bump: self.instruction_datas.bump,
         });
 
+        FavoritesCreated::new(
+            *self.accounts.favorites.key(),
+            *self.accounts.user.key(),
+            u64::from_le_bytes(self.instruction_datas.number),
+        )
+        .emit();
+
         Ok(())
     }
 }
\ No newline at end of file
@@ -1,40 +1,21 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey, ProgramResult};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 use pinocchio_log::log;
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
 
-use crate::{constants::FAVORITES_SEED, state::{favorites, Favorites}};
+use crate::{error::FavoritesError, state::Favorites};
 
+#[derive(Accounts, IdlAccounts)]
 pub struct GetPdaIxsAccounts<'info> {
+    #[account(signer @ FavoritesError::NotSigner)]
     pub user: &'info AccountInfo,
+    #[account(
+        initialized @ FavoritesError::FavoritesNotInitialized,
+        owner = crate::ID @ FavoritesError::InvalidFavoritesOwner,
+    )]
     pub favorites: &'info AccountInfo,
 }
 
-impl<'info> TryFrom<&'info [AccountInfo]> for GetPdaIxsAccounts<'info> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, favorites] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        // check payer is signer
-        if !user.is_signer() {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        // check account is not already initialized
-        if favorites.data_len() == 0 {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if !favorites.is_owned_by(&crate::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
-
-
-        Ok(Self { user, favorites})
-    }
-}
-
 pub struct GetPda<'info> {
     pub accounts: GetPdaIxsAccounts<'info>,
 }
@@ -51,19 +32,10 @@ impl<'info> TryFrom<&'info [AccountInfo]> for GetPda<'info> {
 
 impl<'info> GetPda<'info> {
     pub fn handler(&mut self) -> ProgramResult {
-        let favorites = unsafe {
-            bytemuck::try_from_bytes_mut::<Favorites>(
-                self.accounts.favorites.borrow_mut_data_unchecked(),
-            )
-            .map_err(|_| ProgramError::InvalidAccountData)?
-        };
-
-        let seeds = &[FAVORITES_SEED, self.accounts.user.key().as_ref()];
-        let (favorites_pubkey, _) = pubkey::find_program_address(seeds, &crate::ID);
+        let favorites =
+            unsafe { Favorites::load(self.accounts.favorites.borrow_data_unchecked())? };
 
-        if self.accounts.favorites.key().ne(&favorites_pubkey) {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        favorites.check_address(self.accounts.user.key(), self.accounts.favorites.key())?;
 
         log!(
             "User {}'s favorite number is {}, favorite color ir: {}",
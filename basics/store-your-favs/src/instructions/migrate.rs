@@ -0,0 +1,83 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    error::FavoritesError,
+    events::{Event, FavoritesMigrated},
+    state::Favorites,
+};
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
+pub struct MigrateIxsAccounts<'info> {
+    /// Funds the extra rent for the larger layout.
+    #[account(signer @ FavoritesError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'info AccountInfo,
+    #[account(
+        mut @ FavoritesError::FavoritesNotWritable,
+        owner = crate::ID @ FavoritesError::InvalidFavoritesOwner,
+    )]
+    pub favorites: &'info AccountInfo,
+}
+
+pub struct Migrate<'info> {
+    pub accounts: MigrateIxsAccounts<'info>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for Migrate<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateIxsAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'info> Migrate<'info> {
+    /// Upgrades an untagged v1 favorites account to the tagged v2 layout in place.
+    pub fn handler(&mut self) -> ProgramResult {
+        match self.accounts.favorites.data_len() {
+            Favorites::V1_LEN => {}
+            // only a tagged account of the current size counts as migrated
+            Favorites::LEN => {
+                if self.accounts.favorites.try_borrow_data()?[0] != Favorites::DISCRIMINATOR {
+                    return Err(FavoritesError::InvalidAccountDiscriminator.into());
+                }
+                return Err(FavoritesError::AlreadyMigrated.into());
+            }
+            _ => return Err(FavoritesError::InvalidFavoritesData.into()),
+        }
+
+        // top up rent for the two extra header bytes
+        let minimum_balance = Rent::get()?.minimum_balance(Favorites::LEN);
+        let lamports = self.accounts.favorites.lamports();
+        if lamports < minimum_balance {
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.favorites,
+                lamports: minimum_balance - lamports,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.favorites.realloc(Favorites::LEN, false)?;
+
+        // shift the v1 fields behind the header, then tag the account
+        let mut data = self.accounts.favorites.try_borrow_mut_data()?;
+        data.copy_within(0..Favorites::V1_LEN, Favorites::LEN - Favorites::V1_LEN);
+        data[0] = Favorites::DISCRIMINATOR;
+        data[1] = Favorites::VERSION;
+
+        FavoritesMigrated::new(*self.accounts.favorites.key(), 1, Favorites::VERSION).emit();
+
+        Ok(())
+    }
+}
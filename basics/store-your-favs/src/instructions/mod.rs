@@ -3,12 +3,16 @@ pub use create_pda::*;
 
 pub mod get_pda;
 pub use get_pda::*;
+
+pub mod migrate;
+pub use migrate::*;
 use pinocchio::program_error::ProgramError;
 
 #[repr(u8)]
 pub enum Instruction {
     CreatePda,
     GetPda,
+    Migrate,
 }
 
 impl TryFrom<&u8> for Instruction {
@@ -18,6 +22,7 @@ impl TryFrom<&u8> for Instruction {
         match *value {
             0 => Ok(Instruction::CreatePda),
             1 => Ok(Instruction::GetPda),
+            2 => Ok(Instruction::Migrate),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -1,16 +1,26 @@
 #![no_std]
 #![allow(unexpected_cfgs)]
+#[cfg(not(feature = "no-entrypoint"))]
 use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
+#[cfg(not(feature = "no-entrypoint"))]
 use processor::process_instruction;
 
 pub mod constants;
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod error;
+pub mod events;
+#[cfg(feature = "idl-build")]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
 pub mod state;
 
 pinocchio_pubkey::declare_id!("21cBfH1aaKVeq86icK5pq51FM3ak2QQJ1TWoMLcTTK34");
 
+#[cfg(not(feature = "no-entrypoint"))]
 program_entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
 no_allocator!();
+#[cfg(not(feature = "no-entrypoint"))]
 nostd_panic_handler!();
\ No newline at end of file
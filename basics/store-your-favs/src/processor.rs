@@ -4,7 +4,7 @@ use pinocchio::{
 
 use pinocchio_log::log;
 
-use crate::instructions::{create_pda, get_pda, Instruction};
+use crate::instructions::{create_pda, get_pda, migrate, Instruction};
 
 #[inline(always)]
 pub fn process_instruction(
@@ -19,7 +19,7 @@ pub fn process_instruction(
 
     let (discriminator, data) = instruction_data
         .split_first()
-        .ok_or(ProgramError::IncorrectProgramId)?;
+        .ok_or(ProgramError::InvalidInstructionData)?;
 
     match Instruction::try_from(discriminator)? {
         Instruction::CreatePda => {
@@ -31,6 +31,11 @@ pub fn process_instruction(
             log!("Instruction: GetPda");
             get_pda::GetPda::try_from(accounts)?.handler()
         }
+
+        Instruction::Migrate => {
+            log!("Instruction: Migrate");
+            migrate::Migrate::try_from(accounts)?.handler()
+        }
     }
 
 }
\ No newline at end of file
@@ -1,8 +1,20 @@
 use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+};
+use idl_derive::IdlType;
+
+use crate::{constants::FAVORITES_SEED, error::FavoritesError};
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, IdlType)]
 pub struct Favorites {
+    #[idl(skip)]
+    pub discriminator: u8,
+    #[idl(skip)]
+    pub version: u8,
+    #[idl(ty = "u64")]
     pub number: [u8; 8],
     pub color: [u8; 50],
     pub hobbies: [[u8; 50]; 5],
@@ -11,8 +23,41 @@ pub struct Favorites {
 
 impl Favorites {
     pub const LEN: usize = core::mem::size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 2;
+    /// Size of the untagged v1 layout, which started directly at `number`.
+    pub const V1_LEN: usize = Self::LEN - 2;
+
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        let favorites = bytemuck::try_from_bytes::<Self>(data)
+            .map_err(|_| FavoritesError::InvalidFavoritesData)?;
+
+        if favorites.discriminator != Self::DISCRIMINATOR {
+            return Err(FavoritesError::InvalidAccountDiscriminator.into());
+        }
+        if favorites.version != Self::VERSION {
+            return Err(FavoritesError::UnsupportedAccountVersion.into());
+        }
+
+        Ok(favorites)
+    }
+
+    /// Check that `key` is the favorites PDA of `user`, derived from the stored bump.
+    pub fn check_address(&self, user: &Pubkey, key: &Pubkey) -> Result<(), ProgramError> {
+        let favorites_pubkey =
+            pubkey::create_program_address(&[FAVORITES_SEED, user, &[self.bump]], &crate::ID)
+                .map_err(|_| FavoritesError::InvalidFavoritesAddress)?;
+
+        if key.ne(&favorites_pubkey) {
+            return Err(FavoritesError::InvalidFavoritesAddress.into());
+        }
+
+        Ok(())
+    }
 
     pub fn set_inner(&mut self,data: Self) -> Self {
+        self.discriminator = data.discriminator;
+        self.version = data.version;
         self.number = data.number;
         self.color = data.color;
         self.hobbies = data.hobbies;
@@ -2,39 +2,35 @@
 mod tests {
 
     use favorites::{
-        constants::FAVORITES_SEED, instructions::CreatePdaInstructionData, state::Favorites, ID,
-    };
-    use mollusk_svm::{
-        result::{Check, ProgramResult},
-        Mollusk,
+        constants::FAVORITES_SEED,
+        error::FavoritesError,
+        events::{FavoritesEvent, FavoritesMigrated},
+        instructions::CreatePdaInstructionData,
+        state::Favorites,
+        ID,
     };
+    use mollusk_svm::result::{Check, ProgramResult};
 
     use solana_sdk::{
         account::AccountSharedData,
-        address_lookup_table::instruction,
         instruction::{AccountMeta, Instruction},
         native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
         pubkey::Pubkey,
-        system_program,
     };
+    use test_support::create_padded_array;
 
     pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
 
     #[test]
     fn test_create_pda() {
-        pub fn create_padded_array<const N: usize>(data: &[u8], size: usize) -> [u8; N] {
-            let mut result = [0u8; N];
-            let copy_size = data.len().min(size).min(N);
-            result[..copy_size].copy_from_slice(&data[..copy_size]);
-            result
-        }
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/favorites");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
 
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let user = Pubkey::new_from_array([0x02; 32]);
-        let user_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let user_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
         let (favorites_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
             &[FAVORITES_SEED, user.as_ref()],
@@ -44,6 +40,8 @@ mod tests {
         let favorites_account = AccountSharedData::new(0, 0, &system_program);
 
         let favorites_state = Favorites {
+            discriminator: Favorites::DISCRIMINATOR,
+            version: Favorites::VERSION,
             number: 1u64.to_le_bytes(),
             color: create_padded_array(b"#FFFFFF", 50),
             hobbies: [
@@ -110,19 +108,13 @@ mod tests {
 
     #[test]
     fn test_get_pda() {
-        pub fn create_padded_array<const N: usize>(data: &[u8], size: usize) -> [u8; N] {
-            let mut result = [0u8; N];
-            let copy_size = data.len().min(size).min(N);
-            result[..copy_size].copy_from_slice(&data[..copy_size]);
-            result
-        }
-        let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/favorites");
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
 
         let (system_program, _system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
         let user = Pubkey::new_from_array([0x02; 32]);
-        let user_account = AccountSharedData::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+        let user_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
 
         let (favorites_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
             &[FAVORITES_SEED, user.as_ref()],
@@ -130,6 +122,8 @@ mod tests {
         );
 
         let favorites_state = Favorites {
+            discriminator: Favorites::DISCRIMINATOR,
+            version: Favorites::VERSION,
             number: 1u64.to_le_bytes(),
             color: create_padded_array(b"#FFFFFF", 50),
             hobbies: [
@@ -188,4 +182,130 @@ mod tests {
             assert!(result.program_result == ProgramResult::Success);
         }
     }
+
+    #[test]
+    fn test_migrate_v1_favorites() {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
+        let logs = test_support::record_logs(&mut mollusk);
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let user = Pubkey::new_from_array([0x02; 32]);
+        let user_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (favorites_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
+            &[FAVORITES_SEED, user.as_ref()],
+            &PROGRAM_ID,
+        );
+
+        let favorites_state = Favorites {
+            discriminator: Favorites::DISCRIMINATOR,
+            version: Favorites::VERSION,
+            number: 1u64.to_le_bytes(),
+            color: create_padded_array(b"#FFFFFF", 50),
+            hobbies: [create_padded_array(b"Reading", 50); 5],
+            bump,
+        };
+
+        // v1 favorites are the current layout minus the discriminator and version
+        let mut favorites_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Favorites::V1_LEN),
+            Favorites::V1_LEN,
+            &PROGRAM_ID,
+        );
+        favorites_account.set_data_from_slice(&bytemuck::bytes_of(&favorites_state)[2..]);
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[2],
+            vec![
+                AccountMeta::new(user, true),
+                AccountMeta::new(favorites_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (user, user_account.into()),
+                (favorites_pubkey, favorites_account.into()),
+                (system_program, system_account),
+            ],
+            &[
+                Check::success(),
+                Check::account(&favorites_pubkey)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(Favorites::LEN))
+                    .data(bytemuck::bytes_of(&favorites_state))
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| FavoritesEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [FavoritesEvent::Migrated(FavoritesMigrated::new(
+                favorites_pubkey.to_bytes(),
+                1,
+                Favorites::VERSION,
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_wrong_discriminator() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let user = Pubkey::new_from_array([0x02; 32]);
+        let user_account = AccountSharedData::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (favorites_pubkey, bump) = solana_sdk::pubkey::Pubkey::find_program_address(
+            &[FAVORITES_SEED, user.as_ref()],
+            &PROGRAM_ID,
+        );
+
+        // current length, but not tagged as a favorites account
+        let favorites_state = Favorites {
+            discriminator: Favorites::DISCRIMINATOR + 1,
+            version: Favorites::VERSION,
+            number: 1u64.to_le_bytes(),
+            color: create_padded_array(b"#FFFFFF", 50),
+            hobbies: [create_padded_array(b"Reading", 50); 5],
+            bump,
+        };
+        let mut favorites_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Favorites::LEN),
+            Favorites::LEN,
+            &PROGRAM_ID,
+        );
+        favorites_account.set_data_from_slice(bytemuck::bytes_of(&favorites_state));
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[2],
+            vec![
+                AccountMeta::new(user, true),
+                AccountMeta::new(favorites_pubkey, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        );
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (user, user_account.into()),
+                (favorites_pubkey, favorites_account.into()),
+                (system_program, system_account),
+            ],
+            &[Check::err(ProgramError::Custom(
+                FavoritesError::InvalidAccountDiscriminator as u32,
+            ))],
+        );
+    }
 }
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use favorites::{
+        constants::FAVORITES_SEED,
+        instructions::{CreatePdaInstructionData, Instruction},
+        state::Favorites,
+        ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, check_rejected, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 3;
+
+    fn fuzzer() -> Fuzzer {
+        let user = Pubkey::new_unique();
+        let favorites =
+            Pubkey::find_program_address(&[FAVORITES_SEED, user.as_ref()], &PROGRAM_ID).0;
+        Fuzzer::new(PROGRAM_ID, vec![user, favorites, Pubkey::new_unique()])
+            .data_lens(&[Favorites::LEN, Favorites::V1_LEN])
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_pda_data(data in vec(any::<u8>(), 0..=CreatePdaInstructionData::LEN + 8)) {
+            let parsed = CreatePdaInstructionData::try_from(data.as_slice());
+            prop_assert_eq!(parsed.is_ok(), data.len() == CreatePdaInstructionData::LEN);
+        }
+
+        #[test]
+        fn test_parse_discriminator(discriminator in any::<u8>()) {
+            let parsed = Instruction::try_from(&discriminator);
+            prop_assert_eq!(parsed.is_ok(), discriminator < INSTRUCTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
+        let fuzzer = fuzzer();
+        let data = vec(any::<u8>(), 0..=CreatePdaInstructionData::LEN + 8);
+        fuzz(fuzzer.cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_pda_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "favorites");
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreatePdaInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
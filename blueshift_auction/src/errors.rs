@@ -0,0 +1,94 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+use program_helpers::HelperError;
+
+/// Errors returned by the auction program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuctionError {
+    NotSigner = 6000,
+    InvalidAddress = 6001,
+    InvalidMint = 6002,
+    InvalidTokenAccount = 6003,
+    InvalidAuctionAccount = 6004,
+    InvalidAuctionAddress = 6005,
+    ZeroAmount = 6006,
+    InvalidAccountDiscriminator = 6007,
+    UnsupportedAccountVersion = 6008,
+    InvalidAuction = 6009,
+    AuctionEnded = 6010,
+    AuctionNotEnded = 6011,
+    BidTooLow = 6012,
+    InvalidBidder = 6013,
+}
+
+impl AuctionError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidAddress),
+            6002 => Some(Self::InvalidMint),
+            6003 => Some(Self::InvalidTokenAccount),
+            6004 => Some(Self::InvalidAuctionAccount),
+            6005 => Some(Self::InvalidAuctionAddress),
+            6006 => Some(Self::ZeroAmount),
+            6007 => Some(Self::InvalidAccountDiscriminator),
+            6008 => Some(Self::UnsupportedAccountVersion),
+            6009 => Some(Self::InvalidAuction),
+            6010 => Some(Self::AuctionEnded),
+            6011 => Some(Self::AuctionNotEnded),
+            6012 => Some(Self::BidTooLow),
+            6013 => Some(Self::InvalidBidder),
+            _ => None,
+        }
+    }
+}
+
+impl From<AuctionError> for ProgramError {
+    fn from(error: AuctionError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl HelperError for AuctionError {
+    const NOT_SIGNER: Self = Self::NotSigner;
+    const INVALID_MINT: Self = Self::InvalidMint;
+    const INVALID_TOKEN_ACCOUNT: Self = Self::InvalidTokenAccount;
+    const INVALID_ADDRESS: Self = Self::InvalidAddress;
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self = Self::InvalidAccountDiscriminator;
+    const UNSUPPORTED_ACCOUNT_VERSION: Self = Self::UnsupportedAccountVersion;
+}
+
+impl fmt::Display for AuctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Account must sign the transaction",
+            Self::InvalidAddress => "Associated token account does not match its expected address",
+            Self::InvalidMint => "Account is not a valid SPL Token or Token-2022 mint",
+            Self::InvalidTokenAccount => {
+                "Account is not a valid SPL Token or Token-2022 token account"
+            }
+            Self::InvalidAuctionAccount => {
+                "Auction account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidAuctionAddress => "Auction account does not match its PDA",
+            Self::ZeroAmount => "Amount must be greater than zero",
+            Self::InvalidAccountDiscriminator => "Auction account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Auction account layout version is not supported",
+            Self::InvalidAuction => {
+                "Auction must end in the future, with a nonzero increment and extension >= 0"
+            }
+            Self::AuctionEnded => "Auction has already ended",
+            Self::AuctionNotEnded => "Auction has not ended yet",
+            Self::BidTooLow => "Bid is below the minimum bid or the minimum increment",
+            Self::InvalidBidder => "Account is not the auction's high bidder",
+        };
+
+        f.write_str(message)
+    }
+}
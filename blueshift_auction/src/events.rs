@@ -0,0 +1,171 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`AuctionEvent::decode`].
+
+use core::mem::size_of;
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and made only of byte arrays, so that
+/// every bit pattern is valid and the struct has no padding.
+pub unsafe trait Event: Copy + Sized {
+    const DISCRIMINATOR: u8;
+    const LEN: usize = size_of::<Self>();
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    #[inline(always)]
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[self.as_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuctionCreated {
+    pub header: EventHeader,
+    pub auction: Pubkey,
+    pub seller: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: [u8; 8],
+    pub min_bid: [u8; 8],
+    pub end_ts: [u8; 8],
+}
+
+unsafe impl Event for AuctionCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl AuctionCreated {
+    pub fn new(
+        auction: Pubkey,
+        seller: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        amount: u64,
+        min_bid: u64,
+        end_ts: i64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            auction,
+            seller,
+            mint_a,
+            mint_b,
+            amount: amount.to_le_bytes(),
+            min_bid: min_bid.to_le_bytes(),
+            end_ts: end_ts.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BidPlaced {
+    pub header: EventHeader,
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: [u8; 8],
+    pub end_ts: [u8; 8],
+}
+
+unsafe impl Event for BidPlaced {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl BidPlaced {
+    pub fn new(auction: Pubkey, bidder: Pubkey, amount: u64, end_ts: i64) -> Self {
+        Self {
+            header: Self::header(),
+            auction,
+            bidder,
+            amount: amount.to_le_bytes(),
+            end_ts: end_ts.to_le_bytes(),
+        }
+    }
+}
+
+/// `winner` is the seller and `amount` is 0 when nobody bid.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuctionSettled {
+    pub header: EventHeader,
+    pub auction: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Pubkey,
+    pub amount: [u8; 8],
+}
+
+unsafe impl Event for AuctionSettled {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl AuctionSettled {
+    pub fn new(auction: Pubkey, seller: Pubkey, winner: Pubkey, amount: u64) -> Self {
+        Self {
+            header: Self::header(),
+            auction,
+            seller,
+            winner,
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionEvent {
+    AuctionCreated(AuctionCreated),
+    BidPlaced(BidPlaced),
+    AuctionSettled(AuctionSettled),
+}
+
+impl AuctionEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let [version, discriminator, ..] = data else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        if *version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match *discriminator {
+            AuctionCreated::DISCRIMINATOR => AuctionCreated::read(data).map(Self::AuctionCreated),
+            BidPlaced::DISCRIMINATOR => BidPlaced::read(data).map(Self::BidPlaced),
+            AuctionSettled::DISCRIMINATOR => AuctionSettled::read(data).map(Self::AuctionSettled),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
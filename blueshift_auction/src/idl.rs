@@ -0,0 +1,32 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    AccountLayout, Auction, AuctionError, Bid, BidAccounts, BidInstructionData, CreateAuction,
+    CreateAuctionAccounts, CreateAuctionInstructionData, SettleAuction, SettleAuctionAccounts,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create_auction", *CreateAuction::DISCRIMINATOR)
+            .accounts::<CreateAuctionAccounts>()
+            .args::<CreateAuctionInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("bid", *Bid::DISCRIMINATOR)
+            .accounts::<BidAccounts>()
+            .args::<BidInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("settle_auction", *SettleAuction::DISCRIMINATOR)
+            .accounts::<SettleAuctionAccounts>(),
+    )
+    .account::<Auction>(&[Auction::DISCRIMINATOR, Auction::VERSION])
+    .errors(AuctionError::from_code)
+}
@@ -0,0 +1,195 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Auction, AuctionAccount, AuctionError,
+    events::{BidPlaced, Event},
+};
+
+/// `previous_bidder` and `previous_bidder_ata_b` are left unread on the first
+/// bid; afterwards they must belong to the current high bidder.
+#[derive(Accounts, IdlAccounts)]
+pub struct BidAccounts<'a> {
+    #[account(signer @ AuctionError::NotSigner)]
+    #[idl(writable)]
+    pub bidder: &'a AccountInfo,
+    #[account(check = AuctionAccount::check(auction))]
+    #[idl(writable)]
+    pub auction: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(bidder_ata_b, bidder, mint_b, token_program))]
+    #[idl(writable)]
+    pub bidder_ata_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault_b, auction, mint_b, token_program))]
+    #[idl(writable)]
+    pub vault_b: &'a AccountInfo,
+    pub previous_bidder: &'a AccountInfo,
+    #[idl(writable)]
+    pub previous_bidder_ata_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct BidInstructionData {
+    /// Bid in mint B, escrowed until outbid or settled.
+    pub amount: u64,
+}
+
+impl BidInstructionData {
+    pub const LEN: usize = size_of::<u64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for BidInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data.try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(AuctionError::ZeroAmount.into());
+        }
+
+        Ok(Self { amount })
+    }
+}
+
+pub struct Bid<'a> {
+    pub accounts: BidAccounts<'a>,
+    pub instruction_data: BidInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Bid<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = BidAccounts::try_from(accounts)?;
+        let instruction_data = BidInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Bid<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let amount = self.instruction_data.amount;
+        let now = Clock::get()?.unix_timestamp;
+
+        let data = self.accounts.auction.try_borrow_data()?;
+        let auction = Auction::load(&data)?;
+
+        // Check if the auction is valid
+        let auction_key = create_program_address(
+            &[b"auction", &auction.seller, &auction.seed, &auction.bump],
+            &crate::ID,
+        )?;
+        if &auction_key != self.accounts.auction.key() {
+            return Err(AuctionError::InvalidAuctionAddress.into());
+        }
+        if self.accounts.mint_b.key().ne(&auction.mint_b) {
+            return Err(AuctionError::InvalidMint.into());
+        }
+
+        if now >= auction.end_ts() {
+            return Err(AuctionError::AuctionEnded.into());
+        }
+        if amount < auction.next_min_bid()? {
+            return Err(AuctionError::BidTooLow.into());
+        }
+
+        // A bid inside the anti-sniping window keeps the auction open a while longer
+        let end_ts = match auction.extension() {
+            0 => auction.end_ts(),
+            extension => auction.end_ts().max(now.saturating_add(extension)),
+        };
+
+        let refund = auction.has_bid().then_some(auction.high_bid());
+        if refund.is_some() && self.accounts.previous_bidder.key().ne(&auction.high_bidder) {
+            return Err(AuctionError::InvalidBidder.into());
+        }
+
+        let seller_binding = auction.seller;
+        let seed_binding = auction.seed;
+        let bump_binding = auction.bump;
+        drop(data);
+
+        // Escrow the new bid
+        Transfer {
+            from: self.accounts.bidder_ata_b,
+            to: self.accounts.vault_b,
+            authority: self.accounts.bidder,
+            amount,
+        }
+        .invoke()?;
+
+        // Hand the outbid bidder their tokens back
+        if let Some(refund) = refund {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.previous_bidder_ata_b,
+                self.accounts.mint_b,
+                self.accounts.bidder,
+                self.accounts.previous_bidder,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            let auction_seeds = [
+                Seed::from(b"auction"),
+                Seed::from(seller_binding.as_ref()),
+                Seed::from(&seed_binding),
+                Seed::from(&bump_binding),
+            ];
+            let signer = Signer::from(&auction_seeds);
+
+            Transfer {
+                from: self.accounts.vault_b,
+                to: self.accounts.previous_bidder_ata_b,
+                authority: self.accounts.auction,
+                amount: refund,
+            }
+            .invoke_signed(&[signer])?;
+        }
+
+        // Record the new high bid
+        let mut data = self.accounts.auction.try_borrow_mut_data()?;
+        let auction = Auction::load_mut(data.as_mut())?;
+
+        auction.set_high_bid(*self.accounts.bidder.key(), amount);
+        auction.set_end_ts(end_ts);
+
+        BidPlaced::new(
+            *self.accounts.auction.key(),
+            *self.accounts.bidder.key(),
+            amount,
+            end_ts,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Auction, AuctionError, MintInterface, ProgramAccount,
+    ProgramAccountInit,
+    events::{AuctionCreated, Event},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct CreateAuctionAccounts<'a> {
+    #[account(signer @ AuctionError::NotSigner)]
+    #[idl(writable)]
+    pub seller: &'a AccountInfo,
+    #[idl(writable)]
+    pub auction: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_a))]
+    pub mint_a: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_b))]
+    pub mint_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(seller_ata_a, seller, mint_a, token_program))]
+    #[idl(writable)]
+    pub seller_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+/// `extension` of 0 turns anti-sniping off; otherwise a bid placed within
+/// `extension` seconds of the end pushes the end to `extension` seconds away.
+#[derive(IdlType)]
+pub struct CreateAuctionInstructionData {
+    pub seed: u64,
+    /// Amount of mint A being auctioned.
+    pub amount: u64,
+    /// Smallest first bid, in mint B.
+    pub min_bid: u64,
+    /// Smallest raise over the high bid, in mint B.
+    pub min_increment: u64,
+    /// Unix time bidding closes.
+    pub end_ts: i64,
+    /// Anti-sniping window in seconds.
+    pub extension: i64,
+}
+
+impl CreateAuctionInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 4 + size_of::<i64>() * 2;
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateAuctionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let min_bid = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        let min_increment = u64::from_le_bytes(data[24..32].try_into().unwrap());
+        let end_ts = i64::from_le_bytes(data[32..40].try_into().unwrap());
+        let extension = i64::from_le_bytes(data[40..48].try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(AuctionError::ZeroAmount.into());
+        }
+        // Every bid after the first must strictly beat the one before it
+        if min_increment == 0 || extension < 0 {
+            return Err(AuctionError::InvalidAuction.into());
+        }
+
+        Ok(Self {
+            seed,
+            amount,
+            min_bid,
+            min_increment,
+            end_ts,
+            extension,
+        })
+    }
+}
+
+pub struct CreateAuction<'a> {
+    pub accounts: CreateAuctionAccounts<'a>,
+    pub instruction_data: CreateAuctionInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateAuction<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = CreateAuctionAccounts::try_from(accounts)?;
+        let instruction_data = CreateAuctionInstructionData::try_from(data)?;
+
+        if instruction_data.end_ts <= Clock::get()?.unix_timestamp {
+            return Err(AuctionError::InvalidAuction.into());
+        }
+
+        // Initialize the Accounts needed
+        let (auction_key, bump) = find_program_address(
+            &[
+                b"auction",
+                accounts.seller.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &auction_key != accounts.auction.key() {
+            return Err(AuctionError::InvalidAuctionAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let auction_seeds = [
+            Seed::from(b"auction"),
+            Seed::from(accounts.seller.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<Auction>(
+            accounts.seller,
+            accounts.auction,
+            &auction_seeds,
+            Auction::LEN,
+        )?;
+
+        // Both vaults exist up front so bidders never pay for them
+        AssociatedTokenAccount::init(
+            accounts.vault_a,
+            accounts.mint_a,
+            accounts.seller,
+            accounts.auction,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        AssociatedTokenAccount::init(
+            accounts.vault_b,
+            accounts.mint_b,
+            accounts.seller,
+            accounts.auction,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> CreateAuction<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the auction account
+        let mut data = self.accounts.auction.try_borrow_mut_data()?;
+        let auction = Auction::init(data.as_mut())?;
+
+        auction.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.seller.key(),
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            [self.bump],
+        );
+        auction.set_terms(
+            self.instruction_data.min_bid,
+            self.instruction_data.min_increment,
+            self.instruction_data.end_ts,
+            self.instruction_data.extension,
+        );
+        drop(data);
+
+        // Deposit the asset
+        Transfer {
+            from: self.accounts.seller_ata_a,
+            to: self.accounts.vault_a,
+            authority: self.accounts.seller,
+            amount: self.instruction_data.amount,
+        }
+        .invoke()?;
+
+        AuctionCreated::new(
+            *self.accounts.auction.key(),
+            *self.accounts.seller.key(),
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            self.instruction_data.amount,
+            self.instruction_data.min_bid,
+            self.instruction_data.end_ts,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+pub use program_helpers::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, ProgramAccountInit, TOKEN_2022_MINT_DISCRIMINATOR,
+    TOKEN_2022_PROGRAM_ID,
+};
+
+use crate::{Auction, AuctionError};
+
+pub type SignerAccount = program_helpers::SignerAccount<AuctionError>;
+pub type MintInterface = program_helpers::MintInterface<AuctionError>;
+pub type TokenAccount = program_helpers::TokenAccount<AuctionError>;
+pub type AssociatedTokenAccount = program_helpers::AssociatedTokenAccount<AuctionError>;
+
+pub type ProgramAccount = program_helpers::ProgramAccount<Auction>;
+pub type AuctionAccount = program_helpers::StateAccount<Auction>;
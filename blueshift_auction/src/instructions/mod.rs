@@ -0,0 +1,9 @@
+pub mod bid;
+pub mod create_auction;
+pub mod helpers;
+pub mod settle_auction;
+
+pub use bid::*;
+pub use create_auction::*;
+pub use helpers::*;
+pub use settle_auction::*;
@@ -0,0 +1,158 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    Auction, AuctionAccount, AuctionError, DrainVault, ProgramAccount,
+    events::{AuctionSettled, Event},
+};
+
+/// Anyone may settle once bidding has closed. `winner` is the high bidder,
+/// or the seller when nobody bid. `seller_ata_b` is only touched when
+/// `vault_b` holds tokens.
+#[derive(Accounts, IdlAccounts)]
+pub struct SettleAuctionAccounts<'a> {
+    #[account(signer @ AuctionError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'a AccountInfo,
+    #[idl(writable)]
+    pub seller: &'a AccountInfo,
+    #[account(check = AuctionAccount::check(auction))]
+    #[idl(writable)]
+    pub auction: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault_a, auction, mint_a, token_program))]
+    #[idl(writable)]
+    pub vault_a: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault_b, auction, mint_b, token_program))]
+    #[idl(writable)]
+    pub vault_b: &'a AccountInfo,
+    pub winner: &'a AccountInfo,
+    #[idl(writable)]
+    pub winner_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub seller_ata_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct SettleAuction<'a> {
+    pub accounts: SettleAuctionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SettleAuction<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SettleAuctionAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SettleAuction<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.auction.try_borrow_data()?;
+        let auction = Auction::load(&data)?;
+
+        // Check if the auction is valid
+        let auction_key = create_program_address(
+            &[
+                b"auction",
+                self.accounts.seller.key(),
+                &auction.seed,
+                &auction.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &auction_key != self.accounts.auction.key() {
+            return Err(AuctionError::InvalidAuctionAddress.into());
+        }
+        if self.accounts.mint_a.key().ne(&auction.mint_a)
+            || self.accounts.mint_b.key().ne(&auction.mint_b)
+        {
+            return Err(AuctionError::InvalidMint.into());
+        }
+
+        if Clock::get()?.unix_timestamp < auction.end_ts() {
+            return Err(AuctionError::AuctionNotEnded.into());
+        }
+
+        // Without a bid the asset goes back to the seller
+        let proceeds = auction.has_bid().then_some(auction.high_bid());
+        let winner = match proceeds {
+            Some(_) => &auction.high_bidder,
+            None => self.accounts.seller.key(),
+        };
+        if self.accounts.winner.key().ne(winner) {
+            return Err(AuctionError::InvalidBidder.into());
+        }
+
+        let seed_binding = auction.seed;
+        let bump_binding = auction.bump;
+        drop(data);
+
+        let auction_seeds = [
+            Seed::from(b"auction"),
+            Seed::from(self.accounts.seller.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&auction_seeds);
+
+        // Deliver the asset, and pay the seller the high bid
+        DrainVault {
+            vault: self.accounts.vault_a,
+            to: self.accounts.winner_ata_a,
+            owner: self.accounts.winner,
+            mint: self.accounts.mint_a,
+            payer: self.accounts.payer,
+            destination: self.accounts.seller,
+            authority: self.accounts.auction,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        DrainVault {
+            vault: self.accounts.vault_b,
+            to: self.accounts.seller_ata_b,
+            owner: self.accounts.seller,
+            mint: self.accounts.mint_b,
+            payer: self.accounts.payer,
+            destination: self.accounts.seller,
+            authority: self.accounts.auction,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        // Close the Auction
+        ProgramAccount::close(self.accounts.auction, self.accounts.seller)?;
+
+        AuctionSettled::new(
+            *self.accounts.auction.key(),
+            *self.accounts.seller.key(),
+            *self.accounts.winner.key(),
+            proceeds.unwrap_or(0),
+        )
+        .emit();
+
+        Ok(())
+    }
+}
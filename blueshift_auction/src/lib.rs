@@ -0,0 +1,41 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, entrypoint, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+pub mod errors;
+pub use errors::*;
+
+pub mod events;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
+// 55555555555555555555555555555555555555555555
+pub const ID: Pubkey = [
+    0x3c, 0x79, 0xac, 0x50, 0x87, 0x01, 0x28, 0x1c, 0x10, 0xc4, 0x99, 0x70, 0x67, 0x16, 0xef, 0xb8,
+    0x66, 0x4a, 0xeb, 0xa2, 0xbf, 0x47, 0x34, 0x1e, 0x3b, 0xe2, 0xbd, 0xc1, 0x1f, 0x70, 0x47, 0xdc,
+];
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.split_first() {
+        Some((CreateAuction::DISCRIMINATOR, data)) => {
+            CreateAuction::try_from((data, accounts))?.process()
+        }
+        Some((Bid::DISCRIMINATOR, data)) => Bid::try_from((data, accounts))?.process(),
+        Some((SettleAuction::DISCRIMINATOR, _)) => SettleAuction::try_from(accounts)?.process(),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
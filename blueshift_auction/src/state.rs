@@ -0,0 +1,145 @@
+use core::mem::size_of;
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use idl_derive::IdlType;
+
+use crate::{AccountLayout, AuctionError};
+
+/// An English auction of a deposit of `mint_a`, with bids in `mint_b`. The
+/// asset and the high bid sit in the auction's associated token accounts.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct Auction {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `Auction::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `Auction::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub seller: Pubkey,    // Creator of the auction
+    #[idl(ty = "pubkey")]
+    pub mint_a: Pubkey,    // Token being auctioned
+    #[idl(ty = "pubkey")]
+    pub mint_b: Pubkey,    // Token bids are paid in
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u64")]
+    pub min_bid: [u8; 8],  // Smallest first bid
+    #[idl(ty = "u64")]
+    pub min_increment: [u8; 8], // Smallest raise over the high bid
+    #[idl(ty = "i64")]
+    pub end_ts: [u8; 8],   // Unix time bidding closes
+    #[idl(ty = "i64")]
+    pub extension: [u8; 8], // Anti-sniping window in seconds, 0 when off
+    #[idl(ty = "pubkey")]
+    pub high_bidder: Pubkey, // Owner of the high bid, zeroed before the first bid
+    #[idl(ty = "u64")]
+    pub high_bid: [u8; 8], // Amount of the high bid, 0 before the first bid
+}
+
+impl AccountLayout for Auction {
+    type Error = AuctionError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 8]>();
+
+    const DISCRIMINATOR: u8 = 1;
+    const VERSION: u8 = 1;
+    const INVALID: AuctionError = AuctionError::InvalidAuctionAccount;
+}
+
+impl Auction {
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        u64::from_le_bytes(self.seed)
+    }
+
+    #[inline(always)]
+    pub fn min_bid(&self) -> u64 {
+        u64::from_le_bytes(self.min_bid)
+    }
+
+    #[inline(always)]
+    pub fn min_increment(&self) -> u64 {
+        u64::from_le_bytes(self.min_increment)
+    }
+
+    #[inline(always)]
+    pub fn end_ts(&self) -> i64 {
+        i64::from_le_bytes(self.end_ts)
+    }
+
+    #[inline(always)]
+    pub fn extension(&self) -> i64 {
+        i64::from_le_bytes(self.extension)
+    }
+
+    #[inline(always)]
+    pub fn high_bid(&self) -> u64 {
+        u64::from_le_bytes(self.high_bid)
+    }
+
+    /// Bids are never zero, so an auction without a high bid has no bids.
+    #[inline(always)]
+    pub fn has_bid(&self) -> bool {
+        self.high_bid() != 0
+    }
+
+    /// The smallest bid the auction accepts next.
+    #[inline(always)]
+    pub fn next_min_bid(&self) -> Result<u64, ProgramError> {
+        if !self.has_bid() {
+            return Ok(self.min_bid().max(1));
+        }
+        self.high_bid()
+            .checked_add(self.min_increment())
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        seller: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        bump: [u8; 1],
+    ) {
+        self.seed = seed.to_le_bytes();
+        self.seller = seller;
+        self.mint_a = mint_a;
+        self.mint_b = mint_b;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_terms(&mut self, min_bid: u64, min_increment: u64, end_ts: i64, extension: i64) {
+        self.min_bid = min_bid.to_le_bytes();
+        self.min_increment = min_increment.to_le_bytes();
+        self.end_ts = end_ts.to_le_bytes();
+        self.extension = extension.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_end_ts(&mut self, end_ts: i64) {
+        self.end_ts = end_ts.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_high_bid(&mut self, bidder: Pubkey, amount: u64) {
+        self.high_bidder = bidder;
+        self.high_bid = amount.to_le_bytes();
+    }
+}
@@ -0,0 +1,457 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_auction::{
+        AccountLayout, Auction, AuctionError, Bid, CreateAuction, SettleAuction, ID,
+    };
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, donate_tokens,
+        funded_payer, system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 11;
+    const AMOUNT: u64 = 1_000;
+    const MIN_BID: u64 = 100;
+    const MIN_INCREMENT: u64 = 10;
+    const END_TS: i64 = 2_000;
+    const EXTENSION: i64 = 60;
+    const BIDDER_FUNDS: u64 = 1_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Seller holding AMOUNT of mint A and two bidders holding BIDDER_FUNDS
+    // of mint B each, at unix time 1_000 with no auction created yet
+    struct Fixture {
+        context: Context,
+        seller: Pubkey,
+        alice: Pubkey,
+        bob: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        auction: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_auction");
+            add_token_programs(&mut mollusk);
+            mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+            let (seller, seller_account) = funded_payer();
+            let (alice, alice_account) = funded_payer();
+            let (bob, bob_account) = funded_payer();
+            let mint_a = Pubkey::new_unique();
+            let mint_b = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(seller, seller_account);
+            store.insert(alice, alice_account);
+            store.insert(bob, bob_account);
+            store.insert(
+                mint_a,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(AMOUNT)
+                    .build(&mollusk),
+            );
+            store.insert(
+                mint_b,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(BIDDER_FUNDS * 2)
+                    .build(&mollusk),
+            );
+            for (owner, mint, amount) in [
+                (seller, mint_a, AMOUNT),
+                (alice, mint_b, BIDDER_FUNDS),
+                (bob, mint_b, BIDDER_FUNDS),
+            ] {
+                let (ata, account) = AtaBuilder::new(&owner, &mint)
+                    .amount(amount)
+                    .build(&mollusk);
+                store.insert(ata, account);
+            }
+
+            Self {
+                context: mollusk.with_context(store),
+                seller,
+                alice,
+                bob,
+                mint_a,
+                mint_b,
+                auction: auction_address(&seller, SEED),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        // end_ts sits after the header, seed, three keys, bump, min_bid and min_increment
+        fn end_ts(&self) -> i64 {
+            let data = self.account(&self.auction).data;
+            i64::from_le_bytes(data[123..131].try_into().unwrap())
+        }
+
+        fn set_time(&mut self, unix_timestamp: i64) {
+            self.context.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        }
+
+        fn programs() -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(system_program().0, false),
+                AccountMeta::new_readonly(token_program().0, false),
+                AccountMeta::new_readonly(associated_token_program().0, false),
+            ]
+        }
+
+        fn create_auction(&self, extension: i64) -> Instruction {
+            let mut data = vec![*CreateAuction::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&AMOUNT.to_le_bytes());
+            data.extend_from_slice(&MIN_BID.to_le_bytes());
+            data.extend_from_slice(&MIN_INCREMENT.to_le_bytes());
+            data.extend_from_slice(&END_TS.to_le_bytes());
+            data.extend_from_slice(&extension.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(self.seller, true),
+                        AccountMeta::new(self.auction, false),
+                        AccountMeta::new_readonly(self.mint_a, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(&self.seller, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.auction, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.auction, &self.mint_b), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn bid(&self, bidder: &Pubkey, previous_bidder: &Pubkey, amount: u64) -> Instruction {
+            let mut data = vec![*Bid::DISCRIMINATOR];
+            data.extend_from_slice(&amount.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(*bidder, true),
+                        AccountMeta::new(self.auction, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(bidder, &self.mint_b), false),
+                        AccountMeta::new(self.ata(&self.auction, &self.mint_b), false),
+                        AccountMeta::new_readonly(*previous_bidder, false),
+                        AccountMeta::new(self.ata(previous_bidder, &self.mint_b), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn settle(&self, winner: &Pubkey) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*SettleAuction::DISCRIMINATOR],
+                [
+                    vec![
+                        AccountMeta::new(self.bob, true),
+                        AccountMeta::new(self.seller, false),
+                        AccountMeta::new(self.auction, false),
+                        AccountMeta::new_readonly(self.mint_a, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(&self.auction, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.auction, &self.mint_b), false),
+                        AccountMeta::new_readonly(*winner, false),
+                        AccountMeta::new(self.ata(winner, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.seller, &self.mint_b), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        // Run CreateAuction without anti-sniping, as the setup of the others
+        fn created(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.create_auction(0), &[Check::success()]);
+            self
+        }
+
+        // Place a bid and require it to succeed
+        fn bid_ok(&self, bidder: &Pubkey, previous_bidder: &Pubkey, amount: u64) {
+            self.context.process_and_validate_instruction(
+                &self.bid(bidder, previous_bidder, amount),
+                &[Check::success()],
+            );
+        }
+    }
+
+    fn auction_address(seller: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"auction", seller.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: AuctionError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_create_auction() {
+        let fixture = Fixture::new().created();
+
+        let data = fixture.account(&fixture.auction).data;
+        assert_eq!(data.len(), Auction::LEN);
+        assert_eq!(&data[..2], &[Auction::DISCRIMINATOR, Auction::VERSION]);
+        assert_eq!(fixture.end_ts(), END_TS);
+
+        let vault_a = fixture.ata(&fixture.auction, &fixture.mint_a);
+        assert_eq!(fixture.token_amount(&vault_a), AMOUNT);
+        let vault_b = fixture.ata(&fixture.auction, &fixture.mint_b);
+        assert_eq!(fixture.token_amount(&vault_b), 0);
+    }
+
+    #[test]
+    fn test_create_auction_rejects_past_end() {
+        let mut fixture = Fixture::new();
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.create_auction(0),
+            &[custom(AuctionError::InvalidAuction)],
+        );
+    }
+
+    #[test]
+    fn test_bid_refunds_previous_bidder() {
+        let fixture = Fixture::new().created();
+        let vault_b = fixture.ata(&fixture.auction, &fixture.mint_b);
+
+        // The first bid has no one to refund, so any account fills the slot
+        fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+        assert_eq!(fixture.token_amount(&vault_b), MIN_BID);
+
+        fixture.bid_ok(&fixture.bob, &fixture.alice, MIN_BID + MIN_INCREMENT);
+        assert_eq!(fixture.token_amount(&vault_b), MIN_BID + MIN_INCREMENT);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+            BIDDER_FUNDS
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.bob, &fixture.mint_b)),
+            BIDDER_FUNDS - MIN_BID - MIN_INCREMENT
+        );
+    }
+
+    #[test]
+    fn test_bid_rejects_low_bids() {
+        let fixture = Fixture::new().created();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.bid(&fixture.alice, &fixture.alice, MIN_BID - 1),
+            &[custom(AuctionError::BidTooLow)],
+        );
+
+        fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+        fixture.context.process_and_validate_instruction(
+            &fixture.bid(&fixture.bob, &fixture.alice, MIN_BID + MIN_INCREMENT - 1),
+            &[custom(AuctionError::BidTooLow)],
+        );
+    }
+
+    #[test]
+    fn test_bid_rejects_wrong_previous_bidder() {
+        let fixture = Fixture::new().created();
+        fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.bid(&fixture.bob, &fixture.bob, MIN_BID + MIN_INCREMENT),
+            &[custom(AuctionError::InvalidBidder)],
+        );
+    }
+
+    #[test]
+    fn test_bid_rejects_after_end() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.bid(&fixture.alice, &fixture.alice, MIN_BID),
+            &[custom(AuctionError::AuctionEnded)],
+        );
+    }
+
+    #[test]
+    fn test_anti_sniping_extends_end() {
+        let mut fixture = Fixture::new();
+        fixture.context.process_and_validate_instruction(
+            &fixture.create_auction(EXTENSION),
+            &[Check::success()],
+        );
+
+        // Outside the window the end stays put
+        fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+        assert_eq!(fixture.end_ts(), END_TS);
+
+        fixture.set_time(END_TS - 10);
+        fixture.bid_ok(&fixture.bob, &fixture.alice, MIN_BID + MIN_INCREMENT);
+        assert_eq!(fixture.end_ts(), END_TS - 10 + EXTENSION);
+
+        // Bidding stays open past the original end
+        fixture.set_time(END_TS);
+        fixture.bid_ok(&fixture.alice, &fixture.bob, MIN_BID + MIN_INCREMENT * 2);
+    }
+
+    #[test]
+    fn test_settle_auction() {
+        let mut fixture = Fixture::new().created();
+        fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+        fixture.bid_ok(&fixture.bob, &fixture.alice, MIN_BID + MIN_INCREMENT);
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.settle(&fixture.bob),
+            &[
+                Check::success(),
+                Check::account(&fixture.auction).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.bob, &fixture.mint_a)),
+            AMOUNT
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.seller, &fixture.mint_b)),
+            MIN_BID + MIN_INCREMENT
+        );
+        for mint in [fixture.mint_a, fixture.mint_b] {
+            let vault = fixture.ata(&fixture.auction, &mint);
+            assert_eq!(fixture.account(&vault).lamports, 0);
+        }
+    }
+
+    #[test]
+    fn test_settle_without_bids_returns_asset() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.settle(&fixture.seller),
+            &[
+                Check::success(),
+                Check::account(&fixture.auction).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.seller, &fixture.mint_a)),
+            AMOUNT
+        );
+    }
+
+    #[test]
+    fn test_settle_sweeps_donated_tokens() {
+        for with_bid in [true, false] {
+            let mut fixture = Fixture::new().created();
+            if with_bid {
+                fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+            }
+            fixture.set_time(END_TS);
+
+            // Anyone can send tokens to the vaults, which must not block closing them
+            for mint in [fixture.mint_a, fixture.mint_b] {
+                let vault = fixture.ata(&fixture.auction, &mint);
+                let mut store = fixture.context.account_store.borrow_mut();
+                donate_tokens(store.get_mut(&vault).unwrap(), 1);
+            }
+
+            let winner = if with_bid {
+                fixture.alice
+            } else {
+                fixture.seller
+            };
+            fixture.context.process_and_validate_instruction(
+                &fixture.settle(&winner),
+                &[
+                    Check::success(),
+                    Check::account(&fixture.auction).closed().build(),
+                ],
+            );
+
+            assert_eq!(
+                fixture.token_amount(&fixture.ata(&winner, &fixture.mint_a)),
+                AMOUNT + 1
+            );
+            let proceeds = if with_bid { MIN_BID } else { 0 };
+            assert_eq!(
+                fixture.token_amount(&fixture.ata(&fixture.seller, &fixture.mint_b)),
+                proceeds + 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_settle_rejects_before_end() {
+        let fixture = Fixture::new().created();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.settle(&fixture.seller),
+            &[custom(AuctionError::AuctionNotEnded)],
+        );
+    }
+
+    #[test]
+    fn test_settle_rejects_wrong_winner() {
+        let mut fixture = Fixture::new().created();
+        fixture.bid_ok(&fixture.alice, &fixture.alice, MIN_BID);
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.settle(&fixture.bob),
+            &[custom(AuctionError::InvalidBidder)],
+        );
+    }
+
+    #[test]
+    fn test_auction_error_codes() {
+        for code in 6000..6014 {
+            let error = AuctionError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(AuctionError::from_code(6014), None);
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio_auction::{
+        AccountLayout, Auction, AuctionError, BidInstructionData, CreateAuctionInstructionData, ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        fuzz::{check_invariants, check_rejected, fuzz, Fuzzer},
+        token_program, MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 3;
+
+    fn fuzzer() -> Fuzzer {
+        let seller = Pubkey::new_unique();
+        let auction = Pubkey::find_program_address(
+            &[b"auction", seller.as_ref(), &42u64.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0;
+        let (token_program, token_account) = token_program();
+
+        let mut fuzzer = Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                seller,
+                auction,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .fixed(token_program, token_account)
+        .data_lens(&[Auction::LEN, MINT_LEN, TOKEN_ACCOUNT_LEN])
+        .max_accounts(10);
+        fuzzer.owners.push(token_program);
+        fuzzer
+    }
+
+    fn mollusk() -> Mollusk {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_auction");
+        test_support::add_token_programs(&mut mollusk);
+        mollusk
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_auction_data(
+            data in vec(any::<u8>(), 0..=CreateAuctionInstructionData::LEN + 8),
+        ) {
+            let parsed = CreateAuctionInstructionData::try_from(data.as_slice());
+
+            if data.len() != CreateAuctionInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let field = |index: usize| data[index * 8..][..8].try_into().unwrap();
+            if u64::from_le_bytes(field(1)) == 0 {
+                prop_assert_eq!(parsed.err(), Some(AuctionError::ZeroAmount.into()));
+                return Ok(());
+            }
+            if u64::from_le_bytes(field(3)) == 0 || i64::from_le_bytes(field(5)) < 0 {
+                prop_assert_eq!(parsed.err(), Some(AuctionError::InvalidAuction.into()));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(
+                (parsed.seed, parsed.amount, parsed.min_bid, parsed.min_increment),
+                (
+                    u64::from_le_bytes(field(0)),
+                    u64::from_le_bytes(field(1)),
+                    u64::from_le_bytes(field(2)),
+                    u64::from_le_bytes(field(3)),
+                )
+            );
+            prop_assert_eq!(
+                (parsed.end_ts, parsed.extension),
+                (i64::from_le_bytes(field(4)), i64::from_le_bytes(field(5)))
+            );
+        }
+
+        #[test]
+        fn test_parse_auction_header(
+            data in vec(any::<u8>(), Auction::LEN - 1..=Auction::LEN + 1),
+            tagged in any::<bool>(),
+        ) {
+            let mut data = data;
+            if tagged {
+                data[0] = Auction::DISCRIMINATOR;
+                data[1] = Auction::VERSION;
+            }
+
+            let valid = data.len() == Auction::LEN
+                && data[0] == Auction::DISCRIMINATOR
+                && data[1] == Auction::VERSION;
+            prop_assert_eq!(Auction::load(&data).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = mollusk();
+        let data = (0..INSTRUCTION_COUNT, vec(any::<u8>(), 0..=56))
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_auction_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreateAuctionInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_bid_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(1, BidInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+use program_helpers::HelperError;
+
+/// Errors returned by the distributor program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DistributorError {
+    NotSigner = 6000,
+    InvalidAddress = 6001,
+    InvalidMint = 6002,
+    InvalidTokenAccount = 6003,
+    InvalidDistributorAccount = 6004,
+    InvalidDistributorAddress = 6005,
+    ZeroAmount = 6006,
+    InvalidAccountDiscriminator = 6007,
+    UnsupportedAccountVersion = 6008,
+    InvalidSchedule = 6009,
+    InvalidProof = 6010,
+    AlreadyClaimed = 6011,
+    ClaimWindowClosed = 6012,
+    ClaimWindowOpen = 6013,
+    InvalidClaimStatusAddress = 6014,
+}
+
+impl DistributorError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidAddress),
+            6002 => Some(Self::InvalidMint),
+            6003 => Some(Self::InvalidTokenAccount),
+            6004 => Some(Self::InvalidDistributorAccount),
+            6005 => Some(Self::InvalidDistributorAddress),
+            6006 => Some(Self::ZeroAmount),
+            6007 => Some(Self::InvalidAccountDiscriminator),
+            6008 => Some(Self::UnsupportedAccountVersion),
+            6009 => Some(Self::InvalidSchedule),
+            6010 => Some(Self::InvalidProof),
+            6011 => Some(Self::AlreadyClaimed),
+            6012 => Some(Self::ClaimWindowClosed),
+            6013 => Some(Self::ClaimWindowOpen),
+            6014 => Some(Self::InvalidClaimStatusAddress),
+            _ => None,
+        }
+    }
+}
+
+impl From<DistributorError> for ProgramError {
+    fn from(error: DistributorError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl HelperError for DistributorError {
+    const NOT_SIGNER: Self = Self::NotSigner;
+    const INVALID_MINT: Self = Self::InvalidMint;
+    const INVALID_TOKEN_ACCOUNT: Self = Self::InvalidTokenAccount;
+    const INVALID_ADDRESS: Self = Self::InvalidAddress;
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self = Self::InvalidAccountDiscriminator;
+    const UNSUPPORTED_ACCOUNT_VERSION: Self = Self::UnsupportedAccountVersion;
+}
+
+impl fmt::Display for DistributorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Account must sign the transaction",
+            Self::InvalidAddress => "Associated token account does not match its expected address",
+            Self::InvalidMint => "Account is not a valid SPL Token or Token-2022 mint",
+            Self::InvalidTokenAccount => "Account is not a valid SPL Token token account",
+            Self::InvalidDistributorAccount => {
+                "Distributor account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidDistributorAddress => "Distributor account does not match its PDA",
+            Self::ZeroAmount => "Amount must be greater than zero",
+            Self::InvalidAccountDiscriminator => "Distributor account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => {
+                "Distributor account layout version is not supported"
+            }
+            Self::InvalidSchedule => "Clawback time must be in the future",
+            Self::InvalidProof => "Merkle proof does not match the distributor's root",
+            Self::AlreadyClaimed => "Leaf was already claimed",
+            Self::ClaimWindowClosed => "Claims closed at the clawback time",
+            Self::ClaimWindowOpen => "Clawback is not open before the clawback time",
+            Self::InvalidClaimStatusAddress => "Claim status account does not match its PDA",
+        };
+
+        f.write_str(message)
+    }
+}
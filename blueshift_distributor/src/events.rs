@@ -0,0 +1,171 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`DistributorEvent::decode`].
+
+use core::mem::size_of;
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and made only of byte arrays, so that
+/// every bit pattern is valid and the struct has no padding.
+pub unsafe trait Event: Copy + Sized {
+    const DISCRIMINATOR: u8;
+    const LEN: usize = size_of::<Self>();
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    #[inline(always)]
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[self.as_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DistributorCreated {
+    pub header: EventHeader,
+    pub distributor: Pubkey,
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub amount: [u8; 8],
+    pub clawback_ts: [u8; 8],
+}
+
+unsafe impl Event for DistributorCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl DistributorCreated {
+    pub fn new(
+        distributor: Pubkey,
+        admin: Pubkey,
+        mint: Pubkey,
+        merkle_root: [u8; 32],
+        amount: u64,
+        clawback_ts: i64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            distributor,
+            admin,
+            mint,
+            merkle_root,
+            amount: amount.to_le_bytes(),
+            clawback_ts: clawback_ts.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AirdropClaimed {
+    pub header: EventHeader,
+    pub distributor: Pubkey,
+    pub claimant: Pubkey,
+    pub index: [u8; 8],
+    pub amount: [u8; 8],
+}
+
+unsafe impl Event for AirdropClaimed {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl AirdropClaimed {
+    pub fn new(distributor: Pubkey, claimant: Pubkey, index: u64, amount: u64) -> Self {
+        Self {
+            header: Self::header(),
+            distributor,
+            claimant,
+            index: index.to_le_bytes(),
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DistributionClawedBack {
+    pub header: EventHeader,
+    pub distributor: Pubkey,
+    pub admin: Pubkey,
+    pub amount: [u8; 8],
+    pub total_claimed: [u8; 8],
+}
+
+unsafe impl Event for DistributionClawedBack {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl DistributionClawedBack {
+    pub fn new(distributor: Pubkey, admin: Pubkey, amount: u64, total_claimed: u64) -> Self {
+        Self {
+            header: Self::header(),
+            distributor,
+            admin,
+            amount: amount.to_le_bytes(),
+            total_claimed: total_claimed.to_le_bytes(),
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistributorEvent {
+    DistributorCreated(DistributorCreated),
+    AirdropClaimed(AirdropClaimed),
+    DistributionClawedBack(DistributionClawedBack),
+}
+
+impl DistributorEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let [version, discriminator, ..] = data else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        if *version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match *discriminator {
+            DistributorCreated::DISCRIMINATOR => {
+                DistributorCreated::read(data).map(Self::DistributorCreated)
+            }
+            AirdropClaimed::DISCRIMINATOR => AirdropClaimed::read(data).map(Self::AirdropClaimed),
+            DistributionClawedBack::DISCRIMINATOR => {
+                DistributionClawedBack::read(data).map(Self::DistributionClawedBack)
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
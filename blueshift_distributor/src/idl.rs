@@ -0,0 +1,33 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    AccountLayout, ClaimAirdrop, ClaimAirdropAccounts, ClaimAirdropInstructionData, ClaimStatus,
+    Clawback, ClawbackAccounts, CreateDistributor, CreateDistributorAccounts,
+    CreateDistributorInstructionData, Distributor, DistributorError,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create_distributor", *CreateDistributor::DISCRIMINATOR)
+            .accounts::<CreateDistributorAccounts>()
+            .args::<CreateDistributorInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("claim_airdrop", *ClaimAirdrop::DISCRIMINATOR)
+            .accounts::<ClaimAirdropAccounts>()
+            .args::<ClaimAirdropInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("clawback", *Clawback::DISCRIMINATOR).accounts::<ClawbackAccounts>(),
+    )
+    .account::<Distributor>(&[Distributor::DISCRIMINATOR, Distributor::VERSION])
+    .account::<ClaimStatus>(&[ClaimStatus::DISCRIMINATOR, ClaimStatus::VERSION])
+    .errors(DistributorError::from_code)
+}
@@ -0,0 +1,230 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{create_program_address, find_program_address},
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, ClaimStatus, Distributor, DistributorAccount, DistributorError,
+    ProgramAccount, ProgramAccountInit,
+    events::{AirdropClaimed, Event},
+    merkle::{self, MAX_PROOF_LEN},
+};
+
+/// `claim_status` is created at `[b"claim", distributor, index]`, and
+/// `claimant_ata` when it does not exist yet.
+#[derive(Accounts, IdlAccounts)]
+pub struct ClaimAirdropAccounts<'a> {
+    #[account(signer @ DistributorError::NotSigner)]
+    #[idl(writable)]
+    pub claimant: &'a AccountInfo,
+    #[account(check = DistributorAccount::check(distributor))]
+    #[idl(writable)]
+    pub distributor: &'a AccountInfo,
+    #[account(empty @ DistributorError::AlreadyClaimed)]
+    #[idl(writable)]
+    pub claim_status: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault, distributor, mint, token_program))]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub claimant_ata: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+/// Followed in the instruction data by the merkle proof: up to
+/// `MAX_PROOF_LEN` sibling hashes of 32 bytes each, from the leaf up.
+#[derive(IdlType)]
+pub struct ClaimAirdropInstructionData {
+    /// Index of the claimed leaf.
+    pub index: u64,
+    /// Amount the leaf grants.
+    pub amount: u64,
+}
+
+impl ClaimAirdropInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2;
+}
+
+impl<'a> TryFrom<&'a [u8]> for ClaimAirdropInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(DistributorError::ZeroAmount.into());
+        }
+
+        Ok(Self { index, amount })
+    }
+}
+
+pub struct ClaimAirdrop<'a> {
+    pub accounts: ClaimAirdropAccounts<'a>,
+    pub instruction_data: ClaimAirdropInstructionData,
+    pub proof: &'a [[u8; 32]],
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClaimAirdrop<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ClaimAirdropAccounts::try_from(accounts)?;
+
+        if data.len() < ClaimAirdropInstructionData::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (data, proof) = data.split_at(ClaimAirdropInstructionData::LEN);
+        let instruction_data = ClaimAirdropInstructionData::try_from(data)?;
+
+        if proof.len() % 32 != 0 || proof.len() / 32 > MAX_PROOF_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // `[u8; 32]` has the alignment of `u8`, so any byte slice can be viewed as hashes
+        let proof = unsafe {
+            core::slice::from_raw_parts(proof.as_ptr() as *const [u8; 32], proof.len() / 32)
+        };
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            proof,
+        })
+    }
+}
+
+impl<'a> ClaimAirdrop<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.distributor.try_borrow_mut_data()?;
+        let distributor = Distributor::load_mut(data.as_mut())?;
+
+        // Check if the distributor is valid
+        let distributor_key = create_program_address(
+            &[
+                b"distributor",
+                &distributor.admin,
+                &distributor.seed,
+                &distributor.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &distributor_key != self.accounts.distributor.key() {
+            return Err(DistributorError::InvalidDistributorAddress.into());
+        }
+        if self.accounts.mint.key().ne(&distributor.mint) {
+            return Err(DistributorError::InvalidMint.into());
+        }
+        if Clock::get()?.unix_timestamp >= distributor.clawback_ts() {
+            return Err(DistributorError::ClaimWindowClosed.into());
+        }
+
+        let index = self.instruction_data.index;
+        let amount = self.instruction_data.amount;
+        let leaf = merkle::leaf(index, self.accounts.claimant.key(), amount);
+        if !merkle::verify(self.proof, &distributor.merkle_root, leaf) {
+            return Err(DistributorError::InvalidProof.into());
+        }
+
+        let total_claimed = distributor
+            .total_claimed()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        distributor.set_total_claimed(total_claimed);
+
+        let admin_binding = distributor.admin;
+        let seed_binding = distributor.seed;
+        let bump_binding = distributor.bump;
+        drop(data);
+
+        // Record the claim, so the leaf cannot be claimed again
+        let index_binding = index.to_le_bytes();
+        let (claim_status_key, bump) = find_program_address(
+            &[b"claim", self.accounts.distributor.key(), &index_binding],
+            &crate::ID,
+        );
+        if &claim_status_key != self.accounts.claim_status.key() {
+            return Err(DistributorError::InvalidClaimStatusAddress.into());
+        }
+
+        let claim_bump_binding = [bump];
+        let claim_status_seeds = [
+            Seed::from(b"claim"),
+            Seed::from(self.accounts.distributor.key().as_ref()),
+            Seed::from(&index_binding),
+            Seed::from(&claim_bump_binding),
+        ];
+
+        ProgramAccount::init_allow_prefunded::<ClaimStatus>(
+            self.accounts.claimant,
+            self.accounts.claim_status,
+            &claim_status_seeds,
+            ClaimStatus::LEN,
+        )?;
+        ClaimStatus::init(self.accounts.claim_status.try_borrow_mut_data()?.as_mut())?
+            .set_inner(
+                *self.accounts.distributor.key(),
+                *self.accounts.claimant.key(),
+                index,
+                amount,
+            );
+
+        // Transfer from the Vault to the Claimant
+        AssociatedTokenAccount::init_if_needed(
+            self.accounts.claimant_ata,
+            self.accounts.mint,
+            self.accounts.claimant,
+            self.accounts.claimant,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        let distributor_seeds = [
+            Seed::from(b"distributor"),
+            Seed::from(admin_binding.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&distributor_seeds);
+
+        Transfer {
+            from: self.accounts.vault,
+            to: self.accounts.claimant_ata,
+            authority: self.accounts.distributor,
+            amount,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        AirdropClaimed::new(
+            *self.accounts.distributor.key(),
+            *self.accounts.claimant.key(),
+            index,
+            amount,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::{
+    instructions::{CloseAccount, Transfer},
+    state::TokenAccount,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount,
+    AssociatedTokenAccountCheck, AssociatedTokenAccountInit, Distributor, DistributorAccount,
+    DistributorError, ProgramAccount,
+    events::{DistributionClawedBack, Event},
+};
+
+/// Once claims close, the admin takes back whatever is left in the vault and
+/// closes the distributor.
+#[derive(Accounts, IdlAccounts)]
+pub struct ClawbackAccounts<'a> {
+    #[account(signer @ DistributorError::NotSigner)]
+    #[idl(writable)]
+    pub admin: &'a AccountInfo,
+    #[account(check = DistributorAccount::check(distributor))]
+    #[idl(writable)]
+    pub distributor: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault, distributor, mint, token_program))]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub admin_ata: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct Clawback<'a> {
+    pub accounts: ClawbackAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Clawback<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClawbackAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Clawback<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.distributor.try_borrow_data()?;
+        let distributor = Distributor::load(&data)?;
+
+        // Check if the distributor is valid
+        let distributor_key = create_program_address(
+            &[
+                b"distributor",
+                self.accounts.admin.key(),
+                &distributor.seed,
+                &distributor.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &distributor_key != self.accounts.distributor.key() {
+            return Err(DistributorError::InvalidDistributorAddress.into());
+        }
+        if self.accounts.mint.key().ne(&distributor.mint) {
+            return Err(DistributorError::InvalidMint.into());
+        }
+        if Clock::get()?.unix_timestamp < distributor.clawback_ts() {
+            return Err(DistributorError::ClaimWindowOpen.into());
+        }
+
+        let total_claimed = distributor.total_claimed();
+        let seed_binding = distributor.seed;
+        let bump_binding = distributor.bump;
+        drop(data);
+
+        let distributor_seeds = [
+            Seed::from(b"distributor"),
+            Seed::from(self.accounts.admin.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&distributor_seeds);
+
+        // Return what was not claimed to the admin
+        let amount = {
+            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+
+            vault.amount()
+        };
+
+        if amount > 0 {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.admin_ata,
+                self.accounts.mint,
+                self.accounts.admin,
+                self.accounts.admin,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: self.accounts.vault,
+                to: self.accounts.admin_ata,
+                authority: self.accounts.distributor,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        // Close the vault and the distributor
+        CloseAccount {
+            account: self.accounts.vault,
+            destination: self.accounts.admin,
+            authority: self.accounts.distributor,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        ProgramAccount::close(self.accounts.distributor, self.accounts.admin)?;
+
+        DistributionClawedBack::new(
+            *self.accounts.distributor.key(),
+            *self.accounts.admin.key(),
+            amount,
+            total_claimed,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
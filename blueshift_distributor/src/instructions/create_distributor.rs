@@ -0,0 +1,190 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Distributor, DistributorError, MintInterface, ProgramAccount,
+    ProgramAccountInit,
+    events::{DistributorCreated, Event},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct CreateDistributorAccounts<'a> {
+    #[account(signer @ DistributorError::NotSigner)]
+    #[idl(writable)]
+    pub admin: &'a AccountInfo,
+    #[idl(writable)]
+    pub distributor: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint))]
+    pub mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(admin_ata, admin, mint, token_program))]
+    #[idl(writable)]
+    pub admin_ata: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct CreateDistributorInstructionData {
+    pub seed: u64,
+    /// Root of the tree of `(index, claimant, amount)` leaves.
+    pub merkle_root: [u8; 32],
+    /// Amount deposited into the vault, enough to cover every leaf.
+    pub amount: u64,
+    /// Unix time claims close and the admin may claw back the rest.
+    pub clawback_ts: i64,
+}
+
+impl CreateDistributorInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2 + size_of::<[u8; 32]>() + size_of::<i64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateDistributorInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let merkle_root: [u8; 32] = data[8..40].try_into().unwrap();
+        let amount = u64::from_le_bytes(data[40..48].try_into().unwrap());
+        let clawback_ts = i64::from_le_bytes(data[48..56].try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(DistributorError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            seed,
+            merkle_root,
+            amount,
+            clawback_ts,
+        })
+    }
+}
+
+pub struct CreateDistributor<'a> {
+    pub accounts: CreateDistributorAccounts<'a>,
+    pub instruction_data: CreateDistributorInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateDistributor<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = CreateDistributorAccounts::try_from(accounts)?;
+        let instruction_data = CreateDistributorInstructionData::try_from(data)?;
+
+        // A distribution needs a claim window
+        if instruction_data.clawback_ts <= Clock::get()?.unix_timestamp {
+            return Err(DistributorError::InvalidSchedule.into());
+        }
+
+        // Initialize the Accounts needed
+        let (distributor_key, bump) = find_program_address(
+            &[
+                b"distributor",
+                accounts.admin.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &distributor_key != accounts.distributor.key() {
+            return Err(DistributorError::InvalidDistributorAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let distributor_seeds = [
+            Seed::from(b"distributor"),
+            Seed::from(accounts.admin.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<Distributor>(
+            accounts.admin,
+            accounts.distributor,
+            &distributor_seeds,
+            Distributor::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            accounts.vault,
+            accounts.mint,
+            accounts.admin,
+            accounts.distributor,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> CreateDistributor<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the distributor account
+        let mut data = self.accounts.distributor.try_borrow_mut_data()?;
+        let distributor = Distributor::init(data.as_mut())?;
+
+        distributor.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.admin.key(),
+            *self.accounts.mint.key(),
+            [self.bump],
+        );
+        distributor.set_distribution(
+            self.instruction_data.merkle_root,
+            self.instruction_data.clawback_ts,
+        );
+        drop(data);
+
+        // Fund the vault
+        Transfer {
+            from: self.accounts.admin_ata,
+            to: self.accounts.vault,
+            authority: self.accounts.admin,
+            amount: self.instruction_data.amount,
+        }
+        .invoke()?;
+
+        DistributorCreated::new(
+            *self.accounts.distributor.key(),
+            *self.accounts.admin.key(),
+            *self.accounts.mint.key(),
+            self.instruction_data.merkle_root,
+            self.instruction_data.amount,
+            self.instruction_data.clawback_ts,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
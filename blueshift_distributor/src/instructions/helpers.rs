@@ -0,0 +1,15 @@
+pub use program_helpers::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, ProgramAccountInit, TOKEN_2022_MINT_DISCRIMINATOR,
+    TOKEN_2022_PROGRAM_ID,
+};
+
+use crate::{Distributor, DistributorError};
+
+pub type SignerAccount = program_helpers::SignerAccount<DistributorError>;
+pub type MintInterface = program_helpers::MintInterface<DistributorError>;
+pub type TokenAccount = program_helpers::TokenAccount<DistributorError>;
+pub type AssociatedTokenAccount = program_helpers::AssociatedTokenAccount<DistributorError>;
+
+pub type ProgramAccount = program_helpers::ProgramAccount<Distributor>;
+pub type DistributorAccount = program_helpers::StateAccount<Distributor>;
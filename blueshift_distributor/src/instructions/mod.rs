@@ -0,0 +1,9 @@
+pub mod claim_airdrop;
+pub mod clawback;
+pub mod create_distributor;
+pub mod helpers;
+
+pub use claim_airdrop::*;
+pub use clawback::*;
+pub use create_distributor::*;
+pub use helpers::*;
@@ -0,0 +1,45 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, entrypoint, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+pub mod errors;
+pub use errors::*;
+
+pub mod events;
+
+pub mod merkle;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
+// 44444444444444444444444444444444444444444444
+pub const ID: Pubkey = [
+    0x2d, 0x5b, 0x41, 0x3c, 0x65, 0x40, 0xde, 0x15, 0x0c, 0x93, 0x73, 0x14, 0x4d, 0x51, 0x33, 0xca,
+    0x4c, 0xb8, 0x30, 0xba, 0x0f, 0x75, 0x67, 0x16, 0xac, 0xea, 0x0e, 0x50, 0xd7, 0x94, 0x35, 0xe5,
+];
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.split_first() {
+        Some((CreateDistributor::DISCRIMINATOR, data)) => {
+            CreateDistributor::try_from((data, accounts))?.process()
+        }
+        Some((ClaimAirdrop::DISCRIMINATOR, data)) => {
+            ClaimAirdrop::try_from((data, accounts))?.process()
+        }
+        Some((Clawback::DISCRIMINATOR, _)) => Clawback::try_from(accounts)?.process(),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
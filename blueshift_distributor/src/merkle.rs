@@ -0,0 +1,64 @@
+//! SHA-256 merkle proofs for the distributor.
+//!
+//! Leaves and nodes are hashed under different prefixes, so a node can never
+//! be passed off as a leaf. Each pair is sorted before hashing, which lets a
+//! proof be a plain list of siblings without left or right flags.
+
+use pinocchio::pubkey::Pubkey;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Longest proof accepted, enough for 2^32 leaves.
+pub const MAX_PROOF_LEN: usize = 32;
+
+/// SHA-256 of the concatenation of `values`.
+pub fn hashv(values: &[&[u8]]) -> [u8; 32] {
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash = core::mem::MaybeUninit::<[u8; 32]>::uninit();
+        unsafe {
+            pinocchio::syscalls::sol_sha256(
+                values as *const _ as *const u8,
+                values.len() as u64,
+                hash.as_mut_ptr() as *mut u8,
+            );
+            hash.assume_init()
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for value in values {
+            hasher.update(value);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Leaf granting `amount` to `claimant` at `index`.
+pub fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant,
+        &amount.to_le_bytes(),
+    ])
+}
+
+/// Parent of two nodes, in either order.
+pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[NODE_PREFIX, a, b])
+    } else {
+        hashv(&[NODE_PREFIX, b, a])
+    }
+}
+
+/// Whether `proof` leads from `leaf` up to `root`.
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof.iter().fold(leaf, |hash, sibling| node(&hash, sibling)) == *root
+}
@@ -0,0 +1,124 @@
+use core::mem::size_of;
+use pinocchio::pubkey::Pubkey;
+use idl_derive::IdlType;
+
+use crate::{AccountLayout, DistributorError};
+
+/// Tokens in `mint` handed out to the claimants of a merkle tree, one claim
+/// per leaf, until the admin claws back the rest after `clawback_ts`.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct Distributor {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `Distributor::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `Distributor::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub admin: Pubkey,     // Creator of the distribution, paid the clawback
+    #[idl(ty = "pubkey")]
+    pub mint: Pubkey,      // Token being distributed
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    pub merkle_root: [u8; 32], // Root of the tree of `(index, claimant, amount)` leaves
+    #[idl(ty = "i64")]
+    pub clawback_ts: [u8; 8], // Unix time claims close and the clawback opens
+    #[idl(ty = "u64")]
+    pub total_claimed: [u8; 8], // Amount claimed so far
+}
+
+impl AccountLayout for Distributor {
+    type Error = DistributorError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 32]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>();
+
+    const DISCRIMINATOR: u8 = 1;
+    const VERSION: u8 = 1;
+    const INVALID: DistributorError = DistributorError::InvalidDistributorAccount;
+}
+
+impl Distributor {
+    #[inline(always)]
+    pub fn clawback_ts(&self) -> i64 {
+        i64::from_le_bytes(self.clawback_ts)
+    }
+
+    #[inline(always)]
+    pub fn total_claimed(&self) -> u64 {
+        u64::from_le_bytes(self.total_claimed)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, seed: u64, admin: Pubkey, mint: Pubkey, bump: [u8; 1]) {
+        self.seed = seed.to_le_bytes();
+        self.admin = admin;
+        self.mint = mint;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_distribution(&mut self, merkle_root: [u8; 32], clawback_ts: i64) {
+        self.merkle_root = merkle_root;
+        self.clawback_ts = clawback_ts.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_total_claimed(&mut self, total_claimed: u64) {
+        self.total_claimed = total_claimed.to_le_bytes();
+    }
+}
+
+/// Receipt of the claim of one leaf of a `Distributor`. Its existence at
+/// `[b"claim", distributor, index]` is what stops a second claim.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct ClaimStatus {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `ClaimStatus::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `ClaimStatus::VERSION`
+    #[idl(ty = "pubkey")]
+    pub distributor: Pubkey, // Distributor the leaf belongs to
+    #[idl(ty = "pubkey")]
+    pub claimant: Pubkey,  // Receiver of the claimed tokens
+    #[idl(ty = "u64")]
+    pub index: [u8; 8],    // Index of the claimed leaf
+    #[idl(ty = "u64")]
+    pub amount: [u8; 8],   // Amount claimed
+}
+
+impl AccountLayout for ClaimStatus {
+    type Error = DistributorError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>();
+
+    const DISCRIMINATOR: u8 = 2;
+    const VERSION: u8 = 1;
+    const INVALID: DistributorError = DistributorError::InvalidDistributorAccount;
+}
+
+impl ClaimStatus {
+    #[inline(always)]
+    pub fn set_inner(&mut self, distributor: Pubkey, claimant: Pubkey, index: u64, amount: u64) {
+        self.distributor = distributor;
+        self.claimant = claimant;
+        self.index = index.to_le_bytes();
+        self.amount = amount.to_le_bytes();
+    }
+}
@@ -0,0 +1,407 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_distributor::{
+        merkle, AccountLayout, ClaimAirdrop, ClaimStatus, Clawback, CreateDistributor, Distributor,
+        DistributorError, ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_account, system_program, token_program, AtaBuilder, MerkleTree, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 17;
+    const FUNDS: u64 = 10_000;
+    const START_TS: i64 = 1_000;
+    const CLAWBACK_TS: i64 = START_TS + 1_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Admin holding FUNDS of the mint and a tree granting Alice 300, Bob 500
+    // and Carol 200, at unix time START_TS with no distributor created yet
+    struct Fixture {
+        context: Context,
+        admin: Pubkey,
+        claimants: Vec<(Pubkey, u64)>,
+        tree: MerkleTree,
+        mint: Pubkey,
+        distributor: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_distributor");
+            add_token_programs(&mut mollusk);
+            mollusk.sysvars.clock.unix_timestamp = START_TS;
+
+            let (admin, admin_account) = funded_payer();
+            let mint = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(admin, admin_account);
+            store.insert(
+                mint,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(FUNDS)
+                    .build(&mollusk),
+            );
+            let (ata, account) = AtaBuilder::new(&admin, &mint).amount(FUNDS).build(&mollusk);
+            store.insert(ata, account);
+
+            let mut claimants = Vec::new();
+            for amount in [300, 500, 200] {
+                let (claimant, account) = funded_payer();
+                store.insert(claimant, account);
+                claimants.push((claimant, amount));
+            }
+            let leaves = claimants
+                .iter()
+                .enumerate()
+                .map(|(index, (claimant, amount))| {
+                    merkle::leaf(index as u64, &claimant.to_bytes(), *amount)
+                })
+                .collect();
+
+            Self {
+                context: mollusk.with_context(store),
+                admin,
+                claimants,
+                tree: MerkleTree::new(leaves, merkle::node),
+                mint,
+                distributor: distributor_address(&admin, SEED),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey) -> Pubkey {
+            associated_token_address(owner, &self.mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn set_time(&mut self, unix_timestamp: i64) {
+            self.context.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        }
+
+        fn claim_status(&self, index: u64) -> Pubkey {
+            Pubkey::find_program_address(
+                &[b"claim", self.distributor.as_ref(), &index.to_le_bytes()],
+                &PROGRAM_ID,
+            )
+            .0
+        }
+
+        fn create(&self) -> Instruction {
+            let mut data = vec![*CreateDistributor::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&self.tree.root());
+            data.extend_from_slice(&1_000u64.to_le_bytes());
+            data.extend_from_slice(&CLAWBACK_TS.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(self.admin, true),
+                    AccountMeta::new(self.distributor, false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new(self.ata(&self.admin), false),
+                    AccountMeta::new(self.ata(&self.distributor), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        // Claim `amount` for `claimant` at `index` with the tree's proof of
+        // that index
+        fn claim_as(&self, claimant: &Pubkey, index: u64, amount: u64) -> Instruction {
+            let mut data = vec![*ClaimAirdrop::DISCRIMINATOR];
+            data.extend_from_slice(&index.to_le_bytes());
+            data.extend_from_slice(&amount.to_le_bytes());
+            for sibling in self.tree.proof(index as usize) {
+                data.extend_from_slice(&sibling);
+            }
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(*claimant, true),
+                    AccountMeta::new(self.distributor, false),
+                    AccountMeta::new(self.claim_status(index), false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new(self.ata(&self.distributor), false),
+                    AccountMeta::new(self.ata(claimant), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn claim(&self, index: u64) -> Instruction {
+            let (claimant, amount) = self.claimants[index as usize];
+            self.claim_as(&claimant, index, amount)
+        }
+
+        fn clawback(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*Clawback::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new(self.admin, true),
+                    AccountMeta::new(self.distributor, false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new(self.ata(&self.distributor), false),
+                    AccountMeta::new(self.ata(&self.admin), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn run(&self, instruction: &Instruction) {
+            self.context
+                .process_and_validate_instruction(instruction, &[Check::success()]);
+        }
+
+        // Create the distributor, as the setup of the others
+        fn created(self) -> Self {
+            self.run(&self.create());
+            self
+        }
+    }
+
+    fn distributor_address(admin: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"distributor", admin.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: DistributorError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    proptest! {
+        #[test]
+        fn test_tree_proofs_verify(
+            grants in vec((any::<[u8; 32]>(), 1..u64::MAX), 1..64),
+            tamper in any::<prop::sample::Index>(),
+        ) {
+            let leaves: Vec<_> = grants
+                .iter()
+                .enumerate()
+                .map(|(index, (claimant, amount))| merkle::leaf(index as u64, claimant, *amount))
+                .collect();
+            let tree = MerkleTree::new(leaves.clone(), merkle::node);
+            let root = tree.root();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                prop_assert!(merkle::verify(&tree.proof(index), &root, *leaf));
+            }
+
+            // Any other amount for the same claimant is rejected
+            let index = tamper.index(grants.len());
+            let (claimant, amount) = grants[index];
+            let forged = merkle::leaf(index as u64, &claimant, amount - 1);
+            prop_assert!(!merkle::verify(&tree.proof(index), &root, forged));
+        }
+    }
+
+    #[test]
+    fn test_create_distributor() {
+        let fixture = Fixture::new().created();
+
+        let data = fixture.account(&fixture.distributor).data;
+        assert_eq!(data.len(), Distributor::LEN);
+        assert_eq!(
+            &data[..2],
+            &[Distributor::DISCRIMINATOR, Distributor::VERSION]
+        );
+        let distributor = Distributor::load(&data).unwrap();
+        assert_eq!(distributor.merkle_root, fixture.tree.root());
+        assert_eq!(distributor.clawback_ts(), CLAWBACK_TS);
+        assert_eq!(distributor.total_claimed(), 0);
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.distributor)),
+            1_000
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.admin)),
+            FUNDS - 1_000
+        );
+    }
+
+    #[test]
+    fn test_create_distributor_rejects_past_clawback() {
+        let mut fixture = Fixture::new();
+        fixture.set_time(CLAWBACK_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.create(),
+            &[custom(DistributorError::InvalidSchedule)],
+        );
+    }
+
+    #[test]
+    fn test_claim_pays_claimant() {
+        let fixture = Fixture::new().created();
+        fixture.run(&fixture.claim(1));
+
+        let (bob, _) = fixture.claimants[1];
+        assert_eq!(fixture.token_amount(&fixture.ata(&bob)), 500);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.distributor)),
+            500
+        );
+
+        let data = fixture.account(&fixture.claim_status(1)).data;
+        assert_eq!(data.len(), ClaimStatus::LEN);
+        assert_eq!(
+            &data[..2],
+            &[ClaimStatus::DISCRIMINATOR, ClaimStatus::VERSION]
+        );
+        let data = fixture.account(&fixture.distributor).data;
+        assert_eq!(Distributor::load(&data).unwrap().total_claimed(), 500);
+    }
+
+    #[test]
+    fn test_claim_twice_rejected() {
+        let fixture = Fixture::new().created();
+        fixture.run(&fixture.claim(0));
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(0),
+            &[custom(DistributorError::AlreadyClaimed)],
+        );
+    }
+
+    #[test]
+    fn test_claim_rejects_wrong_amount() {
+        let fixture = Fixture::new().created();
+        let (alice, _) = fixture.claimants[0];
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim_as(&alice, 0, 301),
+            &[custom(DistributorError::InvalidProof)],
+        );
+    }
+
+    #[test]
+    fn test_claim_rejects_other_claimant() {
+        let fixture = Fixture::new().created();
+        // Bob replays Alice's leaf and proof
+        let (bob, _) = fixture.claimants[1];
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim_as(&bob, 0, 300),
+            &[custom(DistributorError::InvalidProof)],
+        );
+    }
+
+    #[test]
+    fn test_claim_rejects_wrong_claim_status() {
+        let fixture = Fixture::new().created();
+        let mut instruction = fixture.claim(0);
+        instruction.accounts[2] = AccountMeta::new(fixture.claim_status(1), false);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(DistributorError::InvalidClaimStatusAddress)],
+        );
+    }
+
+    #[test]
+    fn test_claim_with_prefunded_claim_status() {
+        let fixture = Fixture::new().created();
+        // Lamports sent to the claim PDA ahead of time must not block the claim
+        fixture
+            .context
+            .account_store
+            .borrow_mut()
+            .insert(fixture.claim_status(2), system_account(1));
+        fixture.run(&fixture.claim(2));
+
+        let (carol, _) = fixture.claimants[2];
+        assert_eq!(fixture.token_amount(&fixture.ata(&carol)), 200);
+        assert_eq!(fixture.account(&fixture.claim_status(2)).owner, PROGRAM_ID);
+    }
+
+    #[test]
+    fn test_claim_rejected_after_clawback_ts() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(CLAWBACK_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(0),
+            &[custom(DistributorError::ClaimWindowClosed)],
+        );
+    }
+
+    #[test]
+    fn test_clawback_rejected_before_clawback_ts() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(CLAWBACK_TS - 1);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.clawback(),
+            &[custom(DistributorError::ClaimWindowOpen)],
+        );
+    }
+
+    #[test]
+    fn test_clawback_returns_unclaimed() {
+        let mut fixture = Fixture::new().created();
+        fixture.run(&fixture.claim(0));
+
+        fixture.set_time(CLAWBACK_TS);
+        fixture.run(&fixture.clawback());
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.admin)),
+            FUNDS - 300
+        );
+        assert_eq!(
+            fixture.account(&fixture.ata(&fixture.distributor)).lamports,
+            0
+        );
+        assert_eq!(fixture.account(&fixture.distributor).lamports, 0);
+    }
+
+    #[test]
+    fn test_distributor_error_codes() {
+        for code in 6000..6015 {
+            let error = DistributorError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(DistributorError::from_code(6015), None);
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio_distributor::{
+        AccountLayout, ClaimStatus, CreateDistributorInstructionData, Distributor,
+        DistributorError, ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        fuzz::{check_invariants, check_rejected, fuzz, Fuzzer},
+        token_program, MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 3;
+
+    fn fuzzer() -> Fuzzer {
+        let admin = Pubkey::new_unique();
+        let distributor = Pubkey::find_program_address(
+            &[b"distributor", admin.as_ref(), &42u64.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0;
+        let (token_program, token_account) = token_program();
+
+        let mut fuzzer = Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                admin,
+                distributor,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .fixed(token_program, token_account)
+        .data_lens(&[
+            Distributor::LEN,
+            ClaimStatus::LEN,
+            MINT_LEN,
+            TOKEN_ACCOUNT_LEN,
+        ])
+        .max_accounts(9);
+        fuzzer.owners.push(token_program);
+        fuzzer
+    }
+
+    fn mollusk() -> Mollusk {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_distributor");
+        test_support::add_token_programs(&mut mollusk);
+        mollusk
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_distributor_data(
+            data in vec(any::<u8>(), 0..=CreateDistributorInstructionData::LEN + 8),
+        ) {
+            let parsed = CreateDistributorInstructionData::try_from(data.as_slice());
+
+            if data.len() != CreateDistributorInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let amount = u64::from_le_bytes(data[40..48].try_into().unwrap());
+            if amount == 0 {
+                prop_assert_eq!(parsed.err(), Some(DistributorError::ZeroAmount.into()));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(parsed.seed, u64::from_le_bytes(data[0..8].try_into().unwrap()));
+            prop_assert_eq!(&parsed.merkle_root[..], &data[8..40]);
+            prop_assert_eq!(parsed.amount, amount);
+            prop_assert_eq!(
+                parsed.clawback_ts,
+                i64::from_le_bytes(data[48..56].try_into().unwrap())
+            );
+        }
+
+        #[test]
+        fn test_parse_distributor_header(
+            data in vec(any::<u8>(), Distributor::LEN - 1..=Distributor::LEN + 1),
+            tagged in any::<bool>(),
+        ) {
+            let mut data = data;
+            if tagged {
+                data[0] = Distributor::DISCRIMINATOR;
+                data[1] = Distributor::VERSION;
+            }
+
+            let valid = data.len() == Distributor::LEN
+                && data[0] == Distributor::DISCRIMINATOR
+                && data[1] == Distributor::VERSION;
+            prop_assert_eq!(Distributor::load(&data).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = mollusk();
+        let data = (0..INSTRUCTION_COUNT, vec(any::<u8>(), 0..=96))
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_distributor_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreateDistributorInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
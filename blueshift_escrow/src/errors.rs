@@ -1,12 +1,162 @@
+use core::fmt;
+
 use pinocchio::program_error::ProgramError;
+use program_helpers::HelperError;
+
+/// Errors returned by the escrow program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowError {
+    NotSigner = 6000,
+    InvalidAddress = 6001,
+    InvalidSystemAccount = 6002,
+    InvalidMint = 6003,
+    InvalidTokenAccount = 6004,
+    InvalidEscrowAccount = 6005,
+    InvalidEscrowAddress = 6006,
+    ZeroAmount = 6007,
+    InvalidAccountDiscriminator = 6008,
+    UnsupportedAccountVersion = 6009,
+    AlreadyMigrated = 6010,
+    MissingInstructionsSysvar = 6011,
+    EscrowNotAlone = 6012,
+    DisallowedProgram = 6013,
+    TooManyDisallowedPrograms = 6014,
+    TakeNotTopLevel = 6015,
+    InvalidLegCount = 6016,
+    DuplicateMint = 6017,
+    InvalidAuction = 6018,
+    SlippageExceeded = 6019,
+    InvalidFee = 6020,
+    InvalidResolver = 6021,
+    OptionExpired = 6022,
+    OptionNotExpired = 6023,
+    OptionAlreadySold = 6024,
+    InvalidHolder = 6025,
+    EscrowsDoNotCross = 6026,
+    UnmatchableEscrow = 6027,
+    InvalidBasketAccount = 6028,
+    InvalidOptionAccount = 6029,
+    InvalidParties = 6030,
+    ServiceNotDisputed = 6031,
+    ServiceAlreadyDisputed = 6032,
+    OptionTermsChanged = 6033,
+}
+
+impl EscrowError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidAddress),
+            6002 => Some(Self::InvalidSystemAccount),
+            6003 => Some(Self::InvalidMint),
+            6004 => Some(Self::InvalidTokenAccount),
+            6005 => Some(Self::InvalidEscrowAccount),
+            6006 => Some(Self::InvalidEscrowAddress),
+            6007 => Some(Self::ZeroAmount),
+            6008 => Some(Self::InvalidAccountDiscriminator),
+            6009 => Some(Self::UnsupportedAccountVersion),
+            6010 => Some(Self::AlreadyMigrated),
+            6011 => Some(Self::MissingInstructionsSysvar),
+            6012 => Some(Self::EscrowNotAlone),
+            6013 => Some(Self::DisallowedProgram),
+            6014 => Some(Self::TooManyDisallowedPrograms),
+            6015 => Some(Self::TakeNotTopLevel),
+            6016 => Some(Self::InvalidLegCount),
+            6017 => Some(Self::DuplicateMint),
+            6018 => Some(Self::InvalidAuction),
+            6019 => Some(Self::SlippageExceeded),
+            6020 => Some(Self::InvalidFee),
+            6021 => Some(Self::InvalidResolver),
+            6022 => Some(Self::OptionExpired),
+            6023 => Some(Self::OptionNotExpired),
+            6024 => Some(Self::OptionAlreadySold),
+            6025 => Some(Self::InvalidHolder),
+            6026 => Some(Self::EscrowsDoNotCross),
+            6027 => Some(Self::UnmatchableEscrow),
+            6028 => Some(Self::InvalidBasketAccount),
+            6029 => Some(Self::InvalidOptionAccount),
+            6030 => Some(Self::InvalidParties),
+            6031 => Some(Self::ServiceNotDisputed),
+            6032 => Some(Self::ServiceAlreadyDisputed),
+            6033 => Some(Self::OptionTermsChanged),
+            _ => None,
+        }
+    }
+}
 
-impl From<PinocchioError> for ProgramError {
-    fn from(e: PinocchioError) -> Self {
-        ProgramError::Custom(e as u32)
+impl From<EscrowError> for ProgramError {
+    fn from(error: EscrowError) -> Self {
+        ProgramError::Custom(error as u32)
     }
 }
 
-pub enum PinocchioError {
-    NotSigner,
-    InvalidAddress,
-}
\ No newline at end of file
+impl HelperError for EscrowError {
+    const NOT_SIGNER: Self = Self::NotSigner;
+    const INVALID_MINT: Self = Self::InvalidMint;
+    const INVALID_TOKEN_ACCOUNT: Self = Self::InvalidTokenAccount;
+    const INVALID_ADDRESS: Self = Self::InvalidAddress;
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self = Self::InvalidAccountDiscriminator;
+    const UNSUPPORTED_ACCOUNT_VERSION: Self = Self::UnsupportedAccountVersion;
+}
+
+impl fmt::Display for EscrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Account must sign the transaction",
+            Self::InvalidAddress => "Associated token account does not match its expected address",
+            Self::InvalidSystemAccount => "Account must be owned by the System Program",
+            Self::InvalidMint => "Account is not a valid SPL Token or Token-2022 mint",
+            Self::InvalidTokenAccount => {
+                "Account is not a valid SPL Token or Token-2022 token account"
+            }
+            Self::InvalidEscrowAccount => {
+                "Escrow account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidEscrowAddress => "Escrow account does not match the escrow PDA",
+            Self::ZeroAmount => "Deposit amount must be greater than zero",
+            Self::InvalidAccountDiscriminator => "Escrow account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Escrow account layout version is not supported",
+            Self::AlreadyMigrated => "Escrow account is already on the current layout",
+            Self::MissingInstructionsSysvar => {
+                "Take on a guarded escrow needs the Instructions sysvar account"
+            }
+            Self::EscrowNotAlone => "Another instruction in the transaction uses the escrow",
+            Self::DisallowedProgram => "Transaction calls a program the maker disallowed",
+            Self::TooManyDisallowedPrograms => "Too many disallowed programs",
+            Self::TakeNotTopLevel => "Guarded Take must be a top-level instruction, not a CPI",
+            Self::InvalidLegCount => "Basket must have between one and four legs on each side",
+            Self::DuplicateMint => "Basket lists the same mint twice on one side",
+            Self::InvalidAuction => {
+                "Dutch auction must end after it starts and must not rise in price"
+            }
+            Self::SlippageExceeded => "Current price is above the taker's maximum",
+            Self::InvalidFee => "Fee is above 10000 basis points",
+            Self::InvalidResolver => "Signer may not resolve the service escrow this way",
+            Self::OptionExpired => "Option has expired",
+            Self::OptionNotExpired => "Option has a holder and has not expired yet",
+            Self::OptionAlreadySold => "Option already has a holder",
+            Self::InvalidHolder => "Signer is not the option's holder",
+            Self::EscrowsDoNotCross => "Escrow prices do not cross",
+            Self::UnmatchableEscrow => "Only unguarded, fixed-price token escrows can be matched",
+            Self::InvalidBasketAccount => {
+                "Basket account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidOptionAccount => {
+                "Option account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidParties => "Buyer, recipient and arbiter must be three different accounts",
+            Self::ServiceNotDisputed => "Arbiter may only resolve a disputed service escrow",
+            Self::ServiceAlreadyDisputed => "Service escrow is already disputed",
+            Self::OptionTermsChanged => {
+                "Option strike or expiry differs from what the buyer expected"
+            }
+        };
+
+        f.write_str(message)
+    }
+}
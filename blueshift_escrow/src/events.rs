@@ -0,0 +1,509 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`EscrowEvent::decode`].
+
+use core::mem::size_of;
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and made only of byte arrays, so that
+/// every bit pattern is valid and the struct has no padding.
+pub unsafe trait Event: Copy + Sized {
+    const DISCRIMINATOR: u8;
+    const LEN: usize = size_of::<Self>();
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    #[inline(always)]
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[self.as_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowMade {
+    pub header: EventHeader,
+    pub maker: Pubkey,
+    pub seed: [u8; 8],
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: [u8; 8],
+    pub receive: [u8; 8],
+}
+
+unsafe impl Event for EscrowMade {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl EscrowMade {
+    pub fn new(
+        maker: Pubkey,
+        seed: u64,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        amount: u64,
+        receive: u64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            maker,
+            seed: seed.to_le_bytes(),
+            mint_a,
+            mint_b,
+            amount: amount.to_le_bytes(),
+            receive: receive.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowTaken {
+    pub header: EventHeader,
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub amount: [u8; 8],
+    pub receive: [u8; 8],
+}
+
+unsafe impl Event for EscrowTaken {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl EscrowTaken {
+    pub fn new(escrow: Pubkey, maker: Pubkey, taker: Pubkey, amount: u64, receive: u64) -> Self {
+        Self {
+            header: Self::header(),
+            escrow,
+            maker,
+            taker,
+            amount: amount.to_le_bytes(),
+            receive: receive.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowRefunded {
+    pub header: EventHeader,
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub amount: [u8; 8],
+}
+
+unsafe impl Event for EscrowRefunded {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl EscrowRefunded {
+    pub fn new(escrow: Pubkey, maker: Pubkey, amount: u64) -> Self {
+        Self {
+            header: Self::header(),
+            escrow,
+            maker,
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowMigrated {
+    pub header: EventHeader,
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+unsafe impl Event for EscrowMigrated {
+    const DISCRIMINATOR: u8 = 3;
+}
+
+impl EscrowMigrated {
+    pub fn new(account: Pubkey, from_version: u8, to_version: u8) -> Self {
+        Self {
+            header: Self::header(),
+            account,
+            from_version,
+            to_version,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasketMade {
+    pub header: EventHeader,
+    pub basket: Pubkey,
+    pub maker: Pubkey,
+    pub seed: [u8; 8],
+    pub offered_len: u8,
+    pub requested_len: u8,
+}
+
+unsafe impl Event for BasketMade {
+    const DISCRIMINATOR: u8 = 4;
+}
+
+impl BasketMade {
+    pub fn new(
+        basket: Pubkey,
+        maker: Pubkey,
+        seed: u64,
+        offered_len: u8,
+        requested_len: u8,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            basket,
+            maker,
+            seed: seed.to_le_bytes(),
+            offered_len,
+            requested_len,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasketTaken {
+    pub header: EventHeader,
+    pub basket: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+}
+
+unsafe impl Event for BasketTaken {
+    const DISCRIMINATOR: u8 = 5;
+}
+
+impl BasketTaken {
+    pub fn new(basket: Pubkey, maker: Pubkey, taker: Pubkey) -> Self {
+        Self {
+            header: Self::header(),
+            basket,
+            maker,
+            taker,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasketRefunded {
+    pub header: EventHeader,
+    pub basket: Pubkey,
+    pub maker: Pubkey,
+}
+
+unsafe impl Event for BasketRefunded {
+    const DISCRIMINATOR: u8 = 6;
+}
+
+impl BasketRefunded {
+    pub fn new(basket: Pubkey, maker: Pubkey) -> Self {
+        Self {
+            header: Self::header(),
+            basket,
+            maker,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ServiceOpened {
+    pub header: EventHeader,
+    pub service: Pubkey,
+    pub buyer: Pubkey,
+    pub recipient: Pubkey,
+    pub arbiter: Pubkey,
+    pub mint: Pubkey,
+    pub amount: [u8; 8],
+}
+
+unsafe impl Event for ServiceOpened {
+    const DISCRIMINATOR: u8 = 7;
+}
+
+impl ServiceOpened {
+    pub fn new(
+        service: Pubkey,
+        buyer: Pubkey,
+        recipient: Pubkey,
+        arbiter: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            service,
+            buyer,
+            recipient,
+            arbiter,
+            mint,
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+/// `payee` is the recipient on a release and the buyer on a refund.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ServiceResolved {
+    pub header: EventHeader,
+    pub service: Pubkey,
+    pub resolver: Pubkey,
+    pub payee: Pubkey,
+    pub amount: [u8; 8],
+    pub fee: [u8; 8],
+}
+
+unsafe impl Event for ServiceResolved {
+    const DISCRIMINATOR: u8 = 8;
+}
+
+impl ServiceResolved {
+    pub fn new(service: Pubkey, resolver: Pubkey, payee: Pubkey, amount: u64, fee: u64) -> Self {
+        Self {
+            header: Self::header(),
+            service,
+            resolver,
+            payee,
+            amount: amount.to_le_bytes(),
+            fee: fee.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ServiceDisputed {
+    pub header: EventHeader,
+    pub service: Pubkey,
+    pub authority: Pubkey,
+}
+
+unsafe impl Event for ServiceDisputed {
+    const DISCRIMINATOR: u8 = 13;
+}
+
+impl ServiceDisputed {
+    pub fn new(service: Pubkey, authority: Pubkey) -> Self {
+        Self {
+            header: Self::header(),
+            service,
+            authority,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptionWritten {
+    pub header: EventHeader,
+    pub option: Pubkey,
+    pub writer: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: [u8; 8],
+    pub strike: [u8; 8],
+    pub expiry: [u8; 8],
+}
+
+unsafe impl Event for OptionWritten {
+    const DISCRIMINATOR: u8 = 9;
+}
+
+impl OptionWritten {
+    pub fn new(
+        option: Pubkey,
+        writer: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        amount: u64,
+        strike: u64,
+        expiry: i64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            option,
+            writer,
+            mint_a,
+            mint_b,
+            amount: amount.to_le_bytes(),
+            strike: strike.to_le_bytes(),
+            expiry: expiry.to_le_bytes(),
+        }
+    }
+}
+
+/// Emitted both when the option is bought and when it is transferred.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptionHolderChanged {
+    pub header: EventHeader,
+    pub option: Pubkey,
+    pub holder: Pubkey,
+}
+
+unsafe impl Event for OptionHolderChanged {
+    const DISCRIMINATOR: u8 = 10;
+}
+
+impl OptionHolderChanged {
+    pub fn new(option: Pubkey, holder: Pubkey) -> Self {
+        Self {
+            header: Self::header(),
+            option,
+            holder,
+        }
+    }
+}
+
+/// `exercised` is 1 when the holder exercised, 0 when the writer reclaimed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptionClosed {
+    pub header: EventHeader,
+    pub option: Pubkey,
+    pub writer: Pubkey,
+    pub exercised: u8,
+}
+
+unsafe impl Event for OptionClosed {
+    const DISCRIMINATOR: u8 = 11;
+}
+
+impl OptionClosed {
+    pub fn new(option: Pubkey, writer: Pubkey, exercised: bool) -> Self {
+        Self {
+            header: Self::header(),
+            option,
+            writer,
+            exercised: exercised as u8,
+        }
+    }
+}
+
+/// `filled` is the amount of `escrow_1`'s token A that changed hands and
+/// `surplus` the token B left over for the cranker.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowsMatched {
+    pub header: EventHeader,
+    pub escrow_1: Pubkey,
+    pub escrow_2: Pubkey,
+    pub cranker: Pubkey,
+    pub filled: [u8; 8],
+    pub surplus: [u8; 8],
+}
+
+unsafe impl Event for EscrowsMatched {
+    const DISCRIMINATOR: u8 = 12;
+}
+
+impl EscrowsMatched {
+    pub fn new(
+        escrow_1: Pubkey,
+        escrow_2: Pubkey,
+        cranker: Pubkey,
+        filled: u64,
+        surplus: u64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            escrow_1,
+            escrow_2,
+            cranker,
+            filled: filled.to_le_bytes(),
+            surplus: surplus.to_le_bytes(),
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowEvent {
+    Made(EscrowMade),
+    Taken(EscrowTaken),
+    Refunded(EscrowRefunded),
+    Migrated(EscrowMigrated),
+    BasketMade(BasketMade),
+    BasketTaken(BasketTaken),
+    BasketRefunded(BasketRefunded),
+    ServiceOpened(ServiceOpened),
+    ServiceResolved(ServiceResolved),
+    OptionWritten(OptionWritten),
+    OptionHolderChanged(OptionHolderChanged),
+    OptionClosed(OptionClosed),
+    EscrowsMatched(EscrowsMatched),
+    ServiceDisputed(ServiceDisputed),
+}
+
+impl EscrowEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let [version, discriminator, ..] = data else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        if *version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match *discriminator {
+            EscrowMade::DISCRIMINATOR => EscrowMade::read(data).map(Self::Made),
+            EscrowTaken::DISCRIMINATOR => EscrowTaken::read(data).map(Self::Taken),
+            EscrowRefunded::DISCRIMINATOR => EscrowRefunded::read(data).map(Self::Refunded),
+            EscrowMigrated::DISCRIMINATOR => EscrowMigrated::read(data).map(Self::Migrated),
+            BasketMade::DISCRIMINATOR => BasketMade::read(data).map(Self::BasketMade),
+            BasketTaken::DISCRIMINATOR => BasketTaken::read(data).map(Self::BasketTaken),
+            BasketRefunded::DISCRIMINATOR => BasketRefunded::read(data).map(Self::BasketRefunded),
+            ServiceOpened::DISCRIMINATOR => ServiceOpened::read(data).map(Self::ServiceOpened),
+            ServiceResolved::DISCRIMINATOR => ServiceResolved::read(data).map(Self::ServiceResolved),
+            OptionWritten::DISCRIMINATOR => OptionWritten::read(data).map(Self::OptionWritten),
+            OptionHolderChanged::DISCRIMINATOR => {
+                OptionHolderChanged::read(data).map(Self::OptionHolderChanged)
+            }
+            OptionClosed::DISCRIMINATOR => OptionClosed::read(data).map(Self::OptionClosed),
+            EscrowsMatched::DISCRIMINATOR => EscrowsMatched::read(data).map(Self::EscrowsMatched),
+            ServiceDisputed::DISCRIMINATOR => {
+                ServiceDisputed::read(data).map(Self::ServiceDisputed)
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
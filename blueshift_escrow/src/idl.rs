@@ -0,0 +1,92 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    AccountLayout, Basket, BuyOption, BuyOptionAccounts, BuyOptionInstructionData, CallOption,
+    DisputeService, DisputeServiceAccounts, Escrow, EscrowError, ExerciseOption,
+    ExerciseOptionAccounts, Make, MakeAccounts, MakeBasket, MakeBasketAccounts,
+    MakeBasketInstructionData, MakeInstructionData, Match, MatchAccounts, Migrate, MigrateAccounts,
+    OpenService, OpenServiceAccounts, OpenServiceInstructionData, ReclaimOption,
+    ReclaimOptionAccounts, Refund, RefundAccounts, RefundBasket, RefundBasketAccounts,
+    ResolveService, ResolveServiceAccounts, ResolveServiceInstructionData, ServiceEscrow, Take,
+    TakeAccounts, TakeBasket, TakeBasketAccounts, TakeInstructionData, TransferOption,
+    TransferOptionAccounts, WriteOption, WriteOptionAccounts, WriteOptionInstructionData,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("make", *Make::DISCRIMINATOR)
+            .accounts::<MakeAccounts>()
+            .args::<MakeInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("take", *Take::DISCRIMINATOR)
+            .accounts::<TakeAccounts>()
+            .args::<TakeInstructionData>(),
+    )
+    .instruction(IdlInstruction::new("refund", *Refund::DISCRIMINATOR).accounts::<RefundAccounts>())
+    .instruction(
+        IdlInstruction::new("migrate", *Migrate::DISCRIMINATOR).accounts::<MigrateAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("make_basket", *MakeBasket::DISCRIMINATOR)
+            .accounts::<MakeBasketAccounts>()
+            .args::<MakeBasketInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("take_basket", *TakeBasket::DISCRIMINATOR)
+            .accounts::<TakeBasketAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("refund_basket", *RefundBasket::DISCRIMINATOR)
+            .accounts::<RefundBasketAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("open_service", *OpenService::DISCRIMINATOR)
+            .accounts::<OpenServiceAccounts>()
+            .args::<OpenServiceInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("resolve_service", *ResolveService::DISCRIMINATOR)
+            .accounts::<ResolveServiceAccounts>()
+            .args::<ResolveServiceInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("write_option", *WriteOption::DISCRIMINATOR)
+            .accounts::<WriteOptionAccounts>()
+            .args::<WriteOptionInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("buy_option", *BuyOption::DISCRIMINATOR)
+            .accounts::<BuyOptionAccounts>()
+            .args::<BuyOptionInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("transfer_option", *TransferOption::DISCRIMINATOR)
+            .accounts::<TransferOptionAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("exercise_option", *ExerciseOption::DISCRIMINATOR)
+            .accounts::<ExerciseOptionAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("reclaim_option", *ReclaimOption::DISCRIMINATOR)
+            .accounts::<ReclaimOptionAccounts>(),
+    )
+    .instruction(IdlInstruction::new("match", *Match::DISCRIMINATOR).accounts::<MatchAccounts>())
+    .instruction(
+        IdlInstruction::new("dispute_service", *DisputeService::DISCRIMINATOR)
+            .accounts::<DisputeServiceAccounts>(),
+    )
+    .account::<Escrow>(&[Escrow::DISCRIMINATOR, Escrow::VERSION])
+    .account::<Basket>(&[Basket::DISCRIMINATOR, Basket::VERSION])
+    .account::<ServiceEscrow>(&[ServiceEscrow::DISCRIMINATOR, ServiceEscrow::VERSION])
+    .account::<CallOption>(&[CallOption::DISCRIMINATOR, CallOption::VERSION])
+    .errors(EscrowError::from_code)
+}
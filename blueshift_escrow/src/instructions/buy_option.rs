@@ -0,0 +1,164 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, CallOption, CallOptionAccount, EscrowError,
+    events::{Event, OptionHolderChanged},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct BuyOptionAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub buyer: &'a AccountInfo,
+    pub writer: &'a AccountInfo,
+    #[account(check = CallOptionAccount::check(option))]
+    #[idl(writable)]
+    pub option: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(buyer_ata_b, buyer, mint_b, token_program))]
+    #[idl(writable)]
+    pub buyer_ata_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub writer_ata_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+/// The terms the buyer agreed to. The writer can reclaim an unsold option and
+/// write a new one at the same address, so the buy is rejected unless the
+/// option still matches them.
+#[derive(IdlType)]
+pub struct BuyOptionInstructionData {
+    /// Most token B the buyer pays as the premium.
+    pub max_premium: u64,
+    /// Amount of token B the buyer expects to pay to exercise.
+    pub strike: u64,
+    /// Unix time the buyer expects the option to expire.
+    pub expiry: i64,
+}
+
+impl BuyOptionInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2 + size_of::<i64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for BuyOptionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let max_premium = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let strike = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let expiry = i64::from_le_bytes(data[16..24].try_into().unwrap());
+
+        Ok(Self {
+            max_premium,
+            strike,
+            expiry,
+        })
+    }
+}
+
+pub struct BuyOption<'a> {
+    pub accounts: BuyOptionAccounts<'a>,
+    pub instruction_data: BuyOptionInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BuyOption<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = BuyOptionAccounts::try_from(accounts)?;
+        let instruction_data = BuyOptionInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> BuyOption<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.option.try_borrow_mut_data()?;
+        let option = CallOption::load_mut(data.as_mut())?;
+
+        // Check if the option is valid
+        let option_key = create_program_address(
+            &[
+                b"option",
+                self.accounts.writer.key(),
+                &option.seed,
+                &option.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &option_key != self.accounts.option.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+        if self.accounts.mint_b.key().ne(&option.mint_b) {
+            return Err(EscrowError::InvalidMint.into());
+        }
+        if option.has_holder() {
+            return Err(EscrowError::OptionAlreadySold.into());
+        }
+        if Clock::get()?.unix_timestamp >= option.expiry() {
+            return Err(EscrowError::OptionExpired.into());
+        }
+        if option.strike() != self.instruction_data.strike
+            || option.expiry() != self.instruction_data.expiry
+        {
+            return Err(EscrowError::OptionTermsChanged.into());
+        }
+        if option.premium() > self.instruction_data.max_premium {
+            return Err(EscrowError::SlippageExceeded.into());
+        }
+
+        option.set_holder(*self.accounts.buyer.key());
+        let premium = option.premium();
+        drop(data);
+
+        // Pay the writer
+        if premium > 0 {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.writer_ata_b,
+                self.accounts.mint_b,
+                self.accounts.buyer,
+                self.accounts.writer,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: self.accounts.buyer_ata_b,
+                to: self.accounts.writer_ata_b,
+                authority: self.accounts.buyer,
+                amount: premium,
+            }
+            .invoke()?;
+        }
+
+        OptionHolderChanged::new(*self.accounts.option.key(), *self.accounts.buyer.key()).emit();
+
+        Ok(())
+    }
+}
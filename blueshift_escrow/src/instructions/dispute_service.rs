@@ -0,0 +1,57 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountLayout, EscrowError, ServiceEscrow, ServiceEscrowAccount,
+    events::{Event, ServiceDisputed},
+};
+
+/// Hands the service escrow to its arbiter. Until it is disputed, only the
+/// buyer can settle it, by releasing to the recipient.
+#[derive(Accounts, IdlAccounts)]
+pub struct DisputeServiceAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    pub authority: &'a AccountInfo,
+    #[account(check = ServiceEscrowAccount::check(service))]
+    #[idl(writable)]
+    pub service: &'a AccountInfo,
+}
+
+pub struct DisputeService<'a> {
+    pub accounts: DisputeServiceAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DisputeService<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = DisputeServiceAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> DisputeService<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.service.try_borrow_mut_data()?;
+        let service = ServiceEscrow::load_mut(data.as_mut())?;
+
+        // Only the two sides of the deal can call in the arbiter
+        let authority = self.accounts.authority.key();
+        if authority.ne(&service.buyer) && authority.ne(&service.recipient) {
+            return Err(EscrowError::InvalidResolver.into());
+        }
+        if service.is_disputed() {
+            return Err(EscrowError::ServiceAlreadyDisputed.into());
+        }
+
+        service.set_disputed();
+
+        ServiceDisputed::new(*self.accounts.service.key(), *authority).emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, CallOption, CallOptionAccount, DrainVault, EscrowError,
+    ProgramAccount,
+    events::{Event, OptionClosed},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct ExerciseOptionAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub holder: &'a AccountInfo,
+    #[idl(writable)]
+    pub writer: &'a AccountInfo,
+    #[account(check = CallOptionAccount::check(option))]
+    #[idl(writable)]
+    pub option: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault, option, mint_a, token_program))]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub holder_ata_a: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(holder_ata_b, holder, mint_b, token_program))]
+    #[idl(writable)]
+    pub holder_ata_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub writer_ata_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct ExerciseOption<'a> {
+    pub accounts: ExerciseOptionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExerciseOption<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ExerciseOptionAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ExerciseOption<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.option.try_borrow_data()?;
+        let option = CallOption::load(&data)?;
+
+        // Check if the option is valid
+        let option_key = create_program_address(
+            &[
+                b"option",
+                self.accounts.writer.key(),
+                &option.seed,
+                &option.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &option_key != self.accounts.option.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+        if self.accounts.mint_a.key().ne(&option.mint_a)
+            || self.accounts.mint_b.key().ne(&option.mint_b)
+        {
+            return Err(EscrowError::InvalidMint.into());
+        }
+        if !option.has_holder() || self.accounts.holder.key().ne(&option.holder) {
+            return Err(EscrowError::InvalidHolder.into());
+        }
+        if Clock::get()?.unix_timestamp >= option.expiry() {
+            return Err(EscrowError::OptionExpired.into());
+        }
+
+        let strike = option.strike();
+        let seed_binding = option.seed;
+        let bump_binding = option.bump;
+        drop(data);
+
+        // Pay the strike to the writer
+        AssociatedTokenAccount::init_if_needed(
+            self.accounts.writer_ata_b,
+            self.accounts.mint_b,
+            self.accounts.holder,
+            self.accounts.writer,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        Transfer {
+            from: self.accounts.holder_ata_b,
+            to: self.accounts.writer_ata_b,
+            authority: self.accounts.holder,
+            amount: strike,
+        }
+        .invoke()?;
+
+        let option_seeds = [
+            Seed::from(b"option"),
+            Seed::from(self.accounts.writer.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&option_seeds);
+
+        // Deliver the collateral to the holder, and close the vault and the
+        // option, the writer paid their rent
+        DrainVault {
+            vault: self.accounts.vault,
+            to: self.accounts.holder_ata_a,
+            owner: self.accounts.holder,
+            mint: self.accounts.mint_a,
+            payer: self.accounts.holder,
+            destination: self.accounts.writer,
+            authority: self.accounts.option,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        ProgramAccount::close(self.accounts.option, self.accounts.writer)?;
+
+        OptionClosed::new(*self.accounts.option.key(), *self.accounts.writer.key(), true).emit();
+
+        Ok(())
+    }
+}
@@ -1,78 +1,101 @@
 use core::mem::size_of;
 use pinocchio::{
     ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{Pubkey, find_program_address},
 };
+use pinocchio_system::instructions::Transfer as SystemTransfer;
 use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
 
 use crate::{
-    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, AssociatedTokenAccountInit,
-    Escrow, MintInterface, ProgramAccount, ProgramAccountInit, SignerAccount,
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Escrow, EscrowError, MAX_DISALLOWED_PROGRAMS, MintAccount,
+    NATIVE_SOL, ProgramAccount, ProgramAccountInit,
+    events::{EscrowMade, Event},
 };
 
+/// A side whose mint is `NATIVE_SOL` trades lamports. For a native mint A,
+/// `maker_ata_a` and `vault` are not read and the deposit is held by the
+/// escrow account itself.
+///
+/// The escrow moves tokens through SPL Token only, so both mints must belong
+/// to it. A Token-2022 mint is refused with `InvalidMint` before anything is
+/// created.
+#[derive(Accounts, IdlAccounts)]
 pub struct MakeAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
     pub maker: &'a AccountInfo,
+    #[idl(writable)]
     pub escrow: &'a AccountInfo,
+    #[account(
+        constraint = mint_a.key().ne(mint_b.key()) @ EscrowError::DuplicateMint,
+        check = MintAccount::check(mint_a) if mint_a.key().ne(&NATIVE_SOL),
+    )]
     pub mint_a: &'a AccountInfo,
+    #[account(check = MintAccount::check(mint_b) if mint_b.key().ne(&NATIVE_SOL))]
     pub mint_b: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)
+            if mint_a.key().ne(&NATIVE_SOL),
+    )]
+    #[idl(writable)]
     pub maker_ata_a: &'a AccountInfo,
+    #[idl(writable)]
     pub vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
     pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
     pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
     pub associated_token_account_program: &'a AccountInfo,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for MakeAccounts<'a> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [
-            maker,
-            escrow,
-            mint_a,
-            mint_b,
-            maker_ata_a,
-            vault,
-            system_program,
-            token_program,
-            associated_token_account_program,
-        ] = accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        // Basic Accounts Checks
-        SignerAccount::check(maker)?;
-        MintInterface::check(mint_a)?;
-        MintInterface::check(mint_b)?;
-        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
-
-        // Return the accounts
-        Ok(Self {
-            maker,
-            escrow,
-            mint_a,
-            mint_b,
-            maker_ata_a,
-            vault,
-            system_program,
-            token_program,
-            associated_token_account_program,
-        })
-    }
-}
-
+/// Make takes either the three amounts alone, which leaves the escrow
+/// unguarded, the amounts followed by the guard fields, or both followed by
+/// the Dutch auction fields.
+#[derive(IdlType)]
 pub struct MakeInstructionData {
     pub seed: u64,
+    /// Amount of token B wanted, or the starting price of a Dutch auction.
     pub receive: u64,
     pub amount: u64,
+    /// Option bits, `1` guards Take against sandwich bundles and `2` makes
+    /// the escrow a Dutch auction.
+    pub flags: u8,
+    /// Number of entries in use in `disallowed_programs`.
+    pub disallowed_len: u8,
+    /// Programs a guarded Take refuses to share a transaction with.
+    pub disallowed_programs: [Pubkey; MAX_DISALLOWED_PROGRAMS],
+    /// Amount of token B wanted once the auction ends.
+    pub end_receive: u64,
+    /// Unix time the price starts decaying from `receive`.
+    pub start_ts: i64,
+    /// Unix time the price reaches `end_receive`.
+    pub end_ts: i64,
+}
+
+impl MakeInstructionData {
+    /// Length of the data without the guard fields.
+    pub const BASE_LEN: usize = size_of::<u64>() * 3;
+
+    /// Length of the data with the guard fields.
+    pub const GUARDED_LEN: usize =
+        Self::BASE_LEN + 2 + size_of::<[Pubkey; MAX_DISALLOWED_PROGRAMS]>();
+
+    /// Length of the data with the guard and Dutch auction fields.
+    pub const AUCTION_LEN: usize = Self::GUARDED_LEN + size_of::<u64>() * 3;
 }
 
 impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() * 3 {
+        if data.len() != Self::BASE_LEN
+            && data.len() != Self::GUARDED_LEN
+            && data.len() != Self::AUCTION_LEN
+        {
             return Err(ProgramError::InvalidInstructionData);
         }
 
@@ -80,15 +103,58 @@ impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
         let receive = u64::from_le_bytes(data[8..16].try_into().unwrap());
         let amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
 
+        let mut flags = 0;
+        let mut disallowed_len = 0;
+        let mut disallowed_programs = [[0; 32]; MAX_DISALLOWED_PROGRAMS];
+        if data.len() >= Self::GUARDED_LEN {
+            flags = data[24];
+            disallowed_len = data[25];
+            for (program, bytes) in disallowed_programs
+                .iter_mut()
+                .zip(data[26..Self::GUARDED_LEN].chunks_exact(size_of::<Pubkey>()))
+            {
+                *program = bytes.try_into().unwrap();
+            }
+        }
+
+        let mut end_receive = 0;
+        let mut start_ts = 0;
+        let mut end_ts = 0;
+        if data.len() == Self::AUCTION_LEN {
+            let auction = &data[Self::GUARDED_LEN..];
+            end_receive = u64::from_le_bytes(auction[0..8].try_into().unwrap());
+            start_ts = i64::from_le_bytes(auction[8..16].try_into().unwrap());
+            end_ts = i64::from_le_bytes(auction[16..24].try_into().unwrap());
+        }
+
         // Instruction Checks
         if amount == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+        if flags & !(Escrow::TAKE_GUARD | Escrow::DUTCH_AUCTION) != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // The auction fields come with the flag and only with it
+        if (flags & Escrow::DUTCH_AUCTION != 0) != (data.len() == Self::AUCTION_LEN) {
             return Err(ProgramError::InvalidInstructionData);
         }
+        if disallowed_len as usize > MAX_DISALLOWED_PROGRAMS {
+            return Err(EscrowError::TooManyDisallowedPrograms.into());
+        }
+        if data.len() == Self::AUCTION_LEN && (start_ts >= end_ts || end_receive > receive) {
+            return Err(EscrowError::InvalidAuction.into());
+        }
 
         Ok(Self {
             seed,
             receive,
             amount,
+            flags,
+            disallowed_len,
+            disallowed_programs,
+            end_receive,
+            start_ts,
+            end_ts,
         })
     }
 }
@@ -107,7 +173,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Make<'a> {
         let instruction_data = MakeInstructionData::try_from(data)?;
 
         // Initialize the Accounts needed
-        let (_, bump) = find_program_address(
+        let (escrow_key, bump) = find_program_address(
             &[
                 b"escrow",
                 accounts.maker.key(),
@@ -115,6 +181,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Make<'a> {
             ],
             &crate::ID,
         );
+        if &escrow_key != accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
 
         let seed_binding = instruction_data.seed.to_le_bytes();
         let bump_binding = [bump];
@@ -132,15 +201,17 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Make<'a> {
             Escrow::LEN,
         )?;
 
-        // Initialize the vault
-        AssociatedTokenAccount::init(
-            accounts.vault,
-            accounts.mint_a,
-            accounts.maker,
-            accounts.escrow,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
+        // Initialize the vault, native SOL stays in the escrow account
+        if accounts.mint_a.key().ne(&NATIVE_SOL) {
+            AssociatedTokenAccount::init(
+                accounts.vault,
+                accounts.mint_a,
+                accounts.maker,
+                accounts.escrow,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
 
         Ok(Self {
             accounts,
@@ -156,7 +227,7 @@ impl<'a> Make<'a> {
     pub fn process(&mut self) -> ProgramResult {
         // Populate the escrow account
         let mut data = self.accounts.escrow.try_borrow_mut_data()?;
-        let escrow = Escrow::load_mut(data.as_mut())?;
+        let escrow = Escrow::init(data.as_mut())?;
 
         escrow.set_inner(
             self.instruction_data.seed,
@@ -166,16 +237,48 @@ impl<'a> Make<'a> {
             self.instruction_data.receive,
             [self.bump],
         );
+        escrow.set_guard(
+            self.instruction_data.flags,
+            self.instruction_data.disallowed_len,
+            self.instruction_data.disallowed_programs,
+        );
+        escrow.set_auction(
+            self.instruction_data.end_receive,
+            self.instruction_data.start_ts,
+            self.instruction_data.end_ts,
+        );
 
-        // Transfer tokens to vault
-        Transfer {
-            from: self.accounts.maker_ata_a,
-            to: self.accounts.vault,
-            authority: self.accounts.maker,
-            amount: self.instruction_data.amount,
+        drop(data);
+
+        if self.accounts.mint_a.key().eq(&NATIVE_SOL) {
+            // Deposit lamports on top of the escrow's rent
+            SystemTransfer {
+                from: self.accounts.maker,
+                to: self.accounts.escrow,
+                lamports: self.instruction_data.amount,
+            }
+            .invoke()?;
+        } else {
+            // Transfer tokens to vault
+            Transfer {
+                from: self.accounts.maker_ata_a,
+                to: self.accounts.vault,
+                authority: self.accounts.maker,
+                amount: self.instruction_data.amount,
+            }
+            .invoke()?;
         }
-        .invoke()?;
+
+        EscrowMade::new(
+            *self.accounts.maker.key(),
+            self.instruction_data.seed,
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            self.instruction_data.amount,
+            self.instruction_data.receive,
+        )
+        .emit();
 
         Ok(())
     }
-}
\ No newline at end of file
+}
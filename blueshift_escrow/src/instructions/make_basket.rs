@@ -0,0 +1,239 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Basket, EscrowError, MAX_BASKET_LEGS, MintInterface,
+    ProgramAccount, ProgramAccountInit,
+    events::{BasketMade, Event},
+};
+
+/// The fixed accounts, followed by three accounts per offered leg
+/// (`mint`, `maker_ata`, `vault`) and one per requested leg (`mint`).
+#[derive(Accounts, IdlAccounts)]
+pub struct MakeBasketAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub maker: &'a AccountInfo,
+    #[idl(writable)]
+    pub basket: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+    #[idl(skip)]
+    pub legs: &'a [AccountInfo],
+}
+
+/// Amounts of the slots past `offered_len` and `requested_len` are ignored.
+#[derive(IdlType)]
+pub struct MakeBasketInstructionData {
+    pub seed: u64,
+    /// Number of deposited mints.
+    pub offered_len: u8,
+    /// Number of requested mints.
+    pub requested_len: u8,
+    /// Amount deposited of each offered mint.
+    pub offered_amounts: [u64; MAX_BASKET_LEGS],
+    /// Amount wanted of each requested mint.
+    pub requested_amounts: [u64; MAX_BASKET_LEGS],
+}
+
+impl MakeBasketInstructionData {
+    pub const LEN: usize = size_of::<u64>() + 2 + size_of::<[u64; MAX_BASKET_LEGS]>() * 2;
+}
+
+impl<'a> TryFrom<&'a [u8]> for MakeBasketInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let offered_len = data[8];
+        let requested_len = data[9];
+
+        let mut amounts = data[10..]
+            .chunks_exact(size_of::<u64>())
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+        let offered_amounts = core::array::from_fn(|_| amounts.next().unwrap());
+        let requested_amounts = core::array::from_fn(|_| amounts.next().unwrap());
+
+        // Instruction Checks
+        let leg_count = 1..=MAX_BASKET_LEGS as u8;
+        if !leg_count.contains(&offered_len) || !leg_count.contains(&requested_len) {
+            return Err(EscrowError::InvalidLegCount.into());
+        }
+        if offered_amounts[..offered_len as usize].contains(&0) {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            seed,
+            offered_len,
+            requested_len,
+            offered_amounts,
+            requested_amounts,
+        })
+    }
+}
+
+pub struct MakeBasket<'a> {
+    pub accounts: MakeBasketAccounts<'a>,
+    pub instruction_data: MakeBasketInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for MakeBasket<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = MakeBasketAccounts::try_from(accounts)?;
+        let instruction_data = MakeBasketInstructionData::try_from(data)?;
+
+        let offered_len = instruction_data.offered_len as usize;
+        let requested_len = instruction_data.requested_len as usize;
+        if accounts.legs.len() != offered_len * 3 + requested_len {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        // Initialize the Accounts needed
+        let (basket_key, bump) = find_program_address(
+            &[
+                b"basket",
+                accounts.maker.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &basket_key != accounts.basket.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let basket_seeds = [
+            Seed::from(b"basket"),
+            Seed::from(accounts.maker.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<Basket>(
+            accounts.maker,
+            accounts.basket,
+            &basket_seeds,
+            Basket::LEN,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> MakeBasket<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let (offered, requested) = self
+            .accounts
+            .legs
+            .split_at(self.instruction_data.offered_len as usize * 3);
+
+        // Populate the basket account
+        let mut data = self.accounts.basket.try_borrow_mut_data()?;
+        let basket = Basket::init(data.as_mut())?;
+
+        basket.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.maker.key(),
+            [self.bump],
+        );
+
+        for leg in offered.chunks_exact(3) {
+            let [mint, maker_ata, _] = leg else {
+                unreachable!()
+            };
+
+            MintInterface::check(mint)?;
+            AssociatedTokenAccount::check(
+                maker_ata,
+                self.accounts.maker,
+                mint,
+                self.accounts.token_program,
+            )?;
+            // A second leg of the same mint would share its vault
+            if basket.offered_mints().contains(mint.key()) {
+                return Err(EscrowError::DuplicateMint.into());
+            }
+
+            basket.push_offered(*mint.key());
+        }
+
+        for (mint, amount) in requested
+            .iter()
+            .zip(self.instruction_data.requested_amounts)
+        {
+            MintInterface::check(mint)?;
+            if basket.requested_mints().contains(mint.key()) {
+                return Err(EscrowError::DuplicateMint.into());
+            }
+
+            basket.push_requested(*mint.key(), amount);
+        }
+
+        // The vaults are created with the basket as their owner, so release it first
+        drop(data);
+
+        // Deposit every offered leg into its own vault
+        for (leg, amount) in offered
+            .chunks_exact(3)
+            .zip(self.instruction_data.offered_amounts)
+        {
+            let [mint, maker_ata, vault] = leg else {
+                unreachable!()
+            };
+
+            AssociatedTokenAccount::init(
+                vault,
+                mint,
+                self.accounts.maker,
+                self.accounts.basket,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: maker_ata,
+                to: vault,
+                authority: self.accounts.maker,
+                amount,
+            }
+            .invoke()?;
+        }
+
+        BasketMade::new(
+            *self.accounts.basket.key(),
+            *self.accounts.maker.key(),
+            self.instruction_data.seed,
+            self.instruction_data.offered_len,
+            self.instruction_data.requested_len,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
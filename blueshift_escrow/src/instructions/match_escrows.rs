@@ -0,0 +1,312 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use pinocchio_token::{
+    instructions::{CloseAccount, Transfer},
+    state::TokenAccount,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, Escrow, EscrowError, MintInterface, NATIVE_SOL,
+    ProgramAccount,
+    events::{EscrowsMatched, Event},
+};
+
+/// `escrow_1` offers token A for token B and `escrow_2` offers token B for
+/// token A. `cranker_ata_b` is left unread when the prices leave no surplus,
+/// and `maker_1_ata_a` unless maker 1 is paid in full with A left over.
+#[derive(Accounts, IdlAccounts)]
+pub struct MatchAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub cranker: &'a AccountInfo,
+    #[idl(writable)]
+    pub maker_1: &'a AccountInfo,
+    #[account(check = ProgramAccount::check(escrow_1))]
+    #[idl(writable)]
+    pub escrow_1: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault_1: &'a AccountInfo,
+    #[idl(writable)]
+    pub maker_2: &'a AccountInfo,
+    #[account(
+        check = ProgramAccount::check(escrow_2),
+        constraint = escrow_1.key().ne(escrow_2.key()) @ EscrowError::InvalidEscrowAccount,
+    )]
+    #[idl(writable)]
+    pub escrow_2: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault_2: &'a AccountInfo,
+    #[account(
+        // Native SOL deposits live in the escrow itself, so only token
+        // escrows can be matched
+        constraint = mint_a.key().ne(&NATIVE_SOL) && mint_b.key().ne(&NATIVE_SOL)
+            @ EscrowError::UnmatchableEscrow,
+        check = MintInterface::check(mint_a),
+        // The vaults are only derived once both escrows are known to be matchable
+        check = AssociatedTokenAccount::check(vault_1, escrow_1, mint_a, token_program),
+    )]
+    pub mint_a: &'a AccountInfo,
+    #[account(
+        check = MintInterface::check(mint_b),
+        check = AssociatedTokenAccount::check(vault_2, escrow_2, mint_b, token_program),
+    )]
+    pub mint_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub maker_1_ata_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub maker_2_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub maker_1_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub cranker_ata_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct Match<'a> {
+    pub accounts: MatchAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Match<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MatchAccounts::try_from(accounts)?;
+
+        // Initialize necessary accounts
+        AssociatedTokenAccount::init_if_needed(
+            accounts.maker_1_ata_b,
+            accounts.mint_b,
+            accounts.cranker,
+            accounts.maker_1,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        AssociatedTokenAccount::init_if_needed(
+            accounts.maker_2_ata_a,
+            accounts.mint_a,
+            accounts.cranker,
+            accounts.maker_2,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Match<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data_1 = self.accounts.escrow_1.try_borrow_data()?;
+        let escrow_1 = Escrow::load(&data_1)?;
+        let data_2 = self.accounts.escrow_2.try_borrow_data()?;
+        let escrow_2 = Escrow::load(&data_2)?;
+
+        // Check if both escrows are valid, the same way Take does
+        check_escrow(escrow_1, self.accounts.escrow_1, self.accounts.maker_1)?;
+        check_escrow(escrow_2, self.accounts.escrow_2, self.accounts.maker_2)?;
+        if escrow_1.mint_a.ne(self.accounts.mint_a.key())
+            || escrow_1.mint_b.ne(self.accounts.mint_b.key())
+            || escrow_2.mint_a.ne(self.accounts.mint_b.key())
+            || escrow_2.mint_b.ne(self.accounts.mint_a.key())
+        {
+            return Err(EscrowError::InvalidMint.into());
+        }
+
+        // A guarded escrow must be taken alone and a Dutch auction has no
+        // fixed price to match against
+        if escrow_1.take_guard()
+            || escrow_2.take_guard()
+            || escrow_1.dutch_auction()
+            || escrow_2.dutch_auction()
+        {
+            return Err(EscrowError::UnmatchableEscrow.into());
+        }
+
+        // Maker 1 sells offer_1 of A for receive_1 of B, maker 2 sells
+        // offer_2 of B for receive_2 of A
+        let offer_1 = TokenAccount::from_account_info(self.accounts.vault_1)?.amount();
+        let offer_2 = TokenAccount::from_account_info(self.accounts.vault_2)?.amount();
+        let receive_1 = escrow_1.receive();
+        let receive_2 = escrow_2.receive();
+
+        // The prices cross when maker 1 asks no more B per A than maker 2
+        // pays: receive_1 / offer_1 <= offer_2 / receive_2
+        if (receive_1 as u128) * (receive_2 as u128) > (offer_1 as u128) * (offer_2 as u128) {
+            return Err(EscrowError::EscrowsDoNotCross.into());
+        }
+
+        // The smaller side is filled in full, the larger one in part. Maker 1
+        // is paid at its own price rounded up, maker 2 pays at its own price
+        // rounded down, and the difference is the cranker's surplus.
+        let filled = offer_1.min(receive_2);
+        if filled == 0 {
+            return Err(EscrowError::EscrowsDoNotCross.into());
+        }
+        let paid_1 = mul_div(receive_1, filled, offer_1, true)?;
+        let spent_2 = mul_div(offer_2, filled, receive_2, false)?;
+        let surplus = spent_2
+            .checked_sub(paid_1)
+            .ok_or(EscrowError::EscrowsDoNotCross)?;
+
+        // What each escrow still wants once the match settles. Rounding
+        // maker 1's price up can pay it in full before its deposit runs out,
+        // and the A left over then goes back to it.
+        let remainder_1 = (filled < offer_1)
+            .then(|| receive_1 - paid_1)
+            .filter(|receive| *receive > 0);
+        let remainder_2 = (filled < receive_2).then(|| receive_2 - filled);
+
+        let seed_1 = escrow_1.seed;
+        let bump_1 = escrow_1.bump;
+        let seeds_1 = [
+            Seed::from(b"escrow"),
+            Seed::from(self.accounts.maker_1.key().as_ref()),
+            Seed::from(&seed_1),
+            Seed::from(&bump_1),
+        ];
+        let signer_1 = Signer::from(&seeds_1);
+
+        let seed_2 = escrow_2.seed;
+        let bump_2 = escrow_2.bump;
+        let seeds_2 = [
+            Seed::from(b"escrow"),
+            Seed::from(self.accounts.maker_2.key().as_ref()),
+            Seed::from(&seed_2),
+            Seed::from(&bump_2),
+        ];
+        let signer_2 = Signer::from(&seeds_2);
+
+        drop(data_1);
+        drop(data_2);
+
+        // Transfer A from Vault 1 to Maker 2
+        Transfer {
+            from: self.accounts.vault_1,
+            to: self.accounts.maker_2_ata_a,
+            authority: self.accounts.escrow_1,
+            amount: filled,
+        }
+        .invoke_signed(core::slice::from_ref(&signer_1))?;
+
+        // Transfer B from Vault 2 to Maker 1, and the surplus to the Cranker
+        Transfer {
+            from: self.accounts.vault_2,
+            to: self.accounts.maker_1_ata_b,
+            authority: self.accounts.escrow_2,
+            amount: paid_1,
+        }
+        .invoke_signed(core::slice::from_ref(&signer_2))?;
+
+        if surplus > 0 {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.cranker_ata_b,
+                self.accounts.mint_b,
+                self.accounts.cranker,
+                self.accounts.cranker,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+            Transfer {
+                from: self.accounts.vault_2,
+                to: self.accounts.cranker_ata_b,
+                authority: self.accounts.escrow_2,
+                amount: surplus,
+            }
+            .invoke_signed(core::slice::from_ref(&signer_2))?;
+        }
+
+        // Close a filled escrow, or leave it open wanting the rest
+        match remainder_1 {
+            Some(receive) => {
+                Escrow::load_mut(&mut self.accounts.escrow_1.try_borrow_mut_data()?)?
+                    .set_receive(receive);
+            }
+            None => {
+                DrainVault {
+                    vault: self.accounts.vault_1,
+                    to: self.accounts.maker_1_ata_a,
+                    owner: self.accounts.maker_1,
+                    mint: self.accounts.mint_a,
+                    payer: self.accounts.cranker,
+                    destination: self.accounts.maker_1,
+                    authority: self.accounts.escrow_1,
+                    system_program: self.accounts.system_program,
+                    token_program: self.accounts.token_program,
+                }
+                .invoke_signed(core::slice::from_ref(&signer_1))?;
+                ProgramAccount::close(self.accounts.escrow_1, self.accounts.maker_1)?;
+            }
+        }
+        match remainder_2 {
+            Some(receive) => {
+                Escrow::load_mut(&mut self.accounts.escrow_2.try_borrow_mut_data()?)?
+                    .set_receive(receive);
+            }
+            None => {
+                CloseAccount {
+                    account: self.accounts.vault_2,
+                    destination: self.accounts.maker_2,
+                    authority: self.accounts.escrow_2,
+                }
+                .invoke_signed(core::slice::from_ref(&signer_2))?;
+                ProgramAccount::close(self.accounts.escrow_2, self.accounts.maker_2)?;
+            }
+        }
+
+        EscrowsMatched::new(
+            *self.accounts.escrow_1.key(),
+            *self.accounts.escrow_2.key(),
+            *self.accounts.cranker.key(),
+            filled,
+            surplus,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
+
+fn check_escrow(escrow: &Escrow, account: &AccountInfo, maker: &AccountInfo) -> ProgramResult {
+    let escrow_key = create_program_address(
+        &[b"escrow", maker.key(), &escrow.seed, &escrow.bump],
+        &crate::ID,
+    )?;
+    if &escrow_key != account.key() {
+        return Err(EscrowError::InvalidEscrowAddress.into());
+    }
+
+    Ok(())
+}
+
+/// `amount * numerator / denominator`, rounded up or down.
+fn mul_div(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+    round_up: bool,
+) -> Result<u64, ProgramError> {
+    let product = (amount as u128) * (numerator as u128);
+    let denominator = denominator as u128;
+    let quotient = if round_up {
+        product.div_ceil(denominator)
+    } else {
+        product / denominator
+    };
+
+    u64::try_from(quotient).map_err(|_| ProgramError::ArithmeticOverflow)
+}
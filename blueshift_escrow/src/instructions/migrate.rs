@@ -0,0 +1,103 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountLayout, Escrow, EscrowError,
+    events::{EscrowMigrated, Event},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct MigrateAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub payer: &'a AccountInfo,
+    #[account(
+        // `ProgramAccount::check` only accepts the current layout, so only the
+        // owner is checked
+        owner = crate::ID @ EscrowError::InvalidEscrowAccount,
+    )]
+    #[idl(writable)]
+    pub escrow: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+}
+
+pub struct Migrate<'a> {
+    pub accounts: MigrateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Migrate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Migrate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    /// Upgrades a v1, v2 or v3 escrow to the current layout in place. Escrows
+    /// migrated from v1 or v2 start unguarded, and none of them is a Dutch
+    /// auction.
+    pub fn process(&mut self) -> ProgramResult {
+        let old_len = self.accounts.escrow.data_len();
+        let from_version = match old_len {
+            Escrow::V1_LEN => 1,
+            Escrow::V2_LEN => 2,
+            Escrow::V3_LEN => 3,
+            Escrow::LEN => return Err(EscrowError::AlreadyMigrated.into()),
+            _ => return Err(EscrowError::InvalidEscrowAccount.into()),
+        };
+        // The tagged layouts must already be escrows, so that another account
+        // type of the same size is not re-tagged as one, and must carry the
+        // version their size belongs to
+        if from_version > 1 {
+            let data = self.accounts.escrow.try_borrow_data()?;
+            if data[0] != Escrow::DISCRIMINATOR {
+                return Err(EscrowError::InvalidAccountDiscriminator.into());
+            }
+            if data[1] != from_version {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // Top up rent for the extra header, guard and auction bytes
+        let minimum_balance = Rent::get()?.minimum_balance(Escrow::LEN);
+        let lamports = self.accounts.escrow.lamports();
+        if lamports < minimum_balance {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.escrow,
+                lamports: minimum_balance - lamports,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.escrow.realloc(Escrow::LEN, false)?;
+
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let mut kept_len = old_len;
+        if old_len == Escrow::V1_LEN {
+            // Shift the v1 fields behind the header
+            data.copy_within(0..Escrow::V1_LEN, Escrow::V2_LEN - Escrow::V1_LEN);
+            kept_len = Escrow::V2_LEN;
+        }
+        data[0] = Escrow::DISCRIMINATOR;
+        data[1] = Escrow::VERSION;
+        data[kept_len..].fill(0);
+
+        EscrowMigrated::new(*self.accounts.escrow.key(), from_version, Escrow::VERSION).emit();
+
+        Ok(())
+    }
+}
@@ -1,9 +1,35 @@
+pub mod buy_option;
+pub mod dispute_service;
+pub mod exercise_option;
 pub mod helpers;
 pub mod make;
+pub mod make_basket;
+pub mod match_escrows;
+pub mod migrate;
+pub mod open_service;
+pub mod reclaim_option;
 pub mod refund;
+pub mod refund_basket;
+pub mod resolve_service;
 pub mod take;
+pub mod take_basket;
+pub mod transfer_option;
+pub mod write_option;
 
+pub use buy_option::*;
+pub use dispute_service::*;
+pub use exercise_option::*;
 pub use helpers::*;
 pub use make::*;
+pub use make_basket::*;
+pub use match_escrows::*;
+pub use migrate::*;
+pub use open_service::*;
+pub use reclaim_option::*;
 pub use refund::*;
-pub use take::*;
\ No newline at end of file
+pub use refund_basket::*;
+pub use resolve_service::*;
+pub use take::*;
+pub use take_basket::*;
+pub use transfer_option::*;
+pub use write_option::*;
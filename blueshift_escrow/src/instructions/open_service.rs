@@ -0,0 +1,204 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, EscrowError, MintInterface, NATIVE_SOL, ProgramAccount,
+    ProgramAccountInit, ServiceEscrow,
+    events::{Event, ServiceOpened},
+};
+
+/// The buyer, recipient and arbiter must be three different accounts. For a
+/// native `mint`, `buyer_ata` and `vault` are not read.
+#[derive(Accounts, IdlAccounts)]
+pub struct OpenServiceAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub buyer: &'a AccountInfo,
+    #[idl(writable)]
+    pub service: &'a AccountInfo,
+    #[account(constraint = recipient.key().ne(buyer.key()) @ EscrowError::InvalidParties)]
+    pub recipient: &'a AccountInfo,
+    #[account(
+        constraint = arbiter.key().ne(buyer.key()) && arbiter.key().ne(recipient.key())
+            @ EscrowError::InvalidParties,
+    )]
+    pub arbiter: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint) if mint.key().ne(&NATIVE_SOL))]
+    pub mint: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(buyer_ata, buyer, mint, token_program)
+            if mint.key().ne(&NATIVE_SOL),
+    )]
+    #[idl(writable)]
+    pub buyer_ata: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct OpenServiceInstructionData {
+    pub seed: u64,
+    pub amount: u64,
+    /// Arbiter's cut of a settled dispute, in basis points.
+    pub fee_bps: u16,
+}
+
+impl OpenServiceInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2 + size_of::<u16>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for OpenServiceInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let fee_bps = u16::from_le_bytes(data[16..18].try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+        if fee_bps > ServiceEscrow::MAX_FEE_BPS {
+            return Err(EscrowError::InvalidFee.into());
+        }
+
+        Ok(Self {
+            seed,
+            amount,
+            fee_bps,
+        })
+    }
+}
+
+pub struct OpenService<'a> {
+    pub accounts: OpenServiceAccounts<'a>,
+    pub instruction_data: OpenServiceInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for OpenService<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = OpenServiceAccounts::try_from(accounts)?;
+        let instruction_data = OpenServiceInstructionData::try_from(data)?;
+
+        // Initialize the Accounts needed
+        let (service_key, bump) = find_program_address(
+            &[
+                b"service",
+                accounts.buyer.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &service_key != accounts.service.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let service_seeds = [
+            Seed::from(b"service"),
+            Seed::from(accounts.buyer.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<ServiceEscrow>(
+            accounts.buyer,
+            accounts.service,
+            &service_seeds,
+            ServiceEscrow::LEN,
+        )?;
+
+        // Initialize the vault, native SOL stays in the service account
+        if accounts.mint.key().ne(&NATIVE_SOL) {
+            AssociatedTokenAccount::init(
+                accounts.vault,
+                accounts.mint,
+                accounts.buyer,
+                accounts.service,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> OpenService<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the service account
+        let mut data = self.accounts.service.try_borrow_mut_data()?;
+        let service = ServiceEscrow::init(data.as_mut())?;
+
+        service.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.buyer.key(),
+            *self.accounts.recipient.key(),
+            *self.accounts.arbiter.key(),
+            *self.accounts.mint.key(),
+            [self.bump],
+        );
+        service.set_payment(self.instruction_data.amount, self.instruction_data.fee_bps);
+
+        drop(data);
+
+        if self.accounts.mint.key().eq(&NATIVE_SOL) {
+            // Deposit lamports on top of the service account's rent
+            SystemTransfer {
+                from: self.accounts.buyer,
+                to: self.accounts.service,
+                lamports: self.instruction_data.amount,
+            }
+            .invoke()?;
+        } else {
+            Transfer {
+                from: self.accounts.buyer_ata,
+                to: self.accounts.vault,
+                authority: self.accounts.buyer,
+                amount: self.instruction_data.amount,
+            }
+            .invoke()?;
+        }
+
+        ServiceOpened::new(
+            *self.accounts.service.key(),
+            *self.accounts.buyer.key(),
+            *self.accounts.recipient.key(),
+            *self.accounts.arbiter.key(),
+            *self.accounts.mint.key(),
+            self.instruction_data.amount,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
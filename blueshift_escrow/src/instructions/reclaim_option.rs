@@ -0,0 +1,115 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    CallOption, CallOptionAccount, DrainVault, EscrowError, ProgramAccount,
+    events::{Event, OptionClosed},
+};
+
+/// The writer can reclaim an unsold option at any time, and a sold one
+/// once it has expired.
+#[derive(Accounts, IdlAccounts)]
+pub struct ReclaimOptionAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub writer: &'a AccountInfo,
+    #[account(check = CallOptionAccount::check(option))]
+    #[idl(writable)]
+    pub option: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault, option, mint_a, token_program))]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub writer_ata_a: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct ReclaimOption<'a> {
+    pub accounts: ReclaimOptionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ReclaimOption<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ReclaimOptionAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ReclaimOption<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.option.try_borrow_data()?;
+        let option = CallOption::load(&data)?;
+
+        // Check if the option is valid
+        let option_key = create_program_address(
+            &[
+                b"option",
+                self.accounts.writer.key(),
+                &option.seed,
+                &option.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &option_key != self.accounts.option.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+        if self.accounts.mint_a.key().ne(&option.mint_a) {
+            return Err(EscrowError::InvalidMint.into());
+        }
+        if option.has_holder() && Clock::get()?.unix_timestamp < option.expiry() {
+            return Err(EscrowError::OptionNotExpired.into());
+        }
+
+        let seed_binding = option.seed;
+        let bump_binding = option.bump;
+        drop(data);
+
+        let option_seeds = [
+            Seed::from(b"option"),
+            Seed::from(self.accounts.writer.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&option_seeds);
+
+        // Return the collateral, and close the vault and the option
+        DrainVault {
+            vault: self.accounts.vault,
+            to: self.accounts.writer_ata_a,
+            owner: self.accounts.writer,
+            mint: self.accounts.mint_a,
+            payer: self.accounts.writer,
+            destination: self.accounts.writer,
+            authority: self.accounts.option,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        ProgramAccount::close(self.accounts.option, self.accounts.writer)?;
+
+        OptionClosed::new(*self.accounts.option.key(), *self.accounts.writer.key(), false).emit();
+
+        Ok(())
+    }
+}
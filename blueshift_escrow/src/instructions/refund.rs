@@ -4,65 +4,44 @@ use pinocchio::{
     instruction::{Seed, Signer},
     program_error::ProgramError,
     pubkey::create_program_address,
+    sysvars::{Sysvar, rent::Rent},
 };
 use pinocchio_token::{
     instructions::{CloseAccount, Transfer},
     state::TokenAccount,
 };
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
 
 use crate::{
-    AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountInit, Escrow,
-    MintInterface, ProgramAccount, SignerAccount,
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountInit,
+    Escrow, EscrowError, MintInterface, NATIVE_SOL, ProgramAccount,
+    events::{EscrowRefunded, Event},
 };
 
+/// For a native mint A, `vault` and `maker_ata_a` are not read.
+#[derive(Accounts, IdlAccounts)]
 pub struct RefundAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
     pub maker: &'a AccountInfo,
+    #[account(check = ProgramAccount::check(escrow))]
+    #[idl(writable)]
     pub escrow: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_a) if mint_a.key().ne(&NATIVE_SOL))]
     pub mint_a: &'a AccountInfo,
+    #[idl(writable)]
     pub vault: &'a AccountInfo,
+    #[idl(writable)]
     pub maker_ata_a: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
     pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
     pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
     pub associated_token_account_program: &'a AccountInfo,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
-    type Error = ProgramError;
-
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [
-            maker,
-            escrow,
-            mint_a,
-            vault,
-            maker_ata_a,
-            system_program,
-            token_program,
-            associated_token_account_program,
-        ] = accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        // Basic Accounts Checks
-        SignerAccount::check(maker)?;
-        ProgramAccount::check(escrow)?;
-        MintInterface::check(mint_a)?;
-
-        // Return the accounts
-        Ok(Self {
-            maker,
-            escrow,
-            mint_a,
-            maker_ata_a,
-            vault,
-            system_program,
-            token_program,
-            associated_token_account_program,
-        })
-    }
-}
-
 pub struct Refund<'a> {
     pub accounts: RefundAccounts<'a>,
 }
@@ -73,14 +52,16 @@ impl<'a> TryFrom<&'a [AccountInfo]> for Refund<'a> {
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         let accounts = RefundAccounts::try_from(accounts)?;
 
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_a,
-            accounts.mint_a,
-            accounts.maker,
-            accounts.maker,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
+        if accounts.mint_a.key().ne(&NATIVE_SOL) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_a,
+                accounts.mint_a,
+                accounts.maker,
+                accounts.maker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
 
         Ok(Self { accounts })
     }
@@ -98,16 +79,20 @@ impl<'a> Refund<'a> {
             &[
                 b"escrow",
                 self.accounts.maker.key(),
-                &escrow.seed.to_le_bytes(),
+                &escrow.seed,
                 &escrow.bump,
             ],
             &crate::ID,
         )?;
         if &escrow_key != self.accounts.escrow.key() {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(EscrowError::InvalidEscrowAddress.into());
         }
 
-        let seed_binding = escrow.seed.to_le_bytes();
+        if escrow.mint_a.ne(self.accounts.mint_a.key()) {
+            return Err(EscrowError::InvalidMint.into());
+        }
+
+        let seed_binding = escrow.seed;
         let bump_binding = escrow.bump;
         let escrow_seeds = [
             Seed::from(b"escrow"),
@@ -117,33 +102,48 @@ impl<'a> Refund<'a> {
         ];
         let signer = Signer::from(&escrow_seeds);
 
-        let amount = {
-            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
-
-            vault.amount()
+        let amount = if escrow.mint_a.eq(&NATIVE_SOL) {
+            // The deposit goes back with the escrow's rent when it closes
+            let rent = Rent::get()?.minimum_balance(Escrow::LEN);
+            self.accounts.escrow.lamports().saturating_sub(rent)
+        } else {
+            let amount = {
+                let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+
+                vault.amount()
+            };
+
+            // Transfer from the Vault to the Maker
+            Transfer {
+                from: self.accounts.vault,
+                to: self.accounts.maker_ata_a,
+                authority: self.accounts.escrow,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            // Close the Vault
+            CloseAccount {
+                account: self.accounts.vault,
+                destination: self.accounts.maker,
+                authority: self.accounts.escrow,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            amount
         };
 
-        // Transfer from the Vault to the Maker
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.maker_ata_a,
-            authority: self.accounts.escrow,
-            amount,
-        }
-        .invoke_signed(&[signer.clone()])?;
-
-        // Close the Vault
-        CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
-        }
-        .invoke_signed(&[signer.clone()])?;
-
         // Close the Escrow
         drop(data);
         ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
 
+        EscrowRefunded::new(
+            *self.accounts.escrow.key(),
+            *self.accounts.maker.key(),
+            amount,
+        )
+        .emit();
+
         Ok(())
     }
-}
\ No newline at end of file
+}
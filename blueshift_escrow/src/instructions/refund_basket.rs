@@ -0,0 +1,149 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use pinocchio_token::{
+    instructions::{CloseAccount, Transfer},
+    state::TokenAccount,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Basket, BasketAccount, EscrowError, ProgramAccount,
+    events::{BasketRefunded, Event},
+};
+
+/// The fixed accounts, followed by three accounts per offered leg
+/// (`mint`, `vault`, `maker_ata`), in the order the basket lists them.
+#[derive(Accounts, IdlAccounts)]
+pub struct RefundBasketAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub maker: &'a AccountInfo,
+    #[account(check = BasketAccount::check(basket))]
+    #[idl(writable)]
+    pub basket: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+    #[idl(skip)]
+    pub legs: &'a [AccountInfo],
+}
+
+pub struct RefundBasket<'a> {
+    pub accounts: RefundBasketAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RefundBasket<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RefundBasketAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RefundBasket<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&self) -> ProgramResult {
+        let data = self.accounts.basket.try_borrow_data()?;
+        let basket = Basket::load(&data)?;
+
+        // Check if the basket is valid
+        let basket_key = create_program_address(
+            &[
+                b"basket",
+                self.accounts.maker.key(),
+                &basket.seed,
+                &basket.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &basket_key != self.accounts.basket.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+
+        if self.accounts.legs.len() != basket.offered_mints().len() * 3 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let seed_binding = basket.seed;
+        let bump_binding = basket.bump;
+        let basket_seeds = [
+            Seed::from(b"basket"),
+            Seed::from(self.accounts.maker.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&basket_seeds);
+
+        // Return every deposited leg to the maker
+        for (leg, expected_mint) in self
+            .accounts
+            .legs
+            .chunks_exact(3)
+            .zip(basket.offered_mints())
+        {
+            let [mint, vault, maker_ata] = leg else {
+                unreachable!()
+            };
+
+            if mint.key().ne(expected_mint) {
+                return Err(EscrowError::InvalidMint.into());
+            }
+            AssociatedTokenAccount::check(
+                vault,
+                self.accounts.basket,
+                mint,
+                self.accounts.token_program,
+            )?;
+            AssociatedTokenAccount::init_if_needed(
+                maker_ata,
+                mint,
+                self.accounts.maker,
+                self.accounts.maker,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            let amount = {
+                let vault = TokenAccount::from_account_info(vault)?;
+
+                vault.amount()
+            };
+
+            Transfer {
+                from: vault,
+                to: maker_ata,
+                authority: self.accounts.basket,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            CloseAccount {
+                account: vault,
+                destination: self.accounts.maker,
+                authority: self.accounts.basket,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        // Close the Basket
+        drop(data);
+        ProgramAccount::close(self.accounts.basket, self.accounts.maker)?;
+
+        BasketRefunded::new(*self.accounts.basket.key(), *self.accounts.maker.key()).emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, EscrowError, NATIVE_SOL, ProgramAccount,
+    ServiceEscrow, ServiceEscrowAccount,
+    events::{Event, ServiceResolved},
+};
+
+/// `payee_ata` belongs to the recipient on a release and to the buyer on a
+/// refund. `arbiter_ata` is only read when a fee is paid, and a native `mint`
+/// leaves `vault` and both token accounts unread.
+#[derive(Accounts, IdlAccounts)]
+pub struct ResolveServiceAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub authority: &'a AccountInfo,
+    #[idl(writable)]
+    pub buyer: &'a AccountInfo,
+    #[account(check = ServiceEscrowAccount::check(service))]
+    #[idl(writable)]
+    pub service: &'a AccountInfo,
+    #[idl(writable)]
+    pub recipient: &'a AccountInfo,
+    #[idl(writable)]
+    pub arbiter: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(vault, service, mint, token_program)
+            if mint.key().ne(&NATIVE_SOL),
+    )]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub payee_ata: &'a AccountInfo,
+    #[idl(writable)]
+    pub arbiter_ata: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+/// The buyer may only release, and pays no fee. Once the buyer or recipient
+/// has disputed the service, the arbiter may release or refund, and takes
+/// `fee_bps` of the deposit either way.
+#[derive(IdlType)]
+pub struct ResolveServiceInstructionData {
+    /// `0` pays the recipient, `1` refunds the buyer.
+    pub outcome: u8,
+}
+
+impl ResolveServiceInstructionData {
+    pub const LEN: usize = 1;
+
+    pub const RELEASE: u8 = 0;
+    pub const REFUND: u8 = 1;
+}
+
+impl<'a> TryFrom<&'a [u8]> for ResolveServiceInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let [outcome @ (Self::RELEASE | Self::REFUND)] = *data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        Ok(Self { outcome })
+    }
+}
+
+pub struct ResolveService<'a> {
+    pub accounts: ResolveServiceAccounts<'a>,
+    pub instruction_data: ResolveServiceInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ResolveService<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ResolveServiceAccounts::try_from(accounts)?;
+        let instruction_data = ResolveServiceInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ResolveService<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.service.try_borrow_data()?;
+        let service = ServiceEscrow::load(&data)?;
+
+        // Check if the service escrow is valid
+        let service_key = create_program_address(
+            &[
+                b"service",
+                self.accounts.buyer.key(),
+                &service.seed,
+                &service.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &service_key != self.accounts.service.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+        if self.accounts.recipient.key().ne(&service.recipient)
+            || self.accounts.arbiter.key().ne(&service.arbiter)
+        {
+            return Err(EscrowError::InvalidAddress.into());
+        }
+        if self.accounts.mint.key().ne(&service.mint) {
+            return Err(EscrowError::InvalidMint.into());
+        }
+
+        let release = self.instruction_data.outcome == ResolveServiceInstructionData::RELEASE;
+        let authority = self.accounts.authority.key();
+        let fee = if release && authority.eq(&service.buyer) {
+            0
+        } else if authority.eq(&service.arbiter) {
+            // The arbiter only rules, and is only paid, on a dispute
+            if !service.is_disputed() {
+                return Err(EscrowError::ServiceNotDisputed.into());
+            }
+            service.fee()
+        } else {
+            return Err(EscrowError::InvalidResolver.into());
+        };
+        let payee = if release {
+            self.accounts.recipient
+        } else {
+            self.accounts.buyer
+        };
+        let mut payout = service
+            .amount()
+            .checked_sub(fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let seed_binding = service.seed;
+        let bump_binding = service.bump;
+        drop(data);
+
+        if self.accounts.mint.key().eq(&NATIVE_SOL) {
+            // Pay out the deposit, the service account is ours to debit
+            let mut service_lamports = self.accounts.service.try_borrow_mut_lamports()?;
+            *service_lamports = payout
+                .checked_add(fee)
+                .and_then(|deposit| service_lamports.checked_sub(deposit))
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            drop(service_lamports);
+
+            for (account, amount) in [(payee, payout), (self.accounts.arbiter, fee)] {
+                let mut lamports = account.try_borrow_mut_lamports()?;
+                *lamports = lamports
+                    .checked_add(amount)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+            }
+        } else {
+            let service_seeds = [
+                Seed::from(b"service"),
+                Seed::from(self.accounts.buyer.key().as_ref()),
+                Seed::from(&seed_binding),
+                Seed::from(&bump_binding),
+            ];
+            let signer = Signer::from(&service_seeds);
+
+            if fee > 0 {
+                AssociatedTokenAccount::init_if_needed(
+                    self.accounts.arbiter_ata,
+                    self.accounts.mint,
+                    self.accounts.authority,
+                    self.accounts.arbiter,
+                    self.accounts.system_program,
+                    self.accounts.token_program,
+                )?;
+
+                Transfer {
+                    from: self.accounts.vault,
+                    to: self.accounts.arbiter_ata,
+                    authority: self.accounts.service,
+                    amount: fee,
+                }
+                .invoke_signed(core::slice::from_ref(&signer))?;
+            }
+
+            // The payee takes the rest of the vault
+            payout = DrainVault {
+                vault: self.accounts.vault,
+                to: self.accounts.payee_ata,
+                owner: payee,
+                mint: self.accounts.mint,
+                payer: self.accounts.authority,
+                destination: self.accounts.buyer,
+                authority: self.accounts.service,
+                system_program: self.accounts.system_program,
+                token_program: self.accounts.token_program,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        // Close the service escrow, its rent goes back to the buyer
+        ProgramAccount::close(self.accounts.service, self.accounts.buyer)?;
+
+        ServiceResolved::new(
+            *self.accounts.service.key(),
+            *self.accounts.authority.key(),
+            *payee.key(),
+            payout,
+            fee,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -1,113 +1,134 @@
+use core::mem::size_of;
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::create_program_address,
+    pubkey::{Pubkey, create_program_address},
+    sysvars::{Sysvar, clock::Clock, instructions::Instructions, rent::Rent},
 };
+use pinocchio_system::instructions::Transfer as SystemTransfer;
 use pinocchio_token::{
     instructions::{CloseAccount, Transfer},
     state::TokenAccount,
 };
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
 
 use crate::{
-    AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountCheck,
-    AssociatedTokenAccountInit, Escrow, MintInterface, ProgramAccount, SignerAccount,
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Escrow, EscrowError, MintInterface, NATIVE_SOL, ProgramAccount,
+    events::{EscrowTaken, Event},
 };
 
+/// A native SOL side leaves its token accounts unread: `vault` and
+/// `taker_ata_a` for mint A, `taker_ata_b` and `maker_ata_b` for mint B.
+#[derive(Accounts, IdlAccounts)]
 pub struct TakeAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
     pub taker: &'a AccountInfo,
+    #[idl(writable)]
     pub maker: &'a AccountInfo,
+    #[account(check = ProgramAccount::check(escrow))]
+    #[idl(writable)]
     pub escrow: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_a) if mint_a.key().ne(&NATIVE_SOL))]
     pub mint_a: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_b) if mint_b.key().ne(&NATIVE_SOL))]
     pub mint_b: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)
+            if mint_a.key().ne(&NATIVE_SOL),
+    )]
+    #[idl(writable)]
     pub vault: &'a AccountInfo,
+    #[idl(writable)]
     pub taker_ata_a: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)
+            if mint_b.key().ne(&NATIVE_SOL),
+    )]
+    #[idl(writable)]
     pub taker_ata_b: &'a AccountInfo,
+    #[idl(writable)]
     pub maker_ata_b: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
     pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
     pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
     pub associated_token_account_program: &'a AccountInfo,
+    /// Only read when the escrow has `Escrow::TAKE_GUARD` set.
+    #[idl(optional, address = "Sysvar1nstructions1111111111111111111111111")]
+    pub instructions_sysvar: Option<&'a AccountInfo>,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for TakeAccounts<'a> {
+/// Take takes either no data, which accepts any price, or the most the taker
+/// is willing to pay.
+#[derive(IdlType)]
+pub struct TakeInstructionData {
+    /// Most token B the taker pays, bounding the price of a Dutch auction.
+    pub max_receive: u64,
+}
+
+impl TakeInstructionData {
+    pub const LEN: usize = size_of::<u64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for TakeInstructionData {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [
-            taker,
-            maker,
-            escrow,
-            mint_a,
-            mint_b,
-            vault,
-            taker_ata_a,
-            taker_ata_b,
-            maker_ata_b,
-            system_program,
-            token_program,
-            associated_token_account_program,
-        ] = accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let max_receive = match data.len() {
+            0 => u64::MAX,
+            Self::LEN => u64::from_le_bytes(data.try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
         };
 
-        // Basic Accounts Checks
-        SignerAccount::check(taker)?;
-        ProgramAccount::check(escrow)?;
-        MintInterface::check(mint_a)?;
-        MintInterface::check(mint_b)?;
-        AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
-        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
-
-        // Return the accounts
-        Ok(Self {
-            taker,
-            maker,
-            escrow,
-            mint_a,
-            mint_b,
-            taker_ata_a,
-            taker_ata_b,
-            maker_ata_b,
-            vault,
-            system_program,
-            token_program,
-            associated_token_account_program,
-        })
+        Ok(Self { max_receive })
     }
 }
 
 pub struct Take<'a> {
     pub accounts: TakeAccounts<'a>,
+    pub instruction_data: TakeInstructionData,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for Take<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Take<'a> {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = TakeAccounts::try_from(accounts)?;
+        let instruction_data = TakeInstructionData::try_from(data)?;
 
         // Initialize necessary accounts
-        AssociatedTokenAccount::init_if_needed(
-            accounts.taker_ata_a,
-            accounts.mint_a,
-            accounts.taker,
-            accounts.taker,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
+        if accounts.mint_a.key().ne(&NATIVE_SOL) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.taker_ata_a,
+                accounts.mint_a,
+                accounts.taker,
+                accounts.taker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
 
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_b,
-            accounts.mint_b,
-            accounts.taker,
-            accounts.maker,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
+        if accounts.mint_b.key().ne(&NATIVE_SOL) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_b,
+                accounts.mint_b,
+                accounts.taker,
+                accounts.maker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
 
-        Ok(Self { accounts })
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
     }
 }
 
@@ -123,16 +144,45 @@ impl<'a> Take<'a> {
             &[
                 b"escrow",
                 self.accounts.maker.key(),
-                &escrow.seed.to_le_bytes(),
+                &escrow.seed,
                 &escrow.bump,
             ],
             &crate::ID,
         )?;
         if &escrow_key != self.accounts.escrow.key() {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+
+        // Only the mints recorded at Make can settle the escrow
+        if escrow.mint_a.ne(self.accounts.mint_a.key())
+            || escrow.mint_b.ne(self.accounts.mint_b.key())
+        {
+            return Err(EscrowError::InvalidMint.into());
+        }
+
+        if escrow.take_guard() {
+            let instructions_sysvar = self
+                .accounts
+                .instructions_sysvar
+                .ok_or(EscrowError::MissingInstructionsSysvar)?;
+            check_transaction(
+                instructions_sysvar,
+                self.accounts.escrow.key(),
+                escrow.disallowed_programs(),
+            )?;
         }
 
-        let seed_binding = escrow.seed.to_le_bytes();
+        // A Dutch auction prices token B by the time of the Take
+        let receive = if escrow.dutch_auction() {
+            escrow.current_receive(Clock::get()?.unix_timestamp)?
+        } else {
+            escrow.receive()
+        };
+        if receive > self.instruction_data.max_receive {
+            return Err(EscrowError::SlippageExceeded.into());
+        }
+
+        let seed_binding = escrow.seed;
         let bump_binding = escrow.bump;
         let escrow_seeds = [
             Seed::from(b"escrow"),
@@ -142,42 +192,120 @@ impl<'a> Take<'a> {
         ];
         let signer = Signer::from(&escrow_seeds);
 
-        let amount = {
-            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+        let native_a = escrow.mint_a.eq(&NATIVE_SOL);
+        let amount = if native_a {
+            // Everything above the escrow's rent is the deposit
+            let rent = Rent::get()?.minimum_balance(Escrow::LEN);
+            self.accounts.escrow.lamports().saturating_sub(rent)
+        } else {
+            let amount = {
+                let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+
+                vault.amount()
+            };
 
-            vault.amount()
+            // Transfer from the Vault to the Taker
+            Transfer {
+                from: self.accounts.vault,
+                to: self.accounts.taker_ata_a,
+                authority: self.accounts.escrow,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            // Close the Vault
+            CloseAccount {
+                account: self.accounts.vault,
+                destination: self.accounts.maker,
+                authority: self.accounts.escrow,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            amount
         };
 
-        // Transfer from the Vault to the Taker
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.taker_ata_a,
-            authority: self.accounts.escrow,
-            amount,
+        // Transfer from the Taker to the Maker
+        if escrow.mint_b.eq(&NATIVE_SOL) {
+            SystemTransfer {
+                from: self.accounts.taker,
+                to: self.accounts.maker,
+                lamports: receive,
+            }
+            .invoke()?;
+        } else {
+            Transfer {
+                from: self.accounts.taker_ata_b,
+                to: self.accounts.maker_ata_b,
+                authority: self.accounts.taker,
+                amount: receive,
+            }
+            .invoke()?;
         }
-        .invoke_signed(&[signer.clone()])?;
 
-        // Close the Vault
-        CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
-        }
-        .invoke_signed(&[signer.clone()])?;
+        // Pay out a native deposit, the escrow is ours to debit
+        drop(data);
+        if native_a {
+            let mut escrow_lamports = self.accounts.escrow.try_borrow_mut_lamports()?;
+            *escrow_lamports = escrow_lamports
+                .checked_sub(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            drop(escrow_lamports);
 
-        // Transfer from the Taker to the Maker
-        Transfer {
-            from: self.accounts.taker_ata_b,
-            to: self.accounts.maker_ata_b,
-            authority: self.accounts.taker,
-            amount: escrow.receive,
+            let mut taker_lamports = self.accounts.taker.try_borrow_mut_lamports()?;
+            *taker_lamports = taker_lamports
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
         }
-        .invoke()?;
 
         // Close the Escrow
-        drop(data);
         ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
 
+        EscrowTaken::new(
+            *self.accounts.escrow.key(),
+            *self.accounts.maker.key(),
+            *self.accounts.taker.key(),
+            amount,
+            receive,
+        )
+        .emit();
+
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Rejects a Take invoked through CPI, since the top-level instruction could
+/// then swap around it, and a transaction that has another instruction using
+/// `escrow`, or any instruction calling one of the `disallowed` programs.
+fn check_transaction(
+    instructions_sysvar: &AccountInfo,
+    escrow: &Pubkey,
+    disallowed: &[Pubkey],
+) -> ProgramResult {
+    let instructions = Instructions::try_from(instructions_sysvar)
+        .map_err(|_| EscrowError::MissingInstructionsSysvar)?;
+    let current = instructions.load_current_index() as usize;
+    if instructions.load_instruction_at(current)?.get_program_id() != &crate::ID {
+        return Err(EscrowError::TakeNotTopLevel.into());
+    }
+
+    let mut index = 0;
+    while let Ok(instruction) = instructions.load_instruction_at(index) {
+        if disallowed.contains(instruction.get_program_id()) {
+            return Err(EscrowError::DisallowedProgram.into());
+        }
+
+        if index != current {
+            let mut account = 0;
+            while let Ok(meta) = instruction.get_account_meta_at(account) {
+                if meta.key.eq(escrow) {
+                    return Err(EscrowError::EscrowNotAlone.into());
+                }
+                account += 1;
+            }
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
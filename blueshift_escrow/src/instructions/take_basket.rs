@@ -0,0 +1,194 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use pinocchio_token::{
+    instructions::{CloseAccount, Transfer},
+    state::TokenAccount,
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, Basket, BasketAccount, EscrowError, ProgramAccount,
+    events::{BasketTaken, Event},
+};
+
+/// The fixed accounts, followed by three accounts per offered leg
+/// (`mint`, `vault`, `taker_ata`) and three per requested leg
+/// (`mint`, `taker_ata`, `maker_ata`), in the order the basket lists them.
+#[derive(Accounts, IdlAccounts)]
+pub struct TakeBasketAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub taker: &'a AccountInfo,
+    #[idl(writable)]
+    pub maker: &'a AccountInfo,
+    #[account(check = BasketAccount::check(basket))]
+    #[idl(writable)]
+    pub basket: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+    #[idl(skip)]
+    pub legs: &'a [AccountInfo],
+}
+
+pub struct TakeBasket<'a> {
+    pub accounts: TakeBasketAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TakeBasket<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = TakeBasketAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TakeBasket<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    /// Settles every leg in this one instruction, so either the whole basket
+    /// changes hands or nothing does.
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.basket.try_borrow_data()?;
+        let basket = Basket::load(&data)?;
+
+        // Check if the basket is valid
+        let basket_key = create_program_address(
+            &[
+                b"basket",
+                self.accounts.maker.key(),
+                &basket.seed,
+                &basket.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &basket_key != self.accounts.basket.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+
+        let offered_len = basket.offered_mints().len();
+        let requested_len = basket.requested_mints().len();
+        if self.accounts.legs.len() != (offered_len + requested_len) * 3 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (offered, requested) = self.accounts.legs.split_at(offered_len * 3);
+
+        let seed_binding = basket.seed;
+        let bump_binding = basket.bump;
+        let basket_seeds = [
+            Seed::from(b"basket"),
+            Seed::from(self.accounts.maker.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&basket_seeds);
+
+        // Empty every vault into the taker's accounts
+        for (leg, expected_mint) in offered.chunks_exact(3).zip(basket.offered_mints()) {
+            let [mint, vault, taker_ata] = leg else {
+                unreachable!()
+            };
+
+            if mint.key().ne(expected_mint) {
+                return Err(EscrowError::InvalidMint.into());
+            }
+            AssociatedTokenAccount::check(
+                vault,
+                self.accounts.basket,
+                mint,
+                self.accounts.token_program,
+            )?;
+            AssociatedTokenAccount::init_if_needed(
+                taker_ata,
+                mint,
+                self.accounts.taker,
+                self.accounts.taker,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            let amount = {
+                let vault = TokenAccount::from_account_info(vault)?;
+
+                vault.amount()
+            };
+
+            Transfer {
+                from: vault,
+                to: taker_ata,
+                authority: self.accounts.basket,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            CloseAccount {
+                account: vault,
+                destination: self.accounts.maker,
+                authority: self.accounts.basket,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        // Pay the maker every requested amount
+        for (index, (leg, expected_mint)) in requested
+            .chunks_exact(3)
+            .zip(basket.requested_mints())
+            .enumerate()
+        {
+            let [mint, taker_ata, maker_ata] = leg else {
+                unreachable!()
+            };
+
+            if mint.key().ne(expected_mint) {
+                return Err(EscrowError::InvalidMint.into());
+            }
+            AssociatedTokenAccount::check(
+                taker_ata,
+                self.accounts.taker,
+                mint,
+                self.accounts.token_program,
+            )?;
+            AssociatedTokenAccount::init_if_needed(
+                maker_ata,
+                mint,
+                self.accounts.taker,
+                self.accounts.maker,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: taker_ata,
+                to: maker_ata,
+                authority: self.accounts.taker,
+                amount: basket.requested_amount(index),
+            }
+            .invoke()?;
+        }
+
+        // Close the Basket
+        drop(data);
+        ProgramAccount::close(self.accounts.basket, self.accounts.maker)?;
+
+        BasketTaken::new(
+            *self.accounts.basket.key(),
+            *self.accounts.maker.key(),
+            *self.accounts.taker.key(),
+        )
+        .emit();
+
+        Ok(())
+    }
+}
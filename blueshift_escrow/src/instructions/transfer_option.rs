@@ -0,0 +1,66 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountLayout, CallOption, CallOptionAccount, EscrowError,
+    events::{Event, OptionHolderChanged},
+};
+
+/// Hands the right to exercise to `new_holder`. The option account is only
+/// ever owned by this program, so its holder field is all that changes.
+#[derive(Accounts, IdlAccounts)]
+pub struct TransferOptionAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    pub holder: &'a AccountInfo,
+    #[account(check = CallOptionAccount::check(option))]
+    #[idl(writable)]
+    pub option: &'a AccountInfo,
+    pub new_holder: &'a AccountInfo,
+}
+
+pub struct TransferOption<'a> {
+    pub accounts: TransferOptionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TransferOption<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = TransferOptionAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TransferOption<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.option.try_borrow_mut_data()?;
+        let option = CallOption::load_mut(data.as_mut())?;
+
+        // An unsold option has no holder to match
+        if !option.has_holder() || self.accounts.holder.key().ne(&option.holder) {
+            return Err(EscrowError::InvalidHolder.into());
+        }
+        if Clock::get()?.unix_timestamp >= option.expiry() {
+            return Err(EscrowError::OptionExpired.into());
+        }
+        if self.accounts.new_holder.key() == &[0; 32] {
+            return Err(EscrowError::InvalidAddress.into());
+        }
+
+        option.set_holder(*self.accounts.new_holder.key());
+
+        OptionHolderChanged::new(*self.accounts.option.key(), *self.accounts.new_holder.key())
+            .emit();
+
+        Ok(())
+    }
+}
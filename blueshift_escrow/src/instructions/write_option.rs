@@ -0,0 +1,199 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, CallOption, EscrowError, MintInterface, ProgramAccount,
+    ProgramAccountInit,
+    events::{Event, OptionWritten},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct WriteOptionAccounts<'a> {
+    #[account(signer @ EscrowError::NotSigner)]
+    #[idl(writable)]
+    pub writer: &'a AccountInfo,
+    #[idl(writable)]
+    pub option: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_a))]
+    pub mint_a: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_b))]
+    pub mint_b: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(writer_ata_a, writer, mint_a, token_program))]
+    #[idl(writable)]
+    pub writer_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct WriteOptionInstructionData {
+    pub seed: u64,
+    /// Amount of token A posted as collateral.
+    pub amount: u64,
+    /// Amount of token B the holder pays to exercise.
+    pub strike: u64,
+    /// Amount of token B the buyer pays the writer.
+    pub premium: u64,
+    /// Unix time the option can no longer be exercised.
+    pub expiry: i64,
+}
+
+impl WriteOptionInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 4 + size_of::<i64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for WriteOptionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let strike = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        let premium = u64::from_le_bytes(data[24..32].try_into().unwrap());
+        let expiry = i64::from_le_bytes(data[32..40].try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 || strike == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            seed,
+            amount,
+            strike,
+            premium,
+            expiry,
+        })
+    }
+}
+
+pub struct WriteOption<'a> {
+    pub accounts: WriteOptionAccounts<'a>,
+    pub instruction_data: WriteOptionInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for WriteOption<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = WriteOptionAccounts::try_from(accounts)?;
+        let instruction_data = WriteOptionInstructionData::try_from(data)?;
+
+        if instruction_data.expiry <= Clock::get()?.unix_timestamp {
+            return Err(EscrowError::OptionExpired.into());
+        }
+
+        // Initialize the Accounts needed
+        let (option_key, bump) = find_program_address(
+            &[
+                b"option",
+                accounts.writer.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &option_key != accounts.option.key() {
+            return Err(EscrowError::InvalidEscrowAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let option_seeds = [
+            Seed::from(b"option"),
+            Seed::from(accounts.writer.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<CallOption>(
+            accounts.writer,
+            accounts.option,
+            &option_seeds,
+            CallOption::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            accounts.vault,
+            accounts.mint_a,
+            accounts.writer,
+            accounts.option,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> WriteOption<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the option account
+        let mut data = self.accounts.option.try_borrow_mut_data()?;
+        let option = CallOption::init(data.as_mut())?;
+
+        option.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.writer.key(),
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            [self.bump],
+        );
+        option.set_terms(
+            self.instruction_data.strike,
+            self.instruction_data.premium,
+            self.instruction_data.expiry,
+        );
+
+        drop(data);
+
+        // Post the collateral
+        Transfer {
+            from: self.accounts.writer_ata_a,
+            to: self.accounts.vault,
+            authority: self.accounts.writer,
+            amount: self.instruction_data.amount,
+        }
+        .invoke()?;
+
+        OptionWritten::new(
+            *self.accounts.option.key(),
+            *self.accounts.writer.key(),
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            self.instruction_data.amount,
+            self.instruction_data.strike,
+            self.instruction_data.expiry,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -14,6 +14,11 @@ pub use state::*;
 pub mod errors;
 pub use errors::*;
 
+pub mod events;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
 // 22222222222222222222222222222222222222222222
 pub const ID: Pubkey = [
     0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
@@ -27,8 +32,29 @@ fn process_instruction(
 ) -> ProgramResult {
     match instruction_data.split_first() {
         Some((Make::DISCRIMINATOR, data)) => Make::try_from((data, accounts))?.process(),
-        Some((Take::DISCRIMINATOR, _)) => Take::try_from(accounts)?.process(),
+        Some((Take::DISCRIMINATOR, data)) => Take::try_from((data, accounts))?.process(),
         Some((Refund::DISCRIMINATOR, _)) => Refund::try_from(accounts)?.process(),
+        Some((Migrate::DISCRIMINATOR, _)) => Migrate::try_from(accounts)?.process(),
+        Some((MakeBasket::DISCRIMINATOR, data)) => {
+            MakeBasket::try_from((data, accounts))?.process()
+        }
+        Some((TakeBasket::DISCRIMINATOR, _)) => TakeBasket::try_from(accounts)?.process(),
+        Some((RefundBasket::DISCRIMINATOR, _)) => RefundBasket::try_from(accounts)?.process(),
+        Some((OpenService::DISCRIMINATOR, data)) => {
+            OpenService::try_from((data, accounts))?.process()
+        }
+        Some((ResolveService::DISCRIMINATOR, data)) => {
+            ResolveService::try_from((data, accounts))?.process()
+        }
+        Some((WriteOption::DISCRIMINATOR, data)) => {
+            WriteOption::try_from((data, accounts))?.process()
+        }
+        Some((BuyOption::DISCRIMINATOR, data)) => BuyOption::try_from((data, accounts))?.process(),
+        Some((TransferOption::DISCRIMINATOR, _)) => TransferOption::try_from(accounts)?.process(),
+        Some((ExerciseOption::DISCRIMINATOR, _)) => ExerciseOption::try_from(accounts)?.process(),
+        Some((ReclaimOption::DISCRIMINATOR, _)) => ReclaimOption::try_from(accounts)?.process(),
+        Some((Match::DISCRIMINATOR, _)) => Match::try_from(accounts)?.process(),
+        Some((DisputeService::DISCRIMINATOR, _)) => DisputeService::try_from(accounts)?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
\ No newline at end of file
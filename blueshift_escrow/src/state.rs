@@ -1,43 +1,156 @@
-use core::mem::{size_of, transmute};
+use core::mem::size_of;
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use idl_derive::IdlType;
+
+use crate::{AccountLayout, EscrowError};
+
+/// Capacity of the escrow's disallowed program list.
+pub const MAX_DISALLOWED_PROGRAMS: usize = 4;
 
 #[repr(C)]
+#[derive(IdlType)]
 pub struct Escrow {
-    pub seed: u64,      // Random seed for PDA derivation
-    pub maker: Pubkey,  // Creator of the escrow
-    pub mint_a: Pubkey, // Token being deposited
-    pub mint_b: Pubkey, // Token being requested
-    pub receive: u64,   // Amount of token B wanted
-    pub bump: [u8; 1],  // PDA bump seed
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `Escrow::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `Escrow::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub maker: Pubkey,     // Creator of the escrow
+    #[idl(ty = "pubkey")]
+    pub mint_a: Pubkey,    // Token being deposited
+    #[idl(ty = "pubkey")]
+    pub mint_b: Pubkey,    // Token being requested
+    #[idl(ty = "u64")]
+    pub receive: [u8; 8],  // Amount of token B wanted
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    pub flags: u8,         // Option bits, such as `Escrow::TAKE_GUARD`
+    pub disallowed_len: u8, // Number of entries in use in `disallowed_programs`
+    pub disallowed_programs: [Pubkey; MAX_DISALLOWED_PROGRAMS], // Programs a guarded Take rejects
+    #[idl(ty = "u64")]
+    pub end_receive: [u8; 8], // Amount of token B wanted once a Dutch auction ends
+    #[idl(ty = "i64")]
+    pub start_ts: [u8; 8], // Unix time a Dutch auction starts decaying from `receive`
+    #[idl(ty = "i64")]
+    pub end_ts: [u8; 8], // Unix time a Dutch auction reaches `end_receive`
 }
 
-impl Escrow {
-    pub const LEN: usize = size_of::<u64>()
+impl AccountLayout for Escrow {
+    type Error = EscrowError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
         + size_of::<Pubkey>()
         + size_of::<Pubkey>()
         + size_of::<Pubkey>()
-        + size_of::<u64>()
-        + size_of::<[u8; 1]>();
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 1]>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[Pubkey; MAX_DISALLOWED_PROGRAMS]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>();
+
+    const DISCRIMINATOR: u8 = 1;
+    const VERSION: u8 = 4;
+    const INVALID: EscrowError = EscrowError::InvalidEscrowAccount;
+}
+
+impl Escrow {
+    /// Size of the untagged v1 layout, which started directly at `seed`.
+    pub const V1_LEN: usize = 113;
+
+    /// Size of the v2 layout, which ended at `bump`.
+    pub const V2_LEN: usize = Self::V1_LEN + 2;
+
+    /// Size of the v3 layout, which ended at `disallowed_programs`.
+    pub const V3_LEN: usize = Self::V2_LEN + 2 + size_of::<[Pubkey; MAX_DISALLOWED_PROGRAMS]>();
+
+    /// Flag that makes `Take` inspect the rest of the transaction through the
+    /// Instructions sysvar.
+    pub const TAKE_GUARD: u8 = 1;
+
+    /// Flag that makes the amount of token B decay linearly from `receive` to
+    /// `end_receive` between `start_ts` and `end_ts`.
+    pub const DUTCH_AUCTION: u8 = 2;
 
     #[inline(always)]
-    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if bytes.len() != Escrow::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    pub fn seed(&self) -> u64 {
+        u64::from_le_bytes(self.seed)
+    }
+
+    #[inline(always)]
+    pub fn receive(&self) -> u64 {
+        u64::from_le_bytes(self.receive)
     }
 
     #[inline(always)]
-    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len() != Escrow::LEN {
-            return Err(ProgramError::InvalidAccountData);
+    pub fn take_guard(&self) -> bool {
+        self.flags & Self::TAKE_GUARD != 0
+    }
+
+    #[inline(always)]
+    pub fn dutch_auction(&self) -> bool {
+        self.flags & Self::DUTCH_AUCTION != 0
+    }
+
+    #[inline(always)]
+    pub fn end_receive(&self) -> u64 {
+        u64::from_le_bytes(self.end_receive)
+    }
+
+    #[inline(always)]
+    pub fn start_ts(&self) -> i64 {
+        i64::from_le_bytes(self.start_ts)
+    }
+
+    #[inline(always)]
+    pub fn end_ts(&self) -> i64 {
+        i64::from_le_bytes(self.end_ts)
+    }
+
+    /// The amount of token B a Take at unix time `now` has to pay. A Dutch
+    /// auction holds `receive` until `start_ts`, then decays linearly to
+    /// `end_receive` at `end_ts`. Other escrows always want `receive`.
+    pub fn current_receive(&self, now: i64) -> Result<u64, ProgramError> {
+        let start_receive = self.receive();
+        if !self.dutch_auction() || now <= self.start_ts() {
+            return Ok(start_receive);
+        }
+        if now >= self.end_ts() {
+            return Ok(self.end_receive());
         }
-        Ok(unsafe { &*transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+
+        let decay = start_receive
+            .checked_sub(self.end_receive())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let elapsed = now.abs_diff(self.start_ts());
+        let duration = self.end_ts().abs_diff(self.start_ts());
+        let decayed = (decay as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|decayed| decayed.checked_div(duration as u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // `decayed` never exceeds `decay`, since `elapsed` is below `duration`
+        start_receive
+            .checked_sub(decayed as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// The programs a guarded `Take` refuses to share a transaction with.
+    #[inline(always)]
+    pub fn disallowed_programs(&self) -> &[Pubkey] {
+        &self.disallowed_programs[..self.disallowed_len as usize]
     }
 
     #[inline(always)]
     pub fn set_seed(&mut self, seed: u64) {
-        self.seed = seed;
+        self.seed = seed.to_le_bytes();
     }
 
     #[inline(always)]
@@ -57,7 +170,7 @@ impl Escrow {
 
     #[inline(always)]
     pub fn set_receive(&mut self, receive: u64) {
-        self.receive = receive;
+        self.receive = receive.to_le_bytes();
     }
 
     #[inline(always)]
@@ -75,11 +188,328 @@ impl Escrow {
         receive: u64,
         bump: [u8; 1],
     ) {
-        self.seed = seed;
+        self.seed = seed.to_le_bytes();
         self.maker = maker;
         self.mint_a = mint_a;
         self.mint_b = mint_b;
-        self.receive = receive;
+        self.receive = receive.to_le_bytes();
         self.bump = bump;
     }
-}
\ No newline at end of file
+
+    #[inline(always)]
+    pub fn set_guard(
+        &mut self,
+        flags: u8,
+        disallowed_len: u8,
+        disallowed_programs: [Pubkey; MAX_DISALLOWED_PROGRAMS],
+    ) {
+        self.flags = flags;
+        self.disallowed_len = disallowed_len;
+        self.disallowed_programs = disallowed_programs;
+    }
+
+    #[inline(always)]
+    pub fn set_auction(&mut self, end_receive: u64, start_ts: i64, end_ts: i64) {
+        self.end_receive = end_receive.to_le_bytes();
+        self.start_ts = start_ts.to_le_bytes();
+        self.end_ts = end_ts.to_le_bytes();
+    }
+}
+
+/// Capacity of each side of a basket.
+pub const MAX_BASKET_LEGS: usize = 4;
+
+/// An escrow of up to `MAX_BASKET_LEGS` deposited mints for up to
+/// `MAX_BASKET_LEGS` requested mints. Each deposited mint sits in the
+/// basket's own associated token account.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct Basket {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `Basket::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `Basket::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub maker: Pubkey,     // Creator of the basket
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    pub offered_len: u8,   // Number of entries in use in `offered_mints`
+    pub requested_len: u8, // Number of entries in use in `requested_mints`
+    pub offered_mints: [Pubkey; MAX_BASKET_LEGS],   // Tokens being deposited
+    pub requested_mints: [Pubkey; MAX_BASKET_LEGS], // Tokens being requested
+    pub requested_amounts: [[u8; 8]; MAX_BASKET_LEGS], // Amount wanted of each requested token
+}
+
+impl AccountLayout for Basket {
+    type Error = EscrowError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[Pubkey; MAX_BASKET_LEGS]>()
+        + size_of::<[Pubkey; MAX_BASKET_LEGS]>()
+        + size_of::<[[u8; 8]; MAX_BASKET_LEGS]>();
+
+    const DISCRIMINATOR: u8 = 2;
+    const VERSION: u8 = 1;
+    const INVALID: EscrowError = EscrowError::InvalidBasketAccount;
+}
+
+impl Basket {
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        u64::from_le_bytes(self.seed)
+    }
+
+    /// The deposited mints, in the order of their legs.
+    #[inline(always)]
+    pub fn offered_mints(&self) -> &[Pubkey] {
+        &self.offered_mints[..self.offered_len as usize]
+    }
+
+    /// The requested mints, in the order of their legs.
+    #[inline(always)]
+    pub fn requested_mints(&self) -> &[Pubkey] {
+        &self.requested_mints[..self.requested_len as usize]
+    }
+
+    #[inline(always)]
+    pub fn requested_amount(&self, leg: usize) -> u64 {
+        u64::from_le_bytes(self.requested_amounts[leg])
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, seed: u64, maker: Pubkey, bump: [u8; 1]) {
+        self.seed = seed.to_le_bytes();
+        self.maker = maker;
+        self.bump = bump;
+    }
+
+    /// Appends a deposited mint. The caller keeps the count within
+    /// `MAX_BASKET_LEGS`.
+    #[inline(always)]
+    pub fn push_offered(&mut self, mint: Pubkey) {
+        self.offered_mints[self.offered_len as usize] = mint;
+        self.offered_len += 1;
+    }
+
+    /// Appends a requested mint and the amount wanted of it. The caller keeps
+    /// the count within `MAX_BASKET_LEGS`.
+    #[inline(always)]
+    pub fn push_requested(&mut self, mint: Pubkey, amount: u64) {
+        self.requested_mints[self.requested_len as usize] = mint;
+        self.requested_amounts[self.requested_len as usize] = amount.to_le_bytes();
+        self.requested_len += 1;
+    }
+}
+
+/// A payment for a service, held until the buyer releases it or the arbiter
+/// settles a dispute. A `NATIVE_SOL` deposit sits in the account's lamports.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct ServiceEscrow {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `ServiceEscrow::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `ServiceEscrow::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub buyer: Pubkey,     // Depositor of the payment
+    #[idl(ty = "pubkey")]
+    pub recipient: Pubkey, // Provider of the service
+    #[idl(ty = "pubkey")]
+    pub arbiter: Pubkey,   // Third party that settles disputes
+    #[idl(ty = "pubkey")]
+    pub mint: Pubkey,      // Token deposited, or `NATIVE_SOL` for lamports
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u64")]
+    pub amount: [u8; 8],   // Amount deposited
+    #[idl(ty = "u16")]
+    pub fee_bps: [u8; 2],  // Arbiter's cut of a settled dispute, in basis points
+    #[idl(ty = "bool")]
+    pub disputed: u8,      // Set once the buyer or recipient asks the arbiter to rule
+}
+
+impl AccountLayout for ServiceEscrow {
+    type Error = EscrowError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 2]>()
+        + size_of::<u8>();
+
+    const DISCRIMINATOR: u8 = 3;
+    const VERSION: u8 = 1;
+    const INVALID: EscrowError = EscrowError::InvalidEscrowAccount;
+}
+
+impl ServiceEscrow {
+    pub const MAX_FEE_BPS: u16 = 10_000;
+
+    #[inline(always)]
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+
+    #[inline(always)]
+    pub fn fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.fee_bps)
+    }
+
+    /// The arbiter's cut of the deposit, rounded down.
+    #[inline(always)]
+    pub fn fee(&self) -> u64 {
+        (self.amount() as u128 * self.fee_bps() as u128 / Self::MAX_FEE_BPS as u128) as u64
+    }
+
+    #[inline(always)]
+    pub fn is_disputed(&self) -> bool {
+        self.disputed != 0
+    }
+
+    #[inline(always)]
+    pub fn set_disputed(&mut self) {
+        self.disputed = 1;
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        buyer: Pubkey,
+        recipient: Pubkey,
+        arbiter: Pubkey,
+        mint: Pubkey,
+        bump: [u8; 1],
+    ) {
+        self.seed = seed.to_le_bytes();
+        self.buyer = buyer;
+        self.recipient = recipient;
+        self.arbiter = arbiter;
+        self.mint = mint;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_payment(&mut self, amount: u64, fee_bps: u16) {
+        self.amount = amount.to_le_bytes();
+        self.fee_bps = fee_bps.to_le_bytes();
+    }
+}
+
+/// A covered call: `amount` of `mint_a` held as collateral, which the holder
+/// may buy for `strike` of `mint_b` until `expiry`.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct CallOption {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `CallOption::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `CallOption::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub writer: Pubkey,    // Seller of the option, who posted the collateral
+    #[idl(ty = "pubkey")]
+    pub holder: Pubkey,    // Owner of the right to exercise, zeroed until bought
+    #[idl(ty = "pubkey")]
+    pub mint_a: Pubkey,    // Collateral token
+    #[idl(ty = "pubkey")]
+    pub mint_b: Pubkey,    // Token the strike and premium are paid in
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u64")]
+    pub strike: [u8; 8],   // Amount of token B paid on exercise
+    #[idl(ty = "u64")]
+    pub premium: [u8; 8],  // Amount of token B paid to buy the option
+    #[idl(ty = "i64")]
+    pub expiry: [u8; 8],   // Unix time the option can no longer be exercised
+}
+
+impl AccountLayout for CallOption {
+    type Error = EscrowError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>();
+
+    const DISCRIMINATOR: u8 = 4;
+    const VERSION: u8 = 1;
+    const INVALID: EscrowError = EscrowError::InvalidOptionAccount;
+}
+
+impl CallOption {
+    #[inline(always)]
+    pub fn strike(&self) -> u64 {
+        u64::from_le_bytes(self.strike)
+    }
+
+    #[inline(always)]
+    pub fn premium(&self) -> u64 {
+        u64::from_le_bytes(self.premium)
+    }
+
+    #[inline(always)]
+    pub fn expiry(&self) -> i64 {
+        i64::from_le_bytes(self.expiry)
+    }
+
+    #[inline(always)]
+    pub fn has_holder(&self) -> bool {
+        self.holder != Pubkey::default()
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        writer: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        bump: [u8; 1],
+    ) {
+        self.seed = seed.to_le_bytes();
+        self.writer = writer;
+        self.mint_a = mint_a;
+        self.mint_b = mint_b;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_terms(&mut self, strike: u64, premium: u64, expiry: i64) {
+        self.strike = strike.to_le_bytes();
+        self.premium = premium.to_le_bytes();
+        self.expiry = expiry.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_holder(&mut self, holder: Pubkey) {
+        self.holder = holder;
+    }
+}
@@ -0,0 +1,407 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_escrow::{
+        AccountLayout, Basket, EscrowError, MakeBasket, RefundBasket, TakeBasket, ID,
+        MAX_BASKET_LEGS,
+    };
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 7;
+    // Three tokens for two
+    const DEPOSITS: [u64; 3] = [1_000, 2_000, 3_000];
+    const RECEIVES: [u64; 2] = [400, 500];
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Maker holding DEPOSITS of the offered mints and taker holding
+    // RECEIVES of the requested mints, with no basket made yet
+    struct Fixture {
+        context: Context,
+        maker: Pubkey,
+        taker: Pubkey,
+        offered: Vec<Pubkey>,
+        requested: Vec<Pubkey>,
+        basket: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_escrow");
+            add_token_programs(&mut mollusk);
+
+            let (maker, maker_account) = funded_payer();
+            let (taker, taker_account) = funded_payer();
+            let mut store = HashMap::new();
+            store.insert(maker, maker_account);
+            store.insert(taker, taker_account);
+
+            // A mint per leg, with its whole supply in its holder's ATA
+            let mut mint_legs = |holder: &Pubkey, amounts: &[u64]| -> Vec<Pubkey> {
+                amounts
+                    .iter()
+                    .map(|amount| {
+                        let mint = Pubkey::new_unique();
+                        store.insert(
+                            mint,
+                            MintBuilder::new(&Pubkey::new_unique())
+                                .supply(*amount)
+                                .build(&mollusk),
+                        );
+                        let (ata, account) = AtaBuilder::new(holder, &mint)
+                            .amount(*amount)
+                            .build(&mollusk);
+                        store.insert(ata, account);
+                        mint
+                    })
+                    .collect()
+            };
+            let offered = mint_legs(&maker, &DEPOSITS);
+            let requested = mint_legs(&taker, &RECEIVES);
+
+            Self {
+                context: mollusk.with_context(store),
+                maker,
+                taker,
+                offered,
+                requested,
+                basket: basket_address(&maker, SEED),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn programs() -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(system_program().0, false),
+                AccountMeta::new_readonly(token_program().0, false),
+                AccountMeta::new_readonly(associated_token_program().0, false),
+            ]
+        }
+
+        fn make_basket(&self) -> Instruction {
+            let mut data = vec![*MakeBasket::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.push(DEPOSITS.len() as u8);
+            data.push(RECEIVES.len() as u8);
+            for amounts in [&DEPOSITS[..], &RECEIVES[..]] {
+                for index in 0..MAX_BASKET_LEGS {
+                    let amount = amounts.get(index).copied().unwrap_or_default();
+                    data.extend_from_slice(&amount.to_le_bytes());
+                }
+            }
+
+            let mut accounts = vec![
+                AccountMeta::new(self.maker, true),
+                AccountMeta::new(self.basket, false),
+            ];
+            accounts.extend(Self::programs());
+            for mint in &self.offered {
+                accounts.push(AccountMeta::new_readonly(*mint, false));
+                accounts.push(AccountMeta::new(self.ata(&self.maker, mint), false));
+                accounts.push(AccountMeta::new(self.ata(&self.basket, mint), false));
+            }
+            for mint in &self.requested {
+                accounts.push(AccountMeta::new_readonly(*mint, false));
+            }
+
+            Instruction::new_with_bytes(PROGRAM_ID, &data, accounts)
+        }
+
+        fn take_basket(&self) -> Instruction {
+            let mut accounts = vec![
+                AccountMeta::new(self.taker, true),
+                AccountMeta::new(self.maker, false),
+                AccountMeta::new(self.basket, false),
+            ];
+            accounts.extend(Self::programs());
+            for mint in &self.offered {
+                accounts.push(AccountMeta::new_readonly(*mint, false));
+                accounts.push(AccountMeta::new(self.ata(&self.basket, mint), false));
+                accounts.push(AccountMeta::new(self.ata(&self.taker, mint), false));
+            }
+            for mint in &self.requested {
+                accounts.push(AccountMeta::new_readonly(*mint, false));
+                accounts.push(AccountMeta::new(self.ata(&self.taker, mint), false));
+                accounts.push(AccountMeta::new(self.ata(&self.maker, mint), false));
+            }
+
+            Instruction::new_with_bytes(PROGRAM_ID, &[*TakeBasket::DISCRIMINATOR], accounts)
+        }
+
+        fn refund_basket(&self) -> Instruction {
+            let mut accounts = vec![
+                AccountMeta::new(self.maker, true),
+                AccountMeta::new(self.basket, false),
+            ];
+            accounts.extend(Self::programs());
+            for mint in &self.offered {
+                accounts.push(AccountMeta::new_readonly(*mint, false));
+                accounts.push(AccountMeta::new(self.ata(&self.basket, mint), false));
+                accounts.push(AccountMeta::new(self.ata(&self.maker, mint), false));
+            }
+
+            Instruction::new_with_bytes(PROGRAM_ID, &[*RefundBasket::DISCRIMINATOR], accounts)
+        }
+
+        // Run MakeBasket and require it to succeed, as the setup of the others
+        fn made(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.make_basket(), &[Check::success()]);
+            self
+        }
+    }
+
+    fn basket_address(maker: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"basket", maker.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: EscrowError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_make_basket() {
+        let fixture = Fixture::new().made();
+
+        let data = fixture.account(&fixture.basket).data;
+        assert_eq!(data.len(), Basket::LEN);
+        assert_eq!(&data[..2], &[Basket::DISCRIMINATOR, Basket::VERSION]);
+        // offered_len and requested_len follow the seed, maker and bump
+        assert_eq!(&data[43..45], &[3, 2]);
+
+        for (mint, amount) in fixture.offered.iter().zip(DEPOSITS) {
+            let vault = fixture.ata(&fixture.basket, mint);
+            assert_eq!(fixture.token_amount(&vault), amount);
+            assert_eq!(fixture.token_amount(&fixture.ata(&fixture.maker, mint)), 0);
+        }
+    }
+
+    #[test]
+    fn test_make_basket_rejects_leg_counts() {
+        let fixture = Fixture::new();
+
+        for (offered_len, requested_len) in [(0, 2), (3, 0), (MAX_BASKET_LEGS as u8 + 1, 2)] {
+            let mut instruction = fixture.make_basket();
+            instruction.data[9] = offered_len;
+            instruction.data[10] = requested_len;
+            fixture.context.process_and_validate_instruction(
+                &instruction,
+                &[custom(EscrowError::InvalidLegCount)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_make_basket_rejects_account_count() {
+        // Legs must match the counts in the instruction data exactly
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make_basket();
+        instruction.accounts.pop();
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(ProgramError::NotEnoughAccountKeys)],
+        );
+    }
+
+    #[test]
+    fn test_make_basket_rejects_duplicate_mint() {
+        let mut fixture = Fixture::new();
+        fixture.requested[1] = fixture.requested[0];
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.make_basket(),
+            &[custom(EscrowError::DuplicateMint)],
+        );
+    }
+
+    #[test]
+    fn test_take_basket() {
+        let fixture = Fixture::new().made();
+        let maker_lamports = fixture.account(&fixture.maker).lamports;
+        let basket_lamports = fixture.account(&fixture.basket).lamports;
+        let vault_lamports: u64 = fixture
+            .offered
+            .iter()
+            .map(|mint| {
+                fixture
+                    .account(&fixture.ata(&fixture.basket, mint))
+                    .lamports
+            })
+            .sum();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take_basket(),
+            &[
+                Check::success(),
+                Check::account(&fixture.basket).closed().build(),
+            ],
+        );
+
+        for (mint, amount) in fixture.offered.iter().zip(DEPOSITS) {
+            assert_eq!(
+                fixture
+                    .account(&fixture.ata(&fixture.basket, mint))
+                    .lamports,
+                0
+            );
+            assert_eq!(
+                fixture.token_amount(&fixture.ata(&fixture.taker, mint)),
+                amount
+            );
+        }
+        for (mint, amount) in fixture.requested.iter().zip(RECEIVES) {
+            assert_eq!(fixture.token_amount(&fixture.ata(&fixture.taker, mint)), 0);
+            assert_eq!(
+                fixture.token_amount(&fixture.ata(&fixture.maker, mint)),
+                amount
+            );
+        }
+
+        // The maker gets the rent of the basket and every vault back
+        assert_eq!(
+            fixture.account(&fixture.maker).lamports,
+            maker_lamports + basket_lamports + vault_lamports
+        );
+    }
+
+    #[test]
+    fn test_take_basket_is_atomic() {
+        // The taker is short on the last requested leg, so no leg settles
+        let fixture = Fixture::new().made();
+        let last = fixture.requested[RECEIVES.len() - 1];
+        let (taker_ata, account) = AtaBuilder::new(&fixture.taker, &last)
+            .amount(RECEIVES[RECEIVES.len() - 1] - 1)
+            .build(&fixture.context.mollusk);
+        fixture
+            .context
+            .account_store
+            .borrow_mut()
+            .insert(taker_ata, account);
+
+        let result = fixture.context.process_instruction(&fixture.take_basket());
+
+        assert!(result.program_result.is_err());
+        for (mint, amount) in fixture.offered.iter().zip(DEPOSITS) {
+            let vault = fixture.ata(&fixture.basket, mint);
+            assert_eq!(fixture.token_amount(&vault), amount);
+        }
+        assert_eq!(fixture.account(&fixture.basket).data.len(), Basket::LEN);
+    }
+
+    #[test]
+    fn test_take_basket_rejects_wrong_mint() {
+        // Legs must come in the order the basket lists them
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.take_basket();
+        let first_requested = 5 + DEPOSITS.len() * 3;
+        instruction
+            .accounts
+            .swap(first_requested, first_requested + 3);
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::InvalidMint)]);
+    }
+
+    #[test]
+    fn test_refund_basket() {
+        let fixture = Fixture::new().made();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.refund_basket(),
+            &[
+                Check::success(),
+                Check::account(&fixture.basket).closed().build(),
+            ],
+        );
+
+        for (mint, amount) in fixture.offered.iter().zip(DEPOSITS) {
+            assert_eq!(
+                fixture
+                    .account(&fixture.ata(&fixture.basket, mint))
+                    .lamports,
+                0
+            );
+            assert_eq!(
+                fixture.token_amount(&fixture.ata(&fixture.maker, mint)),
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_refund_basket_rejects_missing_leg() {
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.refund_basket();
+        instruction
+            .accounts
+            .truncate(instruction.accounts.len() - 3);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(ProgramError::NotEnoughAccountKeys)],
+        );
+    }
+
+    #[test]
+    fn test_escrow_instructions_reject_basket() {
+        // A basket is not an escrow, whatever its size
+        let fixture = Fixture::new().made();
+        let mint = fixture.offered[0];
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*pinocchio_escrow::Refund::DISCRIMINATOR],
+            [
+                vec![
+                    AccountMeta::new(fixture.maker, true),
+                    AccountMeta::new(fixture.basket, false),
+                    AccountMeta::new_readonly(mint, false),
+                    AccountMeta::new(fixture.ata(&fixture.basket, &mint), false),
+                    AccountMeta::new(fixture.ata(&fixture.maker, &mint), false),
+                ],
+                Fixture::programs(),
+            ]
+            .concat(),
+        );
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::InvalidEscrowAccount)],
+        );
+    }
+}
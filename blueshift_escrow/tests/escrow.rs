@@ -0,0 +1,1221 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_escrow::{
+        events::{EscrowEvent, EscrowMigrated},
+        AccountLayout, Escrow, EscrowError, ID, MAX_DISALLOWED_PROGRAMS, NATIVE_SOL,
+    };
+    use solana_instruction::{BorrowedAccountMeta, BorrowedInstruction};
+    use solana_instructions_sysvar::construct_instructions_data;
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_program, token_2022_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 42;
+    const DEPOSIT: u64 = 1_000_000;
+    const RECEIVE: u64 = 250_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Maker holding DEPOSIT of mint A and taker holding RECEIVE of mint B,
+    // with no escrow made yet
+    struct Fixture {
+        context: Context,
+        maker: Pubkey,
+        taker: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        token_program: Pubkey,
+        escrow: Pubkey,
+        vault: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_token_program(token_program().0)
+        }
+
+        fn with_token_program(token_program: Pubkey) -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_escrow");
+            add_token_programs(&mut mollusk);
+
+            let (maker, maker_account) = funded_payer();
+            let (taker, taker_account) = funded_payer();
+            let mint_a = Pubkey::new_unique();
+            let mint_b = Pubkey::new_unique();
+
+            let (escrow, _) = escrow_address(&maker, SEED);
+            let vault = associated_token_address(&escrow, &mint_a, &token_program);
+
+            let (maker_ata_a, maker_ata_a_account) = AtaBuilder::new(&maker, &mint_a)
+                .amount(DEPOSIT)
+                .token_program(&token_program)
+                .build(&mollusk);
+            let (taker_ata_b, taker_ata_b_account) = AtaBuilder::new(&taker, &mint_b)
+                .amount(RECEIVE)
+                .token_program(&token_program)
+                .build(&mollusk);
+
+            let mut store = HashMap::new();
+            store.insert(maker, maker_account);
+            store.insert(taker, taker_account);
+            for mint in [mint_a, mint_b] {
+                let account = MintBuilder::new(&Pubkey::new_unique())
+                    .supply(DEPOSIT + RECEIVE)
+                    .token_program(&token_program)
+                    .build(&mollusk);
+                store.insert(mint, account);
+            }
+            store.insert(maker_ata_a, maker_ata_a_account);
+            store.insert(taker_ata_b, taker_ata_b_account);
+
+            Self {
+                context: mollusk.with_context(store),
+                maker,
+                taker,
+                mint_a,
+                mint_b,
+                token_program,
+                escrow,
+                vault,
+            }
+        }
+
+        // Trade native SOL instead of mint A, the maker deposits DEPOSIT lamports
+        fn native_a(mut self) -> Self {
+            self.mint_a = Pubkey::new_from_array(NATIVE_SOL);
+            self.vault = self.ata(&self.escrow, &self.mint_a);
+            self
+        }
+
+        // Ask for RECEIVE lamports instead of mint B
+        fn native_b(mut self) -> Self {
+            self.mint_b = Pubkey::new_from_array(NATIVE_SOL);
+            self
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &self.token_program)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn insert(&self, key: Pubkey, account: Account) {
+            self.context.account_store.borrow_mut().insert(key, account);
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn make(&self) -> Instruction {
+            let data = [
+                vec![*pinocchio_escrow::Make::DISCRIMINATOR],
+                SEED.to_le_bytes().to_vec(),
+                RECEIVE.to_le_bytes().to_vec(),
+                DEPOSIT.to_le_bytes().to_vec(),
+            ]
+            .concat();
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(self.maker, true),
+                    AccountMeta::new(self.escrow, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new_readonly(self.mint_b, false),
+                    AccountMeta::new(self.ata(&self.maker, &self.mint_a), false),
+                    AccountMeta::new(self.vault, false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        // Make with the guard fields, setting `Escrow::TAKE_GUARD`
+        fn make_guarded(&self, disallowed: &[Pubkey]) -> Instruction {
+            let mut instruction = self.make();
+            instruction.data.push(Escrow::TAKE_GUARD);
+            instruction.data.push(disallowed.len() as u8);
+            for index in 0..MAX_DISALLOWED_PROGRAMS {
+                let program = disallowed.get(index).copied().unwrap_or_default();
+                instruction.data.extend_from_slice(program.as_ref());
+            }
+            instruction
+        }
+
+        // Make a Dutch auction from RECEIVE down to `end_receive`
+        fn make_auction(&self, end_receive: u64, start_ts: i64, end_ts: i64) -> Instruction {
+            let mut instruction = self.make_guarded(&[]);
+            instruction.data[25] = Escrow::DUTCH_AUCTION;
+            instruction
+                .data
+                .extend_from_slice(&end_receive.to_le_bytes());
+            instruction.data.extend_from_slice(&start_ts.to_le_bytes());
+            instruction.data.extend_from_slice(&end_ts.to_le_bytes());
+            instruction
+        }
+
+        // Take paying at most `max_receive` of mint B
+        fn take_at_most(&self, max_receive: u64) -> Instruction {
+            let mut instruction = self.take();
+            instruction
+                .data
+                .extend_from_slice(&max_receive.to_le_bytes());
+            instruction
+        }
+
+        fn take(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*pinocchio_escrow::Take::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new(self.taker, true),
+                    AccountMeta::new(self.maker, false),
+                    AccountMeta::new(self.escrow, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new_readonly(self.mint_b, false),
+                    AccountMeta::new(self.vault, false),
+                    AccountMeta::new(self.ata(&self.taker, &self.mint_a), false),
+                    AccountMeta::new(self.ata(&self.taker, &self.mint_b), false),
+                    AccountMeta::new(self.ata(&self.maker, &self.mint_b), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        // Take with the Instructions sysvar as its optional last account
+        fn take_guarded(&self) -> Instruction {
+            let mut instruction = self.take();
+            instruction
+                .accounts
+                .push(AccountMeta::new_readonly(sysvar::instructions::ID, false));
+            instruction
+        }
+
+        fn refund(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*pinocchio_escrow::Refund::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new(self.maker, true),
+                    AccountMeta::new(self.escrow, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new(self.vault, false),
+                    AccountMeta::new(self.ata(&self.maker, &self.mint_a), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn migrate(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*pinocchio_escrow::Migrate::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new(self.maker, true),
+                    AccountMeta::new(self.escrow, false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                ],
+            )
+        }
+
+        // Mollusk runs one instruction at a time and leaves the Instructions
+        // sysvar empty, so write it as the runtime would for `transaction`
+        // and run the instruction at `current`
+        fn process_in_transaction(
+            &self,
+            transaction: &[Instruction],
+            current: usize,
+            checks: &[Check],
+        ) {
+            let borrowed: Vec<BorrowedInstruction> = transaction
+                .iter()
+                .map(|instruction| BorrowedInstruction {
+                    program_id: &instruction.program_id,
+                    accounts: instruction
+                        .accounts
+                        .iter()
+                        .map(|meta| BorrowedAccountMeta {
+                            pubkey: &meta.pubkey,
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                        .collect(),
+                    data: &instruction.data,
+                })
+                .collect();
+            let mut data = construct_instructions_data(&borrowed);
+            let index_offset = data.len() - 2;
+            data[index_offset..].copy_from_slice(&(current as u16).to_le_bytes());
+
+            self.insert(
+                sysvar::instructions::ID,
+                Account {
+                    lamports: 1,
+                    data,
+                    owner: sysvar::ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+            self.context
+                .process_and_validate_instruction(&transaction[current], checks);
+        }
+
+        // Run Make and require it to succeed, as the setup of Take and Refund
+        fn made(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.make(), &[Check::success()]);
+            self
+        }
+    }
+
+    fn escrow_address(maker: &Pubkey, seed: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+    }
+
+    fn custom(error: EscrowError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_make() {
+        let fixture = Fixture::new();
+        let (_, bump) = escrow_address(&fixture.maker, SEED);
+
+        let mut expected = vec![Escrow::DISCRIMINATOR, Escrow::VERSION];
+        expected.extend_from_slice(&SEED.to_le_bytes());
+        expected.extend_from_slice(fixture.maker.as_ref());
+        expected.extend_from_slice(fixture.mint_a.as_ref());
+        expected.extend_from_slice(fixture.mint_b.as_ref());
+        expected.extend_from_slice(&RECEIVE.to_le_bytes());
+        expected.push(bump);
+        // Unguarded, with an empty disallowed program list
+        expected.resize(Escrow::LEN, 0);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.make(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow)
+                    .owner(&PROGRAM_ID)
+                    .data(&expected)
+                    .rent_exempt()
+                    .build(),
+                Check::account(&fixture.vault)
+                    .owner(&fixture.token_program)
+                    .build(),
+            ],
+        );
+
+        assert_eq!(fixture.token_amount(&fixture.vault), DEPOSIT);
+        let maker_ata_a = fixture.ata(&fixture.maker, &fixture.mint_a);
+        assert_eq!(fixture.token_amount(&maker_ata_a), 0);
+    }
+
+    #[test]
+    fn test_make_rejects_missing_signer() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make();
+        instruction.accounts[0].is_signer = false;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::NotSigner)]);
+    }
+
+    #[test]
+    fn test_make_rejects_zero_amount() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make();
+        instruction.data[17..25].copy_from_slice(&0u64.to_le_bytes());
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::ZeroAmount)]);
+    }
+
+    #[test]
+    fn test_make_rejects_truncated_data() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make();
+        instruction.data.truncate(17);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_make_rejects_wrong_escrow_address() {
+        let fixture = Fixture::new();
+        let (other_escrow, _) = escrow_address(&fixture.maker, SEED + 1);
+        let mut instruction = fixture.make();
+        instruction.accounts[1].pubkey = other_escrow;
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::InvalidEscrowAddress)],
+        );
+    }
+
+    #[test]
+    fn test_make_rejects_wrong_mint() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make();
+        // The taker is a system account, not a mint
+        instruction.accounts[3].pubkey = fixture.taker;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::InvalidMint)]);
+    }
+
+    #[test]
+    fn test_make_rejects_same_mint_on_both_sides() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make();
+        instruction.accounts[3].pubkey = fixture.mint_a;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::DuplicateMint)]);
+    }
+
+    #[test]
+    fn test_make_rejects_wrong_ata() {
+        let fixture = Fixture::new();
+
+        // A token account of mint A that is not the maker's associated one
+        let stray = Pubkey::new_unique();
+        let (_, stray_account) = AtaBuilder::new(&fixture.maker, &fixture.mint_a)
+            .amount(DEPOSIT)
+            .build(&fixture.context.mollusk);
+        fixture.insert(stray, stray_account);
+
+        let mut instruction = fixture.make();
+        instruction.accounts[4].pubkey = stray;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::InvalidAddress)]);
+    }
+
+    #[test]
+    fn test_make_rejects_token_2022_mint() {
+        // Token CPIs target SPL Token, so Token-2022 mints are refused before
+        // anything is created
+        let fixture = Fixture::with_token_program(token_2022_program().0);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.make(),
+            &[custom(EscrowError::InvalidMint)],
+        );
+    }
+
+    #[test]
+    fn test_take() {
+        let fixture = Fixture::new().made();
+        let maker_lamports = fixture.account(&fixture.maker).lamports;
+        let escrow_lamports = fixture.account(&fixture.escrow).lamports;
+        let vault_lamports = fixture.account(&fixture.vault).lamports;
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow).closed().build(),
+                Check::account(&fixture.vault).lamports(0).build(),
+            ],
+        );
+
+        let taker_ata_a = fixture.ata(&fixture.taker, &fixture.mint_a);
+        let taker_ata_b = fixture.ata(&fixture.taker, &fixture.mint_b);
+        let maker_ata_b = fixture.ata(&fixture.maker, &fixture.mint_b);
+        assert_eq!(fixture.token_amount(&taker_ata_a), DEPOSIT);
+        assert_eq!(fixture.token_amount(&taker_ata_b), 0);
+        assert_eq!(fixture.token_amount(&maker_ata_b), RECEIVE);
+
+        // The maker gets the rent of both the escrow and the vault back
+        assert_eq!(
+            fixture.account(&fixture.maker).lamports,
+            maker_lamports + escrow_lamports + vault_lamports
+        );
+    }
+
+    #[test]
+    fn test_take_with_existing_maker_ata() {
+        let fixture = Fixture::new().made();
+        let (maker_ata_b, account) = AtaBuilder::new(&fixture.maker, &fixture.mint_b)
+            .amount(1)
+            .build(&fixture.context.mollusk);
+        fixture.insert(maker_ata_b, account);
+
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.take(), &[Check::success()]);
+
+        assert_eq!(fixture.token_amount(&maker_ata_b), RECEIVE + 1);
+    }
+
+    #[test]
+    fn test_take_rejects_missing_signer() {
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.take();
+        instruction.accounts[0].is_signer = false;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::NotSigner)]);
+    }
+
+    #[test]
+    fn test_take_rejects_wrong_maker() {
+        // Paying another maker would not match the escrow PDA seeds
+        let fixture = Fixture::new().made();
+        let (impostor, impostor_account) = funded_payer();
+        fixture.insert(impostor, impostor_account);
+
+        let mut instruction = fixture.take();
+        instruction.accounts[1].pubkey = impostor;
+        instruction.accounts[8].pubkey = fixture.ata(&impostor, &fixture.mint_b);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::InvalidEscrowAddress)],
+        );
+    }
+
+    #[test]
+    fn test_take_rejects_wrong_mint() {
+        // The taker offers a token the maker did not ask for
+        let fixture = Fixture::new().made();
+        let mint_c = Pubkey::new_unique();
+        fixture.insert(
+            mint_c,
+            MintBuilder::new(&Pubkey::new_unique())
+                .supply(RECEIVE)
+                .build(&fixture.context.mollusk),
+        );
+        let (taker_ata_c, account) = AtaBuilder::new(&fixture.taker, &mint_c)
+            .amount(RECEIVE)
+            .build(&fixture.context.mollusk);
+        fixture.insert(taker_ata_c, account);
+
+        let mut instruction = fixture.take();
+        instruction.accounts[4].pubkey = mint_c;
+        instruction.accounts[7].pubkey = taker_ata_c;
+        instruction.accounts[8].pubkey = fixture.ata(&fixture.maker, &mint_c);
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::InvalidMint)]);
+    }
+
+    #[test]
+    fn test_take_rejects_wrong_ata() {
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.take();
+        // The maker's ATA of mint A is not the taker's ATA of mint B
+        instruction.accounts[7].pubkey = fixture.ata(&fixture.maker, &fixture.mint_a);
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::InvalidAddress)]);
+    }
+
+    #[test]
+    fn test_take_rejects_insufficient_funds() {
+        let fixture = Fixture::new().made();
+        let taker_ata_b = fixture.ata(&fixture.taker, &fixture.mint_b);
+        let (_, account) = AtaBuilder::new(&fixture.taker, &fixture.mint_b)
+            .amount(RECEIVE - 1)
+            .build(&fixture.context.mollusk);
+        fixture.insert(taker_ata_b, account);
+
+        let result = fixture.context.process_instruction(&fixture.take());
+
+        assert!(result.program_result.is_err());
+        assert_eq!(fixture.token_amount(&fixture.vault), DEPOSIT);
+    }
+
+    #[test]
+    fn test_double_take() {
+        let fixture = Fixture::new().made();
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.take(), &[Check::success()]);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take(),
+            &[custom(EscrowError::InvalidEscrowAccount)],
+        );
+    }
+
+    #[test]
+    fn test_refund() {
+        let fixture = Fixture::new().made();
+        let maker_lamports = fixture.account(&fixture.maker).lamports;
+        let escrow_lamports = fixture.account(&fixture.escrow).lamports;
+        let vault_lamports = fixture.account(&fixture.vault).lamports;
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.refund(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow).closed().build(),
+                Check::account(&fixture.vault).lamports(0).build(),
+            ],
+        );
+
+        let maker_ata_a = fixture.ata(&fixture.maker, &fixture.mint_a);
+        assert_eq!(fixture.token_amount(&maker_ata_a), DEPOSIT);
+        assert_eq!(
+            fixture.account(&fixture.maker).lamports,
+            maker_lamports + escrow_lamports + vault_lamports
+        );
+    }
+
+    #[test]
+    fn test_refund_rejects_missing_signer() {
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.refund();
+        instruction.accounts[0].is_signer = false;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::NotSigner)]);
+    }
+
+    #[test]
+    fn test_refund_rejects_other_maker() {
+        // Only the maker whose key seeds the escrow PDA can refund it
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.refund();
+        instruction.accounts[0].pubkey = fixture.taker;
+        instruction.accounts[4].pubkey = fixture.ata(&fixture.taker, &fixture.mint_a);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::InvalidEscrowAddress)],
+        );
+    }
+
+    #[test]
+    fn test_refund_rejects_wrong_mint() {
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.refund();
+        instruction.accounts[2].pubkey = fixture.mint_b;
+        instruction.accounts[4].pubkey = fixture.ata(&fixture.maker, &fixture.mint_b);
+
+        fixture
+            .context
+            .process_and_validate_instruction(&instruction, &[custom(EscrowError::InvalidMint)]);
+    }
+
+    #[test]
+    fn test_refund_after_take() {
+        let fixture = Fixture::new().made();
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.take(), &[Check::success()]);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.refund(),
+            &[custom(EscrowError::InvalidEscrowAccount)],
+        );
+    }
+
+    #[test]
+    fn test_take_after_refund() {
+        let fixture = Fixture::new().made();
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.refund(), &[Check::success()]);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take(),
+            &[custom(EscrowError::InvalidEscrowAccount)],
+        );
+    }
+
+    #[test]
+    fn test_take_rejects_wrong_discriminator() {
+        let fixture = Fixture::new().made();
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data[0] = 0xff;
+        fixture.insert(fixture.escrow, escrow);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take(),
+            &[custom(EscrowError::InvalidAccountDiscriminator)],
+        );
+    }
+
+    #[test]
+    fn test_make_guarded() {
+        let fixture = Fixture::new();
+        let disallowed = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.make_guarded(&disallowed),
+            &[Check::success()],
+        );
+
+        let data = fixture.account(&fixture.escrow).data;
+        assert_eq!(data.len(), Escrow::LEN);
+        assert_eq!(data[Escrow::V2_LEN], Escrow::TAKE_GUARD);
+        assert_eq!(data[Escrow::V2_LEN + 1], 2);
+        let programs = &data[Escrow::V2_LEN + 2..];
+        assert_eq!(&programs[..32], disallowed[0].as_ref());
+        assert_eq!(&programs[32..64], disallowed[1].as_ref());
+        assert!(programs[64..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_make_rejects_too_many_disallowed_programs() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make_guarded(&[]);
+        instruction.data[26] = MAX_DISALLOWED_PROGRAMS as u8 + 1;
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::TooManyDisallowedPrograms)],
+        );
+    }
+
+    #[test]
+    fn test_make_rejects_unknown_flags() {
+        let fixture = Fixture::new();
+        let mut instruction = fixture.make_guarded(&[]);
+        instruction.data[25] |= 0b100;
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_guarded_take_alone() {
+        // Unrelated instructions around the Take are fine
+        let fixture = Fixture::new();
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.make_guarded(&[]), &[Check::success()]);
+        let memo = Instruction::new_with_bytes(Pubkey::new_unique(), b"gm", vec![]);
+
+        fixture.process_in_transaction(
+            &[memo.clone(), fixture.take_guarded(), memo],
+            1,
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow).closed().build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_guarded_take_requires_instructions_sysvar() {
+        let fixture = Fixture::new();
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.make_guarded(&[]), &[Check::success()]);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take(),
+            &[custom(EscrowError::MissingInstructionsSysvar)],
+        );
+
+        // Any other account in its place is refused too
+        let mut instruction = fixture.take_guarded();
+        instruction.accounts[12].pubkey = fixture.taker;
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::MissingInstructionsSysvar)],
+        );
+    }
+
+    #[test]
+    fn test_guarded_take_rejects_other_escrow_instruction() {
+        let fixture = Fixture::new();
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.make_guarded(&[]), &[Check::success()]);
+
+        // A bundler taking the escrow and then touching it again in the same
+        // transaction, whether before or after
+        fixture.process_in_transaction(
+            &[fixture.take_guarded(), fixture.refund()],
+            0,
+            &[custom(EscrowError::EscrowNotAlone)],
+        );
+        fixture.process_in_transaction(
+            &[fixture.refund(), fixture.take_guarded()],
+            1,
+            &[custom(EscrowError::EscrowNotAlone)],
+        );
+    }
+
+    #[test]
+    fn test_guarded_take_rejects_disallowed_program() {
+        let fixture = Fixture::new();
+        let swap_program = Pubkey::new_unique();
+        fixture.context.process_and_validate_instruction(
+            &fixture.make_guarded(&[Pubkey::new_unique(), swap_program]),
+            &[Check::success()],
+        );
+        let swap = Instruction::new_with_bytes(
+            swap_program,
+            &[0],
+            vec![AccountMeta::new(fixture.taker, true)],
+        );
+
+        fixture.process_in_transaction(
+            &[swap.clone(), fixture.take_guarded(), swap],
+            1,
+            &[custom(EscrowError::DisallowedProgram)],
+        );
+        assert_eq!(fixture.token_amount(&fixture.vault), DEPOSIT);
+    }
+
+    #[test]
+    fn test_guarded_take_rejects_cpi() {
+        // A wrapper program could swap before and after the Take within its
+        // own top-level instruction, where the sysvar cannot see it
+        let mut fixture = Fixture::new();
+        let forwarder = Pubkey::new_unique();
+        test_support::add_program(&mut fixture.context.mollusk, &forwarder, "cpi_forwarder");
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.make_guarded(&[]), &[Check::success()]);
+
+        let take = fixture.take_guarded();
+        let wrapped = Instruction::new_with_bytes(
+            forwarder,
+            &take.data,
+            [
+                vec![AccountMeta::new_readonly(PROGRAM_ID, false)],
+                take.accounts,
+            ]
+            .concat(),
+        );
+
+        fixture.process_in_transaction(&[wrapped], 0, &[custom(EscrowError::TakeNotTopLevel)]);
+        assert_eq!(fixture.token_amount(&fixture.vault), DEPOSIT);
+    }
+
+    #[test]
+    fn test_unguarded_take_ignores_transaction() {
+        let fixture = Fixture::new().made();
+
+        fixture.process_in_transaction(
+            &[fixture.take_guarded(), fixture.refund()],
+            0,
+            &[Check::success()],
+        );
+    }
+
+    #[test]
+    fn test_migrate_v1() {
+        let mut fixture = Fixture::new().made();
+        let current = fixture.account(&fixture.escrow).data;
+
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data = current[2..Escrow::V2_LEN].to_vec();
+        fixture.insert(fixture.escrow, escrow);
+        let logs = test_support::record_logs(&mut fixture.context.mollusk);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow)
+                    .data(&current)
+                    .rent_exempt()
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| EscrowEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [EscrowEvent::Migrated(EscrowMigrated::new(
+                fixture.escrow.to_bytes(),
+                1,
+                Escrow::VERSION,
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_migrate_v2() {
+        let mut fixture = Fixture::new().made();
+        let current = fixture.account(&fixture.escrow).data;
+
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data = current[..Escrow::V2_LEN].to_vec();
+        escrow.data[1] = 2;
+        fixture.insert(fixture.escrow, escrow);
+        let logs = test_support::record_logs(&mut fixture.context.mollusk);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow)
+                    .data(&current)
+                    .rent_exempt()
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| EscrowEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [EscrowEvent::Migrated(EscrowMigrated::new(
+                fixture.escrow.to_bytes(),
+                2,
+                Escrow::VERSION,
+            ))]
+        );
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[custom(EscrowError::AlreadyMigrated)],
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_other_account_types() {
+        // A program account of the v2 size that is not tagged as an escrow
+        let fixture = Fixture::new().made();
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data.truncate(Escrow::V2_LEN);
+        escrow.data[0] = Escrow::DISCRIMINATOR + 1;
+        escrow.data[1] = 2;
+        fixture.insert(fixture.escrow, escrow);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[custom(EscrowError::InvalidAccountDiscriminator)],
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_mismatched_version() {
+        // An escrow of the v2 size whose header claims v3
+        let fixture = Fixture::new().made();
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data.truncate(Escrow::V2_LEN);
+        escrow.data[1] = 3;
+        fixture.insert(fixture.escrow, escrow);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+
+        // And one of the v3 size that claims v2
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data.resize(Escrow::V3_LEN, 0);
+        escrow.data[1] = 2;
+        fixture.insert(fixture.escrow, escrow);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn test_migrate_v3() {
+        // A guarded v3 escrow keeps its guard
+        let mut fixture = Fixture::new();
+        let disallowed = [Pubkey::new_unique()];
+        fixture.context.process_and_validate_instruction(
+            &fixture.make_guarded(&disallowed),
+            &[Check::success()],
+        );
+        let current = fixture.account(&fixture.escrow).data;
+
+        let mut escrow = fixture.account(&fixture.escrow);
+        escrow.data = current[..Escrow::V3_LEN].to_vec();
+        escrow.data[1] = 3;
+        fixture.insert(fixture.escrow, escrow);
+        let logs = test_support::record_logs(&mut fixture.context.mollusk);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.migrate(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow)
+                    .data(&current)
+                    .rent_exempt()
+                    .build(),
+            ],
+        );
+
+        assert_eq!(
+            test_support::program_data(&logs)
+                .iter()
+                .map(|data| EscrowEvent::decode(data).unwrap())
+                .collect::<Vec<_>>(),
+            [EscrowEvent::Migrated(EscrowMigrated::new(
+                fixture.escrow.to_bytes(),
+                3,
+                Escrow::VERSION,
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_current_receive() {
+        let mut data = vec![0; Escrow::LEN];
+        data[0] = Escrow::DISCRIMINATOR;
+        data[1] = Escrow::VERSION;
+        let escrow = Escrow::load_mut(&mut data).unwrap();
+        escrow.set_receive(1_000);
+        escrow.set_auction(100, 10, 40);
+
+        // Not an auction, the price never moves
+        assert_eq!(escrow.current_receive(25), Ok(1_000));
+
+        escrow.flags = Escrow::DUTCH_AUCTION;
+        assert_eq!(escrow.current_receive(i64::MIN), Ok(1_000));
+        assert_eq!(escrow.current_receive(10), Ok(1_000));
+        assert_eq!(escrow.current_receive(20), Ok(700));
+        // Rounds in the maker's favor
+        assert_eq!(escrow.current_receive(11), Ok(970));
+        assert_eq!(escrow.current_receive(39), Ok(130));
+        assert_eq!(escrow.current_receive(40), Ok(100));
+        assert_eq!(escrow.current_receive(i64::MAX), Ok(100));
+
+        // The widest auction does not overflow
+        escrow.set_receive(u64::MAX);
+        escrow.set_auction(0, i64::MIN, i64::MAX);
+        assert_eq!(escrow.current_receive(0), Ok(u64::MAX / 2));
+    }
+
+    #[test]
+    fn test_dutch_auction_take() {
+        let mut fixture = Fixture::new();
+        fixture.context.process_and_validate_instruction(
+            &fixture.make_auction(RECEIVE / 5, 1_000, 2_000),
+            &[Check::success()],
+        );
+
+        // Halfway through, the price is halfway down
+        fixture.context.mollusk.sysvars.clock.unix_timestamp = 1_500;
+        let price = RECEIVE - (RECEIVE - RECEIVE / 5) / 2;
+        fixture.context.process_and_validate_instruction(
+            &fixture.take_at_most(price),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow).closed().build(),
+            ],
+        );
+
+        let taker_ata_b = fixture.ata(&fixture.taker, &fixture.mint_b);
+        let maker_ata_b = fixture.ata(&fixture.maker, &fixture.mint_b);
+        assert_eq!(fixture.token_amount(&maker_ata_b), price);
+        assert_eq!(fixture.token_amount(&taker_ata_b), RECEIVE - price);
+    }
+
+    #[test]
+    fn test_dutch_auction_take_rejects_slippage() {
+        let mut fixture = Fixture::new();
+        fixture.context.process_and_validate_instruction(
+            &fixture.make_auction(0, 1_000, 2_000),
+            &[Check::success()],
+        );
+        fixture.context.mollusk.sysvars.clock.unix_timestamp = 1_500;
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take_at_most(RECEIVE / 2 - 1),
+            &[custom(EscrowError::SlippageExceeded)],
+        );
+        assert_eq!(fixture.token_amount(&fixture.vault), DEPOSIT);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.take_at_most(RECEIVE / 2),
+            &[Check::success()],
+        );
+    }
+
+    #[test]
+    fn test_take_rejects_malformed_max_receive() {
+        let fixture = Fixture::new().made();
+        let mut instruction = fixture.take_at_most(RECEIVE);
+        instruction.data.pop();
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_make_rejects_invalid_auction() {
+        let fixture = Fixture::new();
+
+        // Ends before it starts, or rises in price
+        for instruction in [
+            fixture.make_auction(0, 2_000, 2_000),
+            fixture.make_auction(RECEIVE + 1, 1_000, 2_000),
+        ] {
+            fixture.context.process_and_validate_instruction(
+                &instruction,
+                &[custom(EscrowError::InvalidAuction)],
+            );
+        }
+
+        // The auction flag needs the auction fields
+        let mut instruction = fixture.make_guarded(&[]);
+        instruction.data[25] = Escrow::DUTCH_AUCTION;
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_native_a() {
+        let fixture = Fixture::new().native_a().made();
+        let escrow_lamports = fixture.account(&fixture.escrow).lamports;
+        let rent = fixture
+            .context
+            .mollusk
+            .sysvars
+            .rent
+            .minimum_balance(Escrow::LEN);
+        assert_eq!(escrow_lamports, rent + DEPOSIT);
+        // No vault is created for native SOL
+        assert_eq!(fixture.account(&fixture.vault).lamports, 0);
+
+        let maker_lamports = fixture.account(&fixture.maker).lamports;
+        let taker_lamports = fixture.account(&fixture.taker).lamports;
+        fixture.context.process_and_validate_instruction(
+            &fixture.take(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow).closed().build(),
+            ],
+        );
+
+        let maker_ata_b = fixture.ata(&fixture.maker, &fixture.mint_b);
+        assert_eq!(fixture.token_amount(&maker_ata_b), RECEIVE);
+        // The taker pays the rent of the maker's new ATA of mint B
+        let ata_rent = fixture.account(&maker_ata_b).lamports;
+        assert_eq!(
+            fixture.account(&fixture.taker).lamports,
+            taker_lamports + DEPOSIT - ata_rent
+        );
+        assert_eq!(
+            fixture.account(&fixture.maker).lamports,
+            maker_lamports + rent
+        );
+    }
+
+    #[test]
+    fn test_native_b() {
+        let fixture = Fixture::new().native_b().made();
+        let maker_lamports = fixture.account(&fixture.maker).lamports;
+        let escrow_lamports = fixture.account(&fixture.escrow).lamports;
+        let vault_lamports = fixture.account(&fixture.vault).lamports;
+
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.take(), &[Check::success()]);
+
+        let taker_ata_a = fixture.ata(&fixture.taker, &fixture.mint_a);
+        assert_eq!(fixture.token_amount(&taker_ata_a), DEPOSIT);
+        assert_eq!(
+            fixture.account(&fixture.maker).lamports,
+            maker_lamports + escrow_lamports + vault_lamports + RECEIVE
+        );
+    }
+
+    #[test]
+    fn test_refund_native_a() {
+        let fixture = Fixture::new().native_a().made();
+        let maker_lamports = fixture.account(&fixture.maker).lamports;
+        let escrow_lamports = fixture.account(&fixture.escrow).lamports;
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.refund(),
+            &[
+                Check::success(),
+                Check::account(&fixture.escrow).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.account(&fixture.maker).lamports,
+            maker_lamports + escrow_lamports
+        );
+    }
+
+    #[test]
+    fn test_native_mint_cannot_skip_the_vault() {
+        // Claiming mint A is native must not settle a token escrow without
+        // touching its vault
+        let fixture = Fixture::new().made();
+        let mut take = fixture.take();
+        take.accounts[3].pubkey = Pubkey::new_from_array(NATIVE_SOL);
+        let mut refund = fixture.refund();
+        refund.accounts[2].pubkey = Pubkey::new_from_array(NATIVE_SOL);
+
+        fixture
+            .context
+            .process_and_validate_instruction(&take, &[custom(EscrowError::InvalidMint)]);
+        fixture
+            .context
+            .process_and_validate_instruction(&refund, &[custom(EscrowError::InvalidMint)]);
+        assert_eq!(fixture.token_amount(&fixture.vault), DEPOSIT);
+    }
+
+    #[test]
+    fn test_escrow_error_codes() {
+        for code in 6000..6034 {
+            let error = EscrowError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(EscrowError::from_code(6034), None);
+    }
+}
@@ -0,0 +1,228 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio_escrow::{
+        AccountLayout, Basket, BuyOptionInstructionData, CallOption, Escrow, EscrowError,
+        MakeBasketInstructionData, MakeInstructionData, OpenServiceInstructionData, ServiceEscrow,
+        WriteOptionInstructionData, ID, MAX_BASKET_LEGS,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        fuzz::{check_invariants, check_rejected, fuzz, Fuzzer},
+        token_program, MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 16;
+
+    const MAKE_LEN: usize = 24;
+
+    fn fuzzer() -> Fuzzer {
+        let maker = Pubkey::new_unique();
+        let escrow = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), &42u64.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0;
+        let (token_program, token_account) = token_program();
+
+        let mut fuzzer = Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                maker,
+                escrow,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .fixed(token_program, token_account)
+        .data_lens(&[
+            Escrow::LEN,
+            Escrow::V1_LEN,
+            Basket::LEN,
+            ServiceEscrow::LEN,
+            CallOption::LEN,
+            MINT_LEN,
+            TOKEN_ACCOUNT_LEN,
+        ])
+        .max_accounts(10);
+        fuzzer.owners.push(token_program);
+        fuzzer
+    }
+
+    fn mollusk() -> Mollusk {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_escrow");
+        test_support::add_token_programs(&mut mollusk);
+        mollusk
+    }
+
+    fn make_data() -> impl Strategy<Value = Vec<u8>> {
+        prop_oneof![
+            vec(any::<u8>(), 0..=MAKE_LEN + 8),
+            (any::<u64>(), any::<u64>(), any::<u64>()).prop_map(|(seed, receive, amount)| {
+                [
+                    seed.to_le_bytes(),
+                    receive.to_le_bytes(),
+                    amount.to_le_bytes(),
+                ]
+                .concat()
+            }),
+        ]
+    }
+
+    fn make_basket_data() -> impl Strategy<Value = Vec<u8>> {
+        prop_oneof![
+            vec(any::<u8>(), 0..=MakeBasketInstructionData::LEN + 8),
+            (
+                any::<u64>(),
+                0..=MAX_BASKET_LEGS as u8 + 1,
+                0..=MAX_BASKET_LEGS as u8 + 1,
+                vec(0..3u64, MAX_BASKET_LEGS * 2),
+            )
+                .prop_map(|(seed, offered_len, requested_len, amounts)| {
+                    let mut data = seed.to_le_bytes().to_vec();
+                    data.push(offered_len);
+                    data.push(requested_len);
+                    for amount in amounts {
+                        data.extend_from_slice(&amount.to_le_bytes());
+                    }
+                    data
+                }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_make_data(data in make_data()) {
+            let parsed = MakeInstructionData::try_from(data.as_slice());
+
+            if data.len() != MAKE_LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let field = |index: usize| u64::from_le_bytes(data[index * 8..][..8].try_into().unwrap());
+            if field(2) == 0 {
+                prop_assert_eq!(parsed.err(), Some(EscrowError::ZeroAmount.into()));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(
+                (parsed.seed, parsed.receive, parsed.amount),
+                (field(0), field(1), field(2))
+            );
+        }
+
+        #[test]
+        fn test_parse_make_basket_data(data in make_basket_data()) {
+            let parsed = MakeBasketInstructionData::try_from(data.as_slice());
+
+            if data.len() != MakeBasketInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let legs = 1..=MAX_BASKET_LEGS as u8;
+            if !legs.contains(&data[8]) || !legs.contains(&data[9]) {
+                prop_assert_eq!(parsed.err(), Some(EscrowError::InvalidLegCount.into()));
+                return Ok(());
+            }
+
+            let amount = |index: usize| u64::from_le_bytes(data[10 + index * 8..][..8].try_into().unwrap());
+            if (0..data[8] as usize).any(|index| amount(index) == 0) {
+                prop_assert_eq!(parsed.err(), Some(EscrowError::ZeroAmount.into()));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(parsed.seed, u64::from_le_bytes(data[..8].try_into().unwrap()));
+            prop_assert_eq!((parsed.offered_len, parsed.requested_len), (data[8], data[9]));
+            for index in 0..MAX_BASKET_LEGS {
+                prop_assert_eq!(parsed.offered_amounts[index], amount(index));
+                prop_assert_eq!(parsed.requested_amounts[index], amount(MAX_BASKET_LEGS + index));
+            }
+        }
+
+        #[test]
+        fn test_parse_escrow_header(data in vec(any::<u8>(), Escrow::V1_LEN..=Escrow::LEN + 1), tagged in any::<bool>()) {
+            let mut data = data;
+            if tagged && data.len() >= 2 {
+                data[0] = Escrow::DISCRIMINATOR;
+                data[1] = Escrow::VERSION;
+            }
+
+            let valid = data.len() == Escrow::LEN
+                && data[0] == Escrow::DISCRIMINATOR
+                && data[1] == Escrow::VERSION;
+            prop_assert_eq!(Escrow::load(&data).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = mollusk();
+        let data = (0..INSTRUCTION_COUNT, make_data())
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_make_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(fuzzer().wrong_length_cases(0, MAKE_LEN), |case| {
+            check_rejected(&mollusk, &case)
+        });
+    }
+
+    #[test]
+    fn test_fuzz_make_basket_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(4, MakeBasketInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_open_service_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(7, OpenServiceInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_write_option_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(9, WriteOptionInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_buy_option_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(10, BuyOptionInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
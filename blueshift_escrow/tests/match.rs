@@ -0,0 +1,363 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_escrow::{
+        AccountLayout, Escrow, EscrowError, Make, Match, ID, MAX_DISALLOWED_PROGRAMS,
+    };
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 11;
+    const FUNDS: u64 = 1_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Alice holding FUNDS of mint A, Bob holding FUNDS of mint B and a
+    // cranker holding neither, with no escrows made yet
+    struct Fixture {
+        context: Context,
+        alice: Pubkey,
+        bob: Pubkey,
+        cranker: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_escrow");
+            add_token_programs(&mut mollusk);
+
+            let (alice, alice_account) = funded_payer();
+            let (bob, bob_account) = funded_payer();
+            let (cranker, cranker_account) = funded_payer();
+            let mint_a = Pubkey::new_unique();
+            let mint_b = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(alice, alice_account);
+            store.insert(bob, bob_account);
+            store.insert(cranker, cranker_account);
+            for (owner, mint) in [(alice, mint_a), (bob, mint_b)] {
+                store.insert(
+                    mint,
+                    MintBuilder::new(&Pubkey::new_unique())
+                        .supply(FUNDS)
+                        .build(&mollusk),
+                );
+                let (ata, account) = AtaBuilder::new(&owner, &mint).amount(FUNDS).build(&mollusk);
+                store.insert(ata, account);
+            }
+
+            Self {
+                context: mollusk.with_context(store),
+                alice,
+                bob,
+                cranker,
+                mint_a,
+                mint_b,
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn programs() -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(system_program().0, false),
+                AccountMeta::new_readonly(token_program().0, false),
+                AccountMeta::new_readonly(associated_token_program().0, false),
+            ]
+        }
+
+        // `maker` deposits `amount` of `mint_a` asking `receive` of `mint_b`
+        fn make(
+            &self,
+            maker: &Pubkey,
+            mint_a: &Pubkey,
+            mint_b: &Pubkey,
+            receive: u64,
+            amount: u64,
+        ) -> Instruction {
+            let escrow = escrow_address(maker, SEED);
+            let mut data = vec![*Make::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&receive.to_le_bytes());
+            data.extend_from_slice(&amount.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(*maker, true),
+                        AccountMeta::new(escrow, false),
+                        AccountMeta::new_readonly(*mint_a, false),
+                        AccountMeta::new_readonly(*mint_b, false),
+                        AccountMeta::new(self.ata(maker, mint_a), false),
+                        AccountMeta::new(self.ata(&escrow, mint_a), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        // Alice sells mint A and Bob sells mint B, each for the other's token
+        fn made(self, alice: (u64, u64), bob: (u64, u64)) -> Self {
+            for instruction in [
+                self.make(&self.alice, &self.mint_a, &self.mint_b, alice.0, alice.1),
+                self.make(&self.bob, &self.mint_b, &self.mint_a, bob.0, bob.1),
+            ] {
+                self.context
+                    .process_and_validate_instruction(&instruction, &[Check::success()]);
+            }
+            self
+        }
+
+        fn escrow(&self, maker: &Pubkey) -> Pubkey {
+            escrow_address(maker, SEED)
+        }
+
+        fn match_escrows(&self) -> Instruction {
+            let escrow_1 = self.escrow(&self.alice);
+            let escrow_2 = self.escrow(&self.bob);
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*Match::DISCRIMINATOR],
+                [
+                    vec![
+                        AccountMeta::new(self.cranker, true),
+                        AccountMeta::new(self.alice, false),
+                        AccountMeta::new(escrow_1, false),
+                        AccountMeta::new(self.ata(&escrow_1, &self.mint_a), false),
+                        AccountMeta::new(self.bob, false),
+                        AccountMeta::new(escrow_2, false),
+                        AccountMeta::new(self.ata(&escrow_2, &self.mint_b), false),
+                        AccountMeta::new_readonly(self.mint_a, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(&self.alice, &self.mint_b), false),
+                        AccountMeta::new(self.ata(&self.bob, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.alice, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.cranker, &self.mint_b), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+    }
+
+    fn escrow_address(maker: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: EscrowError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_match_fills_both() {
+        // Alice asks 200 B for 100 A, Bob pays 250 B for 100 A
+        let fixture = Fixture::new().made((200, 100), (100, 250));
+        let escrow_1 = fixture.escrow(&fixture.alice);
+        let escrow_2 = fixture.escrow(&fixture.bob);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.match_escrows(),
+            &[
+                Check::success(),
+                Check::account(&escrow_1).closed().build(),
+                Check::account(&escrow_2).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+            200
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.bob, &fixture.mint_a)),
+            100
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.cranker, &fixture.mint_b)),
+            50
+        );
+        assert_eq!(
+            fixture
+                .account(&fixture.ata(&escrow_1, &fixture.mint_a))
+                .lamports,
+            0
+        );
+    }
+
+    #[test]
+    fn test_match_partially_fills_larger_escrow() {
+        // Alice asks 200 B for 100 A, Bob pays 120 B for only 50 A
+        let fixture = Fixture::new().made((200, 100), (50, 120));
+        let escrow_1 = fixture.escrow(&fixture.alice);
+        let escrow_2 = fixture.escrow(&fixture.bob);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.match_escrows(),
+            &[Check::success(), Check::account(&escrow_2).closed().build()],
+        );
+
+        // Alice is paid 100 B for half her deposit and still wants 100 B for
+        // the other half
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+            100
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&escrow_1, &fixture.mint_a)),
+            50
+        );
+        let data = fixture.account(&escrow_1).data;
+        assert_eq!(data.len(), Escrow::LEN);
+        assert_eq!(u64::from_le_bytes(data[106..114].try_into().unwrap()), 100);
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.bob, &fixture.mint_a)),
+            50
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.cranker, &fixture.mint_b)),
+            20
+        );
+    }
+
+    #[test]
+    fn test_match_closes_escrow_paid_in_full_by_rounding() {
+        // Alice asks 1 B for 100 A and Bob pays 1 B for 50 A. Half of
+        // Alice's deposit rounds up to her whole price, so she takes back
+        // the other half.
+        let fixture = Fixture::new().made((1, 100), (50, 1));
+        let escrow_1 = fixture.escrow(&fixture.alice);
+        let escrow_2 = fixture.escrow(&fixture.bob);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.match_escrows(),
+            &[
+                Check::success(),
+                Check::account(&escrow_1).closed().build(),
+                Check::account(&escrow_2).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+            1
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_a)),
+            FUNDS - 50
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.bob, &fixture.mint_a)),
+            50
+        );
+        assert_eq!(
+            fixture
+                .account(&fixture.ata(&escrow_1, &fixture.mint_a))
+                .lamports,
+            0
+        );
+    }
+
+    #[test]
+    fn test_match_without_surplus() {
+        let fixture = Fixture::new().made((200, 100), (100, 200));
+
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.match_escrows(), &[Check::success()]);
+
+        assert_eq!(
+            fixture
+                .account(&fixture.ata(&fixture.cranker, &fixture.mint_b))
+                .lamports,
+            0
+        );
+    }
+
+    #[test]
+    fn test_match_rejects_prices_not_crossing() {
+        // Alice asks 200 B for 100 A but Bob only pays 150 B
+        let fixture = Fixture::new().made((200, 100), (100, 150));
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.match_escrows(),
+            &[custom(EscrowError::EscrowsDoNotCross)],
+        );
+    }
+
+    #[test]
+    fn test_match_rejects_guarded_escrow() {
+        let fixture = Fixture::new();
+        let mut guarded = fixture.make(&fixture.bob, &fixture.mint_b, &fixture.mint_a, 100, 250);
+        guarded.data.push(Escrow::TAKE_GUARD);
+        guarded.data.push(0);
+        guarded
+            .data
+            .extend_from_slice(&[0; 32 * MAX_DISALLOWED_PROGRAMS]);
+        for instruction in [
+            fixture.make(&fixture.alice, &fixture.mint_a, &fixture.mint_b, 200, 100),
+            guarded,
+        ] {
+            fixture
+                .context
+                .process_and_validate_instruction(&instruction, &[Check::success()]);
+        }
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.match_escrows(),
+            &[custom(EscrowError::UnmatchableEscrow)],
+        );
+    }
+
+    #[test]
+    fn test_match_rejects_wrong_maker() {
+        let fixture = Fixture::new().made((200, 100), (100, 250));
+        let mut instruction = fixture.match_escrows();
+        instruction.accounts[1] = AccountMeta::new(fixture.bob, false);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(EscrowError::InvalidEscrowAddress)],
+        );
+    }
+}
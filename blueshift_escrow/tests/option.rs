@@ -0,0 +1,459 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_escrow::{
+        AccountLayout, BuyOption, CallOption, EscrowError, ExerciseOption, ReclaimOption,
+        TransferOption, WriteOption, ID,
+    };
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 9;
+    const COLLATERAL: u64 = 100;
+    const STRIKE: u64 = 2_000;
+    const PREMIUM: u64 = 50;
+    const EXPIRY: i64 = 2_000;
+    const QUOTE_FUNDS: u64 = 5_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Writer holding COLLATERAL of mint A and two traders holding QUOTE_FUNDS
+    // of mint B each, at unix time 1_000 with no option written yet
+    struct Fixture {
+        context: Context,
+        writer: Pubkey,
+        alice: Pubkey,
+        bob: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        option: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_escrow");
+            add_token_programs(&mut mollusk);
+            mollusk.sysvars.clock.unix_timestamp = 1_000;
+
+            let (writer, writer_account) = funded_payer();
+            let (alice, alice_account) = funded_payer();
+            let (bob, bob_account) = funded_payer();
+            let mint_a = Pubkey::new_unique();
+            let mint_b = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(writer, writer_account);
+            store.insert(alice, alice_account);
+            store.insert(bob, bob_account);
+            store.insert(
+                mint_a,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(COLLATERAL)
+                    .build(&mollusk),
+            );
+            store.insert(
+                mint_b,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(QUOTE_FUNDS * 2)
+                    .build(&mollusk),
+            );
+            for (owner, mint, amount) in [
+                (writer, mint_a, COLLATERAL),
+                (alice, mint_b, QUOTE_FUNDS),
+                (bob, mint_b, QUOTE_FUNDS),
+            ] {
+                let (ata, account) = AtaBuilder::new(&owner, &mint)
+                    .amount(amount)
+                    .build(&mollusk);
+                store.insert(ata, account);
+            }
+
+            Self {
+                context: mollusk.with_context(store),
+                writer,
+                alice,
+                bob,
+                mint_a,
+                mint_b,
+                option: option_address(&writer, SEED),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        // holder sits after the header, seed and writer
+        fn holder(&self) -> Pubkey {
+            let data = self.account(&self.option).data;
+            Pubkey::new_from_array(data[42..74].try_into().unwrap())
+        }
+
+        fn set_time(&mut self, unix_timestamp: i64) {
+            self.context.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        }
+
+        fn programs() -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(system_program().0, false),
+                AccountMeta::new_readonly(token_program().0, false),
+                AccountMeta::new_readonly(associated_token_program().0, false),
+            ]
+        }
+
+        fn write(&self, expiry: i64) -> Instruction {
+            self.write_with(PREMIUM, expiry)
+        }
+
+        fn write_with(&self, premium: u64, expiry: i64) -> Instruction {
+            let mut data = vec![*WriteOption::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&COLLATERAL.to_le_bytes());
+            data.extend_from_slice(&STRIKE.to_le_bytes());
+            data.extend_from_slice(&premium.to_le_bytes());
+            data.extend_from_slice(&expiry.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(self.writer, true),
+                        AccountMeta::new(self.option, false),
+                        AccountMeta::new_readonly(self.mint_a, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(&self.writer, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.option, &self.mint_a), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        // Buy on the terms WriteOption(EXPIRY) offers
+        fn buy(&self, buyer: &Pubkey) -> Instruction {
+            self.buy_with(buyer, PREMIUM, EXPIRY)
+        }
+
+        fn buy_with(&self, buyer: &Pubkey, max_premium: u64, expiry: i64) -> Instruction {
+            let mut data = vec![*BuyOption::DISCRIMINATOR];
+            data.extend_from_slice(&max_premium.to_le_bytes());
+            data.extend_from_slice(&STRIKE.to_le_bytes());
+            data.extend_from_slice(&expiry.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(*buyer, true),
+                        AccountMeta::new_readonly(self.writer, false),
+                        AccountMeta::new(self.option, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(buyer, &self.mint_b), false),
+                        AccountMeta::new(self.ata(&self.writer, &self.mint_b), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn transfer(&self, holder: &Pubkey, new_holder: &Pubkey) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*TransferOption::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new_readonly(*holder, true),
+                    AccountMeta::new(self.option, false),
+                    AccountMeta::new_readonly(*new_holder, false),
+                ],
+            )
+        }
+
+        fn exercise(&self, holder: &Pubkey) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*ExerciseOption::DISCRIMINATOR],
+                [
+                    vec![
+                        AccountMeta::new(*holder, true),
+                        AccountMeta::new(self.writer, false),
+                        AccountMeta::new(self.option, false),
+                        AccountMeta::new_readonly(self.mint_a, false),
+                        AccountMeta::new_readonly(self.mint_b, false),
+                        AccountMeta::new(self.ata(&self.option, &self.mint_a), false),
+                        AccountMeta::new(self.ata(holder, &self.mint_a), false),
+                        AccountMeta::new(self.ata(holder, &self.mint_b), false),
+                        AccountMeta::new(self.ata(&self.writer, &self.mint_b), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn reclaim(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*ReclaimOption::DISCRIMINATOR],
+                [
+                    vec![
+                        AccountMeta::new(self.writer, true),
+                        AccountMeta::new(self.option, false),
+                        AccountMeta::new_readonly(self.mint_a, false),
+                        AccountMeta::new(self.ata(&self.option, &self.mint_a), false),
+                        AccountMeta::new(self.ata(&self.writer, &self.mint_a), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        // Run WriteOption, as the setup of the others
+        fn written(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.write(EXPIRY), &[Check::success()]);
+            self
+        }
+
+        // Run BuyOption for alice
+        fn bought(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.buy(&self.alice), &[Check::success()]);
+            self
+        }
+    }
+
+    fn option_address(writer: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"option", writer.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: EscrowError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_write_option() {
+        let fixture = Fixture::new().written();
+
+        let data = fixture.account(&fixture.option).data;
+        assert_eq!(data.len(), CallOption::LEN);
+        assert_eq!(
+            &data[..2],
+            &[CallOption::DISCRIMINATOR, CallOption::VERSION]
+        );
+        assert_eq!(fixture.holder(), Pubkey::default());
+
+        let vault = fixture.ata(&fixture.option, &fixture.mint_a);
+        assert_eq!(fixture.token_amount(&vault), COLLATERAL);
+    }
+
+    #[test]
+    fn test_write_option_rejects_past_expiry() {
+        let fixture = Fixture::new();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.write(1_000),
+            &[custom(EscrowError::OptionExpired)],
+        );
+    }
+
+    #[test]
+    fn test_buy_option_pays_premium() {
+        let fixture = Fixture::new().written().bought();
+
+        assert_eq!(fixture.holder(), fixture.alice);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.writer, &fixture.mint_b)),
+            PREMIUM
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+            QUOTE_FUNDS - PREMIUM
+        );
+    }
+
+    #[test]
+    fn test_buy_option_rejects_second_buyer() {
+        let fixture = Fixture::new().written().bought();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.buy(&fixture.bob),
+            &[custom(EscrowError::OptionAlreadySold)],
+        );
+    }
+
+    #[test]
+    fn test_buy_option_rejects_premium_above_max() {
+        let fixture = Fixture::new().written();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.buy_with(&fixture.alice, PREMIUM - 1, EXPIRY),
+            &[custom(EscrowError::SlippageExceeded)],
+        );
+    }
+
+    #[test]
+    fn test_buy_option_rejects_rewritten_option() {
+        // The writer reclaims the unsold option and writes a new one at the
+        // same address before alice's buy lands
+        for (premium, expiry, error) in [
+            (PREMIUM * 10, EXPIRY, EscrowError::SlippageExceeded),
+            (PREMIUM, EXPIRY - 500, EscrowError::OptionTermsChanged),
+        ] {
+            let fixture = Fixture::new().written();
+            fixture
+                .context
+                .process_and_validate_instruction(&fixture.reclaim(), &[Check::success()]);
+            fixture.context.process_and_validate_instruction(
+                &fixture.write_with(premium, expiry),
+                &[Check::success()],
+            );
+
+            fixture
+                .context
+                .process_and_validate_instruction(&fixture.buy(&fixture.alice), &[custom(error)]);
+            assert_eq!(
+                fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+                QUOTE_FUNDS
+            );
+        }
+    }
+
+    #[test]
+    fn test_exercise_option() {
+        let fixture = Fixture::new().written().bought();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.exercise(&fixture.alice),
+            &[
+                Check::success(),
+                Check::account(&fixture.option).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_a)),
+            COLLATERAL
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.writer, &fixture.mint_b)),
+            PREMIUM + STRIKE
+        );
+        let vault = fixture.ata(&fixture.option, &fixture.mint_a);
+        assert_eq!(fixture.account(&vault).lamports, 0);
+    }
+
+    #[test]
+    fn test_exercise_rejects_after_expiry() {
+        let mut fixture = Fixture::new().written().bought();
+        fixture.set_time(EXPIRY);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.exercise(&fixture.alice),
+            &[custom(EscrowError::OptionExpired)],
+        );
+    }
+
+    #[test]
+    fn test_transferred_option_moves_exercise_right() {
+        let fixture = Fixture::new().written().bought();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.transfer(&fixture.alice, &fixture.bob),
+            &[Check::success()],
+        );
+        assert_eq!(fixture.holder(), fixture.bob);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.exercise(&fixture.alice),
+            &[custom(EscrowError::InvalidHolder)],
+        );
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.exercise(&fixture.bob), &[Check::success()]);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.bob, &fixture.mint_a)),
+            COLLATERAL
+        );
+    }
+
+    #[test]
+    fn test_transfer_rejects_non_holder() {
+        let fixture = Fixture::new().written().bought();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.transfer(&fixture.bob, &fixture.bob),
+            &[custom(EscrowError::InvalidHolder)],
+        );
+    }
+
+    #[test]
+    fn test_reclaim_after_expiry() {
+        let mut fixture = Fixture::new().written().bought();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.reclaim(),
+            &[custom(EscrowError::OptionNotExpired)],
+        );
+
+        fixture.set_time(EXPIRY);
+        fixture.context.process_and_validate_instruction(
+            &fixture.reclaim(),
+            &[
+                Check::success(),
+                Check::account(&fixture.option).closed().build(),
+            ],
+        );
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.writer, &fixture.mint_a)),
+            COLLATERAL
+        );
+    }
+
+    #[test]
+    fn test_reclaim_unsold_option() {
+        let fixture = Fixture::new().written();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.reclaim(),
+            &[
+                Check::success(),
+                Check::account(&fixture.option).closed().build(),
+            ],
+        );
+    }
+}
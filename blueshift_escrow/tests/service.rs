@@ -0,0 +1,453 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_escrow::{
+        AccountLayout, DisputeService, EscrowError, OpenService, ResolveService,
+        ResolveServiceInstructionData, ServiceEscrow, ID, NATIVE_SOL,
+    };
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, donate_tokens,
+        funded_payer, system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 5;
+    const AMOUNT: u64 = 10_000;
+    // 2.5%
+    const FEE_BPS: u16 = 250;
+    const FEE: u64 = 250;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Buyer holding AMOUNT of the mint, with no service escrow opened yet
+    struct Fixture {
+        context: Context,
+        buyer: Pubkey,
+        recipient: Pubkey,
+        arbiter: Pubkey,
+        mint: Pubkey,
+        service: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_escrow");
+            add_token_programs(&mut mollusk);
+
+            let (buyer, buyer_account) = funded_payer();
+            let (recipient, recipient_account) = funded_payer();
+            let (arbiter, arbiter_account) = funded_payer();
+            let mint = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(buyer, buyer_account);
+            store.insert(recipient, recipient_account);
+            store.insert(arbiter, arbiter_account);
+            store.insert(
+                mint,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(AMOUNT)
+                    .build(&mollusk),
+            );
+            let (buyer_ata, account) = AtaBuilder::new(&buyer, &mint)
+                .amount(AMOUNT)
+                .build(&mollusk);
+            store.insert(buyer_ata, account);
+
+            Self {
+                context: mollusk.with_context(store),
+                buyer,
+                recipient,
+                arbiter,
+                mint,
+                service: service_address(&buyer, SEED),
+            }
+        }
+
+        // Deposit lamports instead of tokens
+        fn native(mut self) -> Self {
+            self.mint = Pubkey::new_from_array(NATIVE_SOL);
+            self
+        }
+
+        fn ata(&self, owner: &Pubkey) -> Pubkey {
+            associated_token_address(owner, &self.mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn programs() -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(system_program().0, false),
+                AccountMeta::new_readonly(token_program().0, false),
+                AccountMeta::new_readonly(associated_token_program().0, false),
+            ]
+        }
+
+        fn open(&self, fee_bps: u16) -> Instruction {
+            let mut data = vec![*OpenService::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&AMOUNT.to_le_bytes());
+            data.extend_from_slice(&fee_bps.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(self.buyer, true),
+                        AccountMeta::new(self.service, false),
+                        AccountMeta::new_readonly(self.recipient, false),
+                        AccountMeta::new_readonly(self.arbiter, false),
+                        AccountMeta::new_readonly(self.mint, false),
+                        AccountMeta::new(self.ata(&self.buyer), false),
+                        AccountMeta::new(self.ata(&self.service), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn resolve(&self, authority: &Pubkey, outcome: u8) -> Instruction {
+            let payee = match outcome {
+                ResolveServiceInstructionData::RELEASE => self.recipient,
+                _ => self.buyer,
+            };
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*ResolveService::DISCRIMINATOR, outcome],
+                [
+                    vec![
+                        AccountMeta::new(*authority, true),
+                        AccountMeta::new(self.buyer, false),
+                        AccountMeta::new(self.service, false),
+                        AccountMeta::new(self.recipient, false),
+                        AccountMeta::new(self.arbiter, false),
+                        AccountMeta::new_readonly(self.mint, false),
+                        AccountMeta::new(self.ata(&self.service), false),
+                        AccountMeta::new(self.ata(&payee), false),
+                        AccountMeta::new(self.ata(&self.arbiter), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn dispute(&self, authority: &Pubkey) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*DisputeService::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new_readonly(*authority, true),
+                    AccountMeta::new(self.service, false),
+                ],
+            )
+        }
+
+        // Run OpenService with FEE_BPS, as the setup of the others
+        fn opened(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.open(FEE_BPS), &[Check::success()]);
+            self
+        }
+
+        // Have the buyer call in the arbiter
+        fn disputed(self) -> Self {
+            self.context
+                .process_and_validate_instruction(&self.dispute(&self.buyer), &[Check::success()]);
+            self
+        }
+    }
+
+    fn service_address(buyer: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"service", buyer.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: EscrowError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_open_service() {
+        let fixture = Fixture::new().opened();
+
+        let data = fixture.account(&fixture.service).data;
+        assert_eq!(data.len(), ServiceEscrow::LEN);
+        assert_eq!(
+            &data[..2],
+            &[ServiceEscrow::DISCRIMINATOR, ServiceEscrow::VERSION]
+        );
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.service)), AMOUNT);
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.buyer)), 0);
+    }
+
+    #[test]
+    fn test_open_service_rejects_fee_above_total() {
+        let fixture = Fixture::new();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.open(ServiceEscrow::MAX_FEE_BPS + 1),
+            &[custom(EscrowError::InvalidFee)],
+        );
+    }
+
+    #[test]
+    fn test_open_service_rejects_shared_parties() {
+        for case in 0..3 {
+            let mut fixture = Fixture::new();
+            match case {
+                0 => fixture.recipient = fixture.buyer,
+                1 => fixture.arbiter = fixture.buyer,
+                _ => fixture.arbiter = fixture.recipient,
+            }
+
+            fixture.context.process_and_validate_instruction(
+                &fixture.open(FEE_BPS),
+                &[custom(EscrowError::InvalidParties)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_buyer_release_pays_no_fee() {
+        let fixture = Fixture::new().opened();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.buyer, ResolveServiceInstructionData::RELEASE),
+            &[
+                Check::success(),
+                Check::account(&fixture.service).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.recipient)),
+            AMOUNT
+        );
+        assert_eq!(fixture.account(&fixture.ata(&fixture.arbiter)).lamports, 0);
+        assert_eq!(fixture.account(&fixture.ata(&fixture.service)).lamports, 0);
+    }
+
+    #[test]
+    fn test_arbiter_release_takes_fee() {
+        let fixture = Fixture::new().opened().disputed();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.arbiter, ResolveServiceInstructionData::RELEASE),
+            &[Check::success()],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.recipient)),
+            AMOUNT - FEE
+        );
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.arbiter)), FEE);
+    }
+
+    #[test]
+    fn test_release_sweeps_donated_tokens() {
+        let fixture = Fixture::new().opened().disputed();
+        let vault = fixture.ata(&fixture.service);
+        donate_tokens(
+            fixture
+                .context
+                .account_store
+                .borrow_mut()
+                .get_mut(&vault)
+                .unwrap(),
+            1,
+        );
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.arbiter, ResolveServiceInstructionData::RELEASE),
+            &[
+                Check::success(),
+                Check::account(&fixture.service).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.recipient)),
+            AMOUNT - FEE + 1
+        );
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.arbiter)), FEE);
+        assert_eq!(fixture.account(&vault).lamports, 0);
+    }
+
+    #[test]
+    fn test_arbiter_refund() {
+        let fixture = Fixture::new().opened().disputed();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.arbiter, ResolveServiceInstructionData::REFUND),
+            &[
+                Check::success(),
+                Check::account(&fixture.service).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.buyer)),
+            AMOUNT - FEE
+        );
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.arbiter)), FEE);
+    }
+
+    #[test]
+    fn test_arbiter_rejects_undisputed_service() {
+        let fixture = Fixture::new().opened();
+
+        for outcome in [
+            ResolveServiceInstructionData::RELEASE,
+            ResolveServiceInstructionData::REFUND,
+        ] {
+            fixture.context.process_and_validate_instruction(
+                &fixture.resolve(&fixture.arbiter, outcome),
+                &[custom(EscrowError::ServiceNotDisputed)],
+            );
+        }
+
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.service)), AMOUNT);
+    }
+
+    #[test]
+    fn test_recipient_dispute() {
+        let fixture = Fixture::new().opened();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.dispute(&fixture.recipient),
+            &[Check::success()],
+        );
+        assert!(ServiceEscrow::load(&fixture.account(&fixture.service).data)
+            .unwrap()
+            .is_disputed());
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.arbiter, ResolveServiceInstructionData::REFUND),
+            &[Check::success()],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.buyer)),
+            AMOUNT - FEE
+        );
+    }
+
+    #[test]
+    fn test_dispute_rejects_other_signers() {
+        // Only the buyer and the recipient can call in the arbiter
+        let fixture = Fixture::new().opened();
+
+        for authority in [fixture.arbiter, Pubkey::new_unique()] {
+            fixture.context.process_and_validate_instruction(
+                &fixture.dispute(&authority),
+                &[custom(EscrowError::InvalidResolver)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_dispute_twice() {
+        let fixture = Fixture::new().opened().disputed();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.dispute(&fixture.recipient),
+            &[custom(EscrowError::ServiceAlreadyDisputed)],
+        );
+    }
+
+    #[test]
+    fn test_buyer_release_after_dispute() {
+        // A dispute does not stop the buyer from paying in full
+        let fixture = Fixture::new().opened().disputed();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.buyer, ResolveServiceInstructionData::RELEASE),
+            &[Check::success()],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.recipient)),
+            AMOUNT
+        );
+    }
+
+    #[test]
+    fn test_resolve_rejects_other_resolvers() {
+        // The buyer cannot refund themselves and the recipient cannot release
+        let fixture = Fixture::new().opened();
+
+        for (authority, outcome) in [
+            (fixture.buyer, ResolveServiceInstructionData::REFUND),
+            (fixture.recipient, ResolveServiceInstructionData::RELEASE),
+        ] {
+            fixture.context.process_and_validate_instruction(
+                &fixture.resolve(&authority, outcome),
+                &[custom(EscrowError::InvalidResolver)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_rejects_unknown_outcome() {
+        let fixture = Fixture::new().opened();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.arbiter, 2),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_native_service() {
+        let fixture = Fixture::new().native().opened().disputed();
+        let recipient_lamports = fixture.account(&fixture.recipient).lamports;
+        let arbiter_lamports = fixture.account(&fixture.arbiter).lamports;
+        assert_eq!(fixture.account(&fixture.ata(&fixture.service)).lamports, 0);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.resolve(&fixture.arbiter, ResolveServiceInstructionData::RELEASE),
+            &[
+                Check::success(),
+                Check::account(&fixture.service).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.account(&fixture.recipient).lamports,
+            recipient_lamports + AMOUNT - FEE
+        );
+        assert_eq!(
+            fixture.account(&fixture.arbiter).lamports,
+            arbiter_lamports + FEE
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+use program_helpers::HelperError;
+
+/// Errors returned by the pool program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PoolError {
+    NotSigner = 6000,
+    InvalidMint = 6001,
+    InvalidTokenAccount = 6002,
+    InvalidPoolAccount = 6003,
+    InvalidPoolAddress = 6004,
+    ZeroAmount = 6005,
+    InvalidAccountDiscriminator = 6006,
+    UnsupportedAccountVersion = 6007,
+    InvalidFee = 6008,
+    SlippageExceeded = 6009,
+    InsufficientLiquidity = 6010,
+    InvalidAddress = 6011,
+}
+
+impl PoolError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidMint),
+            6002 => Some(Self::InvalidTokenAccount),
+            6003 => Some(Self::InvalidPoolAccount),
+            6004 => Some(Self::InvalidPoolAddress),
+            6005 => Some(Self::ZeroAmount),
+            6006 => Some(Self::InvalidAccountDiscriminator),
+            6007 => Some(Self::UnsupportedAccountVersion),
+            6008 => Some(Self::InvalidFee),
+            6009 => Some(Self::SlippageExceeded),
+            6010 => Some(Self::InsufficientLiquidity),
+            6011 => Some(Self::InvalidAddress),
+            _ => None,
+        }
+    }
+}
+
+impl From<PoolError> for ProgramError {
+    fn from(error: PoolError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl HelperError for PoolError {
+    const NOT_SIGNER: Self = Self::NotSigner;
+    const INVALID_MINT: Self = Self::InvalidMint;
+    const INVALID_TOKEN_ACCOUNT: Self = Self::InvalidTokenAccount;
+    const INVALID_ADDRESS: Self = Self::InvalidAddress;
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self = Self::InvalidAccountDiscriminator;
+    const UNSUPPORTED_ACCOUNT_VERSION: Self = Self::UnsupportedAccountVersion;
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Account must sign the transaction",
+            Self::InvalidMint => "Account is not a valid SPL Token or Token-2022 mint",
+            Self::InvalidTokenAccount => {
+                "Account is not a valid SPL Token or Token-2022 token account"
+            }
+            Self::InvalidPoolAccount => {
+                "Pool account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidPoolAddress => "Pool account does not match the pool PDA",
+            Self::ZeroAmount => "Amount must be greater than zero",
+            Self::InvalidAccountDiscriminator => "Pool account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Pool account layout version is not supported",
+            Self::InvalidFee => "Fee must be below 10000 basis points",
+            Self::SlippageExceeded => "Amount is past the caller's slippage bound",
+            Self::InsufficientLiquidity => "Pool has too little liquidity for this amount",
+            Self::InvalidAddress => "Associated token account does not match its expected address",
+        };
+
+        f.write_str(message)
+    }
+}
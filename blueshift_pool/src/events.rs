@@ -0,0 +1,190 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`PoolEvent::decode`].
+
+use core::mem::size_of;
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and made only of byte arrays, so that
+/// every bit pattern is valid and the struct has no padding.
+pub unsafe trait Event: Copy + Sized {
+    const DISCRIMINATOR: u8;
+    const LEN: usize = size_of::<Self>();
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    #[inline(always)]
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[self.as_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolInitialized {
+    pub header: EventHeader,
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub lp_mint: Pubkey,
+}
+
+unsafe impl Event for PoolInitialized {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl PoolInitialized {
+    pub fn new(
+        pool: Pubkey,
+        creator: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        lp_mint: Pubkey,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            pool,
+            creator,
+            mint_a,
+            mint_b,
+            lp_mint,
+        }
+    }
+}
+
+/// Emitted by both AddLiquidity and RemoveLiquidity, `deposit` telling them
+/// apart. Amounts are what the reserves received or sent.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidityChanged {
+    pub header: EventHeader,
+    pub pool: Pubkey,
+    pub provider: Pubkey,
+    pub amount_a: [u8; 8],
+    pub amount_b: [u8; 8],
+    pub lp: [u8; 8],
+    pub deposit: u8,
+}
+
+unsafe impl Event for LiquidityChanged {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl LiquidityChanged {
+    pub fn new(
+        pool: Pubkey,
+        provider: Pubkey,
+        amount_a: u64,
+        amount_b: u64,
+        lp: u64,
+        deposit: bool,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            pool,
+            provider,
+            amount_a: amount_a.to_le_bytes(),
+            amount_b: amount_b.to_le_bytes(),
+            lp: lp.to_le_bytes(),
+            deposit: deposit as u8,
+        }
+    }
+}
+
+/// `amount_in` is what the input reserve received, after any transfer fee.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Swapped {
+    pub header: EventHeader,
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub amount_in: [u8; 8],
+    pub amount_out: [u8; 8],
+    pub a_to_b: u8,
+}
+
+unsafe impl Event for Swapped {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl Swapped {
+    pub fn new(
+        pool: Pubkey,
+        trader: Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+        a_to_b: bool,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            pool,
+            trader,
+            amount_in: amount_in.to_le_bytes(),
+            amount_out: amount_out.to_le_bytes(),
+            a_to_b: a_to_b as u8,
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolEvent {
+    PoolInitialized(PoolInitialized),
+    LiquidityChanged(LiquidityChanged),
+    Swapped(Swapped),
+}
+
+impl PoolEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let [version, discriminator, ..] = data else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        if *version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match *discriminator {
+            PoolInitialized::DISCRIMINATOR => {
+                PoolInitialized::read(data).map(Self::PoolInitialized)
+            }
+            LiquidityChanged::DISCRIMINATOR => {
+                LiquidityChanged::read(data).map(Self::LiquidityChanged)
+            }
+            Swapped::DISCRIMINATOR => Swapped::read(data).map(Self::Swapped),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
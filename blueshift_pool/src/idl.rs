@@ -0,0 +1,40 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    AccountLayout, AddLiquidity, AddLiquidityAccounts, AddLiquidityInstructionData, InitializePool,
+    InitializePoolAccounts, InitializePoolInstructionData, Pool, PoolError, RemoveLiquidity,
+    RemoveLiquidityAccounts, RemoveLiquidityInstructionData, Swap, SwapAccounts,
+    SwapInstructionData,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("initialize_pool", *InitializePool::DISCRIMINATOR)
+            .accounts::<InitializePoolAccounts>()
+            .args::<InitializePoolInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("add_liquidity", *AddLiquidity::DISCRIMINATOR)
+            .accounts::<AddLiquidityAccounts>()
+            .args::<AddLiquidityInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("remove_liquidity", *RemoveLiquidity::DISCRIMINATOR)
+            .accounts::<RemoveLiquidityAccounts>()
+            .args::<RemoveLiquidityInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("swap", *Swap::DISCRIMINATOR)
+            .accounts::<SwapAccounts>()
+            .args::<SwapInstructionData>(),
+    )
+    .account::<Pool>(&[Pool::DISCRIMINATOR, Pool::VERSION])
+    .errors(PoolError::from_code)
+}
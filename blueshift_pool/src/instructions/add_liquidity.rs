@@ -0,0 +1,238 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountInit,
+    MintToInterface, Pool, PoolAccount, PoolError, TokenProgramInterface, TransferCheckedInterface,
+    events::{Event, LiquidityChanged},
+    mint_decimals, mint_supply, token_amount,
+};
+
+/// `provider_ata_lp` is created when it does not exist yet.
+#[derive(Accounts, IdlAccounts)]
+pub struct AddLiquidityAccounts<'a> {
+    #[account(signer @ PoolError::NotSigner)]
+    #[idl(writable)]
+    pub provider: &'a AccountInfo,
+    #[account(check = PoolAccount::check(pool))]
+    pub pool: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub reserve_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub reserve_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub lp_mint: &'a AccountInfo,
+    #[idl(writable)]
+    pub provider_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub provider_ata_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub provider_ata_lp: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    /// The token program the pool was initialized with.
+    #[account(check = TokenProgramInterface::check(token_program))]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct AddLiquidityInstructionData {
+    /// Most token A deposited.
+    pub max_a: u64,
+    /// Most token B deposited.
+    pub max_b: u64,
+    /// Fewest LP tokens accepted in return.
+    pub min_lp: u64,
+}
+
+impl AddLiquidityInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 3;
+}
+
+impl<'a> TryFrom<&'a [u8]> for AddLiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let max_a = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let max_b = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let min_lp = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+        // Instruction Checks
+        if max_a == 0 || max_b == 0 {
+            return Err(PoolError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            max_a,
+            max_b,
+            min_lp,
+        })
+    }
+}
+
+pub struct AddLiquidity<'a> {
+    pub accounts: AddLiquidityAccounts<'a>,
+    pub instruction_data: AddLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddLiquidity<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = AddLiquidityAccounts::try_from(accounts)?;
+        let instruction_data = AddLiquidityInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> AddLiquidity<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.pool.try_borrow_data()?;
+        let pool = Pool::load(&data)?;
+
+        // Check if the pool is valid
+        let pool_key = create_program_address(
+            &[b"pool", &pool.creator, &pool.seed, &pool.bump],
+            &crate::ID,
+        )?;
+        if &pool_key != self.accounts.pool.key() {
+            return Err(PoolError::InvalidPoolAddress.into());
+        }
+        if pool.mint_a.ne(self.accounts.mint_a.key())
+            || pool.mint_b.ne(self.accounts.mint_b.key())
+            || pool.lp_mint.ne(self.accounts.lp_mint.key())
+        {
+            return Err(PoolError::InvalidMint.into());
+        }
+        if pool.reserve_a.ne(self.accounts.reserve_a.key())
+            || pool.reserve_b.ne(self.accounts.reserve_b.key())
+        {
+            return Err(PoolError::InvalidTokenAccount.into());
+        }
+        if !self
+            .accounts
+            .reserve_a
+            .is_owned_by(self.accounts.token_program.key())
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let reserve_a = token_amount(self.accounts.reserve_a)?;
+        let reserve_b = token_amount(self.accounts.reserve_b)?;
+        let lp_supply = mint_supply(self.accounts.lp_mint)?;
+        let (_, amount_a, amount_b) = Pool::deposit(
+            reserve_a,
+            reserve_b,
+            lp_supply,
+            self.instruction_data.max_a,
+            self.instruction_data.max_b,
+        )
+        .ok_or(PoolError::InsufficientLiquidity)?;
+
+        let creator_binding = pool.creator;
+        let seed_binding = pool.seed;
+        let bump_binding = pool.bump;
+        let pool_seeds = [
+            Seed::from(b"pool"),
+            Seed::from(creator_binding.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&pool_seeds);
+        drop(data);
+
+        if self.accounts.provider_ata_lp.data_is_empty() {
+            AssociatedTokenAccount::init(
+                self.accounts.provider_ata_lp,
+                self.accounts.lp_mint,
+                self.accounts.provider,
+                self.accounts.provider,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+        }
+
+        // Transfer from the Provider to the Reserves
+        TransferCheckedInterface {
+            from: self.accounts.provider_ata_a,
+            mint: self.accounts.mint_a,
+            to: self.accounts.reserve_a,
+            authority: self.accounts.provider,
+            amount: amount_a,
+            decimals: mint_decimals(self.accounts.mint_a)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke()?;
+        TransferCheckedInterface {
+            from: self.accounts.provider_ata_b,
+            mint: self.accounts.mint_b,
+            to: self.accounts.reserve_b,
+            authority: self.accounts.provider,
+            amount: amount_b,
+            decimals: mint_decimals(self.accounts.mint_b)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke()?;
+
+        // A transfer fee is withheld from what the reserves receive, so the
+        // share is priced on what actually arrived
+        let received_a = token_amount(self.accounts.reserve_a)?
+            .checked_sub(reserve_a)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let received_b = token_amount(self.accounts.reserve_b)?
+            .checked_sub(reserve_b)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let (lp, _, _) = Pool::deposit(reserve_a, reserve_b, lp_supply, received_a, received_b)
+            .ok_or(PoolError::InsufficientLiquidity)?;
+        if lp == 0 {
+            return Err(PoolError::InsufficientLiquidity.into());
+        }
+        if lp < self.instruction_data.min_lp {
+            return Err(PoolError::SlippageExceeded.into());
+        }
+
+        // Mint the Provider's share
+        MintToInterface {
+            mint: self.accounts.lp_mint,
+            account: self.accounts.provider_ata_lp,
+            mint_authority: self.accounts.pool,
+            amount: lp,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        LiquidityChanged::new(
+            *self.accounts.pool.key(),
+            *self.accounts.provider.key(),
+            received_a,
+            received_b,
+            lp,
+            true,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
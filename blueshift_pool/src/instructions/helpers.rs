@@ -0,0 +1,15 @@
+pub use program_helpers::{
+    AccountCheck, AccountLayout, AssociatedTokenAccountInit, ProgramAccountInit,
+    TOKEN_2022_MINT_DISCRIMINATOR, TOKEN_2022_PROGRAM_ID, TokenProgramInterface,
+    token_interface::*,
+};
+
+use crate::{Pool, PoolError};
+
+pub type SignerAccount = program_helpers::SignerAccount<PoolError>;
+pub type MintInterface = program_helpers::MintInterface<PoolError>;
+pub type TokenAccountInterface = program_helpers::TokenAccountInterface<PoolError>;
+pub type AssociatedTokenAccount = program_helpers::AssociatedTokenAccount<PoolError>;
+
+pub type ProgramAccount = program_helpers::ProgramAccount<Pool>;
+pub type PoolAccount = program_helpers::StateAccount<Pool>;
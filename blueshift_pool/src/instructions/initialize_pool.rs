@@ -0,0 +1,186 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, MintInterface, Pool, PoolError, ProgramAccount,
+    ProgramAccountInit, TokenAccountInterface, TokenProgramInterface,
+    events::{Event, PoolInitialized},
+};
+
+/// `reserve_a`, `reserve_b` and `lp_mint` are fresh keypairs, created here
+/// under `token_program` with the pool as their authority. The reserves are
+/// sized for the extensions their mint requires, such as a transfer fee.
+#[derive(Accounts, IdlAccounts)]
+pub struct InitializePoolAccounts<'a> {
+    #[account(signer @ PoolError::NotSigner)]
+    #[idl(writable)]
+    pub creator: &'a AccountInfo,
+    #[idl(writable)]
+    pub pool: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint_a))]
+    pub mint_a: &'a AccountInfo,
+    #[account(
+        check = MintInterface::check(mint_b),
+        constraint = mint_a.key().ne(mint_b.key())
+            && mint_a.is_owned_by(token_program.key())
+            && mint_b.is_owned_by(token_program.key())
+            @ PoolError::InvalidMint,
+    )]
+    pub mint_b: &'a AccountInfo,
+    #[account(signer @ PoolError::NotSigner)]
+    #[idl(writable)]
+    pub reserve_a: &'a AccountInfo,
+    #[account(signer @ PoolError::NotSigner)]
+    #[idl(writable)]
+    pub reserve_b: &'a AccountInfo,
+    #[account(signer @ PoolError::NotSigner)]
+    #[idl(writable)]
+    pub lp_mint: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    /// SPL Token or Token-2022, owning both mints.
+    #[account(check = TokenProgramInterface::check(token_program))]
+    pub token_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct InitializePoolInstructionData {
+    pub seed: u64,
+    /// Swap fee left in the pool, in basis points below `Pool::MAX_FEE_BPS`.
+    pub fee_bps: u16,
+}
+
+impl InitializePoolInstructionData {
+    pub const LEN: usize = size_of::<u64>() + size_of::<u16>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for InitializePoolInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let fee_bps = u16::from_le_bytes(data[8..10].try_into().unwrap());
+
+        // Instruction Checks
+        if fee_bps >= Pool::MAX_FEE_BPS {
+            return Err(PoolError::InvalidFee.into());
+        }
+
+        Ok(Self { seed, fee_bps })
+    }
+}
+
+pub struct InitializePool<'a> {
+    pub accounts: InitializePoolAccounts<'a>,
+    pub instruction_data: InitializePoolInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for InitializePool<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = InitializePoolAccounts::try_from(accounts)?;
+        let instruction_data = InitializePoolInstructionData::try_from(data)?;
+
+        // Initialize the Accounts needed
+        let (pool_key, bump) = find_program_address(
+            &[
+                b"pool",
+                accounts.creator.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &pool_key != accounts.pool.key() {
+            return Err(PoolError::InvalidPoolAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let pool_seeds = [
+            Seed::from(b"pool"),
+            Seed::from(accounts.creator.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<Pool>(accounts.creator, accounts.pool, &pool_seeds, Pool::LEN)?;
+
+        let token_program = accounts.token_program.key();
+        TokenAccountInterface::init(
+            accounts.reserve_a,
+            accounts.mint_a,
+            accounts.creator,
+            accounts.pool.key(),
+            token_program,
+        )?;
+        TokenAccountInterface::init(
+            accounts.reserve_b,
+            accounts.mint_b,
+            accounts.creator,
+            accounts.pool.key(),
+            token_program,
+        )?;
+        MintInterface::init(
+            accounts.lp_mint,
+            accounts.creator,
+            Pool::LP_DECIMALS,
+            accounts.pool.key(),
+            token_program,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> InitializePool<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the pool account
+        let mut data = self.accounts.pool.try_borrow_mut_data()?;
+        let pool = Pool::init(data.as_mut())?;
+
+        pool.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.creator.key(),
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            [self.bump],
+        );
+        pool.set_accounts(
+            *self.accounts.reserve_a.key(),
+            *self.accounts.reserve_b.key(),
+            *self.accounts.lp_mint.key(),
+        );
+        pool.set_fee_bps(self.instruction_data.fee_bps);
+
+        PoolInitialized::new(
+            *self.accounts.pool.key(),
+            *self.accounts.creator.key(),
+            *self.accounts.mint_a.key(),
+            *self.accounts.mint_b.key(),
+            *self.accounts.lp_mint.key(),
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+pub mod add_liquidity;
+pub mod helpers;
+pub mod initialize_pool;
+pub mod remove_liquidity;
+pub mod swap;
+
+pub use add_liquidity::*;
+pub use helpers::*;
+pub use initialize_pool::*;
+pub use remove_liquidity::*;
+pub use swap::*;
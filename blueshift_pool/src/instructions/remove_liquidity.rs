@@ -0,0 +1,202 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, BurnInterface, Pool, PoolAccount, PoolError,
+    TokenProgramInterface, TransferCheckedInterface,
+    events::{Event, LiquidityChanged},
+    mint_decimals, mint_supply, token_amount,
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct RemoveLiquidityAccounts<'a> {
+    #[account(signer @ PoolError::NotSigner)]
+    #[idl(writable)]
+    pub provider: &'a AccountInfo,
+    #[account(check = PoolAccount::check(pool))]
+    pub pool: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub reserve_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub reserve_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub lp_mint: &'a AccountInfo,
+    #[idl(writable)]
+    pub provider_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub provider_ata_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub provider_ata_lp: &'a AccountInfo,
+    /// The token program the pool was initialized with.
+    #[account(check = TokenProgramInterface::check(token_program))]
+    pub token_program: &'a AccountInfo,
+}
+
+/// `min_a` and `min_b` bound what the reserves send. A transfer fee on either
+/// mint is withheld from what the provider receives.
+#[derive(IdlType)]
+pub struct RemoveLiquidityInstructionData {
+    /// LP tokens burned.
+    pub lp: u64,
+    /// Fewest token A accepted in return.
+    pub min_a: u64,
+    /// Fewest token B accepted in return.
+    pub min_b: u64,
+}
+
+impl RemoveLiquidityInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 3;
+}
+
+impl<'a> TryFrom<&'a [u8]> for RemoveLiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lp = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let min_a = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let min_b = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+        // Instruction Checks
+        if lp == 0 {
+            return Err(PoolError::ZeroAmount.into());
+        }
+
+        Ok(Self { lp, min_a, min_b })
+    }
+}
+
+pub struct RemoveLiquidity<'a> {
+    pub accounts: RemoveLiquidityAccounts<'a>,
+    pub instruction_data: RemoveLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for RemoveLiquidity<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = RemoveLiquidityAccounts::try_from(accounts)?;
+        let instruction_data = RemoveLiquidityInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RemoveLiquidity<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.pool.try_borrow_data()?;
+        let pool = Pool::load(&data)?;
+
+        // Check if the pool is valid
+        let pool_key = create_program_address(
+            &[b"pool", &pool.creator, &pool.seed, &pool.bump],
+            &crate::ID,
+        )?;
+        if &pool_key != self.accounts.pool.key() {
+            return Err(PoolError::InvalidPoolAddress.into());
+        }
+        if pool.mint_a.ne(self.accounts.mint_a.key())
+            || pool.mint_b.ne(self.accounts.mint_b.key())
+            || pool.lp_mint.ne(self.accounts.lp_mint.key())
+        {
+            return Err(PoolError::InvalidMint.into());
+        }
+        if pool.reserve_a.ne(self.accounts.reserve_a.key())
+            || pool.reserve_b.ne(self.accounts.reserve_b.key())
+        {
+            return Err(PoolError::InvalidTokenAccount.into());
+        }
+        if !self
+            .accounts
+            .reserve_a
+            .is_owned_by(self.accounts.token_program.key())
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (amount_a, amount_b) = Pool::withdraw(
+            token_amount(self.accounts.reserve_a)?,
+            token_amount(self.accounts.reserve_b)?,
+            mint_supply(self.accounts.lp_mint)?,
+            self.instruction_data.lp,
+        )
+        .ok_or(PoolError::InsufficientLiquidity)?;
+        if amount_a < self.instruction_data.min_a || amount_b < self.instruction_data.min_b {
+            return Err(PoolError::SlippageExceeded.into());
+        }
+
+        let creator_binding = pool.creator;
+        let seed_binding = pool.seed;
+        let bump_binding = pool.bump;
+        let pool_seeds = [
+            Seed::from(b"pool"),
+            Seed::from(creator_binding.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&pool_seeds);
+        drop(data);
+
+        // Burn the Provider's share
+        BurnInterface {
+            account: self.accounts.provider_ata_lp,
+            mint: self.accounts.lp_mint,
+            authority: self.accounts.provider,
+            amount: self.instruction_data.lp,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke()?;
+
+        // Transfer from the Reserves to the Provider
+        TransferCheckedInterface {
+            from: self.accounts.reserve_a,
+            mint: self.accounts.mint_a,
+            to: self.accounts.provider_ata_a,
+            authority: self.accounts.pool,
+            amount: amount_a,
+            decimals: mint_decimals(self.accounts.mint_a)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+        TransferCheckedInterface {
+            from: self.accounts.reserve_b,
+            mint: self.accounts.mint_b,
+            to: self.accounts.provider_ata_b,
+            authority: self.accounts.pool,
+            amount: amount_b,
+            decimals: mint_decimals(self.accounts.mint_b)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        LiquidityChanged::new(
+            *self.accounts.pool.key(),
+            *self.accounts.provider.key(),
+            amount_a,
+            amount_b,
+            self.instruction_data.lp,
+            false,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, Pool, PoolAccount, PoolError, TokenAccountInterface,
+    TokenProgramInterface, TransferCheckedInterface,
+    events::{Event, Swapped},
+    mint_decimals, token_amount,
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct SwapAccounts<'a> {
+    #[account(signer @ PoolError::NotSigner)]
+    pub trader: &'a AccountInfo,
+    #[account(check = PoolAccount::check(pool))]
+    pub pool: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub reserve_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub reserve_b: &'a AccountInfo,
+    #[idl(writable)]
+    pub trader_ata_a: &'a AccountInfo,
+    #[idl(writable)]
+    pub trader_ata_b: &'a AccountInfo,
+    /// The token program the pool was initialized with.
+    #[account(check = TokenProgramInterface::check(token_program))]
+    pub token_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct SwapInstructionData {
+    /// Amount of the input token paid in, swap and transfer fees included.
+    pub amount_in: u64,
+    /// Fewest output tokens the trader receives, after any transfer fee.
+    pub min_out: u64,
+    /// `1` pays token A for token B, `0` pays token B for token A.
+    pub a_to_b: u8,
+}
+
+impl SwapInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2 + size_of::<u8>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for SwapInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount_in = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let min_out = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let a_to_b = data[16];
+
+        // Instruction Checks
+        if amount_in == 0 {
+            return Err(PoolError::ZeroAmount.into());
+        }
+        if a_to_b > 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            amount_in,
+            min_out,
+            a_to_b,
+        })
+    }
+}
+
+pub struct Swap<'a> {
+    pub accounts: SwapAccounts<'a>,
+    pub instruction_data: SwapInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Swap<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SwapAccounts::try_from(accounts)?;
+        let instruction_data = SwapInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Swap<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.pool.try_borrow_data()?;
+        let pool = Pool::load(&data)?;
+
+        // Check if the pool is valid
+        let pool_key = create_program_address(
+            &[b"pool", &pool.creator, &pool.seed, &pool.bump],
+            &crate::ID,
+        )?;
+        if &pool_key != self.accounts.pool.key() {
+            return Err(PoolError::InvalidPoolAddress.into());
+        }
+        if pool.mint_a.ne(self.accounts.mint_a.key()) || pool.mint_b.ne(self.accounts.mint_b.key())
+        {
+            return Err(PoolError::InvalidMint.into());
+        }
+        if pool.reserve_a.ne(self.accounts.reserve_a.key())
+            || pool.reserve_b.ne(self.accounts.reserve_b.key())
+        {
+            return Err(PoolError::InvalidTokenAccount.into());
+        }
+        if !self
+            .accounts
+            .reserve_a
+            .is_owned_by(self.accounts.token_program.key())
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let a_to_b = self.instruction_data.a_to_b == 1;
+        let (mint_in, mint_out, reserve_in, reserve_out, trader_in, trader_out) = if a_to_b {
+            (
+                self.accounts.mint_a,
+                self.accounts.mint_b,
+                self.accounts.reserve_a,
+                self.accounts.reserve_b,
+                self.accounts.trader_ata_a,
+                self.accounts.trader_ata_b,
+            )
+        } else {
+            (
+                self.accounts.mint_b,
+                self.accounts.mint_a,
+                self.accounts.reserve_b,
+                self.accounts.reserve_a,
+                self.accounts.trader_ata_b,
+                self.accounts.trader_ata_a,
+            )
+        };
+
+        let creator_binding = pool.creator;
+        let seed_binding = pool.seed;
+        let bump_binding = pool.bump;
+        let pool_seeds = [
+            Seed::from(b"pool"),
+            Seed::from(creator_binding.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&pool_seeds);
+
+        // Transfer from the Trader to the Pool
+        let balance_in = token_amount(reserve_in)?;
+        let balance_out = token_amount(reserve_out)?;
+        TransferCheckedInterface {
+            from: trader_in,
+            mint: mint_in,
+            to: reserve_in,
+            authority: self.accounts.trader,
+            amount: self.instruction_data.amount_in,
+            decimals: mint_decimals(mint_in)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke()?;
+
+        // A transfer fee is withheld from what the reserve receives, so the
+        // swap is priced on what actually arrived
+        let amount_in = token_amount(reserve_in)?
+            .checked_sub(balance_in)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let amount_out = pool
+            .swap_out(balance_in, balance_out, amount_in)
+            .ok_or(PoolError::InsufficientLiquidity)?;
+        if amount_out == 0 {
+            return Err(PoolError::InsufficientLiquidity.into());
+        }
+        drop(data);
+
+        // Transfer back out of the other side. The trader's account is read
+        // before the token program sees it, so it is checked first
+        TokenAccountInterface::check(trader_out)?;
+        let balance_trader = token_amount(trader_out)?;
+        TransferCheckedInterface {
+            from: reserve_out,
+            mint: mint_out,
+            to: trader_out,
+            authority: self.accounts.pool,
+            amount: amount_out,
+            decimals: mint_decimals(mint_out)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        // The fee is withheld on the way out too, so the slippage bound is
+        // checked on what the trader received
+        let received = token_amount(trader_out)?
+            .checked_sub(balance_trader)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if received < self.instruction_data.min_out {
+            return Err(PoolError::SlippageExceeded.into());
+        }
+
+        Swapped::new(
+            *self.accounts.pool.key(),
+            *self.accounts.trader.key(),
+            amount_in,
+            amount_out,
+            a_to_b,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
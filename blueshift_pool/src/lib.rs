@@ -0,0 +1,46 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, entrypoint, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+pub mod errors;
+pub use errors::*;
+
+pub mod events;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
+// 33333333333333333333333333333333333333333333
+pub const ID: Pubkey = [
+    0x1e, 0x3c, 0xd6, 0x28, 0x43, 0x80, 0x94, 0x0e, 0x08, 0x62, 0x4c, 0xb8, 0x33, 0x8b, 0x77, 0xdc,
+    0x33, 0x25, 0x75, 0xd1, 0x5f, 0xa3, 0x9a, 0x0f, 0x1d, 0xf1, 0x5e, 0xe0, 0x8f, 0xb8, 0x23, 0xee,
+];
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.split_first() {
+        Some((InitializePool::DISCRIMINATOR, data)) => {
+            InitializePool::try_from((data, accounts))?.process()
+        }
+        Some((AddLiquidity::DISCRIMINATOR, data)) => {
+            AddLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((RemoveLiquidity::DISCRIMINATOR, data)) => {
+            RemoveLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((Swap::DISCRIMINATOR, data)) => Swap::try_from((data, accounts))?.process(),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
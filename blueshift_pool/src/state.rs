@@ -0,0 +1,173 @@
+use core::mem::size_of;
+use pinocchio::pubkey::Pubkey;
+use idl_derive::IdlType;
+
+use crate::{AccountLayout, PoolError};
+
+/// A constant-product pool between `mint_a` and `mint_b`. Both reserves and
+/// the LP mint are owned by the pool's PDA and run on the same token program.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct Pool {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `Pool::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `Pool::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub creator: Pubkey,   // Account that initialized the pool
+    #[idl(ty = "pubkey")]
+    pub mint_a: Pubkey,    // First token of the pair
+    #[idl(ty = "pubkey")]
+    pub mint_b: Pubkey,    // Second token of the pair
+    #[idl(ty = "pubkey")]
+    pub reserve_a: Pubkey, // Token account holding the pool's token A
+    #[idl(ty = "pubkey")]
+    pub reserve_b: Pubkey, // Token account holding the pool's token B
+    #[idl(ty = "pubkey")]
+    pub lp_mint: Pubkey,   // Mint of the pool's liquidity shares
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u16")]
+    pub fee_bps: [u8; 2],  // Swap fee left in the pool, in basis points
+}
+
+impl AccountLayout for Pool {
+    type Error = PoolError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 2]>();
+
+    const DISCRIMINATOR: u8 = 1;
+    const VERSION: u8 = 1;
+    const INVALID: PoolError = PoolError::InvalidPoolAccount;
+}
+
+impl Pool {
+    /// Fees are out of this many basis points, and must stay below it.
+    pub const MAX_FEE_BPS: u16 = 10_000;
+
+    /// Decimals of every pool's LP mint.
+    pub const LP_DECIMALS: u8 = 6;
+
+    #[inline(always)]
+    pub fn fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.fee_bps)
+    }
+
+    /// Token out of a swap of `amount_in` against the given reserves. The fee
+    /// is taken from `amount_in`, rounded in the pool's favour, and stays in
+    /// the pool so `reserve_in * reserve_out` never decreases.
+    pub fn swap_out(&self, reserve_in: u64, reserve_out: u64, amount_in: u64) -> Option<u64> {
+        let max_fee_bps = Self::MAX_FEE_BPS as u128;
+        let amount_in = (amount_in as u128)
+            .checked_mul(max_fee_bps - self.fee_bps() as u128)?
+            / max_fee_bps;
+        let amount_out = (reserve_out as u128)
+            .checked_mul(amount_in)?
+            .checked_div((reserve_in as u128).checked_add(amount_in)?)?;
+
+        u64::try_from(amount_out).ok()
+    }
+
+    /// LP minted for a deposit of at most `max_a` and `max_b`, and the token A
+    /// and B actually taken. The first deposit sets the price and mints the
+    /// geometric mean, later ones follow the reserve ratio and round the
+    /// deposit up and the LP down.
+    pub fn deposit(
+        reserve_a: u64,
+        reserve_b: u64,
+        lp_supply: u64,
+        max_a: u64,
+        max_b: u64,
+    ) -> Option<(u64, u64, u64)> {
+        if lp_supply == 0 {
+            let lp = isqrt((max_a as u128).checked_mul(max_b as u128)?);
+            return Some((u64::try_from(lp).ok()?, max_a, max_b));
+        }
+        if reserve_a == 0 || reserve_b == 0 {
+            return None;
+        }
+
+        let supply = lp_supply as u128;
+        let lp = (max_a as u128 * supply / reserve_a as u128)
+            .min(max_b as u128 * supply / reserve_b as u128);
+        let amount_a = (lp * reserve_a as u128).div_ceil(supply);
+        let amount_b = (lp * reserve_b as u128).div_ceil(supply);
+
+        Some((
+            u64::try_from(lp).ok()?,
+            u64::try_from(amount_a).ok()?,
+            u64::try_from(amount_b).ok()?,
+        ))
+    }
+
+    /// Token A and B paid out for burning `lp`, both rounded down.
+    pub fn withdraw(reserve_a: u64, reserve_b: u64, lp_supply: u64, lp: u64) -> Option<(u64, u64)> {
+        if lp_supply == 0 || lp > lp_supply {
+            return None;
+        }
+
+        let supply = lp_supply as u128;
+        let amount_a = lp as u128 * reserve_a as u128 / supply;
+        let amount_b = lp as u128 * reserve_b as u128 / supply;
+
+        // Both fit, since `lp` is at most `lp_supply`
+        Some((amount_a as u64, amount_b as u64))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        creator: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        bump: [u8; 1],
+    ) {
+        self.seed = seed.to_le_bytes();
+        self.creator = creator;
+        self.mint_a = mint_a;
+        self.mint_b = mint_b;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_accounts(&mut self, reserve_a: Pubkey, reserve_b: Pubkey, lp_mint: Pubkey) {
+        self.reserve_a = reserve_a;
+        self.reserve_b = reserve_b;
+        self.lp_mint = lp_mint;
+    }
+
+    #[inline(always)]
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.fee_bps = fee_bps.to_le_bytes();
+    }
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method from above converges on the floor
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
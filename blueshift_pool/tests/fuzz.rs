@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio_pool::{
+        AccountLayout, AddLiquidityInstructionData, InitializePoolInstructionData, Pool, PoolError,
+        RemoveLiquidityInstructionData, SwapInstructionData, ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        fuzz::{check_invariants, check_rejected, fuzz, Fuzzer},
+        token_program, MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 4;
+
+    fn fuzzer() -> Fuzzer {
+        let creator = Pubkey::new_unique();
+        let pool = Pubkey::find_program_address(
+            &[b"pool", creator.as_ref(), &42u64.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0;
+        let (token_program, token_account) = token_program();
+
+        let mut fuzzer = Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                creator,
+                pool,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .fixed(token_program, token_account)
+        .data_lens(&[Pool::LEN, MINT_LEN, TOKEN_ACCOUNT_LEN])
+        .max_accounts(13);
+        fuzzer.owners.push(token_program);
+        fuzzer
+    }
+
+    fn mollusk() -> Mollusk {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_pool");
+        test_support::add_token_programs(&mut mollusk);
+        mollusk
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_swap_data(data in vec(any::<u8>(), 0..=SwapInstructionData::LEN + 8)) {
+            let parsed = SwapInstructionData::try_from(data.as_slice());
+
+            if data.len() != SwapInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let amount_in = u64::from_le_bytes(data[0..8].try_into().unwrap());
+            if amount_in == 0 {
+                prop_assert_eq!(parsed.err(), Some(PoolError::ZeroAmount.into()));
+                return Ok(());
+            }
+            if data[16] > 1 {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(parsed.amount_in, amount_in);
+            prop_assert_eq!(parsed.min_out, u64::from_le_bytes(data[8..16].try_into().unwrap()));
+            prop_assert_eq!(parsed.a_to_b, data[16]);
+        }
+
+        #[test]
+        fn test_parse_pool_header(data in vec(any::<u8>(), Pool::LEN - 1..=Pool::LEN + 1), tagged in any::<bool>()) {
+            let mut data = data;
+            if tagged {
+                data[0] = Pool::DISCRIMINATOR;
+                data[1] = Pool::VERSION;
+            }
+
+            let valid = data.len() == Pool::LEN
+                && data[0] == Pool::DISCRIMINATOR
+                && data[1] == Pool::VERSION;
+            prop_assert_eq!(Pool::load(&data).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = mollusk();
+        let data = (0..INSTRUCTION_COUNT, vec(any::<u8>(), 0..=32))
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_initialize_pool_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(0, InitializePoolInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_add_liquidity_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(1, AddLiquidityInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_remove_liquidity_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(2, RemoveLiquidityInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_swap_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(3, SwapInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
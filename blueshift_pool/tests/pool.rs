@@ -0,0 +1,557 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_pool::{
+        AccountLayout, AddLiquidity, InitializePool, Pool, PoolError, RemoveLiquidity, Swap, ID,
+    };
+    use proptest::prelude::*;
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_program, token_2022_program, token_program, AtaBuilder, MintBuilder,
+        TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 13;
+    const FUNDS: u64 = 1_000_000;
+    // 0.3%
+    const FEE_BPS: u16 = 30;
+    // 1%, charged by Token-2022 on every transfer of a transfer-fee mint
+    const TRANSFER_FEE_BPS: u16 = 100;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Alice holding FUNDS of both mints under one token program, with no pool
+    // initialized yet. Mint A may carry a Token-2022 transfer fee.
+    struct Fixture {
+        context: Context,
+        token_program: Pubkey,
+        alice: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        pool: Pubkey,
+        reserve_a: Pubkey,
+        reserve_b: Pubkey,
+        lp_mint: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_token_program(token_program().0)
+        }
+
+        fn with_token_program(token_program: Pubkey) -> Self {
+            Self::with_mints(token_program, None)
+        }
+
+        fn with_transfer_fee() -> Self {
+            Self::with_mints(token_2022_program().0, Some(TRANSFER_FEE_BPS))
+        }
+
+        fn with_mints(token_program: Pubkey, transfer_fee_a: Option<u16>) -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_pool");
+            add_token_programs(&mut mollusk);
+
+            let (alice, alice_account) = funded_payer();
+            let mint_a = Pubkey::new_unique();
+            let mint_b = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(alice, alice_account);
+            for (mint, transfer_fee) in [(mint_a, transfer_fee_a), (mint_b, None)] {
+                let mut mint_builder = MintBuilder::new(&Pubkey::new_unique())
+                    .supply(FUNDS)
+                    .token_program(&token_program);
+                let mut ata_builder = AtaBuilder::new(&alice, &mint)
+                    .amount(FUNDS)
+                    .token_program(&token_program);
+                if let Some(fee_bps) = transfer_fee {
+                    mint_builder = mint_builder.transfer_fee(fee_bps, u64::MAX);
+                    ata_builder = ata_builder.transfer_fee_amount();
+                }
+                store.insert(mint, mint_builder.build(&mollusk));
+                let (ata, account) = ata_builder.build(&mollusk);
+                store.insert(ata, account);
+            }
+
+            Self {
+                context: mollusk.with_context(store),
+                token_program,
+                alice,
+                mint_a,
+                mint_b,
+                pool: pool_address(&alice, SEED),
+                reserve_a: Pubkey::new_unique(),
+                reserve_b: Pubkey::new_unique(),
+                lp_mint: Pubkey::new_unique(),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &self.token_program)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn lp_supply(&self) -> u64 {
+            let data = self.account(&self.lp_mint).data;
+            u64::from_le_bytes(data[36..44].try_into().unwrap())
+        }
+
+        // Product of the reserves, which no instruction but a withdrawal may
+        // lower
+        fn k(&self) -> u128 {
+            self.token_amount(&self.reserve_a) as u128 * self.token_amount(&self.reserve_b) as u128
+        }
+
+        fn initialize(&self, fee_bps: u16) -> Instruction {
+            let mut data = vec![*InitializePool::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&fee_bps.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(self.alice, true),
+                    AccountMeta::new(self.pool, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new_readonly(self.mint_b, false),
+                    AccountMeta::new(self.reserve_a, true),
+                    AccountMeta::new(self.reserve_b, true),
+                    AccountMeta::new(self.lp_mint, true),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                ],
+            )
+        }
+
+        fn add_liquidity(&self, max_a: u64, max_b: u64, min_lp: u64) -> Instruction {
+            let mut data = vec![*AddLiquidity::DISCRIMINATOR];
+            data.extend_from_slice(&max_a.to_le_bytes());
+            data.extend_from_slice(&max_b.to_le_bytes());
+            data.extend_from_slice(&min_lp.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(self.alice, true),
+                    AccountMeta::new_readonly(self.pool, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new_readonly(self.mint_b, false),
+                    AccountMeta::new(self.reserve_a, false),
+                    AccountMeta::new(self.reserve_b, false),
+                    AccountMeta::new(self.lp_mint, false),
+                    AccountMeta::new(self.ata(&self.alice, &self.mint_a), false),
+                    AccountMeta::new(self.ata(&self.alice, &self.mint_b), false),
+                    AccountMeta::new(self.ata(&self.alice, &self.lp_mint), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn remove_liquidity(&self, lp: u64, min_a: u64, min_b: u64) -> Instruction {
+            let mut data = vec![*RemoveLiquidity::DISCRIMINATOR];
+            data.extend_from_slice(&lp.to_le_bytes());
+            data.extend_from_slice(&min_a.to_le_bytes());
+            data.extend_from_slice(&min_b.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(self.alice, true),
+                    AccountMeta::new_readonly(self.pool, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new_readonly(self.mint_b, false),
+                    AccountMeta::new(self.reserve_a, false),
+                    AccountMeta::new(self.reserve_b, false),
+                    AccountMeta::new(self.lp_mint, false),
+                    AccountMeta::new(self.ata(&self.alice, &self.mint_a), false),
+                    AccountMeta::new(self.ata(&self.alice, &self.mint_b), false),
+                    AccountMeta::new(self.ata(&self.alice, &self.lp_mint), false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                ],
+            )
+        }
+
+        fn swap(&self, amount_in: u64, min_out: u64, a_to_b: bool) -> Instruction {
+            let mut data = vec![*Swap::DISCRIMINATOR];
+            data.extend_from_slice(&amount_in.to_le_bytes());
+            data.extend_from_slice(&min_out.to_le_bytes());
+            data.push(a_to_b as u8);
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new_readonly(self.alice, true),
+                    AccountMeta::new_readonly(self.pool, false),
+                    AccountMeta::new_readonly(self.mint_a, false),
+                    AccountMeta::new_readonly(self.mint_b, false),
+                    AccountMeta::new(self.reserve_a, false),
+                    AccountMeta::new(self.reserve_b, false),
+                    AccountMeta::new(self.ata(&self.alice, &self.mint_a), false),
+                    AccountMeta::new(self.ata(&self.alice, &self.mint_b), false),
+                    AccountMeta::new_readonly(self.token_program, false),
+                ],
+            )
+        }
+
+        // Initialize the pool with FEE_BPS and seed it with 10_000 A and
+        // 40_000 B, as the setup of the others
+        fn seeded(self) -> Self {
+            for instruction in [
+                self.initialize(FEE_BPS),
+                self.add_liquidity(10_000, 40_000, 0),
+            ] {
+                self.context
+                    .process_and_validate_instruction(&instruction, &[Check::success()]);
+            }
+            self
+        }
+    }
+
+    fn pool_address(creator: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"pool", creator.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: PoolError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    fn pool_state(fee_bps: u16) -> Vec<u8> {
+        let mut data = vec![0u8; Pool::LEN];
+        Pool::init(&mut data).unwrap().set_fee_bps(fee_bps);
+        data
+    }
+
+    proptest! {
+        #[test]
+        fn test_swap_never_decreases_k(
+            reserve_in in 1..1_000_000_000u64,
+            reserve_out in 1..1_000_000_000u64,
+            amount_in in 1..1_000_000_000u64,
+            fee_bps in 0..Pool::MAX_FEE_BPS,
+        ) {
+            let data = pool_state(fee_bps);
+            let pool = Pool::load(&data).unwrap();
+
+            let amount_out = pool.swap_out(reserve_in, reserve_out, amount_in).unwrap();
+            prop_assert!(amount_out < reserve_out);
+            let k = reserve_in as u128 * reserve_out as u128;
+            let new_k = (reserve_in + amount_in) as u128 * (reserve_out - amount_out) as u128;
+            prop_assert!(new_k >= k);
+        }
+
+        #[test]
+        fn test_liquidity_never_decreases_k_per_share(
+            reserve_a in 1..1_000_000u64,
+            reserve_b in 1..1_000_000u64,
+            lp_supply in 1..1_000_000u64,
+            max_a in 1..1_000_000u64,
+            max_b in 1..1_000_000u64,
+        ) {
+            let (lp, amount_a, amount_b) =
+                Pool::deposit(reserve_a, reserve_b, lp_supply, max_a, max_b).unwrap();
+            prop_assert!(amount_a <= max_a && amount_b <= max_b);
+            let deposited = (reserve_a + amount_a, reserve_b + amount_b, lp_supply + lp);
+            prop_assert!(k_per_share_kept((reserve_a, reserve_b, lp_supply), deposited));
+
+            let (out_a, out_b) = Pool::withdraw(deposited.0, deposited.1, deposited.2, lp).unwrap();
+            prop_assert!(out_a <= amount_a && out_b <= amount_b);
+            let withdrawn = (deposited.0 - out_a, deposited.1 - out_b, lp_supply);
+            prop_assert!(k_per_share_kept(deposited, withdrawn));
+        }
+    }
+
+    // k / supply², what each LP token is worth squared, is no lower `after`
+    fn k_per_share_kept(before: (u64, u64, u64), after: (u64, u64, u64)) -> bool {
+        let k = |(a, b, _): (u64, u64, u64)| a as u128 * b as u128;
+        let supply = |(_, _, supply): (u64, u64, u64)| supply as u128;
+        k(after) * supply(before) * supply(before) >= k(before) * supply(after) * supply(after)
+    }
+
+    #[test]
+    fn test_first_deposit_mints_geometric_mean() {
+        assert_eq!(
+            Pool::deposit(0, 0, 0, 10_000, 40_000),
+            Some((20_000, 10_000, 40_000))
+        );
+        // Rounded down
+        assert_eq!(Pool::deposit(0, 0, 0, 2, 3), Some((2, 2, 3)));
+    }
+
+    #[test]
+    fn test_initialize_pool() {
+        for token_program in [token_program().0, token_2022_program().0] {
+            let fixture = Fixture::with_token_program(token_program);
+
+            fixture.context.process_and_validate_instruction(
+                &fixture.initialize(FEE_BPS),
+                &[Check::success()],
+            );
+
+            let data = fixture.account(&fixture.pool).data;
+            assert_eq!(data.len(), Pool::LEN);
+            assert_eq!(&data[..2], &[Pool::DISCRIMINATOR, Pool::VERSION]);
+            for key in [fixture.reserve_a, fixture.reserve_b, fixture.lp_mint] {
+                assert_eq!(fixture.account(&key).owner, token_program);
+            }
+            // Both reserves are owned by the pool
+            assert_eq!(
+                &fixture.account(&fixture.reserve_a).data[32..64],
+                fixture.pool.as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_pool_rejects_full_fee() {
+        let fixture = Fixture::new();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.initialize(Pool::MAX_FEE_BPS),
+            &[custom(PoolError::InvalidFee)],
+        );
+    }
+
+    #[test]
+    fn test_initialize_pool_rejects_token_account_as_mint() {
+        let fixture = Fixture::with_token_program(token_2022_program().0);
+        // Too long for a bare mint and too short to carry an account type
+        fixture.context.account_store.borrow_mut().insert(
+            fixture.mint_a,
+            Account::new(LAMPORTS_PER_SOL, TOKEN_ACCOUNT_LEN, &token_2022_program().0),
+        );
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.initialize(FEE_BPS),
+            &[custom(PoolError::InvalidMint)],
+        );
+    }
+
+    #[test]
+    fn test_add_liquidity() {
+        let fixture = Fixture::new().seeded();
+        assert_eq!(fixture.lp_supply(), 20_000);
+
+        // Only 1_000 A fits the 1:4 ratio of 4_000 B
+        fixture.context.process_and_validate_instruction(
+            &fixture.add_liquidity(5_000, 4_000, 2_000),
+            &[Check::success()],
+        );
+
+        assert_eq!(fixture.token_amount(&fixture.reserve_a), 11_000);
+        assert_eq!(fixture.token_amount(&fixture.reserve_b), 44_000);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.lp_mint)),
+            22_000
+        );
+    }
+
+    #[test]
+    fn test_add_liquidity_rejects_below_min_lp() {
+        let fixture = Fixture::new().seeded();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.add_liquidity(1_000, 4_000, 2_001),
+            &[custom(PoolError::SlippageExceeded)],
+        );
+    }
+
+    #[test]
+    fn test_remove_liquidity() {
+        let fixture = Fixture::new().seeded();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.remove_liquidity(5_000, 2_500, 10_000),
+            &[Check::success()],
+        );
+
+        assert_eq!(fixture.token_amount(&fixture.reserve_a), 7_500);
+        assert_eq!(fixture.token_amount(&fixture.reserve_b), 30_000);
+        assert_eq!(fixture.lp_supply(), 15_000);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_a)),
+            FUNDS - 7_500
+        );
+    }
+
+    #[test]
+    fn test_remove_liquidity_rejects_below_min_out() {
+        let fixture = Fixture::new().seeded();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.remove_liquidity(5_000, 2_501, 0),
+            &[custom(PoolError::SlippageExceeded)],
+        );
+    }
+
+    #[test]
+    fn test_swaps_never_decrease_k() {
+        for token_program in [token_program().0, token_2022_program().0] {
+            let fixture = Fixture::with_token_program(token_program).seeded();
+
+            for (amount_in, a_to_b) in [(1_000, true), (7_777, false), (1, true), (20_000, false)] {
+                let k = fixture.k();
+                fixture.context.process_and_validate_instruction(
+                    &fixture.swap(amount_in, 1, a_to_b),
+                    &[Check::success()],
+                );
+                assert!(fixture.k() >= k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let fixture = Fixture::new().seeded();
+
+        // 1_000 A less the 0.3% fee against 10_000 A and 40_000 B
+        fixture.context.process_and_validate_instruction(
+            &fixture.swap(1_000, 3_626, true),
+            &[Check::success()],
+        );
+
+        assert_eq!(fixture.token_amount(&fixture.reserve_a), 11_000);
+        assert_eq!(fixture.token_amount(&fixture.reserve_b), 40_000 - 3_626);
+    }
+
+    #[test]
+    fn test_swap_rejects_below_min_out() {
+        let fixture = Fixture::new().seeded();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.swap(1_000, 3_627, true),
+            &[custom(PoolError::SlippageExceeded)],
+        );
+    }
+
+    #[test]
+    fn test_swap_rejects_foreign_reserve() {
+        let fixture = Fixture::new().seeded();
+        let mut instruction = fixture.swap(1_000, 0, true);
+        instruction.accounts[5] =
+            AccountMeta::new(fixture.ata(&fixture.alice, &fixture.mint_b), false);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(PoolError::InvalidTokenAccount)],
+        );
+    }
+
+    #[test]
+    fn test_swap_rejects_non_token_account_out() {
+        // An empty account in place of alice's token B account is rejected
+        // before its balance is read
+        let fixture = Fixture::new().seeded();
+        let mut instruction = fixture.swap(1_000, 0, true);
+        instruction.accounts[7] = AccountMeta::new(Pubkey::new_unique(), false);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(PoolError::InvalidTokenAccount)],
+        );
+    }
+
+    #[test]
+    fn test_initialize_pool_sizes_reserves_for_transfer_fee() {
+        let fixture = Fixture::with_transfer_fee();
+
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.initialize(FEE_BPS), &[Check::success()]);
+
+        // The reserve of the fee mint carries the withheld amount extension
+        assert!(fixture.account(&fixture.reserve_a).data.len() > TOKEN_ACCOUNT_LEN);
+        assert_eq!(
+            fixture.account(&fixture.reserve_b).data.len(),
+            TOKEN_ACCOUNT_LEN
+        );
+    }
+
+    #[test]
+    fn test_add_liquidity_with_transfer_fee() {
+        let fixture = Fixture::with_transfer_fee().seeded();
+
+        // The 1% fee keeps 100 of the 10_000 A, and the LP follows the
+        // 9_900 A that arrived
+        assert_eq!(fixture.token_amount(&fixture.reserve_a), 9_900);
+        assert_eq!(fixture.token_amount(&fixture.reserve_b), 40_000);
+        assert_eq!(fixture.lp_supply(), 19_899);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_a)),
+            FUNDS - 10_000
+        );
+    }
+
+    #[test]
+    fn test_swap_with_transfer_fee() {
+        let fixture = Fixture::with_transfer_fee().seeded();
+
+        // 990 of the 1_000 A arrive, less the 0.3% swap fee against 9_900 A
+        // and 40_000 B
+        fixture.context.process_and_validate_instruction(
+            &fixture.swap(1_000, 3_626, true),
+            &[Check::success()],
+        );
+
+        assert_eq!(fixture.token_amount(&fixture.reserve_a), 9_900 + 990);
+        assert_eq!(fixture.token_amount(&fixture.reserve_b), 40_000 - 3_626);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_b)),
+            FUNDS - 40_000 + 3_626
+        );
+    }
+    #[test]
+    fn test_swap_checks_min_out_after_transfer_fee() {
+        let fixture = Fixture::with_transfer_fee().seeded();
+
+        // 4_000 B less the 0.3% swap fee buys 897 of the 9_900 A, and the 1%
+        // transfer fee leaves the trader 888 of them
+        fixture.context.process_and_validate_instruction(
+            &fixture.swap(4_000, 897, false),
+            &[custom(PoolError::SlippageExceeded)],
+        );
+        fixture.context.process_and_validate_instruction(
+            &fixture.swap(4_000, 888, false),
+            &[Check::success()],
+        );
+
+        assert_eq!(fixture.token_amount(&fixture.reserve_a), 9_900 - 897);
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.mint_a)),
+            FUNDS - 10_000 + 888
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+use program_helpers::HelperError;
+
+/// Errors returned by the staking program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StakingError {
+    NotSigner = 6000,
+    InvalidAddress = 6001,
+    InvalidMint = 6002,
+    InvalidTokenAccount = 6003,
+    InvalidStakePoolAccount = 6004,
+    InvalidStakeEntryAccount = 6005,
+    InvalidStakePoolAddress = 6006,
+    InvalidStakeEntryAddress = 6007,
+    ZeroAmount = 6008,
+    InvalidAccountDiscriminator = 6009,
+    UnsupportedAccountVersion = 6010,
+    DuplicateMint = 6011,
+    InsufficientStake = 6012,
+    NothingToClaim = 6013,
+}
+
+impl StakingError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidAddress),
+            6002 => Some(Self::InvalidMint),
+            6003 => Some(Self::InvalidTokenAccount),
+            6004 => Some(Self::InvalidStakePoolAccount),
+            6005 => Some(Self::InvalidStakeEntryAccount),
+            6006 => Some(Self::InvalidStakePoolAddress),
+            6007 => Some(Self::InvalidStakeEntryAddress),
+            6008 => Some(Self::ZeroAmount),
+            6009 => Some(Self::InvalidAccountDiscriminator),
+            6010 => Some(Self::UnsupportedAccountVersion),
+            6011 => Some(Self::DuplicateMint),
+            6012 => Some(Self::InsufficientStake),
+            6013 => Some(Self::NothingToClaim),
+            _ => None,
+        }
+    }
+}
+
+impl From<StakingError> for ProgramError {
+    fn from(error: StakingError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl HelperError for StakingError {
+    const NOT_SIGNER: Self = Self::NotSigner;
+    const INVALID_MINT: Self = Self::InvalidMint;
+    const INVALID_TOKEN_ACCOUNT: Self = Self::InvalidTokenAccount;
+    const INVALID_ADDRESS: Self = Self::InvalidAddress;
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self = Self::InvalidAccountDiscriminator;
+    const UNSUPPORTED_ACCOUNT_VERSION: Self = Self::UnsupportedAccountVersion;
+}
+
+impl fmt::Display for StakingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Account must sign the transaction",
+            Self::InvalidAddress => "Account does not match its expected address",
+            Self::InvalidMint => "Account is not a valid SPL Token or Token-2022 mint",
+            Self::InvalidTokenAccount => {
+                "Account is not a valid SPL Token or Token-2022 token account"
+            }
+            Self::InvalidStakePoolAccount => {
+                "Stake pool account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidStakeEntryAccount => {
+                "Stake entry account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidStakePoolAddress => "Stake pool account does not match its PDA",
+            Self::InvalidStakeEntryAddress => "Stake entry account does not match its PDA",
+            Self::ZeroAmount => "Amount must be greater than zero",
+            Self::InvalidAccountDiscriminator => "Staking account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Staking account layout version is not supported",
+            Self::DuplicateMint => "Stake mint and reward mint must differ",
+            Self::InsufficientStake => "Unstaking more than is staked",
+            Self::NothingToClaim => "No rewards are owed",
+        };
+
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,200 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`StakingEvent::decode`].
+
+use core::mem::size_of;
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and made only of byte arrays, so that
+/// every bit pattern is valid and the struct has no padding.
+pub unsafe trait Event: Copy + Sized {
+    const DISCRIMINATOR: u8;
+    const LEN: usize = size_of::<Self>();
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    #[inline(always)]
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[self.as_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakePoolCreated {
+    pub header: EventHeader,
+    pub stake_pool: Pubkey,
+    pub admin: Pubkey,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_rate: [u8; 8],
+}
+
+unsafe impl Event for StakePoolCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl StakePoolCreated {
+    pub fn new(
+        stake_pool: Pubkey,
+        admin: Pubkey,
+        stake_mint: Pubkey,
+        reward_mint: Pubkey,
+        reward_rate: u64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            stake_pool,
+            admin,
+            stake_mint,
+            reward_mint,
+            reward_rate: reward_rate.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RewardsFunded {
+    pub header: EventHeader,
+    pub stake_pool: Pubkey,
+    pub amount: [u8; 8],
+    pub unallocated: [u8; 8],
+}
+
+unsafe impl Event for RewardsFunded {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl RewardsFunded {
+    pub fn new(stake_pool: Pubkey, amount: u64, unallocated: u64) -> Self {
+        Self {
+            header: Self::header(),
+            stake_pool,
+            amount: amount.to_le_bytes(),
+            unallocated: unallocated.to_le_bytes(),
+        }
+    }
+}
+
+/// Emitted by both Stake and Unstake, `stake` telling them apart.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakeChanged {
+    pub header: EventHeader,
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: [u8; 8],
+    pub total_staked: [u8; 8],
+    pub stake: u8,
+}
+
+unsafe impl Event for StakeChanged {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl StakeChanged {
+    pub fn new(
+        stake_pool: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        total_staked: u64,
+        stake: bool,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            stake_pool,
+            owner,
+            amount: amount.to_le_bytes(),
+            total_staked: total_staked.to_le_bytes(),
+            stake: stake as u8,
+        }
+    }
+}
+
+/// Emitted whenever rewards are paid out, by Unstake as well as ClaimRewards.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RewardsClaimed {
+    pub header: EventHeader,
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: [u8; 8],
+}
+
+unsafe impl Event for RewardsClaimed {
+    const DISCRIMINATOR: u8 = 3;
+}
+
+impl RewardsClaimed {
+    pub fn new(stake_pool: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        Self {
+            header: Self::header(),
+            stake_pool,
+            owner,
+            amount: amount.to_le_bytes(),
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StakingEvent {
+    StakePoolCreated(StakePoolCreated),
+    RewardsFunded(RewardsFunded),
+    StakeChanged(StakeChanged),
+    RewardsClaimed(RewardsClaimed),
+}
+
+impl StakingEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let [version, discriminator, ..] = data else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        if *version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match *discriminator {
+            StakePoolCreated::DISCRIMINATOR => {
+                StakePoolCreated::read(data).map(Self::StakePoolCreated)
+            }
+            RewardsFunded::DISCRIMINATOR => RewardsFunded::read(data).map(Self::RewardsFunded),
+            StakeChanged::DISCRIMINATOR => StakeChanged::read(data).map(Self::StakeChanged),
+            RewardsClaimed::DISCRIMINATOR => RewardsClaimed::read(data).map(Self::RewardsClaimed),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
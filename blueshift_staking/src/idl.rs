@@ -0,0 +1,45 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    AccountLayout, ClaimRewards, ClaimRewardsAccounts, CreateStakePool, CreateStakePoolAccounts,
+    CreateStakePoolInstructionData, FundRewards, FundRewardsAccounts, FundRewardsInstructionData,
+    Stake, StakeAccounts, StakeEntry, StakeInstructionData, StakePool, StakingError, Unstake,
+    UnstakeAccounts, UnstakeInstructionData,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create_stake_pool", *CreateStakePool::DISCRIMINATOR)
+            .accounts::<CreateStakePoolAccounts>()
+            .args::<CreateStakePoolInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("fund_rewards", *FundRewards::DISCRIMINATOR)
+            .accounts::<FundRewardsAccounts>()
+            .args::<FundRewardsInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("stake", *Stake::DISCRIMINATOR)
+            .accounts::<StakeAccounts>()
+            .args::<StakeInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("unstake", *Unstake::DISCRIMINATOR)
+            .accounts::<UnstakeAccounts>()
+            .args::<UnstakeInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("claim_rewards", *ClaimRewards::DISCRIMINATOR)
+            .accounts::<ClaimRewardsAccounts>(),
+    )
+    .account::<StakePool>(&[StakePool::DISCRIMINATOR, StakePool::VERSION])
+    .account::<StakeEntry>(&[StakeEntry::DISCRIMINATOR, StakeEntry::VERSION])
+    .errors(StakingError::from_code)
+}
@@ -0,0 +1,141 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, StakeEntry, StakeEntryAccount, StakePool, StakePoolAccount,
+    StakingError,
+    events::{Event, RewardsClaimed},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct ClaimRewardsAccounts<'a> {
+    #[account(signer @ StakingError::NotSigner)]
+    #[idl(writable)]
+    pub owner: &'a AccountInfo,
+    #[account(check = StakePoolAccount::check(stake_pool))]
+    #[idl(writable)]
+    pub stake_pool: &'a AccountInfo,
+    #[account(check = StakeEntryAccount::check(stake_entry))]
+    #[idl(writable)]
+    pub stake_entry: &'a AccountInfo,
+    pub reward_mint: &'a AccountInfo,
+    #[idl(writable)]
+    pub owner_ata: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(reward_vault, stake_pool, reward_mint, token_program),
+    )]
+    #[idl(writable)]
+    pub reward_vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct ClaimRewards<'a> {
+    pub accounts: ClaimRewardsAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimRewards<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClaimRewardsAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ClaimRewards<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.stake_pool.try_borrow_mut_data()?;
+        let stake_pool = StakePool::load_mut(data.as_mut())?;
+
+        // Check if the stake pool and the entry are valid
+        let stake_pool_key = create_program_address(
+            &[
+                b"stake_pool",
+                &stake_pool.admin,
+                &stake_pool.seed,
+                &stake_pool.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &stake_pool_key != self.accounts.stake_pool.key() {
+            return Err(StakingError::InvalidStakePoolAddress.into());
+        }
+        if self.accounts.reward_mint.key().ne(&stake_pool.reward_mint) {
+            return Err(StakingError::InvalidMint.into());
+        }
+
+        let mut entry_data = self.accounts.stake_entry.try_borrow_mut_data()?;
+        let stake_entry = StakeEntry::load_mut(entry_data.as_mut())?;
+        if stake_entry.pool.ne(self.accounts.stake_pool.key())
+            || stake_entry.owner.ne(self.accounts.owner.key())
+        {
+            return Err(StakingError::InvalidAddress.into());
+        }
+
+        stake_pool.update(Clock::get()?.unix_timestamp)?;
+        let acc_reward_per_share = stake_pool.acc_reward_per_share();
+        let reward = stake_entry.pending(acc_reward_per_share)?;
+        if reward == 0 {
+            return Err(StakingError::NothingToClaim.into());
+        }
+        stake_entry.settle(stake_entry.amount(), acc_reward_per_share)?;
+
+        let admin_binding = stake_pool.admin;
+        let seed_binding = stake_pool.seed;
+        let bump_binding = stake_pool.bump;
+        drop(entry_data);
+        drop(data);
+
+        let stake_pool_seeds = [
+            Seed::from(b"stake_pool"),
+            Seed::from(admin_binding.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&stake_pool_seeds);
+
+        AssociatedTokenAccount::init_if_needed(
+            self.accounts.owner_ata,
+            self.accounts.reward_mint,
+            self.accounts.owner,
+            self.accounts.owner,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        Transfer {
+            from: self.accounts.reward_vault,
+            to: self.accounts.owner_ata,
+            authority: self.accounts.stake_pool,
+            amount: reward,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        RewardsClaimed::new(
+            *self.accounts.stake_pool.key(),
+            *self.accounts.owner.key(),
+            reward,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
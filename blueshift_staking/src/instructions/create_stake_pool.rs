@@ -0,0 +1,175 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountInit, MintInterface,
+    ProgramAccount, ProgramAccountInit, StakePool, StakingError,
+    events::{Event, StakePoolCreated},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct CreateStakePoolAccounts<'a> {
+    #[account(signer @ StakingError::NotSigner)]
+    #[idl(writable)]
+    pub admin: &'a AccountInfo,
+    #[idl(writable)]
+    pub stake_pool: &'a AccountInfo,
+    #[account(check = MintInterface::check(stake_mint))]
+    pub stake_mint: &'a AccountInfo,
+    #[account(
+        check = MintInterface::check(reward_mint),
+        // Both vaults would be the same associated token account
+        constraint = stake_mint.key().ne(reward_mint.key()) @ StakingError::DuplicateMint,
+    )]
+    pub reward_mint: &'a AccountInfo,
+    #[idl(writable)]
+    pub stake_vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub reward_vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct CreateStakePoolInstructionData {
+    pub seed: u64,
+    /// Rewards streamed per second, shared by every staker.
+    pub reward_rate: u64,
+}
+
+impl CreateStakePoolInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2;
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateStakePoolInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let reward_rate = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        // Instruction Checks
+        if reward_rate == 0 {
+            return Err(StakingError::ZeroAmount.into());
+        }
+
+        Ok(Self { seed, reward_rate })
+    }
+}
+
+pub struct CreateStakePool<'a> {
+    pub accounts: CreateStakePoolAccounts<'a>,
+    pub instruction_data: CreateStakePoolInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateStakePool<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = CreateStakePoolAccounts::try_from(accounts)?;
+        let instruction_data = CreateStakePoolInstructionData::try_from(data)?;
+
+        // Initialize the Accounts needed
+        let (stake_pool_key, bump) = find_program_address(
+            &[
+                b"stake_pool",
+                accounts.admin.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &stake_pool_key != accounts.stake_pool.key() {
+            return Err(StakingError::InvalidStakePoolAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let stake_pool_seeds = [
+            Seed::from(b"stake_pool"),
+            Seed::from(accounts.admin.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<StakePool>(
+            accounts.admin,
+            accounts.stake_pool,
+            &stake_pool_seeds,
+            StakePool::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            accounts.stake_vault,
+            accounts.stake_mint,
+            accounts.admin,
+            accounts.stake_pool,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+        AssociatedTokenAccount::init(
+            accounts.reward_vault,
+            accounts.reward_mint,
+            accounts.admin,
+            accounts.stake_pool,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> CreateStakePool<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the stake pool account
+        let mut data = self.accounts.stake_pool.try_borrow_mut_data()?;
+        let stake_pool = StakePool::init(data.as_mut())?;
+
+        stake_pool.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.admin.key(),
+            *self.accounts.stake_mint.key(),
+            *self.accounts.reward_mint.key(),
+            [self.bump],
+        );
+        stake_pool.set_rewards(
+            self.instruction_data.reward_rate,
+            Clock::get()?.unix_timestamp,
+        );
+
+        StakePoolCreated::new(
+            *self.accounts.stake_pool.key(),
+            *self.accounts.admin.key(),
+            *self.accounts.stake_mint.key(),
+            *self.accounts.reward_mint.key(),
+            self.instruction_data.reward_rate,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
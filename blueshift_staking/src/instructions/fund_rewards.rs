@@ -0,0 +1,137 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck, StakePool,
+    StakePoolAccount, StakingError,
+    events::{Event, RewardsFunded},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct FundRewardsAccounts<'a> {
+    #[account(signer @ StakingError::NotSigner)]
+    pub admin: &'a AccountInfo,
+    #[account(check = StakePoolAccount::check(stake_pool))]
+    #[idl(writable)]
+    pub stake_pool: &'a AccountInfo,
+    pub reward_mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(admin_ata, admin, reward_mint, token_program))]
+    #[idl(writable)]
+    pub admin_ata: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(reward_vault, stake_pool, reward_mint, token_program),
+    )]
+    #[idl(writable)]
+    pub reward_vault: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct FundRewardsInstructionData {
+    /// Rewards added to the vault.
+    pub amount: u64,
+}
+
+impl FundRewardsInstructionData {
+    pub const LEN: usize = size_of::<u64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for FundRewardsInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data.try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(StakingError::ZeroAmount.into());
+        }
+
+        Ok(Self { amount })
+    }
+}
+
+pub struct FundRewards<'a> {
+    pub accounts: FundRewardsAccounts<'a>,
+    pub instruction_data: FundRewardsInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for FundRewards<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = FundRewardsAccounts::try_from(accounts)?;
+        let instruction_data = FundRewardsInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> FundRewards<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.stake_pool.try_borrow_mut_data()?;
+        let stake_pool = StakePool::load_mut(data.as_mut())?;
+
+        // Check if the stake pool is valid
+        let stake_pool_key = create_program_address(
+            &[
+                b"stake_pool",
+                self.accounts.admin.key(),
+                &stake_pool.seed,
+                &stake_pool.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &stake_pool_key != self.accounts.stake_pool.key() {
+            return Err(StakingError::InvalidStakePoolAddress.into());
+        }
+        if self.accounts.reward_mint.key().ne(&stake_pool.reward_mint) {
+            return Err(StakingError::InvalidMint.into());
+        }
+
+        // Accrue at the old funding before the new rewards can be streamed
+        stake_pool.update(Clock::get()?.unix_timestamp)?;
+        let unallocated = stake_pool
+            .unallocated()
+            .checked_add(self.instruction_data.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        stake_pool.set_unallocated(unallocated);
+        drop(data);
+
+        Transfer {
+            from: self.accounts.admin_ata,
+            to: self.accounts.reward_vault,
+            authority: self.accounts.admin,
+            amount: self.instruction_data.amount,
+        }
+        .invoke()?;
+
+        RewardsFunded::new(
+            *self.accounts.stake_pool.key(),
+            self.instruction_data.amount,
+            unallocated,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+pub use program_helpers::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, ProgramAccountInit, TOKEN_2022_MINT_DISCRIMINATOR,
+    TOKEN_2022_PROGRAM_ID,
+};
+
+use crate::{StakeEntry, StakePool, StakingError};
+
+pub type SignerAccount = program_helpers::SignerAccount<StakingError>;
+pub type MintInterface = program_helpers::MintInterface<StakingError>;
+pub type TokenAccount = program_helpers::TokenAccount<StakingError>;
+pub type AssociatedTokenAccount = program_helpers::AssociatedTokenAccount<StakingError>;
+
+pub type ProgramAccount = program_helpers::ProgramAccount<StakePool>;
+pub type StakePoolAccount = program_helpers::StateAccount<StakePool>;
+pub type StakeEntryAccount = program_helpers::StateAccount<StakeEntry>;
@@ -0,0 +1,13 @@
+pub mod claim_rewards;
+pub mod create_stake_pool;
+pub mod fund_rewards;
+pub mod helpers;
+pub mod stake;
+pub mod unstake;
+
+pub use claim_rewards::*;
+pub use create_stake_pool::*;
+pub use fund_rewards::*;
+pub use helpers::*;
+pub use stake::*;
+pub use unstake::*;
@@ -0,0 +1,195 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{create_program_address, find_program_address},
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    ProgramAccount, ProgramAccountInit, StakeEntry, StakeEntryAccount, StakePool, StakePoolAccount,
+    StakingError,
+    events::{Event, StakeChanged},
+};
+
+/// `stake_entry` is created on the owner's first stake.
+#[derive(Accounts, IdlAccounts)]
+pub struct StakeAccounts<'a> {
+    #[account(signer @ StakingError::NotSigner)]
+    #[idl(writable)]
+    pub owner: &'a AccountInfo,
+    #[account(check = StakePoolAccount::check(stake_pool))]
+    #[idl(writable)]
+    pub stake_pool: &'a AccountInfo,
+    #[idl(writable)]
+    pub stake_entry: &'a AccountInfo,
+    pub stake_mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(owner_ata, owner, stake_mint, token_program))]
+    #[idl(writable)]
+    pub owner_ata: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(stake_vault, stake_pool, stake_mint, token_program),
+    )]
+    #[idl(writable)]
+    pub stake_vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct StakeInstructionData {
+    /// Amount added to the stake.
+    pub amount: u64,
+}
+
+impl StakeInstructionData {
+    pub const LEN: usize = size_of::<u64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for StakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data.try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(StakingError::ZeroAmount.into());
+        }
+
+        Ok(Self { amount })
+    }
+}
+
+pub struct Stake<'a> {
+    pub accounts: StakeAccounts<'a>,
+    pub instruction_data: StakeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Stake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = StakeAccounts::try_from(accounts)?;
+        let instruction_data = StakeInstructionData::try_from(data)?;
+
+        // Initialize the stake entry on the first stake
+        if accounts.stake_entry.data_is_empty() {
+            let (stake_entry_key, bump) = find_program_address(
+                &[b"stake", accounts.stake_pool.key(), accounts.owner.key()],
+                &crate::ID,
+            );
+            if &stake_entry_key != accounts.stake_entry.key() {
+                return Err(StakingError::InvalidStakeEntryAddress.into());
+            }
+
+            let bump_binding = [bump];
+            let stake_entry_seeds = [
+                Seed::from(b"stake"),
+                Seed::from(accounts.stake_pool.key().as_ref()),
+                Seed::from(accounts.owner.key().as_ref()),
+                Seed::from(&bump_binding),
+            ];
+
+            ProgramAccount::init::<StakeEntry>(
+                accounts.owner,
+                accounts.stake_entry,
+                &stake_entry_seeds,
+                StakeEntry::LEN,
+            )?;
+
+            let mut data = accounts.stake_entry.try_borrow_mut_data()?;
+            StakeEntry::init(data.as_mut())?.set_inner(
+                *accounts.stake_pool.key(),
+                *accounts.owner.key(),
+                bump_binding,
+            );
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Stake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        StakeEntryAccount::check(self.accounts.stake_entry)?;
+
+        let mut data = self.accounts.stake_pool.try_borrow_mut_data()?;
+        let stake_pool = StakePool::load_mut(data.as_mut())?;
+
+        // Check if the stake pool and the entry are valid
+        let stake_pool_key = create_program_address(
+            &[
+                b"stake_pool",
+                &stake_pool.admin,
+                &stake_pool.seed,
+                &stake_pool.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &stake_pool_key != self.accounts.stake_pool.key() {
+            return Err(StakingError::InvalidStakePoolAddress.into());
+        }
+        if self.accounts.stake_mint.key().ne(&stake_pool.stake_mint) {
+            return Err(StakingError::InvalidMint.into());
+        }
+
+        let mut entry_data = self.accounts.stake_entry.try_borrow_mut_data()?;
+        let stake_entry = StakeEntry::load_mut(entry_data.as_mut())?;
+        if stake_entry.pool.ne(self.accounts.stake_pool.key())
+            || stake_entry.owner.ne(self.accounts.owner.key())
+        {
+            return Err(StakingError::InvalidAddress.into());
+        }
+
+        // Accrue to the old stakes before this one starts earning
+        stake_pool.update(Clock::get()?.unix_timestamp)?;
+        stake_entry.stake(
+            self.instruction_data.amount,
+            stake_pool.acc_reward_per_share(),
+        )?;
+        let total_staked = stake_pool
+            .total_staked()
+            .checked_add(self.instruction_data.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        stake_pool.set_total_staked(total_staked);
+        drop(entry_data);
+        drop(data);
+
+        Transfer {
+            from: self.accounts.owner_ata,
+            to: self.accounts.stake_vault,
+            authority: self.accounts.owner,
+            amount: self.instruction_data.amount,
+        }
+        .invoke()?;
+
+        StakeChanged::new(
+            *self.accounts.stake_pool.key(),
+            *self.accounts.owner.key(),
+            self.instruction_data.amount,
+            total_staked,
+            true,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, ProgramAccount, StakeEntry, StakeEntryAccount, StakePool,
+    StakePoolAccount, StakingError,
+    events::{Event, RewardsClaimed, StakeChanged},
+};
+
+/// Pays out every pending reward along with the stake. Unstaking everything
+/// closes `stake_entry` to the owner.
+#[derive(Accounts, IdlAccounts)]
+pub struct UnstakeAccounts<'a> {
+    #[account(signer @ StakingError::NotSigner)]
+    #[idl(writable)]
+    pub owner: &'a AccountInfo,
+    #[account(check = StakePoolAccount::check(stake_pool))]
+    #[idl(writable)]
+    pub stake_pool: &'a AccountInfo,
+    #[account(check = StakeEntryAccount::check(stake_entry))]
+    #[idl(writable)]
+    pub stake_entry: &'a AccountInfo,
+    pub stake_mint: &'a AccountInfo,
+    pub reward_mint: &'a AccountInfo,
+    #[idl(writable)]
+    pub owner_stake_ata: &'a AccountInfo,
+    #[idl(writable)]
+    pub owner_reward_ata: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(stake_vault, stake_pool, stake_mint, token_program),
+    )]
+    #[idl(writable)]
+    pub stake_vault: &'a AccountInfo,
+    #[account(
+        check = AssociatedTokenAccount::check(reward_vault, stake_pool, reward_mint, token_program),
+    )]
+    #[idl(writable)]
+    pub reward_vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct UnstakeInstructionData {
+    /// Amount taken out of the stake.
+    pub amount: u64,
+}
+
+impl UnstakeInstructionData {
+    pub const LEN: usize = size_of::<u64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for UnstakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data.try_into().unwrap());
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(StakingError::ZeroAmount.into());
+        }
+
+        Ok(Self { amount })
+    }
+}
+
+pub struct Unstake<'a> {
+    pub accounts: UnstakeAccounts<'a>,
+    pub instruction_data: UnstakeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Unstake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UnstakeAccounts::try_from(accounts)?;
+        let instruction_data = UnstakeInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Unstake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.stake_pool.try_borrow_mut_data()?;
+        let stake_pool = StakePool::load_mut(data.as_mut())?;
+
+        // Check if the stake pool and the entry are valid
+        let stake_pool_key = create_program_address(
+            &[
+                b"stake_pool",
+                &stake_pool.admin,
+                &stake_pool.seed,
+                &stake_pool.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &stake_pool_key != self.accounts.stake_pool.key() {
+            return Err(StakingError::InvalidStakePoolAddress.into());
+        }
+        if self.accounts.stake_mint.key().ne(&stake_pool.stake_mint)
+            || self.accounts.reward_mint.key().ne(&stake_pool.reward_mint)
+        {
+            return Err(StakingError::InvalidMint.into());
+        }
+
+        let mut entry_data = self.accounts.stake_entry.try_borrow_mut_data()?;
+        let stake_entry = StakeEntry::load_mut(entry_data.as_mut())?;
+        if stake_entry.pool.ne(self.accounts.stake_pool.key())
+            || stake_entry.owner.ne(self.accounts.owner.key())
+        {
+            return Err(StakingError::InvalidAddress.into());
+        }
+        let remaining = stake_entry
+            .amount()
+            .checked_sub(self.instruction_data.amount)
+            .ok_or(StakingError::InsufficientStake)?;
+
+        // Pay out everything owed on the old stake
+        stake_pool.update(Clock::get()?.unix_timestamp)?;
+        let acc_reward_per_share = stake_pool.acc_reward_per_share();
+        let reward = stake_entry.pending(acc_reward_per_share)?;
+        stake_entry.settle(remaining, acc_reward_per_share)?;
+        let total_staked = stake_pool.total_staked() - self.instruction_data.amount;
+        stake_pool.set_total_staked(total_staked);
+
+        let admin_binding = stake_pool.admin;
+        let seed_binding = stake_pool.seed;
+        let bump_binding = stake_pool.bump;
+        drop(entry_data);
+        drop(data);
+
+        let stake_pool_seeds = [
+            Seed::from(b"stake_pool"),
+            Seed::from(admin_binding.as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&stake_pool_seeds);
+
+        AssociatedTokenAccount::init_if_needed(
+            self.accounts.owner_stake_ata,
+            self.accounts.stake_mint,
+            self.accounts.owner,
+            self.accounts.owner,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        Transfer {
+            from: self.accounts.stake_vault,
+            to: self.accounts.owner_stake_ata,
+            authority: self.accounts.stake_pool,
+            amount: self.instruction_data.amount,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        if reward > 0 {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.owner_reward_ata,
+                self.accounts.reward_mint,
+                self.accounts.owner,
+                self.accounts.owner,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: self.accounts.reward_vault,
+                to: self.accounts.owner_reward_ata,
+                authority: self.accounts.stake_pool,
+                amount: reward,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            RewardsClaimed::new(
+                *self.accounts.stake_pool.key(),
+                *self.accounts.owner.key(),
+                reward,
+            )
+            .emit();
+        }
+
+        // Nothing is left to earn on an empty stake
+        if remaining == 0 {
+            ProgramAccount::close(self.accounts.stake_entry, self.accounts.owner)?;
+        }
+
+        StakeChanged::new(
+            *self.accounts.stake_pool.key(),
+            *self.accounts.owner.key(),
+            self.instruction_data.amount,
+            total_staked,
+            false,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, entrypoint, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+pub mod errors;
+pub use errors::*;
+
+pub mod events;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
+// 77777777777777777777777777777777777777777777
+pub const ID: Pubkey = [
+    0x5a, 0xb6, 0x82, 0x78, 0xca, 0x81, 0xbc, 0x2a, 0x19, 0x26, 0xe6, 0x28, 0x9a, 0xa2, 0x67, 0x94,
+    0x99, 0x70, 0x61, 0x74, 0x1e, 0xea, 0xce, 0x2d, 0x59, 0xd4, 0x1c, 0xa1, 0xaf, 0x28, 0x6b, 0xca,
+];
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.split_first() {
+        Some((CreateStakePool::DISCRIMINATOR, data)) => {
+            CreateStakePool::try_from((data, accounts))?.process()
+        }
+        Some((FundRewards::DISCRIMINATOR, data)) => {
+            FundRewards::try_from((data, accounts))?.process()
+        }
+        Some((Stake::DISCRIMINATOR, data)) => Stake::try_from((data, accounts))?.process(),
+        Some((Unstake::DISCRIMINATOR, data)) => Unstake::try_from((data, accounts))?.process(),
+        Some((ClaimRewards::DISCRIMINATOR, _)) => ClaimRewards::try_from(accounts)?.process(),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,245 @@
+use core::mem::size_of;
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use idl_derive::IdlType;
+
+use crate::{AccountLayout, StakingError};
+
+/// Rewards in `reward_mint` streamed at `reward_rate` per second to everyone
+/// staking `stake_mint`, pro rata to their stake. Only rewards the admin has
+/// funded are ever handed out.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct StakePool {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `StakePool::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `StakePool::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub admin: Pubkey,     // Creator of the pool, the only one funding rewards
+    #[idl(ty = "pubkey")]
+    pub stake_mint: Pubkey, // Token being staked
+    #[idl(ty = "pubkey")]
+    pub reward_mint: Pubkey, // Token paid out as rewards
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u64")]
+    pub reward_rate: [u8; 8], // Rewards streamed per second
+    #[idl(ty = "u64")]
+    pub total_staked: [u8; 8], // Sum of every stake
+    #[idl(ty = "u128")]
+    pub acc_reward_per_share: [u8; 16], // Rewards per staked token, times `PRECISION`
+    #[idl(ty = "i64")]
+    pub last_update_ts: [u8; 8], // Unix time rewards were last accrued
+    #[idl(ty = "u64")]
+    pub unallocated: [u8; 8], // Funded rewards not yet accrued to stakers
+}
+
+impl AccountLayout for StakePool {
+    type Error = StakingError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 16]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>();
+
+    const DISCRIMINATOR: u8 = 1;
+    const VERSION: u8 = 1;
+    const INVALID: StakingError = StakingError::InvalidStakePoolAccount;
+}
+
+impl StakePool {
+    /// Scale of `acc_reward_per_share`.
+    pub const PRECISION: u128 = 1_000_000_000_000;
+
+    #[inline(always)]
+    pub fn reward_rate(&self) -> u64 {
+        u64::from_le_bytes(self.reward_rate)
+    }
+
+    #[inline(always)]
+    pub fn total_staked(&self) -> u64 {
+        u64::from_le_bytes(self.total_staked)
+    }
+
+    #[inline(always)]
+    pub fn acc_reward_per_share(&self) -> u128 {
+        u128::from_le_bytes(self.acc_reward_per_share)
+    }
+
+    #[inline(always)]
+    pub fn last_update_ts(&self) -> i64 {
+        i64::from_le_bytes(self.last_update_ts)
+    }
+
+    #[inline(always)]
+    pub fn unallocated(&self) -> u64 {
+        u64::from_le_bytes(self.unallocated)
+    }
+
+    /// Accrues the rewards streamed since `last_update_ts` to the current
+    /// stakers, capped by what is funded. Nothing accrues while nobody
+    /// stakes. `acc_reward_per_share` rounds down, so the stakers are never
+    /// owed more than was allocated.
+    pub fn update(&mut self, now: i64) -> Result<(), ProgramError> {
+        let elapsed = now.saturating_sub(self.last_update_ts());
+        if elapsed <= 0 {
+            return Ok(());
+        }
+
+        let total_staked = self.total_staked();
+        if total_staked > 0 {
+            let streamed = (elapsed as u128).saturating_mul(self.reward_rate() as u128);
+            let reward = streamed.min(self.unallocated() as u128);
+            let acc_reward_per_share = self
+                .acc_reward_per_share()
+                .checked_add(reward * Self::PRECISION / total_staked as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            self.acc_reward_per_share = acc_reward_per_share.to_le_bytes();
+            // `reward` is at most `unallocated`
+            self.unallocated = (self.unallocated() - reward as u64).to_le_bytes();
+        }
+        self.last_update_ts = now.to_le_bytes();
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        admin: Pubkey,
+        stake_mint: Pubkey,
+        reward_mint: Pubkey,
+        bump: [u8; 1],
+    ) {
+        self.seed = seed.to_le_bytes();
+        self.admin = admin;
+        self.stake_mint = stake_mint;
+        self.reward_mint = reward_mint;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_rewards(&mut self, reward_rate: u64, last_update_ts: i64) {
+        self.reward_rate = reward_rate.to_le_bytes();
+        self.last_update_ts = last_update_ts.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_total_staked(&mut self, total_staked: u64) {
+        self.total_staked = total_staked.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_unallocated(&mut self, unallocated: u64) {
+        self.unallocated = unallocated.to_le_bytes();
+    }
+}
+
+/// One owner's stake in a `StakePool`. `reward_debt` is the part of
+/// `amount * acc_reward_per_share` that was already paid out or was accrued
+/// before the stake was made, so the difference is what the owner is owed.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct StakeEntry {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `StakeEntry::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `StakeEntry::VERSION`
+    #[idl(ty = "pubkey")]
+    pub pool: Pubkey,      // Stake pool this entry belongs to
+    #[idl(ty = "pubkey")]
+    pub owner: Pubkey,     // Staker, the only one able to unstake or claim
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u64")]
+    pub amount: [u8; 8],   // Amount staked
+    #[idl(ty = "u128")]
+    pub reward_debt: [u8; 16], // Settled rewards, times `StakePool::PRECISION`
+}
+
+impl AccountLayout for StakeEntry {
+    type Error = StakingError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 16]>();
+
+    const DISCRIMINATOR: u8 = 2;
+    const VERSION: u8 = 1;
+    const INVALID: StakingError = StakingError::InvalidStakeEntryAccount;
+}
+
+impl StakeEntry {
+    #[inline(always)]
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+
+    #[inline(always)]
+    pub fn reward_debt(&self) -> u128 {
+        u128::from_le_bytes(self.reward_debt)
+    }
+
+    /// Rewards owed at `acc_reward_per_share`, rounded down.
+    pub fn pending(&self, acc_reward_per_share: u128) -> Result<u64, ProgramError> {
+        let accrued = (self.amount() as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let pending = accrued.saturating_sub(self.reward_debt()) / StakePool::PRECISION;
+
+        u64::try_from(pending).map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+
+    /// Adds `amount` to the stake without touching what is already owed.
+    pub fn stake(&mut self, amount: u64, acc_reward_per_share: u128) -> Result<(), ProgramError> {
+        let staked = self
+            .amount()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let reward_debt = (amount as u128)
+            .checked_mul(acc_reward_per_share)
+            .and_then(|debt| debt.checked_add(self.reward_debt()))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.amount = staked.to_le_bytes();
+        self.reward_debt = reward_debt.to_le_bytes();
+        Ok(())
+    }
+
+    /// Marks everything owed as paid and sets the stake to `amount`. Any
+    /// fraction of a token still owed is forfeited to the pool.
+    pub fn settle(&mut self, amount: u64, acc_reward_per_share: u128) -> Result<(), ProgramError> {
+        let reward_debt = (amount as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.amount = amount.to_le_bytes();
+        self.reward_debt = reward_debt.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, pool: Pubkey, owner: Pubkey, bump: [u8; 1]) {
+        self.pool = pool;
+        self.owner = owner;
+        self.bump = bump;
+    }
+}
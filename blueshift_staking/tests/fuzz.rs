@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio_staking::{
+        AccountLayout, CreateStakePoolInstructionData, FundRewardsInstructionData, StakeEntry,
+        StakeInstructionData, StakePool, StakingError, UnstakeInstructionData, ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        fuzz::{check_invariants, check_rejected, fuzz, Fuzzer},
+        token_program, MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 5;
+
+    fn fuzzer() -> Fuzzer {
+        let admin = Pubkey::new_unique();
+        let stake_pool = Pubkey::find_program_address(
+            &[b"stake_pool", admin.as_ref(), &42u64.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0;
+        let (token_program, token_account) = token_program();
+
+        let mut fuzzer = Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                admin,
+                stake_pool,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .fixed(token_program, token_account)
+        .data_lens(&[StakePool::LEN, StakeEntry::LEN, MINT_LEN, TOKEN_ACCOUNT_LEN])
+        .max_accounts(10);
+        fuzzer.owners.push(token_program);
+        fuzzer
+    }
+
+    fn mollusk() -> Mollusk {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_staking");
+        test_support::add_token_programs(&mut mollusk);
+        mollusk
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_stake_pool_data(
+            data in vec(any::<u8>(), 0..=CreateStakePoolInstructionData::LEN + 8),
+        ) {
+            let parsed = CreateStakePoolInstructionData::try_from(data.as_slice());
+
+            if data.len() != CreateStakePoolInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let field = |index: usize| u64::from_le_bytes(data[index * 8..][..8].try_into().unwrap());
+            if field(1) == 0 {
+                prop_assert_eq!(parsed.err(), Some(StakingError::ZeroAmount.into()));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!((parsed.seed, parsed.reward_rate), (field(0), field(1)));
+        }
+
+        #[test]
+        fn test_parse_stake_pool_header(
+            data in vec(any::<u8>(), StakePool::LEN - 1..=StakePool::LEN + 1),
+            tagged in any::<bool>(),
+        ) {
+            let mut data = data;
+            if tagged {
+                data[0] = StakePool::DISCRIMINATOR;
+                data[1] = StakePool::VERSION;
+            }
+
+            let valid = data.len() == StakePool::LEN
+                && data[0] == StakePool::DISCRIMINATOR
+                && data[1] == StakePool::VERSION;
+            prop_assert_eq!(StakePool::load(&data).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = mollusk();
+        let data = (0..INSTRUCTION_COUNT, vec(any::<u8>(), 0..=24))
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_stake_pool_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreateStakePoolInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_fund_rewards_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(1, FundRewardsInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_stake_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(2, StakeInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_unstake_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(3, UnstakeInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
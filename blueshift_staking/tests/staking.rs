@@ -0,0 +1,495 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_staking::{
+        AccountLayout, ClaimRewards, CreateStakePool, FundRewards, Stake, StakeEntry, StakePool,
+        StakingError, Unstake, ID,
+    };
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, funded_payer,
+        system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 21;
+    const FUNDS: u64 = 10_000;
+    const REWARD_RATE: u64 = 10;
+    const START_TS: i64 = 1_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Admin holding FUNDS of the reward mint, Alice and Bob FUNDS of the stake
+    // mint each, at unix time START_TS with no pool created yet
+    struct Fixture {
+        context: Context,
+        admin: Pubkey,
+        alice: Pubkey,
+        bob: Pubkey,
+        stake_mint: Pubkey,
+        reward_mint: Pubkey,
+        stake_pool: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_staking");
+            add_token_programs(&mut mollusk);
+            mollusk.sysvars.clock.unix_timestamp = START_TS;
+
+            let (admin, admin_account) = funded_payer();
+            let (alice, alice_account) = funded_payer();
+            let (bob, bob_account) = funded_payer();
+            let stake_mint = Pubkey::new_unique();
+            let reward_mint = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(admin, admin_account);
+            store.insert(alice, alice_account);
+            store.insert(bob, bob_account);
+            store.insert(
+                stake_mint,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(FUNDS * 2)
+                    .build(&mollusk),
+            );
+            store.insert(
+                reward_mint,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(FUNDS)
+                    .build(&mollusk),
+            );
+            for (owner, mint) in [(admin, reward_mint), (alice, stake_mint), (bob, stake_mint)] {
+                let (ata, account) = AtaBuilder::new(&owner, &mint).amount(FUNDS).build(&mollusk);
+                store.insert(ata, account);
+            }
+
+            Self {
+                context: mollusk.with_context(store),
+                admin,
+                alice,
+                bob,
+                stake_mint,
+                reward_mint,
+                stake_pool: stake_pool_address(&admin, SEED),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+            associated_token_address(owner, mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn rewards(&self, owner: &Pubkey) -> u64 {
+            let ata = self.ata(owner, &self.reward_mint);
+            if self.account(&ata).data.is_empty() {
+                return 0;
+            }
+            self.token_amount(&ata)
+        }
+
+        fn set_time(&mut self, unix_timestamp: i64) {
+            self.context.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        }
+
+        fn stake_entry(&self, owner: &Pubkey) -> Pubkey {
+            Pubkey::find_program_address(
+                &[b"stake", self.stake_pool.as_ref(), owner.as_ref()],
+                &PROGRAM_ID,
+            )
+            .0
+        }
+
+        fn create(&self, stake_mint: &Pubkey) -> Instruction {
+            let mut data = vec![*CreateStakePool::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&REWARD_RATE.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(self.admin, true),
+                    AccountMeta::new(self.stake_pool, false),
+                    AccountMeta::new_readonly(*stake_mint, false),
+                    AccountMeta::new_readonly(self.reward_mint, false),
+                    AccountMeta::new(self.ata(&self.stake_pool, stake_mint), false),
+                    AccountMeta::new(self.ata(&self.stake_pool, &self.reward_mint), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn fund(&self, amount: u64) -> Instruction {
+            let mut data = vec![*FundRewards::DISCRIMINATOR];
+            data.extend_from_slice(&amount.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new_readonly(self.admin, true),
+                    AccountMeta::new(self.stake_pool, false),
+                    AccountMeta::new_readonly(self.reward_mint, false),
+                    AccountMeta::new(self.ata(&self.admin, &self.reward_mint), false),
+                    AccountMeta::new(self.ata(&self.stake_pool, &self.reward_mint), false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                ],
+            )
+        }
+
+        fn stake(&self, owner: &Pubkey, amount: u64) -> Instruction {
+            let mut data = vec![*Stake::DISCRIMINATOR];
+            data.extend_from_slice(&amount.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(*owner, true),
+                    AccountMeta::new(self.stake_pool, false),
+                    AccountMeta::new(self.stake_entry(owner), false),
+                    AccountMeta::new_readonly(self.stake_mint, false),
+                    AccountMeta::new(self.ata(owner, &self.stake_mint), false),
+                    AccountMeta::new(self.ata(&self.stake_pool, &self.stake_mint), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                ],
+            )
+        }
+
+        fn unstake(&self, owner: &Pubkey, amount: u64) -> Instruction {
+            let mut data = vec![*Unstake::DISCRIMINATOR];
+            data.extend_from_slice(&amount.to_le_bytes());
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                vec![
+                    AccountMeta::new(*owner, true),
+                    AccountMeta::new(self.stake_pool, false),
+                    AccountMeta::new(self.stake_entry(owner), false),
+                    AccountMeta::new_readonly(self.stake_mint, false),
+                    AccountMeta::new_readonly(self.reward_mint, false),
+                    AccountMeta::new(self.ata(owner, &self.stake_mint), false),
+                    AccountMeta::new(self.ata(owner, &self.reward_mint), false),
+                    AccountMeta::new(self.ata(&self.stake_pool, &self.stake_mint), false),
+                    AccountMeta::new(self.ata(&self.stake_pool, &self.reward_mint), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn claim(&self, owner: &Pubkey) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*ClaimRewards::DISCRIMINATOR],
+                vec![
+                    AccountMeta::new(*owner, true),
+                    AccountMeta::new(self.stake_pool, false),
+                    AccountMeta::new(self.stake_entry(owner), false),
+                    AccountMeta::new_readonly(self.reward_mint, false),
+                    AccountMeta::new(self.ata(owner, &self.reward_mint), false),
+                    AccountMeta::new(self.ata(&self.stake_pool, &self.reward_mint), false),
+                    AccountMeta::new_readonly(system_program().0, false),
+                    AccountMeta::new_readonly(token_program().0, false),
+                    AccountMeta::new_readonly(associated_token_program().0, false),
+                ],
+            )
+        }
+
+        fn run(&self, instruction: &Instruction) {
+            self.context
+                .process_and_validate_instruction(instruction, &[Check::success()]);
+        }
+
+        // Create the pool and fund it with `amount` of rewards, as the setup
+        // of the others
+        fn funded(self, amount: u64) -> Self {
+            self.run(&self.create(&self.stake_mint));
+            self.run(&self.fund(amount));
+            self
+        }
+    }
+
+    fn stake_pool_address(admin: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"stake_pool", admin.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: StakingError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    proptest! {
+        #[test]
+        fn test_rewards_never_exceed_funding(
+            funded in 0..1_000_000u64,
+            reward_rate in 1..1_000u64,
+            steps in vec((1..1_000i64, 1..1_000_000u64, any::<bool>()), 1..20),
+        ) {
+            let mut pool_data = vec![0u8; StakePool::LEN];
+            let pool = StakePool::init(&mut pool_data).unwrap();
+            pool.set_rewards(reward_rate, 0);
+            pool.set_unallocated(funded);
+
+            let mut entries = [vec![0u8; StakeEntry::LEN], vec![0u8; StakeEntry::LEN]];
+            for data in entries.iter_mut() {
+                StakeEntry::init(data).unwrap();
+            }
+
+            // Alternate stakers, each adding to their stake at a later time
+            let mut now = 0;
+            let mut paid = 0u64;
+            for (i, (elapsed, amount, claim)) in steps.into_iter().enumerate() {
+                now += elapsed;
+                pool.update(now).unwrap();
+                let acc = pool.acc_reward_per_share();
+                let entry = StakeEntry::load_mut(&mut entries[i % 2]).unwrap();
+                if claim {
+                    paid += entry.pending(acc).unwrap();
+                    entry.settle(entry.amount(), acc).unwrap();
+                }
+                entry.stake(amount, acc).unwrap();
+                pool.set_total_staked(pool.total_staked() + amount);
+            }
+
+            now += 1_000_000;
+            pool.update(now).unwrap();
+            let acc = pool.acc_reward_per_share();
+            for data in entries.iter_mut() {
+                paid += StakeEntry::load(data).unwrap().pending(acc).unwrap();
+            }
+            prop_assert!(paid + pool.unallocated() <= funded);
+        }
+    }
+
+    #[test]
+    fn test_update_streams_pro_rata() {
+        let mut data = vec![0u8; StakePool::LEN];
+        let pool = StakePool::init(&mut data).unwrap();
+        pool.set_rewards(REWARD_RATE, START_TS);
+        pool.set_unallocated(1_000);
+
+        // Nothing accrues while nobody stakes
+        pool.update(START_TS + 10).unwrap();
+        assert_eq!(pool.unallocated(), 1_000);
+        assert_eq!(pool.acc_reward_per_share(), 0);
+
+        pool.set_total_staked(400);
+        pool.update(START_TS + 50).unwrap();
+        assert_eq!(pool.unallocated(), 600);
+        assert_eq!(pool.acc_reward_per_share(), StakePool::PRECISION);
+
+        // Capped by what is funded
+        pool.update(START_TS + 1_000).unwrap();
+        assert_eq!(pool.unallocated(), 0);
+        assert_eq!(pool.acc_reward_per_share(), StakePool::PRECISION * 5 / 2);
+    }
+
+    #[test]
+    fn test_create_stake_pool() {
+        let fixture = Fixture::new();
+        fixture.run(&fixture.create(&fixture.stake_mint));
+
+        let data = fixture.account(&fixture.stake_pool).data;
+        assert_eq!(data.len(), StakePool::LEN);
+        assert_eq!(&data[..2], &[StakePool::DISCRIMINATOR, StakePool::VERSION]);
+        for mint in [fixture.stake_mint, fixture.reward_mint] {
+            assert_eq!(
+                fixture
+                    .account(&fixture.ata(&fixture.stake_pool, &mint))
+                    .owner,
+                token_program().0
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_stake_pool_rejects_same_mint() {
+        let fixture = Fixture::new();
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.create(&fixture.reward_mint),
+            &[custom(StakingError::DuplicateMint)],
+        );
+    }
+
+    #[test]
+    fn test_fund_rewards_rejects_other_signer() {
+        let mut fixture = Fixture::new().funded(1_000);
+        // Alice holds reward tokens but is not the admin
+        let (ata, account) = AtaBuilder::new(&fixture.alice, &fixture.reward_mint)
+            .amount(FUNDS)
+            .build(&fixture.context.mollusk);
+        fixture
+            .context
+            .account_store
+            .borrow_mut()
+            .insert(ata, account);
+        fixture.admin = fixture.alice;
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.fund(1_000),
+            &[custom(StakingError::InvalidStakePoolAddress)],
+        );
+    }
+
+    #[test]
+    fn test_stake_accrues_over_time() {
+        let mut fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.stake_pool, &fixture.stake_mint)),
+            100
+        );
+
+        fixture.set_time(START_TS + 30);
+        fixture.run(&fixture.claim(&fixture.alice));
+        assert_eq!(fixture.rewards(&fixture.alice), 300);
+
+        // Claiming again in the same second finds nothing
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(&fixture.alice),
+            &[custom(StakingError::NothingToClaim)],
+        );
+    }
+
+    #[test]
+    fn test_rewards_split_pro_rata() {
+        let mut fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+        fixture.run(&fixture.stake(&fixture.bob, 300));
+
+        fixture.set_time(START_TS + 40);
+        fixture.run(&fixture.claim(&fixture.alice));
+        fixture.run(&fixture.claim(&fixture.bob));
+
+        assert_eq!(fixture.rewards(&fixture.alice), 100);
+        assert_eq!(fixture.rewards(&fixture.bob), 300);
+    }
+
+    #[test]
+    fn test_late_stake_earns_only_from_then() {
+        let mut fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+
+        fixture.set_time(START_TS + 10);
+        fixture.run(&fixture.stake(&fixture.bob, 100));
+
+        fixture.set_time(START_TS + 20);
+        fixture.run(&fixture.claim(&fixture.alice));
+        fixture.run(&fixture.claim(&fixture.bob));
+
+        assert_eq!(fixture.rewards(&fixture.alice), 150);
+        assert_eq!(fixture.rewards(&fixture.bob), 50);
+    }
+
+    #[test]
+    fn test_rewards_capped_by_funding() {
+        let mut fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+
+        fixture.set_time(START_TS + 1_000);
+        fixture.run(&fixture.claim(&fixture.alice));
+        assert_eq!(fixture.rewards(&fixture.alice), 1_000);
+
+        // Topping up streams again from now on
+        fixture.run(&fixture.fund(1_000));
+        fixture.set_time(START_TS + 1_010);
+        fixture.run(&fixture.claim(&fixture.alice));
+        assert_eq!(fixture.rewards(&fixture.alice), 1_100);
+    }
+
+    #[test]
+    fn test_unstake_pays_rewards() {
+        let mut fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+
+        fixture.set_time(START_TS + 10);
+        fixture.run(&fixture.unstake(&fixture.alice, 40));
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.stake_mint)),
+            FUNDS - 60
+        );
+        assert_eq!(fixture.rewards(&fixture.alice), 100);
+        assert_eq!(
+            fixture
+                .account(&fixture.stake_entry(&fixture.alice))
+                .data
+                .len(),
+            StakeEntry::LEN
+        );
+    }
+
+    #[test]
+    fn test_unstake_everything_closes_entry() {
+        let mut fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+
+        fixture.set_time(START_TS + 10);
+        fixture.run(&fixture.unstake(&fixture.alice, 100));
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.alice, &fixture.stake_mint)),
+            FUNDS
+        );
+        assert_eq!(fixture.rewards(&fixture.alice), 100);
+        assert_eq!(
+            fixture
+                .account(&fixture.stake_entry(&fixture.alice))
+                .lamports,
+            0
+        );
+    }
+
+    #[test]
+    fn test_unstake_rejects_more_than_staked() {
+        let fixture = Fixture::new().funded(1_000);
+        fixture.run(&fixture.stake(&fixture.alice, 100));
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.unstake(&fixture.alice, 101),
+            &[custom(StakingError::InsufficientStake)],
+        );
+    }
+
+    #[test]
+    fn test_staking_error_codes() {
+        for code in 6000..6014 {
+            let error = StakingError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(StakingError::from_code(6014), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Instructions for invoking the vault program from another program.
+//!
+//! Depend on this crate with the `cpi` feature, which leaves out the
+//! entrypoint.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    ProgramResult,
+};
+
+/// Deposit lamports into the owner's empty vault.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Owner
+///   1. `[WRITE]` Vault PDA, seeds `[b"vault", owner]`
+///   2. `[]` System program
+pub struct Deposit<'a> {
+    /// Owner.
+    pub owner: &'a AccountInfo,
+
+    /// Vault PDA.
+    pub vault: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+
+    /// Lamports to deposit.
+    pub amount: u64,
+}
+
+impl Deposit<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.owner.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // - [0   ]: instruction discriminator
+        // - [1..9]: amount
+        let mut instruction_data = [0; 9];
+        instruction_data[0] = *crate::Deposit::DISCRIMINATOR;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.owner, self.vault, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Withdraw every lamport in the owner's vault.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Owner
+///   1. `[WRITE]` Vault PDA, seeds `[b"vault", owner]`
+///   2. `[]` System program
+pub struct Withdraw<'a> {
+    /// Owner.
+    pub owner: &'a AccountInfo,
+
+    /// Vault PDA.
+    pub vault: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+}
+
+impl Withdraw<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable_signer(self.owner.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // - [0]: instruction discriminator
+        let instruction_data = [*crate::Withdraw::DISCRIMINATOR];
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.owner, self.vault, self.system_program],
+            signers,
+        )
+    }
+}
@@ -0,0 +1,51 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+
+/// Errors returned by the vault program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VaultError {
+    NotSigner = 6000,
+    InvalidVaultOwner = 6001,
+    VaultNotEmpty = 6002,
+    InvalidVaultAddress = 6003,
+    ZeroAmount = 6004,
+}
+
+impl VaultError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidVaultOwner),
+            6002 => Some(Self::VaultNotEmpty),
+            6003 => Some(Self::InvalidVaultAddress),
+            6004 => Some(Self::ZeroAmount),
+            _ => None,
+        }
+    }
+}
+
+impl From<VaultError> for ProgramError {
+    fn from(error: VaultError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl fmt::Display for VaultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Owner must sign the transaction",
+            Self::InvalidVaultOwner => "Vault account must be owned by the System Program",
+            Self::VaultNotEmpty => "Vault already holds lamports",
+            Self::InvalidVaultAddress => "Vault account does not match the vault PDA",
+            Self::ZeroAmount => "Deposit amount must be greater than zero",
+        };
+
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,24 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    Deposit, DepositAccounts, DepositInstructionData, VaultError, Withdraw, WithdrawAccounts,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("deposit", *Deposit::DISCRIMINATOR)
+            .accounts::<DepositAccounts>()
+            .args::<DepositInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("withdraw", *Withdraw::DISCRIMINATOR).accounts::<WithdrawAccounts>(),
+    )
+    .errors(VaultError::from_code)
+}
@@ -1,45 +1,32 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address, ProgramResult};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 use pinocchio_system::instructions::Transfer;
 use core::mem::size_of;
+use accounts_derive::Accounts;
+use idl_derive::{IdlAccounts, IdlType};
 
+use crate::{
+    events::{Event, VaultDeposited},
+    VaultError,
+};
 
 
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct DepositAccounts<'a> {
+    #[account(signer @ VaultError::NotSigner)]
+    #[idl(writable)]
     pub owner: &'a AccountInfo,
+    #[account(
+        owner = pinocchio_system::ID @ VaultError::InvalidVaultOwner,
+        constraint = vault.lamports() == 0 @ VaultError::VaultNotEmpty,
+        seeds = [b"vault", owner.key()] @ VaultError::InvalidVaultAddress,
+    )]
+    #[idl(writable)]
     pub vault: &'a AccountInfo,
 }
- 
-impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
-    type Error = ProgramError;
- 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [owner, vault, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
- 
-        // Accounts Checks
-        if !owner.is_signer() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
- 
-        if !vault.is_owned_by(&pinocchio_system::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
- 
-        if vault.lamports().ne(&0) {
-            return Err(ProgramError::InvalidAccountData);
-        }
- 
-        let (vault_key, _) = find_program_address(&[b"vault", owner.key().as_ref()], &crate::ID);
-        if &vault_key != vault.key() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
- 
-        // Return the accounts
-        Ok(Self { owner, vault })
-    }
-}
 
+#[derive(IdlType)]
 pub struct DepositInstructionData {
     pub amount: u64,
 }
@@ -56,7 +43,7 @@ impl<'a> TryFrom<&'a [u8]> for DepositInstructionData {
  
         // Instruction Checks
         if amount.eq(&0) {
-            return Err(ProgramError::InvalidInstructionData);
+            return Err(VaultError::ZeroAmount.into());
         }
  
         Ok(Self { amount })
@@ -92,6 +79,13 @@ impl<'a> Deposit<'a> {
             lamports: self.instruction_data.amount,
         }
         .invoke()?;
+
+        VaultDeposited::new(
+            *self.accounts.vault.key(),
+            *self.accounts.owner.key(),
+            self.instruction_data.amount,
+        )
+        .emit();
  
         Ok(())
     }
@@ -1,41 +1,29 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address, ProgramResult,instruction::{Seed, Signer}};
 use pinocchio_system::instructions::Transfer;
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
 
+use crate::{
+    events::{Event, VaultWithdrawn},
+    VaultError,
+};
+
+#[derive(Accounts, IdlAccounts)]
+#[accounts(system_program)]
 pub struct WithdrawAccounts<'a> {
+    #[account(signer @ VaultError::NotSigner)]
+    #[idl(writable)]
     pub owner: &'a AccountInfo,
+    #[account(owner = pinocchio_system::ID @ VaultError::InvalidVaultOwner)]
+    #[idl(writable)]
     pub vault: &'a AccountInfo,
-    pub bumps: [u8; 1],
-}
- 
-impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
-    type Error = ProgramError;
- 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [owner, vault, _] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
- 
-        // Basic Accounts Checks
-        if !owner.is_signer() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
- 
-        if !vault.is_owned_by(&pinocchio_system::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
- 
- 
-        let (vault_key, bump) = find_program_address(&[b"vault", owner.key().as_ref()], &crate::ID);
-        if &vault_key != vault.key() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
- 
-        Ok(Self { owner, vault, bumps: [bump] })
-    }
 }
 
+/// The vault holds no data to store its bump in, so Withdraw derives the
+/// address, and the bump it signs with, on every call.
 pub struct Withdraw<'a> {
     pub accounts: WithdrawAccounts<'a>,
+    pub bump: [u8; 1],
 }
  
 impl<'a> TryFrom<&'a [AccountInfo]> for Withdraw<'a> {
@@ -44,7 +32,16 @@ impl<'a> TryFrom<&'a [AccountInfo]> for Withdraw<'a> {
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         let accounts = WithdrawAccounts::try_from(accounts)?;
  
-        Ok(Self { accounts })
+        let (vault_key, bump) =
+            find_program_address(&[b"vault", accounts.owner.key().as_ref()], &crate::ID);
+        if &vault_key != accounts.vault.key() {
+            return Err(VaultError::InvalidVaultAddress.into());
+        }
+ 
+        Ok(Self {
+            accounts,
+            bump: [bump],
+        })
     }
 }
  
@@ -56,17 +53,20 @@ impl<'a> Withdraw<'a> {
         let seeds = [
             Seed::from(b"vault"),
             Seed::from(self.accounts.owner.key().as_ref()),
-            Seed::from(&self.accounts.bumps),
+            Seed::from(&self.bump),
         ];
         let signers = [Signer::from(&seeds)];
  
         // Transfer all lamports from vault to owner
+        let amount = self.accounts.vault.lamports();
         Transfer {
             from: self.accounts.vault,
             to: self.accounts.owner,
-            lamports: self.accounts.vault.lamports(),
+            lamports: amount,
         }
         .invoke_signed(&signers)?;
+
+        VaultWithdrawn::new(*self.accounts.vault.key(), *self.accounts.owner.key(), amount).emit();
  
         Ok(())
     }
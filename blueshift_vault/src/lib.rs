@@ -1,10 +1,30 @@
 #![no_std]
  
-use pinocchio::{account_info::AccountInfo, entrypoint, nostd_panic_handler, program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+use pinocchio::pubkey::Pubkey;
+#[cfg(not(feature = "no-entrypoint"))]
+use pinocchio::{account_info::AccountInfo, entrypoint, nostd_panic_handler, program_error::ProgramError, ProgramResult};
  
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
 nostd_panic_handler!();
+
+// Host builds of the cdylib still need the panic handler from std when the
+// entrypoint is left out.
+#[cfg(all(feature = "no-entrypoint", not(target_os = "solana")))]
+extern crate std;
  
+#[cfg(feature = "cpi")]
+pub mod cpi;
+
+pub mod errors;
+pub use errors::*;
+
+pub mod events;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
 pub mod instructions;
 pub use instructions::*;
  
@@ -16,6 +36,7 @@ pub const ID: Pubkey = [
     0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
 ];
  
+#[cfg(not(feature = "no-entrypoint"))]
 fn process_instruction(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
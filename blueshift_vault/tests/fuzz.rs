@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use blueshift_vault::{DepositInstructionData, VaultError, ID};
+    use pinocchio::program_error::ProgramError;
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::fuzz::{check_invariants, check_rejected, fuzz, Fuzzer};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 2;
+
+    const DEPOSIT_LEN: usize = 8;
+
+    fn fuzzer() -> Fuzzer {
+        let owner = Pubkey::new_unique();
+        let vault = Pubkey::find_program_address(&[b"vault", owner.as_ref()], &PROGRAM_ID).0;
+        Fuzzer::new(PROGRAM_ID, vec![owner, vault, Pubkey::new_unique()])
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_deposit_data(data in vec(any::<u8>(), 0..=DEPOSIT_LEN + 8)) {
+            let parsed = DepositInstructionData::try_from(data.as_slice());
+
+            let expected = if data.len() != DEPOSIT_LEN {
+                Err(ProgramError::InvalidInstructionData)
+            } else {
+                match u64::from_le_bytes(data.as_slice().try_into().unwrap()) {
+                    0 => Err(VaultError::ZeroAmount.into()),
+                    amount => Ok(amount),
+                }
+            };
+            prop_assert_eq!(parsed.map(|data| data.amount), expected);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "blueshift_vault");
+        let data = (0..INSTRUCTION_COUNT, vec(any::<u8>(), 0..=DEPOSIT_LEN + 8))
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "blueshift_vault");
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_deposit_wrong_length() {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "blueshift_vault");
+        fuzz(fuzzer().wrong_length_cases(0, DEPOSIT_LEN), |case| {
+            check_rejected(&mollusk, &case)
+        });
+    }
+}
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod tests {
+    use blueshift_vault::{VaultError, ID};
+    use mollusk_svm::{result::Check, Mollusk};
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{funded_payer, system_account, system_program, PAYER_LAMPORTS};
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const AMOUNT: u64 = LAMPORTS_PER_SOL;
+
+    fn setup() -> (Mollusk, Pubkey, Account) {
+        let mollusk = test_support::mollusk(&PROGRAM_ID, "blueshift_vault");
+        let (owner, owner_account) = funded_payer();
+        (mollusk, owner, owner_account)
+    }
+
+    fn vault_address(owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"vault", owner.as_ref()], &PROGRAM_ID).0
+    }
+
+    fn instruction(discriminator: u8, data: &[u8], owner: &Pubkey, vault: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[&[discriminator], data].concat(),
+            vec![
+                AccountMeta::new(*owner, true),
+                AccountMeta::new(*vault, false),
+                AccountMeta::new_readonly(system_program().0, false),
+            ],
+        )
+    }
+
+    fn deposit(owner: &Pubkey, vault: &Pubkey, amount: u64) -> Instruction {
+        instruction(0, &amount.to_le_bytes(), owner, vault)
+    }
+
+    fn withdraw(owner: &Pubkey, vault: &Pubkey) -> Instruction {
+        instruction(1, &[], owner, vault)
+    }
+
+    fn custom(error: VaultError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_deposit() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+
+        mollusk.process_and_validate_instruction(
+            &deposit(&owner, &vault, AMOUNT),
+            &[
+                (owner, owner_account),
+                (vault, system_account(0)),
+                system_program(),
+            ],
+            &[
+                Check::success(),
+                Check::account(&owner)
+                    .lamports(PAYER_LAMPORTS - AMOUNT)
+                    .build(),
+                Check::account(&vault)
+                    .lamports(AMOUNT)
+                    .owner(&system_program().0)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_deposit_rejects_missing_signer() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+        let mut instruction = deposit(&owner, &vault, AMOUNT);
+        instruction.accounts[0].is_signer = false;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account),
+                (vault, system_account(0)),
+                system_program(),
+            ],
+            &[custom(VaultError::NotSigner)],
+        );
+    }
+
+    #[test]
+    fn test_deposit_rejects_wrong_vault_address() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &deposit(&owner, &vault, AMOUNT),
+            &[
+                (owner, owner_account),
+                (vault, system_account(0)),
+                system_program(),
+            ],
+            &[custom(VaultError::InvalidVaultAddress)],
+        );
+    }
+
+    #[test]
+    fn test_deposit_rejects_non_empty_vault() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+
+        mollusk.process_and_validate_instruction(
+            &deposit(&owner, &vault, AMOUNT),
+            &[
+                (owner, owner_account),
+                (vault, system_account(AMOUNT)),
+                system_program(),
+            ],
+            &[custom(VaultError::VaultNotEmpty)],
+        );
+    }
+
+    #[test]
+    fn test_deposit_rejects_program_owned_vault() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+
+        mollusk.process_and_validate_instruction(
+            &deposit(&owner, &vault, AMOUNT),
+            &[
+                (owner, owner_account),
+                (vault, Account::new(0, 0, &PROGRAM_ID)),
+                system_program(),
+            ],
+            &[custom(VaultError::InvalidVaultOwner)],
+        );
+    }
+
+    #[test]
+    fn test_deposit_rejects_zero_amount() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+
+        mollusk.process_and_validate_instruction(
+            &deposit(&owner, &vault, 0),
+            &[
+                (owner, owner_account),
+                (vault, system_account(0)),
+                system_program(),
+            ],
+            &[custom(VaultError::ZeroAmount)],
+        );
+    }
+
+    #[test]
+    fn test_deposit_rejects_truncated_data() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+        let mut instruction = deposit(&owner, &vault, AMOUNT);
+        instruction.data.truncate(5);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account),
+                (vault, system_account(0)),
+                system_program(),
+            ],
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn test_withdraw() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+
+        mollusk.process_and_validate_instruction(
+            &withdraw(&owner, &vault),
+            &[
+                (owner, owner_account),
+                (vault, system_account(AMOUNT)),
+                system_program(),
+            ],
+            &[
+                Check::success(),
+                Check::account(&owner)
+                    .lamports(PAYER_LAMPORTS + AMOUNT)
+                    .build(),
+                Check::account(&vault).lamports(0).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_deposit_then_withdraw() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+
+        mollusk.process_and_validate_instruction_chain(
+            &[
+                (&deposit(&owner, &vault, AMOUNT), &[Check::success()]),
+                (
+                    &withdraw(&owner, &vault),
+                    &[
+                        Check::success(),
+                        Check::account(&owner).lamports(PAYER_LAMPORTS).build(),
+                        Check::account(&vault).lamports(0).build(),
+                    ],
+                ),
+            ],
+            &[
+                (owner, owner_account),
+                (vault, system_account(0)),
+                system_program(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_withdraw_rejects_missing_signer() {
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&owner);
+        let mut instruction = withdraw(&owner, &vault);
+        instruction.accounts[0].is_signer = false;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (owner, owner_account),
+                (vault, system_account(AMOUNT)),
+                system_program(),
+            ],
+            &[custom(VaultError::NotSigner)],
+        );
+    }
+
+    #[test]
+    fn test_withdraw_rejects_other_owner() {
+        // Someone else's vault does not match the signer's vault PDA
+        let (mollusk, owner, owner_account) = setup();
+        let vault = vault_address(&Pubkey::new_unique());
+
+        mollusk.process_and_validate_instruction(
+            &withdraw(&owner, &vault),
+            &[
+                (owner, owner_account),
+                (vault, system_account(AMOUNT)),
+                system_program(),
+            ],
+            &[custom(VaultError::InvalidVaultAddress)],
+        );
+    }
+
+    #[test]
+    fn test_vault_error_codes() {
+        for code in 6000..6005 {
+            let error = VaultError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(VaultError::from_code(6005), None);
+    }
+}
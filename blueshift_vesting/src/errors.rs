@@ -0,0 +1,88 @@
+use core::fmt;
+
+use pinocchio::program_error::ProgramError;
+use program_helpers::HelperError;
+
+/// Errors returned by the vesting program.
+///
+/// Each variant is surfaced as `ProgramError::Custom(code)`. Codes are part of
+/// the program interface: never renumber or reuse one, only append.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VestingError {
+    NotSigner = 6000,
+    InvalidAddress = 6001,
+    InvalidMint = 6002,
+    InvalidTokenAccount = 6003,
+    InvalidVestingAccount = 6004,
+    InvalidVestingAddress = 6005,
+    ZeroAmount = 6006,
+    InvalidAccountDiscriminator = 6007,
+    UnsupportedAccountVersion = 6008,
+    InvalidSchedule = 6009,
+    NothingToClaim = 6010,
+    NotRevocable = 6011,
+}
+
+impl VestingError {
+    /// Decode the code carried by `ProgramError::Custom` back into a variant.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000 => Some(Self::NotSigner),
+            6001 => Some(Self::InvalidAddress),
+            6002 => Some(Self::InvalidMint),
+            6003 => Some(Self::InvalidTokenAccount),
+            6004 => Some(Self::InvalidVestingAccount),
+            6005 => Some(Self::InvalidVestingAddress),
+            6006 => Some(Self::ZeroAmount),
+            6007 => Some(Self::InvalidAccountDiscriminator),
+            6008 => Some(Self::UnsupportedAccountVersion),
+            6009 => Some(Self::InvalidSchedule),
+            6010 => Some(Self::NothingToClaim),
+            6011 => Some(Self::NotRevocable),
+            _ => None,
+        }
+    }
+}
+
+impl From<VestingError> for ProgramError {
+    fn from(error: VestingError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl HelperError for VestingError {
+    const NOT_SIGNER: Self = Self::NotSigner;
+    const INVALID_MINT: Self = Self::InvalidMint;
+    const INVALID_TOKEN_ACCOUNT: Self = Self::InvalidTokenAccount;
+    const INVALID_ADDRESS: Self = Self::InvalidAddress;
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self = Self::InvalidAccountDiscriminator;
+    const UNSUPPORTED_ACCOUNT_VERSION: Self = Self::UnsupportedAccountVersion;
+}
+
+impl fmt::Display for VestingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotSigner => "Account must sign the transaction",
+            Self::InvalidAddress => "Account does not match its expected address",
+            Self::InvalidMint => "Account is not a valid SPL Token or Token-2022 mint",
+            Self::InvalidTokenAccount => {
+                "Account is not a valid SPL Token or Token-2022 token account"
+            }
+            Self::InvalidVestingAccount => {
+                "Vesting account is not owned by the program or has the wrong size"
+            }
+            Self::InvalidVestingAddress => "Vesting account does not match its PDA",
+            Self::ZeroAmount => "Amount must be greater than zero",
+            Self::InvalidAccountDiscriminator => "Vesting account has the wrong discriminator",
+            Self::UnsupportedAccountVersion => "Vesting account layout version is not supported",
+            Self::InvalidSchedule => {
+                "Vesting schedule must satisfy start <= cliff <= end and start < end"
+            }
+            Self::NothingToClaim => "No vested tokens are left to claim",
+            Self::NotRevocable => "Vesting grant is not revocable",
+        };
+
+        f.write_str(message)
+    }
+}
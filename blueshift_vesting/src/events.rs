@@ -0,0 +1,165 @@
+//! Binary events emitted through `sol_log_data`.
+//!
+//! Each event is logged as a single `Program data:` field laid out as
+//! `[version: u8][discriminator: u8][payload]`, with integers little-endian.
+//! Indexers should base64-decode the field and hand it to [`VestingEvent::decode`].
+
+use core::mem::size_of;
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Version of the event layout, bumped on any breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub version: u8,
+    pub discriminator: u8,
+}
+
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and made only of byte arrays, so that
+/// every bit pattern is valid and the struct has no padding.
+pub unsafe trait Event: Copy + Sized {
+    const DISCRIMINATOR: u8;
+    const LEN: usize = size_of::<Self>();
+
+    fn header() -> EventHeader {
+        EventHeader {
+            version: EVENT_VERSION,
+            discriminator: Self::DISCRIMINATOR,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    #[inline(always)]
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+
+    fn emit(&self) {
+        sol_log_data(&[self.as_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingCreated {
+    pub header: EventHeader,
+    pub vesting: Pubkey,
+    pub grantor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total: [u8; 8],
+}
+
+unsafe impl Event for VestingCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+impl VestingCreated {
+    pub fn new(
+        vesting: Pubkey,
+        grantor: Pubkey,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        total: u64,
+    ) -> Self {
+        Self {
+            header: Self::header(),
+            vesting,
+            grantor,
+            beneficiary,
+            mint,
+            total: total.to_le_bytes(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestedClaimed {
+    pub header: EventHeader,
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: [u8; 8],
+    pub claimed: [u8; 8],
+}
+
+unsafe impl Event for VestedClaimed {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+impl VestedClaimed {
+    pub fn new(vesting: Pubkey, beneficiary: Pubkey, amount: u64, claimed: u64) -> Self {
+        Self {
+            header: Self::header(),
+            vesting,
+            beneficiary,
+            amount: amount.to_le_bytes(),
+            claimed: claimed.to_le_bytes(),
+        }
+    }
+}
+
+/// `vested` went to the beneficiary and `returned` to the grantor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingRevoked {
+    pub header: EventHeader,
+    pub vesting: Pubkey,
+    pub grantor: Pubkey,
+    pub vested: [u8; 8],
+    pub returned: [u8; 8],
+}
+
+unsafe impl Event for VestingRevoked {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+impl VestingRevoked {
+    pub fn new(vesting: Pubkey, grantor: Pubkey, vested: u64, returned: u64) -> Self {
+        Self {
+            header: Self::header(),
+            vesting,
+            grantor,
+            vested: vested.to_le_bytes(),
+            returned: returned.to_le_bytes(),
+        }
+    }
+}
+
+/// Host-side view of every event this program emits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VestingEvent {
+    VestingCreated(VestingCreated),
+    VestedClaimed(VestedClaimed),
+    VestingRevoked(VestingRevoked),
+}
+
+impl VestingEvent {
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let [version, discriminator, ..] = data else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        if *version != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match *discriminator {
+            VestingCreated::DISCRIMINATOR => VestingCreated::read(data).map(Self::VestingCreated),
+            VestedClaimed::DISCRIMINATOR => VestedClaimed::read(data).map(Self::VestedClaimed),
+            VestingRevoked::DISCRIMINATOR => VestingRevoked::read(data).map(Self::VestingRevoked),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
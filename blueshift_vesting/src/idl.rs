@@ -0,0 +1,31 @@
+//! The program's IDL, compiled only with the `idl-build` feature.
+
+use ::idl::{Idl, IdlInstruction};
+
+use crate::{
+    AccountLayout, ClaimVested, ClaimVestedAccounts, CreateVesting, CreateVestingAccounts,
+    CreateVestingInstructionData, RevokeVesting, RevokeVestingAccounts, Vesting, VestingError,
+};
+
+pub fn idl() -> Idl {
+    Idl::new(
+        env!("CARGO_CRATE_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        &crate::ID,
+    )
+    .instruction(
+        IdlInstruction::new("create_vesting", *CreateVesting::DISCRIMINATOR)
+            .accounts::<CreateVestingAccounts>()
+            .args::<CreateVestingInstructionData>(),
+    )
+    .instruction(
+        IdlInstruction::new("claim_vested", *ClaimVested::DISCRIMINATOR)
+            .accounts::<ClaimVestedAccounts>(),
+    )
+    .instruction(
+        IdlInstruction::new("revoke_vesting", *RevokeVesting::DISCRIMINATOR)
+            .accounts::<RevokeVestingAccounts>(),
+    )
+    .account::<Vesting>(&[Vesting::DISCRIMINATOR, Vesting::VERSION])
+    .errors(VestingError::from_code)
+}
@@ -0,0 +1,154 @@
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, ProgramAccount, Vesting, VestingAccount, VestingError,
+    events::{Event, VestedClaimed},
+};
+
+/// The final claim closes the vault and the vesting account to the grantor,
+/// who paid their rent.
+#[derive(Accounts, IdlAccounts)]
+pub struct ClaimVestedAccounts<'a> {
+    #[account(signer @ VestingError::NotSigner)]
+    #[idl(writable)]
+    pub beneficiary: &'a AccountInfo,
+    #[idl(writable)]
+    pub grantor: &'a AccountInfo,
+    #[account(check = VestingAccount::check(vesting))]
+    #[idl(writable)]
+    pub vesting: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault, vesting, mint, token_program))]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub beneficiary_ata: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct ClaimVested<'a> {
+    pub accounts: ClaimVestedAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimVested<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClaimVestedAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ClaimVested<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.vesting.try_borrow_mut_data()?;
+        let vesting = Vesting::load_mut(data.as_mut())?;
+
+        // Check if the vesting account is valid
+        let vesting_key = create_program_address(
+            &[
+                b"vesting",
+                self.accounts.grantor.key(),
+                &vesting.seed,
+                &vesting.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &vesting_key != self.accounts.vesting.key() {
+            return Err(VestingError::InvalidVestingAddress.into());
+        }
+        if self.accounts.beneficiary.key().ne(&vesting.beneficiary) {
+            return Err(VestingError::InvalidAddress.into());
+        }
+        if self.accounts.mint.key().ne(&vesting.mint) {
+            return Err(VestingError::InvalidMint.into());
+        }
+
+        let vested = vesting.vested(Clock::get()?.unix_timestamp);
+        let mut amount = vested
+            .checked_sub(vesting.claimed())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if amount == 0 {
+            return Err(VestingError::NothingToClaim.into());
+        }
+        vesting.set_claimed(vested);
+        let finished = vested == vesting.total();
+
+        let seed_binding = vesting.seed;
+        let bump_binding = vesting.bump;
+        drop(data);
+
+        let vesting_seeds = [
+            Seed::from(b"vesting"),
+            Seed::from(self.accounts.grantor.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&vesting_seeds);
+
+        if finished {
+            // The final claim empties the vault, then the vault and the
+            // vesting account are closed
+            amount = DrainVault {
+                vault: self.accounts.vault,
+                to: self.accounts.beneficiary_ata,
+                owner: self.accounts.beneficiary,
+                mint: self.accounts.mint,
+                payer: self.accounts.beneficiary,
+                destination: self.accounts.grantor,
+                authority: self.accounts.vesting,
+                system_program: self.accounts.system_program,
+                token_program: self.accounts.token_program,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            ProgramAccount::close(self.accounts.vesting, self.accounts.grantor)?;
+        } else {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.beneficiary_ata,
+                self.accounts.mint,
+                self.accounts.beneficiary,
+                self.accounts.beneficiary,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: self.accounts.vault,
+                to: self.accounts.beneficiary_ata,
+                authority: self.accounts.vesting,
+                amount,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        VestedClaimed::new(
+            *self.accounts.vesting.key(),
+            *self.accounts.beneficiary.key(),
+            amount,
+            vested,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
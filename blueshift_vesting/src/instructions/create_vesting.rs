@@ -0,0 +1,200 @@
+use accounts_derive::Accounts;
+use core::mem::size_of;
+use idl_derive::{IdlAccounts, IdlType};
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    AccountCheck, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, MintInterface, ProgramAccount, ProgramAccountInit, Vesting,
+    VestingError,
+    events::{Event, VestingCreated},
+};
+
+#[derive(Accounts, IdlAccounts)]
+pub struct CreateVestingAccounts<'a> {
+    #[account(signer @ VestingError::NotSigner)]
+    #[idl(writable)]
+    pub grantor: &'a AccountInfo,
+    pub beneficiary: &'a AccountInfo,
+    #[idl(writable)]
+    pub vesting: &'a AccountInfo,
+    #[account(check = MintInterface::check(mint))]
+    pub mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(grantor_ata, grantor, mint, token_program))]
+    #[idl(writable)]
+    pub grantor_ata: &'a AccountInfo,
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+#[derive(IdlType)]
+pub struct CreateVestingInstructionData {
+    pub seed: u64,
+    /// Amount locked for the beneficiary.
+    pub amount: u64,
+    /// Unix time unlocking starts.
+    pub start_ts: i64,
+    /// Unix time of the first claim, between `start_ts` and `end_ts`.
+    pub cliff_ts: i64,
+    /// Unix time everything is unlocked.
+    pub end_ts: i64,
+    /// `1` lets the grantor revoke the unvested remainder, `0` forbids it.
+    pub revocable: u8,
+}
+
+impl CreateVestingInstructionData {
+    pub const LEN: usize = size_of::<u64>() * 2 + size_of::<i64>() * 3 + size_of::<u8>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateVestingInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let start_ts = i64::from_le_bytes(data[16..24].try_into().unwrap());
+        let cliff_ts = i64::from_le_bytes(data[24..32].try_into().unwrap());
+        let end_ts = i64::from_le_bytes(data[32..40].try_into().unwrap());
+        let revocable = data[40];
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(VestingError::ZeroAmount.into());
+        }
+        if revocable > 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if start_ts >= end_ts || cliff_ts < start_ts || cliff_ts > end_ts {
+            return Err(VestingError::InvalidSchedule.into());
+        }
+
+        Ok(Self {
+            seed,
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+            revocable,
+        })
+    }
+}
+
+pub struct CreateVesting<'a> {
+    pub accounts: CreateVestingAccounts<'a>,
+    pub instruction_data: CreateVestingInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateVesting<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = CreateVestingAccounts::try_from(accounts)?;
+        let instruction_data = CreateVestingInstructionData::try_from(data)?;
+
+        // Initialize the Accounts needed
+        let (vesting_key, bump) = find_program_address(
+            &[
+                b"vesting",
+                accounts.grantor.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &vesting_key != accounts.vesting.key() {
+            return Err(VestingError::InvalidVestingAddress.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let vesting_seeds = [
+            Seed::from(b"vesting"),
+            Seed::from(accounts.grantor.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<Vesting>(
+            accounts.grantor,
+            accounts.vesting,
+            &vesting_seeds,
+            Vesting::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            accounts.vault,
+            accounts.mint,
+            accounts.grantor,
+            accounts.vesting,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> CreateVesting<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Populate the vesting account
+        let mut data = self.accounts.vesting.try_borrow_mut_data()?;
+        let vesting = Vesting::init(data.as_mut())?;
+
+        vesting.set_inner(
+            self.instruction_data.seed,
+            *self.accounts.grantor.key(),
+            *self.accounts.beneficiary.key(),
+            *self.accounts.mint.key(),
+            [self.bump],
+        );
+        vesting.set_schedule(
+            self.instruction_data.amount,
+            self.instruction_data.start_ts,
+            self.instruction_data.cliff_ts,
+            self.instruction_data.end_ts,
+            self.instruction_data.revocable == 1,
+        );
+
+        drop(data);
+
+        // Lock the grant
+        Transfer {
+            from: self.accounts.grantor_ata,
+            to: self.accounts.vault,
+            authority: self.accounts.grantor,
+            amount: self.instruction_data.amount,
+        }
+        .invoke()?;
+
+        VestingCreated::new(
+            *self.accounts.vesting.key(),
+            *self.accounts.grantor.key(),
+            *self.accounts.beneficiary.key(),
+            *self.accounts.mint.key(),
+            self.instruction_data.amount,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
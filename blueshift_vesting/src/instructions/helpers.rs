@@ -0,0 +1,15 @@
+pub use program_helpers::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, ProgramAccountInit, TOKEN_2022_MINT_DISCRIMINATOR,
+    TOKEN_2022_PROGRAM_ID,
+};
+
+use crate::{Vesting, VestingError};
+
+pub type SignerAccount = program_helpers::SignerAccount<VestingError>;
+pub type MintInterface = program_helpers::MintInterface<VestingError>;
+pub type TokenAccount = program_helpers::TokenAccount<VestingError>;
+pub type AssociatedTokenAccount = program_helpers::AssociatedTokenAccount<VestingError>;
+
+pub type ProgramAccount = program_helpers::ProgramAccount<Vesting>;
+pub type VestingAccount = program_helpers::StateAccount<Vesting>;
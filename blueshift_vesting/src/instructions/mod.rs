@@ -0,0 +1,9 @@
+pub mod claim_vested;
+pub mod create_vesting;
+pub mod helpers;
+pub mod revoke_vesting;
+
+pub use claim_vested::*;
+pub use create_vesting::*;
+pub use helpers::*;
+pub use revoke_vesting::*;
@@ -0,0 +1,153 @@
+use accounts_derive::Accounts;
+use idl_derive::IdlAccounts;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    AccountCheck, AccountClose, AccountLayout, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, DrainVault, ProgramAccount, Vesting, VestingAccount, VestingError,
+    events::{Event, VestingRevoked},
+};
+
+/// Revoking pays the beneficiary whatever has vested and not been claimed,
+/// returns the rest to the grantor and closes the grant.
+#[derive(Accounts, IdlAccounts)]
+pub struct RevokeVestingAccounts<'a> {
+    #[account(signer @ VestingError::NotSigner)]
+    #[idl(writable)]
+    pub grantor: &'a AccountInfo,
+    pub beneficiary: &'a AccountInfo,
+    #[account(check = VestingAccount::check(vesting))]
+    #[idl(writable)]
+    pub vesting: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    #[account(check = AssociatedTokenAccount::check(vault, vesting, mint, token_program))]
+    #[idl(writable)]
+    pub vault: &'a AccountInfo,
+    #[idl(writable)]
+    pub grantor_ata: &'a AccountInfo,
+    #[idl(writable)]
+    pub beneficiary_ata: &'a AccountInfo,
+    #[idl(address = "11111111111111111111111111111111")]
+    pub system_program: &'a AccountInfo,
+    #[idl(address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")]
+    pub token_program: &'a AccountInfo,
+    #[idl(address = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")]
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+pub struct RevokeVesting<'a> {
+    pub accounts: RevokeVestingAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeVesting<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RevokeVestingAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RevokeVesting<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.vesting.try_borrow_data()?;
+        let vesting = Vesting::load(&data)?;
+
+        // Check if the vesting account is valid
+        let vesting_key = create_program_address(
+            &[
+                b"vesting",
+                self.accounts.grantor.key(),
+                &vesting.seed,
+                &vesting.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &vesting_key != self.accounts.vesting.key() {
+            return Err(VestingError::InvalidVestingAddress.into());
+        }
+        if self.accounts.beneficiary.key().ne(&vesting.beneficiary) {
+            return Err(VestingError::InvalidAddress.into());
+        }
+        if self.accounts.mint.key().ne(&vesting.mint) {
+            return Err(VestingError::InvalidMint.into());
+        }
+        if !vesting.revocable() {
+            return Err(VestingError::NotRevocable.into());
+        }
+
+        let vested = vesting.vested(Clock::get()?.unix_timestamp);
+        let owed = vested
+            .checked_sub(vesting.claimed())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let seed_binding = vesting.seed;
+        let bump_binding = vesting.bump;
+        drop(data);
+
+        let vesting_seeds = [
+            Seed::from(b"vesting"),
+            Seed::from(self.accounts.grantor.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&vesting_seeds);
+
+        // Pay the beneficiary what has vested, the grantor takes back the rest
+        if owed > 0 {
+            AssociatedTokenAccount::init_if_needed(
+                self.accounts.beneficiary_ata,
+                self.accounts.mint,
+                self.accounts.grantor,
+                self.accounts.beneficiary,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            Transfer {
+                from: self.accounts.vault,
+                to: self.accounts.beneficiary_ata,
+                authority: self.accounts.vesting,
+                amount: owed,
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+        }
+
+        let returned = DrainVault {
+            vault: self.accounts.vault,
+            to: self.accounts.grantor_ata,
+            owner: self.accounts.grantor,
+            mint: self.accounts.mint,
+            payer: self.accounts.grantor,
+            destination: self.accounts.grantor,
+            authority: self.accounts.vesting,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        // Close the vesting account
+        ProgramAccount::close(self.accounts.vesting, self.accounts.grantor)?;
+
+        VestingRevoked::new(
+            *self.accounts.vesting.key(),
+            *self.accounts.grantor.key(),
+            owed,
+            returned,
+        )
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, entrypoint, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+pub mod errors;
+pub use errors::*;
+
+pub mod events;
+
+#[cfg(feature = "idl-build")]
+pub mod idl;
+
+// 66666666666666666666666666666666666666666666
+pub const ID: Pubkey = [
+    0x4b, 0x98, 0x17, 0x64, 0xa8, 0xc1, 0x72, 0x23, 0x14, 0xf5, 0xbf, 0xcc, 0x80, 0xdc, 0xab, 0xa6,
+    0x7f, 0xdd, 0xa6, 0x8b, 0x6f, 0x19, 0x01, 0x25, 0xca, 0xdb, 0x6d, 0x31, 0x67, 0x4c, 0x59, 0xd3,
+];
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.split_first() {
+        Some((CreateVesting::DISCRIMINATOR, data)) => {
+            CreateVesting::try_from((data, accounts))?.process()
+        }
+        Some((ClaimVested::DISCRIMINATOR, _)) => ClaimVested::try_from(accounts)?.process(),
+        Some((RevokeVesting::DISCRIMINATOR, _)) => RevokeVesting::try_from(accounts)?.process(),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
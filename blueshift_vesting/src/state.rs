@@ -0,0 +1,148 @@
+use core::mem::size_of;
+use pinocchio::pubkey::Pubkey;
+use idl_derive::IdlType;
+
+use crate::{AccountLayout, VestingError};
+
+/// Tokens locked for a beneficiary, unlocking linearly from `start_ts` to
+/// `end_ts` with nothing claimable before `cliff_ts`.
+#[repr(C)]
+#[derive(IdlType)]
+pub struct Vesting {
+    #[idl(skip)]
+    pub discriminator: u8, // Account type tag, always `Vesting::DISCRIMINATOR`
+    #[idl(skip)]
+    pub version: u8,       // Layout version, always `Vesting::VERSION`
+    #[idl(ty = "u64")]
+    pub seed: [u8; 8],     // Random seed for PDA derivation
+    #[idl(ty = "pubkey")]
+    pub grantor: Pubkey,   // Creator of the grant
+    #[idl(ty = "pubkey")]
+    pub beneficiary: Pubkey, // Receiver of the vested tokens
+    #[idl(ty = "pubkey")]
+    pub mint: Pubkey,      // Token being vested
+    #[idl(ty = "u8")]
+    pub bump: [u8; 1],     // PDA bump seed
+    #[idl(ty = "u64")]
+    pub total: [u8; 8],    // Amount locked at creation
+    #[idl(ty = "u64")]
+    pub claimed: [u8; 8],  // Amount already claimed
+    #[idl(ty = "i64")]
+    pub start_ts: [u8; 8], // Unix time unlocking starts
+    #[idl(ty = "i64")]
+    pub cliff_ts: [u8; 8], // Unix time of the first claim
+    #[idl(ty = "i64")]
+    pub end_ts: [u8; 8],   // Unix time everything is unlocked
+    #[idl(ty = "u8")]
+    pub revocable: u8,     // 1 when the grantor may revoke
+}
+
+impl AccountLayout for Vesting {
+    type Error = VestingError;
+
+    const OWNER: Pubkey = crate::ID;
+    const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 1]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<u8>();
+
+    const DISCRIMINATOR: u8 = 1;
+    const VERSION: u8 = 1;
+    const INVALID: VestingError = VestingError::InvalidVestingAccount;
+}
+
+impl Vesting {
+    #[inline(always)]
+    pub fn total(&self) -> u64 {
+        u64::from_le_bytes(self.total)
+    }
+
+    #[inline(always)]
+    pub fn claimed(&self) -> u64 {
+        u64::from_le_bytes(self.claimed)
+    }
+
+    #[inline(always)]
+    pub fn start_ts(&self) -> i64 {
+        i64::from_le_bytes(self.start_ts)
+    }
+
+    #[inline(always)]
+    pub fn cliff_ts(&self) -> i64 {
+        i64::from_le_bytes(self.cliff_ts)
+    }
+
+    #[inline(always)]
+    pub fn end_ts(&self) -> i64 {
+        i64::from_le_bytes(self.end_ts)
+    }
+
+    #[inline(always)]
+    pub fn revocable(&self) -> bool {
+        self.revocable == 1
+    }
+
+    /// Amount unlocked at `now`, rounded down. Unlocking accrues from
+    /// `start_ts`, so reaching the cliff releases everything accrued so far.
+    #[inline(always)]
+    pub fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff_ts() {
+            return 0;
+        }
+        if now >= self.end_ts() {
+            return self.total();
+        }
+
+        // `elapsed` is below `duration` here, so the share never exceeds `total`
+        // and the product of two `u64`s cannot overflow a `u128`
+        let elapsed = now.abs_diff(self.start_ts()) as u128;
+        let duration = self.end_ts().abs_diff(self.start_ts()) as u128;
+        (self.total() as u128 * elapsed / duration) as u64
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        seed: u64,
+        grantor: Pubkey,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        bump: [u8; 1],
+    ) {
+        self.seed = seed.to_le_bytes();
+        self.grantor = grantor;
+        self.beneficiary = beneficiary;
+        self.mint = mint;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn set_schedule(
+        &mut self,
+        total: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        revocable: bool,
+    ) {
+        self.total = total.to_le_bytes();
+        self.start_ts = start_ts.to_le_bytes();
+        self.cliff_ts = cliff_ts.to_le_bytes();
+        self.end_ts = end_ts.to_le_bytes();
+        self.revocable = revocable as u8;
+    }
+
+    #[inline(always)]
+    pub fn set_claimed(&mut self, claimed: u64) {
+        self.claimed = claimed.to_le_bytes();
+    }
+}
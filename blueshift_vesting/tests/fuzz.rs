@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio::program_error::ProgramError;
+    use pinocchio_vesting::{AccountLayout, CreateVestingInstructionData, Vesting, VestingError, ID};
+    use proptest::{collection::vec, prelude::*};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        fuzz::{check_invariants, check_rejected, fuzz, Fuzzer},
+        token_program, MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const INSTRUCTION_COUNT: u8 = 3;
+
+    fn fuzzer() -> Fuzzer {
+        let grantor = Pubkey::new_unique();
+        let vesting = Pubkey::find_program_address(
+            &[b"vesting", grantor.as_ref(), &42u64.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0;
+        let (token_program, token_account) = token_program();
+
+        let mut fuzzer = Fuzzer::new(
+            PROGRAM_ID,
+            vec![
+                grantor,
+                vesting,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+        .fixed(token_program, token_account)
+        .data_lens(&[Vesting::LEN, MINT_LEN, TOKEN_ACCOUNT_LEN])
+        .max_accounts(10);
+        fuzzer.owners.push(token_program);
+        fuzzer
+    }
+
+    fn mollusk() -> Mollusk {
+        let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_vesting");
+        test_support::add_token_programs(&mut mollusk);
+        mollusk
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_create_vesting_data(
+            data in vec(any::<u8>(), 0..=CreateVestingInstructionData::LEN + 8),
+        ) {
+            let parsed = CreateVestingInstructionData::try_from(data.as_slice());
+
+            if data.len() != CreateVestingInstructionData::LEN {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+
+            let field = |index: usize| data[index * 8..][..8].try_into().unwrap();
+            let (start_ts, cliff_ts, end_ts) = (
+                i64::from_le_bytes(field(2)),
+                i64::from_le_bytes(field(3)),
+                i64::from_le_bytes(field(4)),
+            );
+            if u64::from_le_bytes(field(1)) == 0 {
+                prop_assert_eq!(parsed.err(), Some(VestingError::ZeroAmount.into()));
+                return Ok(());
+            }
+            if data[40] > 1 {
+                prop_assert_eq!(parsed.err(), Some(ProgramError::InvalidInstructionData));
+                return Ok(());
+            }
+            if start_ts >= end_ts || cliff_ts < start_ts || cliff_ts > end_ts {
+                prop_assert_eq!(parsed.err(), Some(VestingError::InvalidSchedule.into()));
+                return Ok(());
+            }
+
+            let parsed = parsed.unwrap();
+            prop_assert_eq!(
+                (parsed.seed, parsed.amount),
+                (u64::from_le_bytes(field(0)), u64::from_le_bytes(field(1)))
+            );
+            prop_assert_eq!(
+                (parsed.start_ts, parsed.cliff_ts, parsed.end_ts, parsed.revocable),
+                (start_ts, cliff_ts, end_ts, data[40])
+            );
+        }
+
+        #[test]
+        fn test_parse_vesting_header(
+            data in vec(any::<u8>(), Vesting::LEN - 1..=Vesting::LEN + 1),
+            tagged in any::<bool>(),
+        ) {
+            let mut data = data;
+            if tagged {
+                data[0] = Vesting::DISCRIMINATOR;
+                data[1] = Vesting::VERSION;
+            }
+
+            let valid = data.len() == Vesting::LEN
+                && data[0] == Vesting::DISCRIMINATOR
+                && data[1] == Vesting::VERSION;
+            prop_assert_eq!(Vesting::load(&data).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_any_input() {
+        let mollusk = mollusk();
+        let data = (0..INSTRUCTION_COUNT, vec(any::<u8>(), 0..=48))
+            .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat());
+        fuzz(fuzzer().cases_with(data), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_fuzz_unknown_instruction() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().unknown_instruction_cases(INSTRUCTION_COUNT),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+
+    #[test]
+    fn test_fuzz_create_vesting_wrong_length() {
+        let mollusk = mollusk();
+        fuzz(
+            fuzzer().wrong_length_cases(0, CreateVestingInstructionData::LEN),
+            |case| check_rejected(&mollusk, &case),
+        );
+    }
+}
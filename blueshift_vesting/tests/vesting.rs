@@ -0,0 +1,411 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mollusk_svm::{result::Check, MolluskContext};
+    use pinocchio_vesting::{
+        AccountLayout, ClaimVested, CreateVesting, RevokeVesting, Vesting, VestingError, ID,
+    };
+    use solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        add_token_programs, associated_token_address, associated_token_program, donate_tokens,
+        funded_payer, system_program, token_program, AtaBuilder, MintBuilder,
+    };
+
+    pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+    const SEED: u64 = 3;
+    const TOTAL: u64 = 1_000;
+    const START_TS: i64 = 1_000;
+    const CLIFF_TS: i64 = 1_250;
+    const END_TS: i64 = 2_000;
+
+    type Context = MolluskContext<HashMap<Pubkey, Account>>;
+
+    // Grantor holding TOTAL of the mint at unix time START_TS, with no grant yet
+    struct Fixture {
+        context: Context,
+        grantor: Pubkey,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        vesting: Pubkey,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut mollusk = test_support::mollusk(&PROGRAM_ID, "pinocchio_vesting");
+            add_token_programs(&mut mollusk);
+            mollusk.sysvars.clock.unix_timestamp = START_TS;
+
+            let (grantor, grantor_account) = funded_payer();
+            let (beneficiary, beneficiary_account) = funded_payer();
+            let mint = Pubkey::new_unique();
+
+            let mut store = HashMap::new();
+            store.insert(grantor, grantor_account);
+            store.insert(beneficiary, beneficiary_account);
+            store.insert(
+                mint,
+                MintBuilder::new(&Pubkey::new_unique())
+                    .supply(TOTAL)
+                    .build(&mollusk),
+            );
+            let (grantor_ata, account) = AtaBuilder::new(&grantor, &mint)
+                .amount(TOTAL)
+                .build(&mollusk);
+            store.insert(grantor_ata, account);
+
+            Self {
+                context: mollusk.with_context(store),
+                grantor,
+                beneficiary,
+                mint,
+                vesting: vesting_address(&grantor, SEED),
+            }
+        }
+
+        fn ata(&self, owner: &Pubkey) -> Pubkey {
+            associated_token_address(owner, &self.mint, &token_program().0)
+        }
+
+        fn account(&self, key: &Pubkey) -> Account {
+            self.context
+                .account_store
+                .borrow()
+                .get(key)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn token_amount(&self, key: &Pubkey) -> u64 {
+            let data = self.account(key).data;
+            u64::from_le_bytes(data[64..72].try_into().unwrap())
+        }
+
+        fn set_time(&mut self, unix_timestamp: i64) {
+            self.context.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        }
+
+        fn programs() -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(system_program().0, false),
+                AccountMeta::new_readonly(token_program().0, false),
+                AccountMeta::new_readonly(associated_token_program().0, false),
+            ]
+        }
+
+        fn create(&self, cliff_ts: i64, revocable: bool) -> Instruction {
+            let mut data = vec![*CreateVesting::DISCRIMINATOR];
+            data.extend_from_slice(&SEED.to_le_bytes());
+            data.extend_from_slice(&TOTAL.to_le_bytes());
+            data.extend_from_slice(&START_TS.to_le_bytes());
+            data.extend_from_slice(&cliff_ts.to_le_bytes());
+            data.extend_from_slice(&END_TS.to_le_bytes());
+            data.push(revocable as u8);
+
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &data,
+                [
+                    vec![
+                        AccountMeta::new(self.grantor, true),
+                        AccountMeta::new_readonly(self.beneficiary, false),
+                        AccountMeta::new(self.vesting, false),
+                        AccountMeta::new_readonly(self.mint, false),
+                        AccountMeta::new(self.ata(&self.grantor), false),
+                        AccountMeta::new(self.ata(&self.vesting), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn claim(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*ClaimVested::DISCRIMINATOR],
+                [
+                    vec![
+                        AccountMeta::new(self.beneficiary, true),
+                        AccountMeta::new(self.grantor, false),
+                        AccountMeta::new(self.vesting, false),
+                        AccountMeta::new_readonly(self.mint, false),
+                        AccountMeta::new(self.ata(&self.vesting), false),
+                        AccountMeta::new(self.ata(&self.beneficiary), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        fn revoke(&self) -> Instruction {
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[*RevokeVesting::DISCRIMINATOR],
+                [
+                    vec![
+                        AccountMeta::new(self.grantor, true),
+                        AccountMeta::new_readonly(self.beneficiary, false),
+                        AccountMeta::new(self.vesting, false),
+                        AccountMeta::new_readonly(self.mint, false),
+                        AccountMeta::new(self.ata(&self.vesting), false),
+                        AccountMeta::new(self.ata(&self.grantor), false),
+                        AccountMeta::new(self.ata(&self.beneficiary), false),
+                    ],
+                    Self::programs(),
+                ]
+                .concat(),
+            )
+        }
+
+        // Send tokens straight to the vault, as anyone can
+        fn donate(&self, amount: u64) {
+            let vault = self.ata(&self.vesting);
+            let mut store = self.context.account_store.borrow_mut();
+            donate_tokens(store.get_mut(&vault).unwrap(), amount);
+        }
+
+        // Run CreateVesting with a revocable grant, as the setup of the others
+        fn created(self) -> Self {
+            self.context.process_and_validate_instruction(
+                &self.create(CLIFF_TS, true),
+                &[Check::success()],
+            );
+            self
+        }
+    }
+
+    fn vesting_address(grantor: &Pubkey, seed: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"vesting", grantor.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn custom(error: VestingError) -> Check<'static> {
+        Check::err(ProgramError::Custom(error as u32))
+    }
+
+    #[test]
+    fn test_vested() {
+        let mut data = vec![0u8; Vesting::LEN];
+        let vesting = Vesting::init(&mut data).unwrap();
+        vesting.set_schedule(TOTAL, START_TS, CLIFF_TS, END_TS, false);
+
+        assert_eq!(vesting.vested(START_TS), 0);
+        assert_eq!(vesting.vested(CLIFF_TS - 1), 0);
+        // The cliff releases everything accrued since the start
+        assert_eq!(vesting.vested(CLIFF_TS), 250);
+        assert_eq!(vesting.vested(1_333), 333);
+        assert_eq!(vesting.vested(END_TS), TOTAL);
+        assert_eq!(vesting.vested(i64::MAX), TOTAL);
+    }
+
+    #[test]
+    fn test_create_vesting() {
+        let fixture = Fixture::new().created();
+
+        let data = fixture.account(&fixture.vesting).data;
+        assert_eq!(data.len(), Vesting::LEN);
+        assert_eq!(&data[..2], &[Vesting::DISCRIMINATOR, Vesting::VERSION]);
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.vesting)), TOTAL);
+    }
+
+    #[test]
+    fn test_create_vesting_rejects_invalid_schedule() {
+        let fixture = Fixture::new();
+
+        for cliff_ts in [START_TS - 1, END_TS + 1] {
+            fixture.context.process_and_validate_instruction(
+                &fixture.create(cliff_ts, true),
+                &[custom(VestingError::InvalidSchedule)],
+            );
+        }
+    }
+
+    #[test]
+    fn test_claim_rejects_before_cliff() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(CLIFF_TS - 1);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(),
+            &[custom(VestingError::NothingToClaim)],
+        );
+    }
+
+    #[test]
+    fn test_claim_linear_unlock() {
+        let mut fixture = Fixture::new().created();
+        let beneficiary_ata = fixture.ata(&fixture.beneficiary);
+
+        fixture.set_time(1_500);
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.claim(), &[Check::success()]);
+        assert_eq!(fixture.token_amount(&beneficiary_ata), 500);
+
+        // Nothing more until time passes
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(),
+            &[custom(VestingError::NothingToClaim)],
+        );
+
+        fixture.set_time(1_750);
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.claim(), &[Check::success()]);
+        assert_eq!(fixture.token_amount(&beneficiary_ata), 750);
+    }
+
+    #[test]
+    fn test_final_claim_closes_grant() {
+        let mut fixture = Fixture::new().created();
+        let grantor_lamports = fixture.account(&fixture.grantor).lamports;
+        let rent = fixture.account(&fixture.vesting).lamports
+            + fixture.account(&fixture.ata(&fixture.vesting)).lamports;
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(),
+            &[
+                Check::success(),
+                Check::account(&fixture.vesting).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.beneficiary)),
+            TOTAL
+        );
+        assert_eq!(
+            fixture.account(&fixture.grantor).lamports,
+            grantor_lamports + rent
+        );
+    }
+
+    #[test]
+    fn test_claim_after_final_claim() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(END_TS);
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.claim(), &[Check::success()]);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(),
+            &[custom(VestingError::InvalidVestingAccount)],
+        );
+    }
+
+    #[test]
+    fn test_revoke_splits_vault() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(1_500);
+        fixture
+            .context
+            .process_and_validate_instruction(&fixture.claim(), &[Check::success()]);
+
+        fixture.set_time(1_600);
+        fixture.context.process_and_validate_instruction(
+            &fixture.revoke(),
+            &[
+                Check::success(),
+                Check::account(&fixture.vesting).closed().build(),
+            ],
+        );
+
+        // The vested 600 goes to the beneficiary, the unvested 400 back to the grantor
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.beneficiary)),
+            600
+        );
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.grantor)), 400);
+        assert_eq!(fixture.account(&fixture.ata(&fixture.vesting)).lamports, 0);
+    }
+
+    #[test]
+    fn test_final_claim_sweeps_donated_tokens() {
+        let mut fixture = Fixture::new().created();
+        fixture.donate(1);
+        fixture.set_time(END_TS);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.claim(),
+            &[
+                Check::success(),
+                Check::account(&fixture.vesting).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.beneficiary)),
+            TOTAL + 1
+        );
+    }
+
+    #[test]
+    fn test_revoke_returns_donated_tokens_to_grantor() {
+        let mut fixture = Fixture::new().created();
+        fixture.donate(1);
+        fixture.set_time(1_600);
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.revoke(),
+            &[
+                Check::success(),
+                Check::account(&fixture.vesting).closed().build(),
+            ],
+        );
+
+        assert_eq!(
+            fixture.token_amount(&fixture.ata(&fixture.beneficiary)),
+            600
+        );
+        assert_eq!(fixture.token_amount(&fixture.ata(&fixture.grantor)), 401);
+    }
+
+    #[test]
+    fn test_revoke_rejects_irrevocable_grant() {
+        let fixture = Fixture::new();
+        fixture.context.process_and_validate_instruction(
+            &fixture.create(CLIFF_TS, false),
+            &[Check::success()],
+        );
+
+        fixture.context.process_and_validate_instruction(
+            &fixture.revoke(),
+            &[custom(VestingError::NotRevocable)],
+        );
+    }
+
+    #[test]
+    fn test_claim_rejects_other_signer() {
+        let mut fixture = Fixture::new().created();
+        fixture.set_time(END_TS);
+        let mut instruction = fixture.claim();
+        instruction.accounts[0] = AccountMeta::new(fixture.grantor, true);
+
+        fixture.context.process_and_validate_instruction(
+            &instruction,
+            &[custom(VestingError::InvalidAddress)],
+        );
+    }
+
+    #[test]
+    fn test_vesting_error_codes() {
+        for code in 6000..6012 {
+            let error = VestingError::from_code(code).unwrap();
+            assert_eq!(error as u32, code);
+        }
+        assert_eq!(VestingError::from_code(6012), None);
+    }
+}
@@ -0,0 +1,562 @@
+//! Measures every instruction of every program and compares the compute units
+//! to `baseline.json`. See the crate docs for how to run it.
+
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use compute_units::{
+    baseline_from, compare, load_baseline, regressions, render_json, render_markdown,
+    save_baseline, Measurement, Session,
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+};
+use test_support::{
+    add_token_programs, associated_token_address, associated_token_program, funded_payer,
+    system_account, system_program, token_program, AtaBuilder, MintBuilder,
+};
+
+fn main() -> ExitCode {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let baseline_path = manifest_dir.join("baseline.json");
+    let report_dir = manifest_dir.join("../target/compute-units");
+
+    let measurements: Vec<Measurement> = [
+        hello_solana(),
+        account_data(),
+        close_account(),
+        counter(),
+        favorites(),
+        sol_transfer(),
+        escrow(),
+        vault(),
+    ]
+    .concat();
+
+    if std::env::var_os("CU_UPDATE_BASELINE").is_some() {
+        save_baseline(&baseline_path, &baseline_from(&measurements)).unwrap();
+        println!("Wrote {}", baseline_path.display());
+    }
+
+    let tolerance = std::env::var("CU_TOLERANCE")
+        .map(|tolerance| tolerance.parse().expect("CU_TOLERANCE must be a number"))
+        .unwrap_or(0.0);
+    let baseline = load_baseline(&baseline_path).unwrap();
+    let rows = compare(&measurements, &baseline, tolerance);
+
+    let markdown = render_markdown(&rows);
+    fs::create_dir_all(&report_dir).unwrap();
+    fs::write(report_dir.join("report.md"), &markdown).unwrap();
+    fs::write(report_dir.join("report.json"), render_json(&rows)).unwrap();
+    println!("{markdown}");
+
+    let regressions = regressions(&rows);
+    if regressions.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+
+    for row in regressions {
+        eprintln!(
+            "{}::{} regressed: {} CUs against a baseline of {}",
+            row.measurement.program,
+            row.measurement.instruction,
+            row.measurement.compute_units,
+            row.baseline.unwrap_or_default(),
+        );
+    }
+    ExitCode::FAILURE
+}
+
+fn writable(key: Pubkey) -> AccountMeta {
+    AccountMeta::new(key, false)
+}
+
+fn signer(key: Pubkey) -> AccountMeta {
+    AccountMeta::new(key, true)
+}
+
+fn readonly(key: Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(key, false)
+}
+
+fn hello_solana() -> Vec<Measurement> {
+    let program_id = Pubkey::new_from_array(hello_solana::ID);
+    let mut session = Session::new(
+        "hello_solana",
+        test_support::mollusk(&program_id, "hello_solana"),
+        [],
+    );
+
+    session.measure(
+        "Hello",
+        &Instruction::new_with_bytes(program_id, &[], vec![]),
+    );
+    session.finish()
+}
+
+fn account_data() -> Vec<Measurement> {
+    use account_data::{instructions::CreateAddressInfoInstructionData, state::AddressInfo};
+
+    let program_id = Pubkey::new_from_array(account_data::ID);
+    let (payer, payer_account) = funded_payer();
+    let address_info = Pubkey::new_unique();
+    let mut session = Session::new(
+        "account_data",
+        test_support::mollusk(&program_id, "account_data"),
+        [(payer, payer_account), system_program()],
+    );
+
+    let data = CreateAddressInfoInstructionData {
+        name: test_support::create_padded_array(b"Raunit", 50),
+        house_number: 69,
+        street: test_support::create_padded_array(b"Kolkata Street", 50),
+        city: test_support::create_padded_array(b"Main City", 50),
+    };
+    session.measure(
+        "Create",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[0], bytemuck::bytes_of(&data)].concat(),
+            vec![
+                signer(payer),
+                AccountMeta::new(address_info, true),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+
+    session.strip_header(&address_info, AddressInfo::V1_LEN);
+    session.measure(
+        "Migrate",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[1],
+            vec![
+                signer(payer),
+                writable(address_info),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+
+    session.finish()
+}
+
+fn close_account() -> Vec<Measurement> {
+    use close_acccount::state::User;
+
+    let program_id = Pubkey::new_from_array(close_acccount::ID);
+    let (payer, payer_account) = funded_payer();
+    let (user, _) = Pubkey::find_program_address(&[User::SEED_PREFIX, payer.as_ref()], &program_id);
+    let mut session = Session::new(
+        "close_acccount",
+        test_support::mollusk(&program_id, "close_acccount"),
+        [(payer, payer_account), system_program()],
+    );
+
+    let accounts = vec![signer(payer), writable(user), readonly(system_program().0)];
+    let name: [u8; 64] = test_support::create_padded_array(b"raunit", 64);
+
+    session.measure(
+        "CreateUser",
+        &Instruction::new_with_bytes(program_id, &[&[0], &name[..]].concat(), accounts.clone()),
+    );
+
+    session.strip_header(&user, User::V1_LEN);
+    session.measure(
+        "MigrateUser",
+        &Instruction::new_with_bytes(program_id, &[2], accounts.clone()),
+    );
+
+    session.measure(
+        "CloseUser",
+        &Instruction::new_with_bytes(program_id, &[1], accounts),
+    );
+
+    session.finish()
+}
+
+fn counter() -> Vec<Measurement> {
+    use counter::{
+        constants::COUNTER_SEED, instructions::CreateCounterInstructionData, state::Counter,
+    };
+
+    let program_id = Pubkey::new_from_array(counter::ID);
+    let (owner, owner_account) = funded_payer();
+    let (counter, bump) = Pubkey::find_program_address(&[COUNTER_SEED], &program_id);
+    let mut session = Session::new(
+        "counter",
+        test_support::mollusk(&program_id, "counter"),
+        [(owner, owner_account), system_program()],
+    );
+
+    let accounts = vec![
+        signer(owner),
+        writable(counter),
+        readonly(system_program().0),
+    ];
+    let data = CreateCounterInstructionData {
+        initial_value: 100u64.to_le_bytes(),
+        bump,
+    };
+
+    session.measure(
+        "Create",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[0], bytemuck::bytes_of(&data)].concat(),
+            accounts.clone(),
+        ),
+    );
+    session.measure(
+        "Increase",
+        &Instruction::new_with_bytes(program_id, &[1], accounts.clone()),
+    );
+    session.measure(
+        "Decrease",
+        &Instruction::new_with_bytes(program_id, &[2], accounts.clone()),
+    );
+
+    session.strip_header(&counter, Counter::V1_LEN);
+    session.measure(
+        "Migrate",
+        &Instruction::new_with_bytes(program_id, &[3], accounts),
+    );
+
+    session.finish()
+}
+
+fn favorites() -> Vec<Measurement> {
+    use favorites::{
+        constants::FAVORITES_SEED, instructions::CreatePdaInstructionData, state::Favorites,
+    };
+
+    let program_id = Pubkey::new_from_array(favorites::ID);
+    let (user, user_account) = funded_payer();
+    let (favorites, bump) =
+        Pubkey::find_program_address(&[FAVORITES_SEED, user.as_ref()], &program_id);
+    let mut session = Session::new(
+        "favorites",
+        test_support::mollusk(&program_id, "favorites"),
+        [(user, user_account), system_program()],
+    );
+
+    let accounts = vec![
+        signer(user),
+        writable(favorites),
+        readonly(system_program().0),
+    ];
+    let data = CreatePdaInstructionData {
+        number: 7u64.to_le_bytes(),
+        color: test_support::create_padded_array(b"red", 50),
+        hobbies: [test_support::create_padded_array(b"reading", 50); 5],
+        bump,
+    };
+
+    session.measure(
+        "CreatePda",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[0], bytemuck::bytes_of(&data)].concat(),
+            accounts.clone(),
+        ),
+    );
+    session.measure(
+        "GetPda",
+        &Instruction::new_with_bytes(program_id, &[1], vec![signer(user), readonly(favorites)]),
+    );
+
+    session.strip_header(&favorites, Favorites::V1_LEN);
+    session.measure(
+        "Migrate",
+        &Instruction::new_with_bytes(program_id, &[2], accounts),
+    );
+
+    session.finish()
+}
+
+fn sol_transfer() -> Vec<Measurement> {
+    use sol_trasnfer::{
+        constants::STREAM_SEED, instructions::create_stream::CreateStreamInstructionData,
+        state::Stream,
+    };
+
+    const SEED: u64 = 7;
+    const TOTAL: u64 = LAMPORTS_PER_SOL;
+    const START: i64 = 1_000;
+    const END: i64 = 2_000;
+
+    let program_id = Pubkey::new_from_array(sol_trasnfer::ID);
+    let (payer, payer_account) = funded_payer();
+    let recipient = Pubkey::new_unique();
+    // TransferSolWithProgram debits the payer directly, which needs a
+    // program-owned account
+    let program_payer = Pubkey::new_unique();
+    let (stream, bump) = Pubkey::find_program_address(
+        &[
+            STREAM_SEED,
+            payer.as_ref(),
+            recipient.as_ref(),
+            &SEED.to_le_bytes(),
+        ],
+        &program_id,
+    );
+    let mut session = Session::new(
+        "sol_trasnfer",
+        test_support::mollusk(&program_id, "sol_trasnfer"),
+        [
+            (payer, payer_account),
+            (recipient, system_account(0)),
+            (
+                program_payer,
+                Account::new(LAMPORTS_PER_SOL, 0, &program_id),
+            ),
+            system_program(),
+        ],
+    );
+
+    let amount = (LAMPORTS_PER_SOL / 10).to_le_bytes();
+    session.measure(
+        "TransferSolWithProgram",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[0], &amount[..]].concat(),
+            vec![
+                signer(program_payer),
+                writable(recipient),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+    session.measure(
+        "TransferSolWithCpi",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[1], &amount[..]].concat(),
+            vec![
+                signer(payer),
+                writable(recipient),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+
+    let data = CreateStreamInstructionData {
+        seed: SEED.to_le_bytes(),
+        total: TOTAL.to_le_bytes(),
+        start_ts: START.to_le_bytes(),
+        end_ts: END.to_le_bytes(),
+        bump,
+    };
+    session.context.mollusk.sysvars.clock.unix_timestamp = START;
+    session.measure(
+        "CreateStream",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[2], bytemuck::bytes_of(&data)].concat(),
+            vec![
+                signer(payer),
+                writable(recipient),
+                writable(stream),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+
+    session.context.mollusk.sysvars.clock.unix_timestamp = (START + END) / 2;
+    session.measure(
+        "WithdrawStream",
+        &Instruction::new_with_bytes(program_id, &[3], vec![signer(recipient), writable(stream)]),
+    );
+
+    session.strip_header(&stream, Stream::V1_LEN);
+    session.measure(
+        "MigrateStream",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[5],
+            vec![
+                signer(payer),
+                writable(stream),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+
+    session.measure(
+        "CancelStream",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[4],
+            vec![signer(payer), writable(recipient), writable(stream)],
+        ),
+    );
+
+    session.finish()
+}
+
+fn escrow() -> Vec<Measurement> {
+    use pinocchio_escrow::{Escrow, Make, Migrate, Refund, Take};
+
+    const DEPOSIT: u64 = 1_000_000;
+    const RECEIVE: u64 = 250_000;
+
+    let program_id = Pubkey::new_from_array(pinocchio_escrow::ID);
+    let mut mollusk = test_support::mollusk(&program_id, "pinocchio_escrow");
+    add_token_programs(&mut mollusk);
+
+    let token_program = token_program().0;
+    let ata = |owner: &Pubkey, mint: &Pubkey| associated_token_address(owner, mint, &token_program);
+
+    let (maker, maker_account) = funded_payer();
+    let (taker, taker_account) = funded_payer();
+    let mint_a = Pubkey::new_unique();
+    let mint_b = Pubkey::new_unique();
+    let mint = MintBuilder::new(&Pubkey::new_unique())
+        .supply(2 * DEPOSIT + RECEIVE)
+        .build(&mollusk);
+    let (maker_ata_a, maker_ata_a_account) = AtaBuilder::new(&maker, &mint_a)
+        .amount(2 * DEPOSIT)
+        .build(&mollusk);
+    let (taker_ata_b, taker_ata_b_account) = AtaBuilder::new(&taker, &mint_b)
+        .amount(RECEIVE)
+        .build(&mollusk);
+
+    let mut session = Session::new(
+        "pinocchio_escrow",
+        mollusk,
+        [
+            (maker, maker_account),
+            (taker, taker_account),
+            (mint_a, mint.clone()),
+            (mint_b, mint),
+            (maker_ata_a, maker_ata_a_account),
+            (taker_ata_b, taker_ata_b_account),
+        ],
+    );
+
+    let escrow_for = |seed: u64| {
+        let (escrow, _) = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), &seed.to_le_bytes()],
+            &program_id,
+        );
+        (escrow, ata(&escrow, &mint_a))
+    };
+    let make = |seed: u64| {
+        let (escrow, vault) = escrow_for(seed);
+        let data = [
+            vec![*Make::DISCRIMINATOR],
+            seed.to_le_bytes().to_vec(),
+            RECEIVE.to_le_bytes().to_vec(),
+            DEPOSIT.to_le_bytes().to_vec(),
+        ]
+        .concat();
+
+        Instruction::new_with_bytes(
+            program_id,
+            &data,
+            vec![
+                signer(maker),
+                writable(escrow),
+                readonly(mint_a),
+                readonly(mint_b),
+                writable(maker_ata_a),
+                writable(vault),
+                readonly(system_program().0),
+                readonly(token_program),
+                readonly(associated_token_program().0),
+            ],
+        )
+    };
+
+    // Taken escrow
+    let (escrow, vault) = escrow_for(1);
+    session.measure("Make", &make(1));
+    session.measure(
+        "Take",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[*Take::DISCRIMINATOR],
+            vec![
+                signer(taker),
+                writable(maker),
+                writable(escrow),
+                readonly(mint_a),
+                readonly(mint_b),
+                writable(vault),
+                writable(ata(&taker, &mint_a)),
+                writable(taker_ata_b),
+                writable(ata(&maker, &mint_b)),
+                readonly(system_program().0),
+                readonly(token_program),
+                readonly(associated_token_program().0),
+            ],
+        ),
+    );
+
+    // Migrated, then refunded escrow
+    let (escrow, vault) = escrow_for(2);
+    session.run(&make(2));
+    session.strip_header(&escrow, Escrow::V1_LEN);
+    session.measure(
+        "Migrate",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[*Migrate::DISCRIMINATOR],
+            vec![
+                signer(maker),
+                writable(escrow),
+                readonly(system_program().0),
+            ],
+        ),
+    );
+    session.measure(
+        "Refund",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[*Refund::DISCRIMINATOR],
+            vec![
+                signer(maker),
+                writable(escrow),
+                readonly(mint_a),
+                writable(vault),
+                writable(maker_ata_a),
+                readonly(system_program().0),
+                readonly(token_program),
+                readonly(associated_token_program().0),
+            ],
+        ),
+    );
+
+    session.finish()
+}
+
+fn vault() -> Vec<Measurement> {
+    let program_id = Pubkey::new_from_array(blueshift_vault::ID);
+    let (owner, owner_account) = funded_payer();
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", owner.as_ref()], &program_id);
+    let mut session = Session::new(
+        "blueshift_vault",
+        test_support::mollusk(&program_id, "blueshift_vault"),
+        [(owner, owner_account), system_program()],
+    );
+
+    let accounts = vec![signer(owner), writable(vault), readonly(system_program().0)];
+
+    session.measure(
+        "Deposit",
+        &Instruction::new_with_bytes(
+            program_id,
+            &[&[0], &LAMPORTS_PER_SOL.to_le_bytes()[..]].concat(),
+            accounts.clone(),
+        ),
+    );
+    session.measure(
+        "Withdraw",
+        &Instruction::new_with_bytes(program_id, &[1], accounts),
+    );
+
+    session.finish()
+}
@@ -0,0 +1,278 @@
+//! Compute-unit benchmarks for every program in the workspace.
+//!
+//! The `compute_units` bench runs each instruction on Mollusk, records the
+//! compute units it consumed and compares them to `baseline.json`:
+//!
+//! ```sh
+//! cargo build-sbf
+//! cargo bench -p compute-units                          # fails on a regression
+//! CU_UPDATE_BASELINE=1 cargo bench -p compute-units     # accept the new numbers
+//! ```
+//!
+//! `CU_TOLERANCE` sets how many percent above the baseline an instruction may
+//! go before it counts as a regression (0 by default, compute units are
+//! deterministic). The report is written to `target/compute-units/` as
+//! `report.md` and `report.json`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+};
+
+use mollusk_svm::{result::ContextResult, Mollusk, MolluskContext};
+use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey};
+
+/// Compute units of one instruction, as measured by Mollusk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub program: String,
+    pub instruction: String,
+    pub compute_units: u64,
+}
+
+/// Stored compute units, keyed by program then instruction name.
+pub type Baseline = BTreeMap<String, BTreeMap<String, u64>>;
+
+/// Runs the instructions of one program against a stateful Mollusk context,
+/// so each instruction sees the accounts left behind by the previous one.
+pub struct Session {
+    pub context: MolluskContext<HashMap<Pubkey, Account>>,
+    program: &'static str,
+    measurements: Vec<Measurement>,
+}
+
+impl Session {
+    pub fn new(
+        program: &'static str,
+        mollusk: Mollusk,
+        accounts: impl IntoIterator<Item = (Pubkey, Account)>,
+    ) -> Self {
+        Self {
+            context: mollusk.with_context(accounts.into_iter().collect()),
+            program,
+            measurements: Vec::new(),
+        }
+    }
+
+    /// Process a setup instruction without recording it.
+    ///
+    /// Panics if it fails, since every later number would be meaningless.
+    pub fn run(&mut self, instruction: &Instruction) -> ContextResult {
+        let result = self.context.process_instruction(instruction);
+        if result.program_result.is_err() {
+            panic!(
+                "{}: instruction failed with {:?}",
+                self.program, result.program_result
+            );
+        }
+        result
+    }
+
+    /// Process `instruction` and record its compute units under `name`.
+    pub fn measure(&mut self, name: &str, instruction: &Instruction) {
+        let result = self.context.process_instruction(instruction);
+        if result.program_result.is_err() {
+            panic!(
+                "{}::{name} failed with {:?}",
+                self.program, result.program_result
+            );
+        }
+
+        self.measurements.push(Measurement {
+            program: self.program.to_string(),
+            instruction: name.to_string(),
+            compute_units: result.compute_units_consumed,
+        });
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Account {
+        self.context
+            .account_store
+            .borrow()
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.context.account_store.borrow_mut().insert(key, account);
+    }
+
+    /// Rewrite a tagged state account into its v1 layout by dropping the
+    /// two-byte header and any fields past `v1_len`, so `Migrate` has
+    /// something to migrate.
+    pub fn strip_header(&mut self, key: &Pubkey, v1_len: usize) {
+        let mut account = self.account(key);
+        account.data.drain(..2);
+        account.data.truncate(v1_len);
+        self.set_account(*key, account);
+    }
+
+    pub fn finish(self) -> Vec<Measurement> {
+        self.measurements
+    }
+}
+
+/// How a measurement compares to the baseline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Not in the baseline yet.
+    New,
+    Unchanged,
+    Improved,
+    /// Within the tolerance, but above the baseline.
+    Increased,
+    /// Above the baseline by more than the tolerance.
+    Regressed,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Unchanged => "unchanged",
+            Self::Improved => "improved",
+            Self::Increased => "increased",
+            Self::Regressed => "REGRESSED",
+        }
+    }
+}
+
+/// One line of the report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub measurement: Measurement,
+    pub baseline: Option<u64>,
+    pub status: Status,
+}
+
+impl Row {
+    /// Difference to the baseline, in compute units.
+    pub fn delta(&self) -> Option<i64> {
+        self.baseline
+            .map(|baseline| self.measurement.compute_units as i64 - baseline as i64)
+    }
+}
+
+/// Compare measurements to the baseline, allowing `tolerance_percent` of
+/// growth before an instruction counts as regressed.
+pub fn compare(
+    measurements: &[Measurement],
+    baseline: &Baseline,
+    tolerance_percent: f64,
+) -> Vec<Row> {
+    measurements
+        .iter()
+        .map(|measurement| {
+            let stored = baseline
+                .get(&measurement.program)
+                .and_then(|instructions| instructions.get(&measurement.instruction))
+                .copied();
+
+            let status = match stored {
+                None => Status::New,
+                Some(stored) if measurement.compute_units == stored => Status::Unchanged,
+                Some(stored) if measurement.compute_units < stored => Status::Improved,
+                Some(stored) => {
+                    let allowed = stored as f64 * (1.0 + tolerance_percent / 100.0);
+                    if measurement.compute_units as f64 > allowed {
+                        Status::Regressed
+                    } else {
+                        Status::Increased
+                    }
+                }
+            };
+
+            Row {
+                measurement: measurement.clone(),
+                baseline: stored,
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Rows that fail the run.
+pub fn regressions(rows: &[Row]) -> Vec<&Row> {
+    rows.iter()
+        .filter(|row| row.status == Status::Regressed)
+        .collect()
+}
+
+/// Baseline holding exactly the given measurements.
+pub fn baseline_from(measurements: &[Measurement]) -> Baseline {
+    let mut baseline = Baseline::new();
+    for measurement in measurements {
+        baseline
+            .entry(measurement.program.clone())
+            .or_default()
+            .insert(measurement.instruction.clone(), measurement.compute_units);
+    }
+    baseline
+}
+
+/// Read a baseline, treating a missing file as empty.
+pub fn load_baseline(path: &Path) -> io::Result<Baseline> {
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Baseline::new()),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn save_baseline(path: &Path, baseline: &Baseline) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(baseline).map_err(io::Error::other)?;
+    fs::write(path, json + "\n")
+}
+
+/// Markdown table of the rows, one line per instruction.
+pub fn render_markdown(rows: &[Row]) -> String {
+    let mut out = String::from(
+        "| Program | Instruction | CUs | Baseline | Delta | Status |\n\
+         |---|---|---:|---:|---:|---|\n",
+    );
+
+    for row in rows {
+        let baseline = row
+            .baseline
+            .map_or_else(|| "-".to_string(), |baseline| baseline.to_string());
+        let delta = row
+            .delta()
+            .map_or_else(|| "-".to_string(), |delta| format!("{delta:+}"));
+
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            row.measurement.program,
+            row.measurement.instruction,
+            row.measurement.compute_units,
+            baseline,
+            delta,
+            row.status.label(),
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// JSON array of the rows, for CI tooling.
+pub fn render_json(rows: &[Row]) -> String {
+    let rows: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            serde_json::json!({
+                "program": row.measurement.program,
+                "instruction": row.measurement.instruction,
+                "compute_units": row.measurement.compute_units,
+                "baseline": row.baseline,
+                "delta": row.delta(),
+                "status": row.status.label(),
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&rows).unwrap() + "\n"
+}
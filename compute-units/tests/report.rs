@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use compute_units::{
+        baseline_from, compare, load_baseline, regressions, render_json, render_markdown,
+        save_baseline, Measurement, Status,
+    };
+
+    fn measurement(instruction: &str, compute_units: u64) -> Measurement {
+        Measurement {
+            program: "counter".to_string(),
+            instruction: instruction.to_string(),
+            compute_units,
+        }
+    }
+
+    #[test]
+    fn test_compare_statuses() {
+        let baseline = baseline_from(&[
+            measurement("Create", 1_000),
+            measurement("Increase", 1_000),
+            measurement("Decrease", 1_000),
+            measurement("Migrate", 1_000),
+        ]);
+
+        let rows = compare(
+            &[
+                measurement("Create", 1_000),
+                measurement("Increase", 900),
+                measurement("Decrease", 1_010),
+                measurement("Migrate", 1_030),
+                measurement("Close", 500),
+            ],
+            &baseline,
+            2.0,
+        );
+
+        let statuses: Vec<_> = rows.iter().map(|row| row.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                Status::Unchanged,
+                Status::Improved,
+                Status::Increased,
+                Status::Regressed,
+                Status::New,
+            ]
+        );
+        assert_eq!(rows[1].delta(), Some(-100));
+        assert_eq!(rows[4].delta(), None);
+
+        let regressed = regressions(&rows);
+        assert_eq!(regressed.len(), 1);
+        assert_eq!(regressed[0].measurement.instruction, "Migrate");
+    }
+
+    #[test]
+    fn test_zero_tolerance_fails_on_any_increase() {
+        let baseline = baseline_from(&[measurement("Create", 1_000)]);
+        let rows = compare(&[measurement("Create", 1_001)], &baseline, 0.0);
+
+        assert_eq!(rows[0].status, Status::Regressed);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = std::env::temp_dir().join(format!("cu-baseline-{}.json", std::process::id()));
+        let baseline = baseline_from(&[measurement("Create", 1_000), measurement("Increase", 900)]);
+
+        save_baseline(&path, &baseline).unwrap();
+        assert_eq!(load_baseline(&path).unwrap(), baseline);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(load_baseline(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_render_reports() {
+        let baseline = baseline_from(&[measurement("Create", 1_000)]);
+        let rows = compare(
+            &[measurement("Create", 1_200), measurement("Increase", 900)],
+            &baseline,
+            0.0,
+        );
+
+        let markdown = render_markdown(&rows);
+        assert!(markdown.contains("| counter | Create | 1200 | 1000 | +200 | REGRESSED |"));
+        assert!(markdown.contains("| counter | Increase | 900 | - | - | new |"));
+
+        let json: serde_json::Value = serde_json::from_str(&render_json(&rows)).unwrap();
+        assert_eq!(json[0]["delta"], 200);
+        assert_eq!(json[1]["baseline"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,291 @@
+//! Derive macros that describe program types for the `idl` crate.
+//!
+//! Every generated impl is wrapped in `#[cfg(feature = "idl-build")]`, so a
+//! program only depends on `idl` while its IDL is being generated and its
+//! on-chain build does not change.
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, ExprLit, Field, Fields, Lit,
+    LitStr, Meta, Result,
+};
+
+/// Types that `#[idl(ty = "...")]` may name.
+const PRIMITIVES: &[&str] = &[
+    "bool", "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128", "f32", "f64",
+    "pubkey",
+];
+
+/// Implements `idl::IdlAccounts` for an accounts struct, one account per
+/// field in declaration order.
+///
+/// Field attributes:
+///
+/// - `#[idl(signer)]` and `#[idl(writable)]` set the account flags.
+/// - `#[idl(address = "<base58>")]` pins the account to a fixed address.
+/// - `#[idl(optional)]` marks a trailing account the instruction can do
+///   without.
+/// - `#[idl(skip)]` leaves out a field holding a variable number of trailing
+///   accounts, which the IDL cannot list.
+///
+/// The `signer` and `mut` checks of an `accounts_derive::Accounts`
+/// `#[account(...)]` attribute set the same flags, so a field only needs
+/// `#[idl(...)]` for what its checks do not cover.
+///
+/// The struct attribute `#[accounts(system_program)]`, which has
+/// `accounts_derive::Accounts` check a trailing system program that has no
+/// field, appends the system program.
+#[proc_macro_derive(IdlAccounts, attributes(idl, account, accounts))]
+pub fn derive_idl_accounts(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_accounts(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `idl::IdlStruct` and `idl::IdlType` for a struct with named
+/// fields, such as instruction data or an account's state.
+///
+/// Field attributes:
+///
+/// - `#[idl(ty = "u64")]` overrides the field type, for the little-endian
+///   byte arrays the programs store numbers and keys in.
+/// - `#[idl(skip)]` leaves the field out, for example a header that the IDL
+///   already describes as a discriminator.
+#[proc_macro_derive(IdlType, attributes(idl))]
+pub fn derive_idl_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_type(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_accounts(input: &DeriveInput) -> Result<TokenStream2> {
+    let mut system_program = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("accounts"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("system_program") {
+                system_program = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `system_program`"))
+            }
+        })?;
+    }
+
+    let mut accounts = Vec::new();
+    for field in named_fields(input)? {
+        let name = field_name(field);
+        let docs = docs(&field.attrs);
+        let (mut signer, mut writable) = account_flags(&field.attrs);
+        let mut optional = false;
+        let mut skip = false;
+        let mut address: Option<LitStr> = None;
+
+        for attr in idl_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                } else if meta.path.is_ident("signer") {
+                    signer = true;
+                } else if meta.path.is_ident("writable") {
+                    writable = true;
+                } else if meta.path.is_ident("optional") {
+                    optional = true;
+                } else if meta.path.is_ident("address") {
+                    address = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta
+                        .error("expected `signer`, `writable`, `optional`, `address` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if skip {
+            continue;
+        }
+
+        let address = match address {
+            Some(address) => quote!(::core::option::Option::Some(#address)),
+            None => quote!(::core::option::Option::None),
+        };
+
+        accounts.push(quote! {
+            ::idl::IdlAccount {
+                name: #name,
+                docs: ::idl::__private::vec![#(#docs),*],
+                writable: #writable,
+                signer: #signer,
+                optional: #optional,
+                address: #address,
+            }
+        });
+    }
+
+    if system_program {
+        accounts.push(quote!(::idl::IdlAccount::system_program()));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[cfg(feature = "idl-build")]
+        impl #impl_generics ::idl::IdlAccounts for #ident #ty_generics #where_clause {
+            fn idl_accounts() -> ::idl::__private::Vec<::idl::IdlAccount> {
+                ::idl::__private::vec![#(#accounts),*]
+            }
+        }
+    })
+}
+
+fn expand_type(input: &DeriveInput) -> Result<TokenStream2> {
+    let mut fields = Vec::new();
+    for field in named_fields(input)? {
+        let mut skip = false;
+        let mut ty: Option<LitStr> = None;
+
+        for attr in idl_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                } else if meta.path.is_ident("ty") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    if !PRIMITIVES.contains(&lit.value().as_str()) {
+                        return Err(Error::new(
+                            lit.span(),
+                            format!("expected one of {}", PRIMITIVES.join(", ")),
+                        ));
+                    }
+                    ty = Some(lit);
+                } else {
+                    return Err(meta.error("expected `skip` or `ty`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if skip {
+            continue;
+        }
+
+        let name = field_name(field);
+        let docs = docs(&field.attrs);
+        let field_ty = &field.ty;
+        let ty = match ty {
+            Some(ty) => quote!(::idl::Type::Primitive(#ty)),
+            None => quote!(<#field_ty as ::idl::IdlType>::idl_type()),
+        };
+
+        fields.push(quote! {
+            ::idl::IdlField {
+                name: #name,
+                docs: ::idl::__private::vec![#(#docs),*],
+                ty: #ty,
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let docs = docs(&input.attrs);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[cfg(feature = "idl-build")]
+        impl #impl_generics ::idl::IdlStruct for #ident #ty_generics #where_clause {
+            fn idl_name() -> &'static str {
+                #name
+            }
+
+            fn idl_docs() -> ::idl::__private::Vec<&'static str> {
+                ::idl::__private::vec![#(#docs),*]
+            }
+
+            fn idl_fields() -> ::idl::__private::Vec<::idl::IdlField> {
+                ::idl::__private::vec![#(#fields),*]
+            }
+        }
+
+        #[cfg(feature = "idl-build")]
+        impl #impl_generics ::idl::IdlType for #ident #ty_generics #where_clause {
+            fn idl_type() -> ::idl::Type {
+                ::idl::Type::Defined(#name)
+            }
+        }
+    })
+}
+
+fn named_fields(input: &DeriveInput) -> Result<impl Iterator<Item = &Field>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields.named.iter()),
+            _ => Err(Error::new_spanned(
+                &input.ident,
+                "expected a struct with named fields",
+            )),
+        },
+        _ => Err(Error::new_spanned(&input.ident, "expected a struct")),
+    }
+}
+
+fn field_name(field: &Field) -> String {
+    field.ident.as_ref().expect("named field").to_string()
+}
+
+/// The `signer` and `mut` checks among a field's `#[account(...)]`
+/// attributes. Only the first token of each comma-separated check is looked
+/// at, so expressions such as `constraint = a.is_signer()` do not count.
+fn account_flags(attrs: &[Attribute]) -> (bool, bool) {
+    let mut signer = false;
+    let mut writable = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("account")) {
+        let Meta::List(list) = &attr.meta else {
+            continue;
+        };
+        let mut check_start = true;
+        for token in list.tokens.clone() {
+            match &token {
+                TokenTree::Ident(ident) if check_start => {
+                    signer |= ident == "signer";
+                    writable |= ident == "mut";
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                    check_start = true;
+                    continue;
+                }
+                _ => {}
+            }
+            check_start = false;
+        }
+    }
+    (signer, writable)
+}
+
+fn idl_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("idl"))
+}
+
+/// The `///` lines of an item, without the leading space.
+fn docs(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+//! Generates the IDL of every program in the workspace.
+//!
+//! ```sh
+//! cargo run -p idl-gen    # writes target/idl/<program>.json
+//! ```
+//!
+//! Set `IDL_OUT_DIR` to write the files somewhere else. `hello-solana`
+//! accepts any instruction and has no IDL.
+
+use std::{fs, io, path::PathBuf};
+
+use idl::Idl;
+
+/// The IDL of every program, built from its `idl-build` feature.
+pub fn idls() -> Vec<Idl> {
+    vec![
+        account_data::idl::idl(),
+        close_acccount::idl::idl(),
+        counter::idl::idl(),
+        favorites::idl::idl(),
+        sol_trasnfer::idl::idl(),
+        pinocchio_auction::idl::idl(),
+        pinocchio_distributor::idl::idl(),
+        pinocchio_escrow::idl::idl(),
+        pinocchio_pool::idl::idl(),
+        pinocchio_staking::idl::idl(),
+        blueshift_vault::idl::idl(),
+        pinocchio_vesting::idl::idl(),
+    ]
+}
+
+/// Directory the IDLs are written to: `IDL_OUT_DIR`, or the workspace
+/// `target/idl`.
+pub fn out_dir() -> PathBuf {
+    match std::env::var_os("IDL_OUT_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("target")
+            .join("idl"),
+    }
+}
+
+/// Write each IDL to `<out_dir>/<program>.json` and return the paths.
+pub fn write_all(idls: &[Idl]) -> io::Result<Vec<PathBuf>> {
+    let dir = out_dir();
+    fs::create_dir_all(&dir)?;
+
+    idls.iter()
+        .map(|idl| {
+            let path = dir.join(idl.name).with_extension("json");
+            let json = serde_json::to_string_pretty(&idl.to_json()).map_err(io::Error::other)?;
+            fs::write(&path, json + "\n")?;
+            Ok(path)
+        })
+        .collect()
+}
@@ -0,0 +1,6 @@
+fn main() {
+    let paths = idl_gen::write_all(&idl_gen::idls()).expect("failed to write the IDLs");
+    for path in paths {
+        println!("{}", path.display());
+    }
+}
@@ -0,0 +1,301 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, mem::size_of};
+
+    use idl::{Idl, IdlField, Type};
+    use pinocchio_escrow::AccountLayout;
+
+    fn idl(name: &str) -> Idl {
+        idl_gen::idls()
+            .into_iter()
+            .find(|idl| idl.name == name)
+            .unwrap_or_else(|| panic!("no IDL for {name}"))
+    }
+
+    fn type_size(ty: &Type) -> usize {
+        match ty {
+            Type::Primitive("bool" | "u8" | "i8") => 1,
+            Type::Primitive("u16" | "i16") => 2,
+            Type::Primitive("u32" | "i32") => 4,
+            Type::Primitive("u64" | "i64") => 8,
+            Type::Primitive("u128" | "i128") => 16,
+            Type::Primitive("pubkey") => 32,
+            Type::Array(ty, len) => type_size(ty) * len,
+            ty => panic!("no fixed size for {ty:?}"),
+        }
+    }
+
+    fn fields_size(fields: &[IdlField]) -> usize {
+        fields.iter().map(|field| type_size(&field.ty)).sum()
+    }
+
+    /// Bytes of instruction data after the discriminator.
+    fn args_size(idl: &Idl, instruction: &str) -> usize {
+        fields_size(&idl.instruction_named(instruction).unwrap().args)
+    }
+
+    /// Bytes of account data, discriminator included.
+    fn account_size(idl: &Idl, account: &str) -> usize {
+        let def = idl.accounts.iter().find(|def| def.name == account).unwrap();
+        let ty = idl.types.iter().find(|ty| ty.name == account).unwrap();
+        def.discriminator.len() + fields_size(&ty.fields)
+    }
+
+    #[test]
+    fn test_idls_are_consistent() {
+        for idl in idl_gen::idls() {
+            let discriminators: HashSet<_> = idl
+                .instructions
+                .iter()
+                .map(|instruction| instruction.discriminator.clone())
+                .collect();
+            assert_eq!(discriminators.len(), idl.instructions.len(), "{}", idl.name);
+
+            for instruction in &idl.instructions {
+                let names: HashSet<_> = instruction.accounts.iter().map(|a| a.name).collect();
+                assert_eq!(
+                    names.len(),
+                    instruction.accounts.len(),
+                    "{}",
+                    instruction.name
+                );
+            }
+
+            assert!(!idl.errors.is_empty(), "{}", idl.name);
+            assert_eq!(idl.errors[0].code, idl::FIRST_ERROR_CODE);
+        }
+    }
+
+    #[test]
+    fn test_layouts_match_the_programs() {
+        let counter = idl("counter");
+        assert_eq!(
+            args_size(&counter, "create"),
+            counter::instructions::CreateCounterInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&counter, "Counter"),
+            counter::state::Counter::LEN
+        );
+
+        let favorites = idl("favorites");
+        assert_eq!(
+            args_size(&favorites, "create_pda"),
+            size_of::<favorites::instructions::CreatePdaInstructionData>()
+        );
+        assert_eq!(
+            account_size(&favorites, "Favorites"),
+            favorites::state::Favorites::LEN
+        );
+
+        let account_data = idl("account_data");
+        assert_eq!(
+            args_size(&account_data, "create"),
+            size_of::<account_data::instructions::CreateAddressInfoInstructionData>()
+        );
+        assert_eq!(
+            account_size(&account_data, "AddressInfo"),
+            account_data::state::AddressInfo::LEN
+        );
+
+        let close_account = idl("close_acccount");
+        assert_eq!(
+            args_size(&close_account, "create_user"),
+            size_of::<close_acccount::instructions::create_user::CreateUserInstructionData>()
+        );
+        assert_eq!(
+            account_size(&close_account, "User"),
+            close_acccount::state::User::LEN
+        );
+
+        let transfer = idl("sol_trasnfer");
+        assert_eq!(
+            args_size(&transfer, "create_stream"),
+            size_of::<sol_trasnfer::instructions::create_stream::CreateStreamInstructionData>()
+        );
+        assert_eq!(
+            args_size(&transfer, "transfer_sol_with_cpi"),
+            size_of::<u64>()
+        );
+        assert_eq!(
+            account_size(&transfer, "Stream"),
+            sol_trasnfer::state::Stream::LEN
+        );
+
+        let escrow = idl("pinocchio_escrow");
+        assert_eq!(
+            args_size(&escrow, "make"),
+            pinocchio_escrow::MakeInstructionData::AUCTION_LEN
+        );
+        assert_eq!(
+            args_size(&escrow, "take"),
+            pinocchio_escrow::TakeInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&escrow, "Escrow"),
+            pinocchio_escrow::Escrow::LEN
+        );
+        assert_eq!(
+            args_size(&escrow, "make_basket"),
+            pinocchio_escrow::MakeBasketInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&escrow, "Basket"),
+            pinocchio_escrow::Basket::LEN
+        );
+        assert_eq!(
+            args_size(&escrow, "open_service"),
+            pinocchio_escrow::OpenServiceInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&escrow, "resolve_service"),
+            pinocchio_escrow::ResolveServiceInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&escrow, "ServiceEscrow"),
+            pinocchio_escrow::ServiceEscrow::LEN
+        );
+        assert_eq!(
+            args_size(&escrow, "write_option"),
+            pinocchio_escrow::WriteOptionInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&escrow, "buy_option"),
+            pinocchio_escrow::BuyOptionInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&escrow, "CallOption"),
+            pinocchio_escrow::CallOption::LEN
+        );
+
+        let pool = idl("pinocchio_pool");
+        assert_eq!(
+            args_size(&pool, "initialize_pool"),
+            pinocchio_pool::InitializePoolInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&pool, "add_liquidity"),
+            pinocchio_pool::AddLiquidityInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&pool, "remove_liquidity"),
+            pinocchio_pool::RemoveLiquidityInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&pool, "swap"),
+            pinocchio_pool::SwapInstructionData::LEN
+        );
+        assert_eq!(account_size(&pool, "Pool"), pinocchio_pool::Pool::LEN);
+
+        let staking = idl("pinocchio_staking");
+        assert_eq!(
+            args_size(&staking, "create_stake_pool"),
+            pinocchio_staking::CreateStakePoolInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&staking, "fund_rewards"),
+            pinocchio_staking::FundRewardsInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&staking, "stake"),
+            pinocchio_staking::StakeInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&staking, "unstake"),
+            pinocchio_staking::UnstakeInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&staking, "StakePool"),
+            pinocchio_staking::StakePool::LEN
+        );
+        assert_eq!(
+            account_size(&staking, "StakeEntry"),
+            pinocchio_staking::StakeEntry::LEN
+        );
+
+        let distributor = idl("pinocchio_distributor");
+        assert_eq!(
+            args_size(&distributor, "create_distributor"),
+            pinocchio_distributor::CreateDistributorInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&distributor, "claim_airdrop"),
+            pinocchio_distributor::ClaimAirdropInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&distributor, "Distributor"),
+            pinocchio_distributor::Distributor::LEN
+        );
+        assert_eq!(
+            account_size(&distributor, "ClaimStatus"),
+            pinocchio_distributor::ClaimStatus::LEN
+        );
+
+        let auction = idl("pinocchio_auction");
+        assert_eq!(
+            args_size(&auction, "create_auction"),
+            pinocchio_auction::CreateAuctionInstructionData::LEN
+        );
+        assert_eq!(
+            args_size(&auction, "bid"),
+            pinocchio_auction::BidInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&auction, "Auction"),
+            pinocchio_auction::Auction::LEN
+        );
+
+        let vesting = idl("pinocchio_vesting");
+        assert_eq!(
+            args_size(&vesting, "create_vesting"),
+            pinocchio_vesting::CreateVestingInstructionData::LEN
+        );
+        assert_eq!(
+            account_size(&vesting, "Vesting"),
+            pinocchio_vesting::Vesting::LEN
+        );
+
+        let vault = idl("blueshift_vault");
+        assert_eq!(args_size(&vault, "deposit"), size_of::<u64>());
+        assert_eq!(args_size(&vault, "withdraw"), 0);
+    }
+
+    #[test]
+    fn test_counter_idl() {
+        let counter = idl("counter");
+        let json = counter.to_json();
+
+        assert_eq!(
+            json["address"],
+            "Ag8tR8rXHLwUGPCfgGUJYjcYnFnqFdJ8XfjGP5LeRpg6"
+        );
+
+        let create = &json["instructions"][0];
+        assert_eq!(create["name"], "create");
+        assert_eq!(create["discriminator"], serde_json::json!([0]));
+        assert_eq!(
+            create["accounts"],
+            serde_json::json!([
+                { "name": "maker", "writable": true, "signer": true },
+                { "name": "counter", "writable": true },
+                { "name": "system_program", "address": idl::SYSTEM_PROGRAM },
+            ])
+        );
+        assert_eq!(
+            create["args"],
+            serde_json::json!([
+                { "name": "initial_value", "type": "u64" },
+                { "name": "bump", "type": "u8" },
+            ])
+        );
+
+        assert_eq!(
+            json["accounts"],
+            serde_json::json!([{ "name": "Counter", "discriminator": [1, 3] }])
+        );
+        assert_eq!(
+            json["errors"].as_array().unwrap().len(),
+            counter.errors.len()
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Interface descriptions (IDLs) for the pinocchio programs.
+//!
+//! The programs describe their accounts structs, instruction data and state
+//! with the [`IdlAccounts`] and [`IdlType`] derives. Behind its `idl-build`
+//! feature each program also exposes an `idl()` function that lists its
+//! instructions, accounts and errors. [`Idl::to_json`] renders the result in
+//! the Anchor IDL format (spec 0.1.0, as produced by Anchor 0.30), which
+//! Anchor's TypeScript client and Codama's Anchor importer both read.
+//!
+//! ```sh
+//! cargo run -p idl-gen    # writes target/idl/<program>.json
+//! ```
+//!
+//! The discriminators are the programs' own rather than Anchor's hashes: one
+//! byte for an instruction, and the discriminator and version bytes that
+//! start every state account.
+
+use std::fmt::{Debug, Display};
+
+use serde_json::{json, Map, Value};
+
+pub use idl_derive::{IdlAccounts, IdlType};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use std::{vec, vec::Vec};
+}
+
+/// Address of the system program.
+pub const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+/// Address of the SPL Token program.
+pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Address of the Associated Token Account program.
+pub const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Code of the first custom error, shared by every program's error enum.
+pub const FIRST_ERROR_CODE: u32 = 6000;
+
+/// The type of an instruction argument or a struct field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    /// A primitive such as `u64` or `pubkey`.
+    Primitive(&'static str),
+    /// A fixed-size array.
+    Array(Box<Type>, usize),
+    /// A struct listed under the IDL's `types`.
+    Defined(&'static str),
+}
+
+impl Type {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Primitive(name) => json!(name),
+            Self::Array(ty, len) => json!({ "array": [ty.to_json(), len] }),
+            Self::Defined(name) => json!({ "defined": { "name": name } }),
+        }
+    }
+}
+
+/// A Rust type with an IDL counterpart.
+pub trait IdlType {
+    fn idl_type() -> Type;
+}
+
+macro_rules! primitive {
+    ($($ty:ty => $name:literal),* $(,)?) => {
+        $(
+            impl IdlType for $ty {
+                fn idl_type() -> Type {
+                    Type::Primitive($name)
+                }
+            }
+        )*
+    };
+}
+
+primitive! {
+    bool => "bool",
+    u8 => "u8",
+    i8 => "i8",
+    u16 => "u16",
+    i16 => "i16",
+    u32 => "u32",
+    i32 => "i32",
+    u64 => "u64",
+    i64 => "i64",
+    u128 => "u128",
+    i128 => "i128",
+}
+
+impl<T: IdlType, const N: usize> IdlType for [T; N] {
+    fn idl_type() -> Type {
+        Type::Array(Box::new(T::idl_type()), N)
+    }
+}
+
+/// A struct with named fields, derived with `#[derive(IdlType)]`.
+pub trait IdlStruct {
+    fn idl_name() -> &'static str;
+    fn idl_docs() -> Vec<&'static str>;
+    fn idl_fields() -> Vec<IdlField>;
+}
+
+/// The accounts of an instruction, derived with `#[derive(IdlAccounts)]`.
+pub trait IdlAccounts {
+    fn idl_accounts() -> Vec<IdlAccount>;
+}
+
+/// One account an instruction takes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlAccount {
+    pub name: &'static str,
+    pub docs: Vec<&'static str>,
+    pub writable: bool,
+    pub signer: bool,
+    /// The instruction also accepts the accounts before this one alone.
+    pub optional: bool,
+    /// Fixed address, for programs and sysvars.
+    pub address: Option<&'static str>,
+}
+
+impl IdlAccount {
+    pub fn system_program() -> Self {
+        Self {
+            name: "system_program",
+            docs: Vec::new(),
+            writable: false,
+            signer: false,
+            optional: false,
+            address: Some(SYSTEM_PROGRAM),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut account = Map::new();
+        account.insert("name".into(), json!(self.name));
+        insert_docs(&mut account, &self.docs);
+        if self.writable {
+            account.insert("writable".into(), json!(true));
+        }
+        if self.signer {
+            account.insert("signer".into(), json!(true));
+        }
+        if self.optional {
+            account.insert("optional".into(), json!(true));
+        }
+        if let Some(address) = self.address {
+            account.insert("address".into(), json!(address));
+        }
+        Value::Object(account)
+    }
+}
+
+/// A named instruction argument or struct field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlField {
+    pub name: &'static str,
+    pub docs: Vec<&'static str>,
+    pub ty: Type,
+}
+
+impl IdlField {
+    pub fn to_json(&self) -> Value {
+        let mut field = Map::new();
+        field.insert("name".into(), json!(self.name));
+        insert_docs(&mut field, &self.docs);
+        field.insert("type".into(), self.ty.to_json());
+        Value::Object(field)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlInstruction {
+    pub name: &'static str,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<IdlAccount>,
+    pub args: Vec<IdlField>,
+}
+
+impl IdlInstruction {
+    /// An instruction without accounts or arguments, selected by the first
+    /// byte of the instruction data.
+    pub fn new(name: &'static str, discriminator: u8) -> Self {
+        Self {
+            name,
+            discriminator: vec![discriminator],
+            accounts: Vec::new(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn accounts<A: IdlAccounts>(mut self) -> Self {
+        self.accounts = A::idl_accounts();
+        self
+    }
+
+    /// Take the arguments from the fields of `T`, which follow the
+    /// discriminator in the instruction data.
+    pub fn args<T: IdlStruct>(mut self) -> Self {
+        self.args = T::idl_fields();
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "discriminator": self.discriminator,
+            "accounts": self.accounts.iter().map(IdlAccount::to_json).collect::<Vec<_>>(),
+            "args": self.args.iter().map(IdlField::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// A struct listed under the IDL's `types`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlTypeDef {
+    pub name: &'static str,
+    pub docs: Vec<&'static str>,
+    pub fields: Vec<IdlField>,
+}
+
+impl IdlTypeDef {
+    pub fn of<T: IdlStruct>() -> Self {
+        Self {
+            name: T::idl_name(),
+            docs: T::idl_docs(),
+            fields: T::idl_fields(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut def = Map::new();
+        def.insert("name".into(), json!(self.name));
+        insert_docs(&mut def, &self.docs);
+        def.insert(
+            "type".into(),
+            json!({
+                "kind": "struct",
+                "fields": self.fields.iter().map(IdlField::to_json).collect::<Vec<_>>(),
+            }),
+        );
+        Value::Object(def)
+    }
+}
+
+/// A state account type and the bytes its data starts with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlAccountDef {
+    pub name: &'static str,
+    pub discriminator: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlError {
+    pub code: u32,
+    pub name: String,
+    pub msg: String,
+}
+
+/// The IDL of one program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Idl {
+    pub name: &'static str,
+    pub version: &'static str,
+    /// Base58 program id.
+    pub address: String,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlAccountDef>,
+    pub types: Vec<IdlTypeDef>,
+    pub errors: Vec<IdlError>,
+}
+
+impl Idl {
+    pub fn new(name: &'static str, version: &'static str, program_id: &[u8; 32]) -> Self {
+        Self {
+            name,
+            version,
+            address: bs58::encode(program_id).into_string(),
+            instructions: Vec::new(),
+            accounts: Vec::new(),
+            types: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn instruction(mut self, instruction: IdlInstruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Add the state account `T`, whose data starts with `discriminator`
+    /// and continues with the fields of `T`.
+    pub fn account<T: IdlStruct>(mut self, discriminator: &[u8]) -> Self {
+        self.accounts.push(IdlAccountDef {
+            name: T::idl_name(),
+            discriminator: discriminator.to_vec(),
+        });
+        self.types.push(IdlTypeDef::of::<T>());
+        self
+    }
+
+    /// List the program's errors by decoding codes from
+    /// [`FIRST_ERROR_CODE`] up until `from_code` returns `None`. The name is
+    /// the variant's `Debug` output and the message its `Display` output.
+    pub fn errors<E: Debug + Display>(mut self, from_code: impl Fn(u32) -> Option<E>) -> Self {
+        self.errors = (FIRST_ERROR_CODE..)
+            .map_while(|code| {
+                from_code(code).map(|error| IdlError {
+                    code,
+                    name: format!("{error:?}"),
+                    msg: error.to_string(),
+                })
+            })
+            .collect();
+        self
+    }
+
+    pub fn instruction_named(&self, name: &str) -> Option<&IdlInstruction> {
+        self.instructions
+            .iter()
+            .find(|instruction| instruction.name == name)
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "address": self.address,
+            "metadata": {
+                "name": self.name,
+                "version": self.version,
+                "spec": "0.1.0",
+            },
+            "instructions": self.instructions.iter().map(IdlInstruction::to_json).collect::<Vec<_>>(),
+            "accounts": self
+                .accounts
+                .iter()
+                .map(|account| json!({ "name": account.name, "discriminator": account.discriminator }))
+                .collect::<Vec<_>>(),
+            "errors": self
+                .errors
+                .iter()
+                .map(|error| json!({ "code": error.code, "name": error.name, "msg": error.msg }))
+                .collect::<Vec<_>>(),
+            "types": self.types.iter().map(IdlTypeDef::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn insert_docs(object: &mut Map<String, Value>, docs: &[&'static str]) {
+    if !docs.is_empty() {
+        object.insert("docs".into(), json!(docs));
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use idl::{Idl, IdlAccount, IdlInstruction, IdlType, Type, SYSTEM_PROGRAM};
+    use serde_json::json;
+
+    #[derive(Debug)]
+    enum SampleError {
+        First,
+        Second,
+    }
+
+    impl SampleError {
+        fn from_code(code: u32) -> Option<Self> {
+            match code {
+                6000 => Some(Self::First),
+                6001 => Some(Self::Second),
+                _ => None,
+            }
+        }
+    }
+
+    impl core::fmt::Display for SampleError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::First => f.write_str("first"),
+                Self::Second => f.write_str("second"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_type_json() {
+        assert_eq!(u64::idl_type().to_json(), json!("u64"));
+        assert_eq!(
+            <[[u8; 50]; 5]>::idl_type().to_json(),
+            json!({ "array": [{ "array": ["u8", 50] }, 5] })
+        );
+        assert_eq!(
+            Type::Defined("Counter").to_json(),
+            json!({ "defined": { "name": "Counter" } })
+        );
+    }
+
+    #[test]
+    fn test_account_json_omits_unset_flags() {
+        let account = IdlAccount {
+            name: "counter",
+            docs: Vec::new(),
+            writable: true,
+            signer: false,
+            optional: false,
+            address: None,
+        };
+        assert_eq!(
+            account.to_json(),
+            json!({ "name": "counter", "writable": true })
+        );
+        assert_eq!(
+            IdlAccount::system_program().to_json(),
+            json!({ "name": "system_program", "address": SYSTEM_PROGRAM })
+        );
+    }
+
+    #[test]
+    fn test_idl_json() {
+        let idl = Idl::new("sample", "0.1.0", &[0; 32])
+            .instruction(IdlInstruction::new("ping", 7))
+            .errors(SampleError::from_code);
+
+        assert_eq!(
+            idl.to_json(),
+            json!({
+                "address": SYSTEM_PROGRAM,
+                "metadata": { "name": "sample", "version": "0.1.0", "spec": "0.1.0" },
+                "instructions": [
+                    { "name": "ping", "discriminator": [7], "accounts": [], "args": [] }
+                ],
+                "accounts": [],
+                "errors": [
+                    { "code": 6000, "name": "First", "msg": "first" },
+                    { "code": 6001, "name": "Second", "msg": "second" }
+                ],
+                "types": []
+            })
+        );
+    }
+}
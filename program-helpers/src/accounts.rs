@@ -0,0 +1,724 @@
+use core::marker::PhantomData;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{Pubkey, find_program_address},
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_associated_token_account::instructions::Create;
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount};
+use pinocchio_token::{
+    instructions::{CloseAccount, InitializeAccount3, InitializeMint2, Transfer},
+    state::Mint,
+};
+
+use crate::{GetAccountDataSizeInterface, InitializeAccount3Interface, InitializeMint2Interface};
+
+/// Errors the shared checks fail with, in the calling program's own codes.
+pub trait HelperError: Sized + Into<ProgramError> {
+    const NOT_SIGNER: Self;
+    const INVALID_MINT: Self;
+    const INVALID_TOKEN_ACCOUNT: Self;
+    /// A token account that is not at its associated token address.
+    const INVALID_ADDRESS: Self;
+    /// A state account whose discriminator belongs to another type.
+    const INVALID_ACCOUNT_DISCRIMINATOR: Self;
+    /// A state account on a layout version the program does not read.
+    const UNSUPPORTED_ACCOUNT_VERSION: Self;
+}
+
+pub trait AccountCheck {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError>;
+}
+
+pub struct SignerAccount<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for SignerAccount<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if !account.is_signer() {
+            return Err(E::NOT_SIGNER.into());
+        }
+        Ok(())
+    }
+}
+
+pub struct MintAccount<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for MintAccount<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if unsafe { account.owner().ne(&pinocchio_token::ID) } {
+            return Err(E::INVALID_MINT.into());
+        }
+
+        if account.data_len() != Mint::LEN {
+            return Err(E::INVALID_MINT.into());
+        }
+
+        Ok(())
+    }
+}
+
+pub trait MintInit {
+    fn init(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &[u8; 32],
+        freeze_authority: Option<&[u8; 32]>,
+    ) -> ProgramResult;
+    fn init_if_needed(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &[u8; 32],
+        freeze_authority: Option<&[u8; 32]>,
+    ) -> ProgramResult;
+}
+
+impl<E: HelperError> MintInit for MintAccount<E> {
+    fn init(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &[u8; 32],
+        freeze_authority: Option<&[u8; 32]>,
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(Mint::LEN);
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: pinocchio_token::state::Mint::LEN as u64,
+            owner: &pinocchio_token::ID,
+        }
+        .invoke()?;
+
+        InitializeMint2 {
+            mint: account,
+            decimals,
+            mint_authority,
+            freeze_authority,
+        }
+        .invoke()
+    }
+
+    fn init_if_needed(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &[u8; 32],
+        freeze_authority: Option<&[u8; 32]>,
+    ) -> ProgramResult {
+        match Self::check(account) {
+            Ok(_) => Ok(()),
+            Err(_) => Self::init(account, payer, decimals, mint_authority, freeze_authority),
+        }
+    }
+}
+
+pub struct TokenAccount<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for TokenAccount<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if !is_token_account(account) {
+            return Err(E::INVALID_TOKEN_ACCOUNT.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// An SPL Token account, as `TokenAccount` checks it.
+fn is_token_account(account: &AccountInfo) -> bool {
+    account.is_owned_by(&pinocchio_token::ID)
+        && account.data_len() == pinocchio_token::state::TokenAccount::LEN
+}
+
+pub trait TokenInit {
+    fn init(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &[u8; 32],
+    ) -> ProgramResult;
+    fn init_if_needed(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &[u8; 32],
+    ) -> ProgramResult;
+}
+
+impl<E: HelperError> TokenInit for TokenAccount<E> {
+    fn init(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &[u8; 32],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(pinocchio_token::state::TokenAccount::LEN);
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: pinocchio_token::state::TokenAccount::LEN as u64,
+            owner: &pinocchio_token::ID,
+        }
+        .invoke()?;
+
+        // Initialize the Token Account
+        InitializeAccount3 {
+            account,
+            mint,
+            owner,
+        }
+        .invoke()
+    }
+
+    fn init_if_needed(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &[u8; 32],
+    ) -> ProgramResult {
+        match Self::check(account) {
+            Ok(_) => Ok(()),
+            Err(_) => Self::init(account, mint, payer, owner),
+        }
+    }
+}
+
+// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: [u8; 32] = [
+    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
+    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
+];
+
+const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
+pub const TOKEN_2022_MINT_DISCRIMINATOR: u8 = 0x01;
+pub const TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 0x02;
+
+/// Token-2022 account data of `len` bytes, or longer with extensions tagged
+/// with `discriminator`. Data too short to carry the tag is neither.
+fn is_token_2022_account(data: &[u8], len: usize, discriminator: u8) -> bool {
+    data.len() == len
+        || (data.len() > TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET
+            && data[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET] == discriminator)
+}
+
+pub struct Mint2022Account<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for Mint2022Account<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if unsafe { account.owner().ne(&TOKEN_2022_PROGRAM_ID) } {
+            return Err(E::INVALID_MINT.into());
+        }
+
+        let data = account.try_borrow_data()?;
+
+        if !is_token_2022_account(&data, Mint::LEN, TOKEN_2022_MINT_DISCRIMINATOR) {
+            return Err(E::INVALID_MINT.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: HelperError> MintInit for Mint2022Account<E> {
+    fn init(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &[u8; 32],
+        freeze_authority: Option<&[u8; 32]>,
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(pinocchio_token::state::Mint::LEN);
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: pinocchio_token::state::Mint::LEN as u64,
+            owner: &TOKEN_2022_PROGRAM_ID,
+        }
+        .invoke()?;
+
+        InitializeMint2Interface {
+            mint: account,
+            decimals,
+            mint_authority,
+            freeze_authority,
+            token_program: &TOKEN_2022_PROGRAM_ID,
+        }
+        .invoke()
+    }
+
+    fn init_if_needed(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &[u8; 32],
+        freeze_authority: Option<&[u8; 32]>,
+    ) -> ProgramResult {
+        match Self::check(account) {
+            Ok(_) => Ok(()),
+            Err(_) => Self::init(account, payer, decimals, mint_authority, freeze_authority),
+        }
+    }
+}
+pub struct TokenAccount2022Account<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for TokenAccount2022Account<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if unsafe { account.owner().ne(&TOKEN_2022_PROGRAM_ID) } {
+            return Err(E::INVALID_TOKEN_ACCOUNT.into());
+        }
+
+        let data = account.try_borrow_data()?;
+
+        if !is_token_2022_account(
+            &data,
+            pinocchio_token::state::TokenAccount::LEN,
+            TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR,
+        ) {
+            return Err(E::INVALID_TOKEN_ACCOUNT.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: HelperError> TokenInit for TokenAccount2022Account<E> {
+    fn init(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &[u8; 32],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(pinocchio_token::state::TokenAccount::LEN);
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: pinocchio_token::state::TokenAccount::LEN as u64,
+            owner: &TOKEN_2022_PROGRAM_ID,
+        }
+        .invoke()?;
+
+        InitializeAccount3Interface {
+            account,
+            mint,
+            owner,
+            token_program: &TOKEN_2022_PROGRAM_ID,
+        }
+        .invoke()
+    }
+
+    fn init_if_needed(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &[u8; 32],
+    ) -> ProgramResult {
+        match Self::check(account) {
+            Ok(_) => Ok(()),
+            Err(_) => Self::init(account, mint, payer, owner),
+        }
+    }
+}
+
+pub struct MintInterface<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for MintInterface<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if unsafe { account.owner().ne(&TOKEN_2022_PROGRAM_ID) } {
+            MintAccount::<E>::check(account)
+        } else {
+            Mint2022Account::<E>::check(account)
+        }
+    }
+}
+
+impl<E: HelperError> MintInterface<E> {
+    /// Creates a mint without extensions under `token_program`.
+    pub fn init(
+        account: &AccountInfo,
+        payer: &AccountInfo,
+        decimals: u8,
+        mint_authority: &Pubkey,
+        token_program: &Pubkey,
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(Mint::LEN);
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: Mint::LEN as u64,
+            owner: token_program,
+        }
+        .invoke()?;
+
+        InitializeMint2Interface {
+            mint: account,
+            decimals,
+            mint_authority,
+            freeze_authority: None,
+            token_program,
+        }
+        .invoke()
+    }
+}
+
+pub struct TokenAccountInterface<E>(PhantomData<E>);
+
+impl<E: HelperError> AccountCheck for TokenAccountInterface<E> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if unsafe { account.owner().ne(&TOKEN_2022_PROGRAM_ID) } {
+            TokenAccount::<E>::check(account)
+        } else {
+            TokenAccount2022Account::<E>::check(account)
+        }
+    }
+}
+
+impl<E: HelperError> TokenAccountInterface<E> {
+    /// Creates a token account of `mint` under `token_program`, sized for the
+    /// extensions the mint requires its token accounts to carry.
+    pub fn init(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &Pubkey,
+        token_program: &Pubkey,
+    ) -> ProgramResult {
+        let space = GetAccountDataSizeInterface {
+            mint,
+            token_program,
+        }
+        .invoke()?;
+        let lamports = Rent::get()?.minimum_balance(space);
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: space as u64,
+            owner: token_program,
+        }
+        .invoke()?;
+
+        InitializeAccount3Interface {
+            account,
+            mint,
+            owner,
+            token_program,
+        }
+        .invoke()
+    }
+}
+
+pub struct TokenProgramInterface;
+
+impl AccountCheck for TokenProgramInterface {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key().ne(&pinocchio_token::ID) && account.key().ne(&TOKEN_2022_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(())
+    }
+}
+
+pub trait AssociatedTokenAccountCheck {
+    fn check(
+        account: &AccountInfo,
+        authority: &AccountInfo,
+        mint: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> Result<(), ProgramError>;
+}
+pub struct AssociatedTokenAccount<E>(PhantomData<E>);
+
+impl<E: HelperError> AssociatedTokenAccountCheck for AssociatedTokenAccount<E> {
+    fn check(
+        account: &AccountInfo,
+        authority: &AccountInfo,
+        mint: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        TokenAccount::<E>::check(account)?;
+
+        if !is_associated_token_address(account, authority, mint, token_program) {
+            return Err(E::INVALID_ADDRESS.into());
+        }
+
+        Ok(())
+    }
+}
+
+fn is_associated_token_address(
+    account: &AccountInfo,
+    authority: &AccountInfo,
+    mint: &AccountInfo,
+    token_program: &AccountInfo,
+) -> bool {
+    find_program_address(
+        &[authority.key(), token_program.key(), mint.key()],
+        &pinocchio_associated_token_account::ID,
+    )
+    .0
+    .eq(account.key())
+}
+
+pub trait AssociatedTokenAccountInit {
+    fn init(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &AccountInfo,
+        system_program: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> ProgramResult;
+    fn init_if_needed(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &AccountInfo,
+        system_program: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> ProgramResult;
+}
+
+impl<E: HelperError> AssociatedTokenAccountInit for AssociatedTokenAccount<E> {
+    fn init(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &AccountInfo,
+        system_program: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> ProgramResult {
+        create_associated_token_account(account, mint, payer, owner, system_program, token_program)
+    }
+
+    fn init_if_needed(
+        account: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        owner: &AccountInfo,
+        system_program: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> ProgramResult {
+        create_associated_token_account_if_needed(
+            account,
+            mint,
+            payer,
+            owner,
+            system_program,
+            token_program,
+        )
+    }
+}
+
+// The error type only names why a check failed, which creating the account
+// never reports, so `DrainVault` shares these with every program's
+// `AssociatedTokenAccount`.
+
+fn create_associated_token_account(
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    payer: &AccountInfo,
+    owner: &AccountInfo,
+    system_program: &AccountInfo,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    Create {
+        funding_account: payer,
+        account,
+        wallet: owner,
+        mint,
+        system_program,
+        token_program,
+    }
+    .invoke()
+}
+
+fn create_associated_token_account_if_needed(
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    payer: &AccountInfo,
+    owner: &AccountInfo,
+    system_program: &AccountInfo,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    if is_token_account(account) && is_associated_token_address(account, owner, mint, token_program)
+    {
+        return Ok(());
+    }
+
+    create_associated_token_account(account, mint, payer, owner, system_program, token_program)
+}
+
+/// Creates the accounts a program owns. `ProgramAccount` implements it with
+/// the owner of its state type.
+pub trait ProgramAccountInit {
+    /// Program that owns the accounts `init` creates.
+    const OWNER: Pubkey;
+
+    fn init<'a, T: Sized>(
+        payer: &AccountInfo,
+        account: &AccountInfo,
+        seeds: &[Seed<'a>],
+        space: usize,
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+
+        let signer = [Signer::from(seeds)];
+
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: space as u64,
+            owner: &Self::OWNER,
+        }
+        .invoke_signed(&signer)?;
+
+        Ok(())
+    }
+
+    /// Like `init`, for an address that anyone can fund ahead of time, which
+    /// would make `CreateAccount` fail. A pre-funded account is topped up to
+    /// rent exemption and claimed in place instead.
+    fn init_allow_prefunded<'a, T: Sized>(
+        payer: &AccountInfo,
+        account: &AccountInfo,
+        seeds: &[Seed<'a>],
+        space: usize,
+    ) -> ProgramResult {
+        if account.lamports() == 0 {
+            return Self::init::<T>(payer, account, seeds, space);
+        }
+
+        let lamports = Rent::get()?.minimum_balance(space);
+
+        let signer = [Signer::from(seeds)];
+
+        let shortfall = lamports.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            pinocchio_system::instructions::Transfer {
+                from: payer,
+                to: account,
+                lamports: shortfall,
+            }
+            .invoke()?;
+        }
+        Allocate {
+            account,
+            space: space as u64,
+        }
+        .invoke_signed(&signer)?;
+        Assign {
+            account,
+            owner: &Self::OWNER,
+        }
+        .invoke_signed(&signer)?;
+
+        Ok(())
+    }
+}
+
+pub trait AccountClose {
+    fn close(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+        {
+            let mut data = account.try_borrow_mut_data()?;
+            data[0] = 0xff;
+        }
+
+        {
+            let mut lamports = destination.try_borrow_mut_lamports()?;
+            *lamports = lamports
+                .checked_add(account.lamports())
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        account.realloc(1, true)?;
+        account.close()
+    }
+}
+
+/// Pays out everything a program-owned token vault holds, then closes it.
+///
+/// The balance is read from the vault rather than from the program's state,
+/// so tokens anyone sent to the vault directly go out with the rest. The
+/// token program refuses to close an account that still holds tokens, so
+/// leaving them behind would block closing the vault.
+pub struct DrainVault<'a> {
+    /// Vault to empty and close.
+    pub vault: &'a AccountInfo,
+
+    /// Associated token account that receives the balance.
+    pub to: &'a AccountInfo,
+
+    /// Owner of `to`.
+    pub owner: &'a AccountInfo,
+
+    /// Mint of the vault.
+    pub mint: &'a AccountInfo,
+
+    /// Pays for `to` if it has to be created.
+    pub payer: &'a AccountInfo,
+
+    /// Receives the vault's rent.
+    pub destination: &'a AccountInfo,
+
+    /// Program account that owns the vault.
+    pub authority: &'a AccountInfo,
+
+    /// System program.
+    pub system_program: &'a AccountInfo,
+
+    /// Token program.
+    pub token_program: &'a AccountInfo,
+}
+
+impl DrainVault<'_> {
+    /// Returns the amount paid out. `to` is only created when the vault
+    /// holds tokens.
+    pub fn invoke_signed(&self, signers: &[Signer]) -> Result<u64, ProgramError> {
+        let amount = pinocchio_token::state::TokenAccount::from_account_info(self.vault)?.amount();
+
+        if amount > 0 {
+            create_associated_token_account_if_needed(
+                self.to,
+                self.mint,
+                self.payer,
+                self.owner,
+                self.system_program,
+                self.token_program,
+            )?;
+
+            Transfer {
+                from: self.vault,
+                to: self.to,
+                authority: self.authority,
+                amount,
+            }
+            .invoke_signed(signers)?;
+        }
+
+        CloseAccount {
+            account: self.vault,
+            destination: self.destination,
+            authority: self.authority,
+        }
+        .invoke_signed(signers)?;
+
+        Ok(amount)
+    }
+}
@@ -0,0 +1,15 @@
+//! Account checks, account creation, state account layouts and token CPIs
+//! shared by the workspace's token programs.
+//!
+//! The checks report failures in the calling program's own error codes. Each
+//! program implements [`HelperError`] for its error enum and names the
+//! helpers it uses with that enum filled in, as in
+//! `pub type MintInterface = program_helpers::MintInterface<EscrowError>;`.
+
+pub mod accounts;
+pub mod state;
+pub mod token_interface;
+
+pub use accounts::*;
+pub use state::*;
+pub use token_interface::*;
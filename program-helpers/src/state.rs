@@ -0,0 +1,92 @@
+use core::{marker::PhantomData, mem::transmute};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{AccountCheck, AccountClose, HelperError, ProgramAccountInit};
+
+/// Layout shared by every state account: a one-byte discriminator and a
+/// one-byte layout version, followed by the type's fields. Each type only
+/// supplies its size, owner and header values, and gets the header handling
+/// here.
+pub trait AccountLayout: Sized {
+    /// Error enum of the owning program.
+    type Error: HelperError;
+
+    /// Program that owns accounts of this type.
+    const OWNER: Pubkey;
+    /// Size of the account data, header included.
+    const LEN: usize;
+    const DISCRIMINATOR: u8;
+    const VERSION: u8;
+    /// Error for an account that is not owned by the program or is not the
+    /// size of this type.
+    const INVALID: Self::Error;
+
+    /// Writes the header into freshly allocated account data.
+    #[inline(always)]
+    fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(Self::INVALID.into());
+        }
+        bytes[0] = Self::DISCRIMINATOR;
+        bytes[1] = Self::VERSION;
+        Self::load_mut(bytes)
+    }
+
+    #[inline(always)]
+    fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Checks the length, discriminator and version of raw account data.
+    #[inline(always)]
+    fn check_header(bytes: &[u8]) -> Result<(), ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(Self::INVALID.into());
+        }
+        if bytes[0] != Self::DISCRIMINATOR {
+            return Err(Self::Error::INVALID_ACCOUNT_DISCRIMINATOR.into());
+        }
+        if bytes[1] != Self::VERSION {
+            return Err(Self::Error::UNSUPPORTED_ACCOUNT_VERSION.into());
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `account` is owned by `T`'s program and holds a `T`.
+pub struct StateAccount<T>(PhantomData<T>);
+
+impl<T: AccountLayout> AccountCheck for StateAccount<T> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if unsafe { account.owner().ne(&T::OWNER) } {
+            return Err(T::INVALID.into());
+        }
+
+        T::check_header(&account.try_borrow_data()?)
+    }
+}
+
+/// Creates and closes the accounts of `T`'s program. A program names it with
+/// its main state type, as in
+/// `pub type ProgramAccount = program_helpers::ProgramAccount<Escrow>;`, and
+/// `check` accepts that type.
+pub struct ProgramAccount<T>(PhantomData<T>);
+
+impl<T: AccountLayout> AccountCheck for ProgramAccount<T> {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        StateAccount::<T>::check(account)
+    }
+}
+
+impl<T: AccountLayout> ProgramAccountInit for ProgramAccount<T> {
+    const OWNER: Pubkey = T::OWNER;
+}
+
+impl<T> AccountClose for ProgramAccount<T> {}
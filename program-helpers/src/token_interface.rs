@@ -0,0 +1,263 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::{get_return_data, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// `pinocchio_token` always sends its instructions to SPL Token. Token-2022
+// shares the layouts below, so these send the same bytes to `token_program`,
+// which is either of the two.
+
+/// Transfer tokens from one token account to another, checked against the
+/// mint's decimals. Token-2022 requires this form for mints with a transfer
+/// fee, and withholds the fee from what `to` receives.
+pub struct TransferCheckedInterface<'a> {
+    pub from: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub to: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub amount: u64,
+    pub decimals: u8,
+    pub token_program: &'a Pubkey,
+}
+
+impl TransferCheckedInterface<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.from.key()),
+            AccountMeta::readonly(self.mint.key()),
+            AccountMeta::writable(self.to.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        let mut instruction_data = [0u8; 10];
+        instruction_data[0] = 12;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+        instruction_data[9] = self.decimals;
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.from, self.mint, self.to, self.authority],
+            signers,
+        )
+    }
+}
+
+/// Mint new tokens into a token account.
+pub struct MintToInterface<'a> {
+    pub mint: &'a AccountInfo,
+    pub account: &'a AccountInfo,
+    pub mint_authority: &'a AccountInfo,
+    pub amount: u64,
+    pub token_program: &'a Pubkey,
+}
+
+impl MintToInterface<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::readonly_signer(self.mint_authority.key()),
+        ];
+
+        let mut instruction_data = [0u8; 9];
+        instruction_data[0] = 7;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.mint, self.account, self.mint_authority],
+            signers,
+        )
+    }
+}
+
+/// Burn tokens from a token account.
+pub struct BurnInterface<'a> {
+    pub account: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub amount: u64,
+    pub token_program: &'a Pubkey,
+}
+
+impl BurnInterface<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        let mut instruction_data = [0u8; 9];
+        instruction_data[0] = 8;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.account, self.mint, self.authority],
+            signers,
+        )
+    }
+}
+
+/// Initialize a new mint.
+pub struct InitializeMint2Interface<'a> {
+    pub mint: &'a AccountInfo,
+    pub decimals: u8,
+    pub mint_authority: &'a Pubkey,
+    pub freeze_authority: Option<&'a Pubkey>,
+    pub token_program: &'a Pubkey,
+}
+
+impl InitializeMint2Interface<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas = [AccountMeta::writable(self.mint.key())];
+
+        // A missing freeze authority is a zero tag followed by zeroes
+        let mut instruction_data = [0u8; 67];
+        instruction_data[0] = 20;
+        instruction_data[1] = self.decimals;
+        instruction_data[2..34].copy_from_slice(self.mint_authority);
+        if let Some(freeze_authority) = self.freeze_authority {
+            instruction_data[34] = 1;
+            instruction_data[35..67].copy_from_slice(freeze_authority);
+        }
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&instruction, &[self.mint], &[])
+    }
+}
+
+/// Initialize a new token account.
+pub struct InitializeAccount3Interface<'a> {
+    pub account: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub owner: &'a Pubkey,
+    pub token_program: &'a Pubkey,
+}
+
+impl InitializeAccount3Interface<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::readonly(self.mint.key()),
+        ];
+
+        let mut instruction_data = [0u8; 33];
+        instruction_data[0] = 18;
+        instruction_data[1..33].copy_from_slice(self.owner);
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&instruction, &[self.account, self.mint], &[])
+    }
+}
+
+/// Ask the token program how large a token account of `mint` must be. SPL
+/// Token always answers 165, Token-2022 adds room for the extensions the
+/// mint requires, such as the withheld amount of a transfer-fee mint.
+pub struct GetAccountDataSizeInterface<'a> {
+    pub mint: &'a AccountInfo,
+    pub token_program: &'a Pubkey,
+}
+
+impl GetAccountDataSizeInterface<'_> {
+    pub fn invoke(&self) -> Result<usize, ProgramError> {
+        let account_metas = [AccountMeta::readonly(self.mint.key())];
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &[21],
+        };
+
+        invoke_signed(&instruction, &[self.mint], &[])?;
+
+        // The size comes back as a little-endian u64 in the return data
+        let return_data = get_return_data().ok_or(ProgramError::InvalidAccountData)?;
+        if return_data.program_id().ne(self.token_program) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let size: [u8; 8] = return_data
+            .as_slice()
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(u64::from_le_bytes(size) as usize)
+    }
+}
+
+/// Amount held by a token account of either token program. The caller has
+/// checked the account with `TokenAccountInterface`, and a short account is
+/// still rejected rather than read past its end.
+#[inline(always)]
+pub fn token_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let amount = data.get(64..72).ok_or(ProgramError::InvalidAccountData)?;
+    Ok(u64::from_le_bytes(amount.try_into().unwrap()))
+}
+
+/// Supply of a mint of either token program. The caller has checked the
+/// account with `MintInterface`.
+#[inline(always)]
+pub fn mint_supply(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let supply = data.get(36..44).ok_or(ProgramError::InvalidAccountData)?;
+    Ok(u64::from_le_bytes(supply.try_into().unwrap()))
+}
+
+/// Decimals of a mint of either token program, as `TransferCheckedInterface`
+/// expects them. The caller has checked the account with `MintInterface`.
+#[inline(always)]
+pub fn mint_decimals(account: &AccountInfo) -> Result<u8, ProgramError> {
+    let data = account.try_borrow_data()?;
+    data.get(44)
+        .copied()
+        .ok_or(ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,50 @@
+//! Test-only program that passes its instruction on to another program
+//! through CPI, for tests of how a program behaves below the top level.
+//!
+//! The first account is the program to call. The remaining accounts, with
+//! their signer and writable flags, and the whole instruction data are
+//! forwarded unchanged.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke,
+    entrypoint,
+    instruction::{AccountMeta, Instruction},
+    nostd_panic_handler,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [program, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let metas: Vec<AccountMeta> = accounts
+        .iter()
+        .map(|account| AccountMeta::new(account.key(), account.is_writable(), account.is_signer()))
+        .collect();
+    let infos: Vec<&AccountInfo> = accounts.iter().collect();
+
+    slice_invoke(
+        &Instruction {
+            program_id: program.key(),
+            accounts: &metas,
+            data: instruction_data,
+        },
+        &infos,
+    )
+}
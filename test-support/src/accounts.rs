@@ -0,0 +1,44 @@
+use mollusk_svm::Mollusk;
+use mollusk_svm_programs_token::{associated_token, token, token2022};
+use solana_sdk::{account::Account, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+/// Lamports given to payers by [`funded_payer`].
+pub const PAYER_LAMPORTS: u64 = 10 * LAMPORTS_PER_SOL;
+
+/// A system-owned account holding `lamports` and no data.
+pub fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &system_program().0)
+}
+
+/// A fresh payer key with [`PAYER_LAMPORTS`] to spend on rent and transfers.
+pub fn funded_payer() -> (Pubkey, Account) {
+    (Pubkey::new_unique(), system_account(PAYER_LAMPORTS))
+}
+
+/// Key and account of the system program.
+pub fn system_program() -> (Pubkey, Account) {
+    mollusk_svm::program::keyed_account_for_system_program()
+}
+
+/// Key and account of the SPL Token program.
+pub fn token_program() -> (Pubkey, Account) {
+    token::keyed_account()
+}
+
+/// Key and account of the SPL Token-2022 program.
+pub fn token_2022_program() -> (Pubkey, Account) {
+    token2022::keyed_account()
+}
+
+/// Key and account of the SPL Associated Token Account program.
+pub fn associated_token_program() -> (Pubkey, Account) {
+    associated_token::keyed_account()
+}
+
+/// Register SPL Token, Token-2022 and the Associated Token Account program so
+/// programs under test can CPI into them.
+pub fn add_token_programs(mollusk: &mut Mollusk) {
+    token::add_program(mollusk);
+    token2022::add_program(mollusk);
+    associated_token::add_program(mollusk);
+}
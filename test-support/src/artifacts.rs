@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use mollusk_svm::{program::loader_keys::LOADER_V3, Mollusk};
+use solana_sdk::pubkey::Pubkey;
+
+/// Directory holding the built programs.
+///
+/// Honors `SBF_OUT_DIR` like `cargo build-sbf`, and otherwise falls back to
+/// the workspace `target/deploy`, which is where `cargo build-sbf` writes
+/// every member's artifact when run from the workspace root.
+pub fn deploy_dir() -> PathBuf {
+    match std::env::var_os("SBF_OUT_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("target")
+            .join("deploy"),
+    }
+}
+
+/// Path of the `<name>.so` artifact, where `name` is the crate's lib name
+/// (for example `counter` or `pinocchio_escrow`).
+pub fn program_path(name: &str) -> PathBuf {
+    deploy_dir().join(name).with_extension("so")
+}
+
+/// Read the ELF of a built program.
+///
+/// Panics with a hint to run `cargo build-sbf` if the artifact is missing.
+pub fn load_program(name: &str) -> Vec<u8> {
+    let path = program_path(name);
+    std::fs::read(&path).unwrap_or_else(|err| {
+        panic!(
+            "could not read program `{name}` at {}: {err}. Run `cargo build-sbf` from the workspace root first",
+            path.display()
+        )
+    })
+}
+
+/// Register a built program under `program_id`, so it can be invoked directly
+/// or through CPI.
+pub fn add_program(mollusk: &mut Mollusk, program_id: &Pubkey, name: &str) {
+    mollusk.add_program_with_elf_and_loader(program_id, &load_program(name), &LOADER_V3);
+}
+
+/// A Mollusk instance with a single built program loaded, the counterpart of
+/// `Mollusk::new(program_id, "./target/deploy/<name>")`.
+pub fn mollusk(program_id: &Pubkey, name: &str) -> Mollusk {
+    let mut mollusk = Mollusk::default();
+    add_program(&mut mollusk, program_id, name);
+    mollusk
+}
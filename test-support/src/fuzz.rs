@@ -0,0 +1,250 @@
+//! Property-based fuzzing of a program's `process_instruction` under Mollusk.
+//!
+//! A [`Fuzzer`] describes the accounts worth throwing at a program. Its
+//! [`Fuzzer::cases`] strategy picks an arbitrary account list out of them,
+//! fills the accounts with arbitrary lamports, owners and data, and pairs the
+//! list with arbitrary instruction data. [`check_invariants`] then runs one
+//! case and asserts what must hold for every input:
+//!
+//! - the program never panics or aborts,
+//! - lamports are conserved across the accounts.
+//!
+//! The number of cases defaults to proptest's 256 and follows `PROPTEST_CASES`.
+
+use mollusk_svm::{result::InstructionResult, Mollusk};
+use proptest::{
+    collection::vec,
+    prelude::*,
+    sample::select,
+    test_runner::{Config, TestCaseError, TestRunner},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+
+/// One generated input: an instruction and the accounts it references.
+#[derive(Clone, Debug)]
+pub struct FuzzCase {
+    pub instruction: Instruction,
+    pub accounts: Vec<(Pubkey, Account)>,
+}
+
+/// The pool of accounts the fuzzer draws from.
+#[derive(Clone, Debug)]
+pub struct Fuzzer {
+    pub program_id: Pubkey,
+    /// Accounts passed exactly as given, such as the system program.
+    pub fixed: Vec<(Pubkey, Account)>,
+    /// Addresses that get arbitrary account contents, typically the PDAs the
+    /// program derives plus a few random keys.
+    pub keys: Vec<Pubkey>,
+    /// Candidate owners of the arbitrary accounts.
+    pub owners: Vec<Pubkey>,
+    /// Data sizes worth hitting exactly, such as the state account sizes.
+    pub data_lens: Vec<usize>,
+    /// Upper bound on the number of account metas in an instruction.
+    pub max_accounts: usize,
+    /// Upper bound on the instruction data length.
+    pub max_data: usize,
+}
+
+impl Fuzzer {
+    /// A fuzzer over `keys`, with the system program as the only fixed
+    /// account and the program and the system program as owners.
+    pub fn new(program_id: Pubkey, keys: Vec<Pubkey>) -> Self {
+        let system_program = crate::system_program();
+        Self {
+            program_id,
+            owners: vec![program_id, system_program.0],
+            fixed: vec![system_program],
+            keys,
+            data_lens: Vec::new(),
+            max_accounts: 8,
+            max_data: 128,
+        }
+    }
+
+    pub fn data_lens(mut self, data_lens: &[usize]) -> Self {
+        self.data_lens = data_lens.to_vec();
+        self
+    }
+
+    pub fn fixed(mut self, key: Pubkey, account: Account) -> Self {
+        self.fixed.push((key, account));
+        self
+    }
+
+    pub fn max_accounts(mut self, max_accounts: usize) -> Self {
+        self.max_accounts = max_accounts;
+        self
+    }
+
+    fn account(&self) -> impl Strategy<Value = Account> {
+        let lamports = prop_oneof![Just(0u64), 1u64..10_000, 1_000_000u64..10_000_000_000,];
+
+        let max_len = self.data_lens.iter().copied().max().unwrap_or(0).max(64);
+        let data = if self.data_lens.is_empty() {
+            vec(any::<u8>(), 0..=max_len).boxed()
+        } else {
+            prop_oneof![
+                vec(any::<u8>(), 0..=max_len),
+                select(self.data_lens.clone()).prop_flat_map(|len| vec(any::<u8>(), len)),
+            ]
+            .boxed()
+        };
+
+        (lamports, select(self.owners.clone()), data).prop_map(|(lamports, owner, data)| Account {
+            lamports,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+
+    /// Arbitrary instructions to the program, with instruction data built by
+    /// `data`.
+    pub fn cases_with(
+        &self,
+        data: impl Strategy<Value = Vec<u8>>,
+    ) -> impl Strategy<Value = FuzzCase> {
+        let program_id = self.program_id;
+        let fixed = self.fixed.clone();
+        let keys = self.keys.clone();
+        let pool = fixed.len() + keys.len();
+
+        let metas = vec(
+            (0..pool, any::<bool>(), any::<bool>()),
+            0..=self.max_accounts,
+        );
+        let contents = vec(self.account(), keys.len());
+
+        (metas, contents, data).prop_map(move |(metas, contents, data)| {
+            let key_of = |index: usize| {
+                fixed
+                    .get(index)
+                    .map(|(key, _)| *key)
+                    .unwrap_or_else(|| keys[index - fixed.len()])
+            };
+
+            let account_metas = metas
+                .iter()
+                .map(|&(index, is_signer, is_writable)| AccountMeta {
+                    pubkey: key_of(index),
+                    is_signer,
+                    is_writable,
+                })
+                .collect();
+
+            let mut accounts: Vec<(Pubkey, Account)> = Vec::new();
+            for &(index, _, _) in &metas {
+                let key = key_of(index);
+                if accounts.iter().any(|(existing, _)| existing == &key) {
+                    continue;
+                }
+                let account = match fixed.get(index) {
+                    Some((_, account)) => account.clone(),
+                    None => contents[index - fixed.len()].clone(),
+                };
+                accounts.push((key, account));
+            }
+
+            FuzzCase {
+                instruction: Instruction::new_with_bytes(program_id, &data, account_metas),
+                accounts,
+            }
+        })
+    }
+
+    /// Arbitrary instructions with arbitrary instruction data.
+    pub fn cases(&self) -> impl Strategy<Value = FuzzCase> {
+        self.cases_with(vec(any::<u8>(), 0..=self.max_data))
+    }
+
+    /// Instructions whose data starts with `discriminator` followed by a
+    /// payload of any length except `expected_len`.
+    pub fn wrong_length_cases(
+        &self,
+        discriminator: u8,
+        expected_len: usize,
+    ) -> impl Strategy<Value = FuzzCase> {
+        let data = vec(any::<u8>(), 0..=self.max_data.max(expected_len + 8))
+            .prop_filter("payload has the expected length", move |payload| {
+                payload.len() != expected_len
+            })
+            .prop_map(move |payload| [vec![discriminator], payload].concat());
+
+        self.cases_with(data)
+    }
+
+    /// Instructions whose first byte is not below `instruction_count`,
+    /// including empty instruction data.
+    pub fn unknown_instruction_cases(
+        &self,
+        instruction_count: u8,
+    ) -> impl Strategy<Value = FuzzCase> {
+        let data = prop_oneof![
+            Just(Vec::new()),
+            (
+                instruction_count..=u8::MAX,
+                vec(any::<u8>(), 0..=self.max_data)
+            )
+                .prop_map(|(discriminator, payload)| [vec![discriminator], payload].concat()),
+        ];
+
+        self.cases_with(data)
+    }
+}
+
+fn total_lamports(accounts: &[(Pubkey, Account)]) -> u128 {
+    accounts
+        .iter()
+        .map(|(_, account)| account.lamports as u128)
+        .sum()
+}
+
+/// Run one case and check the invariants that hold for every input.
+pub fn check_invariants(
+    mollusk: &Mollusk,
+    case: &FuzzCase,
+) -> Result<InstructionResult, TestCaseError> {
+    let result = mollusk.process_instruction(&case.instruction, &case.accounts);
+
+    // Panics, aborts and memory faults all surface as ProgramFailedToComplete
+    prop_assert_ne!(
+        result.raw_result.clone(),
+        Err(InstructionError::ProgramFailedToComplete),
+        "program panicked on {:?}",
+        case.instruction
+    );
+
+    prop_assert_eq!(
+        total_lamports(&case.accounts),
+        total_lamports(&result.resulting_accounts),
+        "lamports not conserved"
+    );
+
+    Ok(result)
+}
+
+/// Like [`check_invariants`], and also require the instruction to fail.
+pub fn check_rejected(mollusk: &Mollusk, case: &FuzzCase) -> Result<(), TestCaseError> {
+    let result = check_invariants(mollusk, case)?;
+    prop_assert!(
+        result.program_result.is_err(),
+        "invalid input accepted: {:?}",
+        case.instruction
+    );
+    Ok(())
+}
+
+/// Run `test` against cases drawn from `strategy`, panicking with the
+/// minimal failing case if one is found.
+pub fn fuzz<S: Strategy>(strategy: S, test: impl Fn(S::Value) -> Result<(), TestCaseError>) {
+    let mut runner = TestRunner::new(Config::default());
+    if let Err(err) = runner.run(&strategy, test) {
+        panic!("{err}");
+    }
+}
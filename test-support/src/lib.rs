@@ -0,0 +1,26 @@
+//! Shared helpers for the Mollusk tests of every program in the workspace.
+//!
+//! Programs are loaded from the `.so` artifacts written by `cargo build-sbf`,
+//! so the tests of one program can load any other program for cross-program
+//! scenarios.
+
+pub mod accounts;
+pub mod artifacts;
+pub mod fuzz;
+pub mod logs;
+pub mod merkle;
+pub mod token;
+
+pub use accounts::*;
+pub use artifacts::*;
+pub use logs::*;
+pub use merkle::*;
+pub use token::*;
+
+/// Copy `data` into a zero-padded fixed-size array, truncating at `size`.
+pub fn create_padded_array<const N: usize>(data: &[u8], size: usize) -> [u8; N] {
+    let mut result = [0u8; N];
+    let copy_size = data.len().min(size).min(N);
+    result[..copy_size].copy_from_slice(&data[..copy_size]);
+    result
+}
@@ -0,0 +1,29 @@
+use std::{cell::RefCell, rc::Rc};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mollusk_svm::Mollusk;
+use solana_log_collector::LogCollector;
+
+/// Record the logs of every instruction `mollusk` processes from now on.
+pub fn record_logs(mollusk: &mut Mollusk) -> Rc<RefCell<LogCollector>> {
+    let logs = LogCollector::new_ref();
+    mollusk.logger = Some(logs.clone());
+    logs
+}
+
+/// Every `sol_log_data` call recorded so far, in order, with its fields
+/// joined. Programs log each event as a single field, so each entry is one
+/// event ready for the program's `decode`.
+pub fn program_data(logs: &RefCell<LogCollector>) -> Vec<Vec<u8>> {
+    logs.borrow()
+        .get_recorded_content()
+        .iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .map(|fields| {
+            fields
+                .split(' ')
+                .flat_map(|field| STANDARD.decode(field).expect("base64 log field"))
+                .collect()
+        })
+        .collect()
+}
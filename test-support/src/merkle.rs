@@ -0,0 +1,53 @@
+/// Hash of two sibling nodes, supplied by the program so the tree is built
+/// under the same convention it verifies with.
+pub type NodeHash = fn(&[u8; 32], &[u8; 32]) -> [u8; 32];
+
+/// Host-side merkle tree over pre-hashed leaves, for building distribution
+/// roots and claim proofs offline.
+///
+/// A node without a sibling is promoted to the next level unchanged, so its
+/// proof is one hash shorter.
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; 32]>, node: NodeHash) -> Self {
+        assert!(!leaves.is_empty(), "a merkle tree needs at least one leaf");
+
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// Siblings of the leaf at `index`, from the leaf up to the root.
+    pub fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        assert!(index < self.levels[0].len(), "leaf index out of range");
+
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
@@ -0,0 +1,210 @@
+use mollusk_svm::Mollusk;
+use mollusk_svm_programs_token::{associated_token, token};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+/// Size of an SPL mint without extensions.
+pub const MINT_LEN: usize = 82;
+/// Size of an SPL token account without extensions.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+// Token accounts store `AccountState::Initialized` as 1
+const ACCOUNT_STATE_INITIALIZED: u8 = 1;
+
+// Token-2022 pads a mint with extensions to the token account size, then tags
+// the account type and appends each extension as type, length and value
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXTENSION_TRANSFER_FEE_AMOUNT: u16 = 2;
+
+fn push_extension(data: &mut Vec<u8>, extension_type: u16, value: &[u8]) {
+    data.extend_from_slice(&extension_type.to_le_bytes());
+    data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    data.extend_from_slice(value);
+}
+
+/// Address of the associated token account of `owner` for `mint`.
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &associated_token::ID,
+    )
+    .0
+}
+
+// Write a `COption<Pubkey>` as the token program packs it: a u32 tag then the key
+fn pack_option_key(dst: &mut [u8], key: Option<&Pubkey>) {
+    if let Some(key) = key {
+        dst[..4].copy_from_slice(&1u32.to_le_bytes());
+        dst[4..36].copy_from_slice(key.as_ref());
+    }
+}
+
+/// Builds an initialized, rent-exempt mint account owned by SPL Token, or by
+/// Token-2022 through [`MintBuilder::token_program`].
+pub struct MintBuilder {
+    mint_authority: Pubkey,
+    freeze_authority: Option<Pubkey>,
+    supply: u64,
+    decimals: u8,
+    token_program: Pubkey,
+    transfer_fee: Option<(u16, u64)>,
+}
+
+impl MintBuilder {
+    pub fn new(mint_authority: &Pubkey) -> Self {
+        Self {
+            mint_authority: *mint_authority,
+            freeze_authority: None,
+            supply: 0,
+            decimals: 6,
+            token_program: token::ID,
+            transfer_fee: None,
+        }
+    }
+
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn supply(mut self, supply: u64) -> Self {
+        self.supply = supply;
+        self
+    }
+
+    pub fn freeze_authority(mut self, freeze_authority: &Pubkey) -> Self {
+        self.freeze_authority = Some(*freeze_authority);
+        self
+    }
+
+    pub fn token_program(mut self, token_program: &Pubkey) -> Self {
+        self.token_program = *token_program;
+        self
+    }
+
+    /// Add a Token-2022 transfer fee of `fee_bps`, capped at `maximum_fee`
+    /// per transfer. Token accounts of the mint then need
+    /// [`AtaBuilder::transfer_fee_amount`].
+    pub fn transfer_fee(mut self, fee_bps: u16, maximum_fee: u64) -> Self {
+        self.transfer_fee = Some((fee_bps, maximum_fee));
+        self
+    }
+
+    pub fn data(&self) -> Vec<u8> {
+        let mut data = vec![0; MINT_LEN];
+        pack_option_key(&mut data[0..36], Some(&self.mint_authority));
+        data[36..44].copy_from_slice(&self.supply.to_le_bytes());
+        data[44] = self.decimals;
+        data[45] = 1; // is_initialized
+        pack_option_key(&mut data[46..82], self.freeze_authority.as_ref());
+
+        if let Some((fee_bps, maximum_fee)) = self.transfer_fee {
+            data.resize(TOKEN_ACCOUNT_LEN, 0);
+            data.push(ACCOUNT_TYPE_MINT);
+
+            // No config or withdraw authority and nothing withheld, then the
+            // same fee as both the older and the newer fee from epoch 0
+            let mut config = vec![0; 72];
+            for _ in 0..2 {
+                config.extend_from_slice(&0u64.to_le_bytes());
+                config.extend_from_slice(&maximum_fee.to_le_bytes());
+                config.extend_from_slice(&fee_bps.to_le_bytes());
+            }
+            push_extension(&mut data, EXTENSION_TRANSFER_FEE_CONFIG, &config);
+        }
+        data
+    }
+
+    pub fn build(&self, mollusk: &Mollusk) -> Account {
+        let data = self.data();
+        Account {
+            lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+            data,
+            owner: self.token_program,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
+
+/// Builds an initialized associated token account holding `amount` tokens.
+pub struct AtaBuilder {
+    owner: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    transfer_fee_amount: bool,
+}
+
+impl AtaBuilder {
+    pub fn new(owner: &Pubkey, mint: &Pubkey) -> Self {
+        Self {
+            owner: *owner,
+            mint: *mint,
+            amount: 0,
+            token_program: token::ID,
+            transfer_fee_amount: false,
+        }
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    pub fn token_program(mut self, token_program: &Pubkey) -> Self {
+        self.token_program = *token_program;
+        self
+    }
+
+    /// Add the Token-2022 withheld amount that accounts of a mint with a
+    /// transfer fee carry, see [`MintBuilder::transfer_fee`].
+    pub fn transfer_fee_amount(mut self) -> Self {
+        self.transfer_fee_amount = true;
+        self
+    }
+
+    pub fn address(&self) -> Pubkey {
+        associated_token_address(&self.owner, &self.mint, &self.token_program)
+    }
+
+    pub fn data(&self) -> Vec<u8> {
+        let mut data = vec![0; TOKEN_ACCOUNT_LEN];
+        data[0..32].copy_from_slice(self.mint.as_ref());
+        data[32..64].copy_from_slice(self.owner.as_ref());
+        data[64..72].copy_from_slice(&self.amount.to_le_bytes());
+        // no delegate, no native reserve, no close authority
+        data[108] = ACCOUNT_STATE_INITIALIZED;
+
+        if self.transfer_fee_amount {
+            data.push(ACCOUNT_TYPE_ACCOUNT);
+            push_extension(
+                &mut data,
+                EXTENSION_TRANSFER_FEE_AMOUNT,
+                &0u64.to_le_bytes(),
+            );
+        }
+        data
+    }
+
+    pub fn build(&self, mollusk: &Mollusk) -> (Pubkey, Account) {
+        let data = self.data();
+        let account = Account {
+            lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+            data,
+            owner: self.token_program,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        (self.address(), account)
+    }
+}
+
+/// Credit `amount` to an existing token account, as if someone had sent it
+/// tokens without going through the program.
+pub fn donate_tokens(account: &mut Account, amount: u64) {
+    let balance = u64::from_le_bytes(account.data[64..72].try_into().unwrap());
+    account.data[64..72].copy_from_slice(&(balance + amount).to_le_bytes());
+}
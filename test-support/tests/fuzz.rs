@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use proptest::prelude::*;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    };
+    use test_support::{
+        funded_payer,
+        fuzz::{check_invariants, check_rejected, fuzz, FuzzCase, Fuzzer},
+        system_program,
+    };
+
+    // The system program is a builtin, so the harness can be exercised
+    // without any compiled program.
+    fn fuzzer() -> Fuzzer {
+        Fuzzer::new(
+            system_program().0,
+            vec![
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        )
+    }
+
+    fn transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+        // `SystemInstruction::Transfer` is variant 2, followed by the amount
+        Instruction::new_with_bincode(
+            system_program().0,
+            &(2u32, lamports),
+            vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+        )
+    }
+
+    #[test]
+    fn test_system_program_any_input() {
+        let mollusk = Mollusk::default();
+        fuzz(fuzzer().cases(), |case| {
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    fn test_system_program_unknown_instruction() {
+        let mollusk = Mollusk::default();
+        // System instructions are tagged with a little-endian u32, and 13 is
+        // past the last variant
+        fuzz(fuzzer().unknown_instruction_cases(13), |case| {
+            check_rejected(&mollusk, &case)
+        });
+    }
+
+    #[test]
+    fn test_transfers_conserve_lamports() {
+        let mollusk = Mollusk::default();
+        let (from, from_account) = funded_payer();
+        let (to, to_account) = funded_payer();
+
+        fuzz(any::<u64>(), |lamports| {
+            let case = FuzzCase {
+                instruction: transfer(&from, &to, lamports),
+                accounts: vec![(from, from_account.clone()), (to, to_account.clone())],
+            };
+            check_invariants(&mollusk, &case).map(|_| ())
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid input accepted")]
+    fn test_check_rejected_catches_success() {
+        let mollusk = Mollusk::default();
+        let (from, from_account) = funded_payer();
+        let (to, to_account) = funded_payer();
+
+        fuzz(1..1_000u64, |lamports| {
+            let case = FuzzCase {
+                instruction: transfer(&from, &to, lamports),
+                accounts: vec![(from, from_account.clone()), (to, to_account.clone())],
+            };
+            check_rejected(&mollusk, &case)
+        });
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_support::MerkleTree;
+
+    // Order-independent like the programs' node hash, which proofs without
+    // left or right flags rely on. Collisions do not matter here.
+    fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = a[i]
+                .wrapping_add(b[i])
+                .wrapping_mul(31)
+                .wrapping_add(i as u8);
+        }
+        out
+    }
+
+    fn leaves(count: usize) -> Vec<[u8; 32]> {
+        (0..count).map(|i| [i as u8 + 1; 32]).collect()
+    }
+
+    #[test]
+    fn test_single_leaf_is_root() {
+        let tree = MerkleTree::new(leaves(1), node);
+
+        assert_eq!(tree.root(), [1; 32]);
+        assert!(tree.proof(0).is_empty());
+    }
+
+    #[test]
+    fn test_unpaired_leaf_is_promoted() {
+        let leaves = leaves(3);
+        let tree = MerkleTree::new(leaves.clone(), node);
+
+        assert_eq!(tree.root(), node(&node(&leaves[0], &leaves[1]), &leaves[2]));
+        assert_eq!(tree.proof(0), vec![leaves[1], leaves[2]]);
+        assert_eq!(tree.proof(2), vec![node(&leaves[0], &leaves[1])]);
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_every_proof_leads_to_root(count in 1usize..64) {
+            let leaves = leaves(count);
+            let tree = MerkleTree::new(leaves.clone(), node);
+
+            let depth = (usize::BITS - (count - 1).leading_zeros()) as usize;
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index);
+                prop_assert!(proof.len() <= depth);
+                let root = proof.iter().fold(*leaf, |hash, sibling| node(&hash, sibling));
+                prop_assert_eq!(root, tree.root());
+            }
+        }
+    }
+}
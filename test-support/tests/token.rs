@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use mollusk_svm::Mollusk;
+    use pinocchio_token::state::{Mint, TokenAccount};
+    use solana_sdk::pubkey::Pubkey;
+    use test_support::{
+        associated_token_address, token_2022_program, token_program, AtaBuilder, MintBuilder,
+        MINT_LEN, TOKEN_ACCOUNT_LEN,
+    };
+
+    #[test]
+    fn test_mint_builder_layout() {
+        let mollusk = Mollusk::default();
+        let authority = Pubkey::new_unique();
+        let freeze = Pubkey::new_unique();
+
+        let account = MintBuilder::new(&authority)
+            .decimals(9)
+            .supply(1_000)
+            .freeze_authority(&freeze)
+            .build(&mollusk);
+
+        assert_eq!(account.data.len(), MINT_LEN);
+        assert_eq!(account.data.len(), Mint::LEN);
+        assert_eq!(account.owner, token_program().0);
+        assert_eq!(
+            account.lamports,
+            mollusk.sysvars.rent.minimum_balance(MINT_LEN)
+        );
+
+        let mint = unsafe { Mint::from_bytes(&account.data) };
+        assert!(mint.is_initialized());
+        assert_eq!(mint.mint_authority(), Some(&authority.to_bytes()));
+        assert_eq!(mint.freeze_authority(), Some(&freeze.to_bytes()));
+        assert_eq!(mint.supply(), 1_000);
+        assert_eq!(mint.decimals(), 9);
+    }
+
+    #[test]
+    fn test_ata_builder_layout() {
+        let mollusk = Mollusk::default();
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (token_2022, _) = token_2022_program();
+
+        let (address, account) = AtaBuilder::new(&owner, &mint)
+            .amount(42)
+            .token_program(&token_2022)
+            .build(&mollusk);
+
+        assert_eq!(address, associated_token_address(&owner, &mint, &token_2022));
+        assert_eq!(account.data.len(), TOKEN_ACCOUNT_LEN);
+        assert_eq!(account.data.len(), TokenAccount::LEN);
+        assert_eq!(account.owner, token_2022);
+
+        let token = unsafe { TokenAccount::from_bytes(&account.data) };
+        assert!(token.is_initialized());
+        assert_eq!(token.mint(), &mint.to_bytes());
+        assert_eq!(token.owner(), &owner.to_bytes());
+        assert_eq!(token.amount(), 42);
+        assert!(!token.has_delegate());
+        assert!(!token.is_native());
+    }
+}